@@ -1,3 +1,4 @@
+pub use cache_key::{CacheKey, CacheKeyHasher};
 pub use canonical_url::{CanonicalUrl, RepositoryUrl};
 pub use digest::digest;
 pub use stable_hash::{StableHash, StableHasher};
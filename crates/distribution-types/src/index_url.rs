@@ -11,6 +11,7 @@ use url::Url;
 
 use pep508_rs::{split_scheme, Scheme, VerbatimUrl};
 use uv_fs::normalize_url_path;
+use uv_normalize::{InvalidNameError, PackageName};
 
 use crate::Verbatim;
 
@@ -44,6 +45,21 @@ impl Verbatim for IndexUrl {
     }
 }
 
+impl IndexUrl {
+    /// Return the verbatim representation of the URL, with any credentials removed.
+    ///
+    /// Used when writing the URL to an output file, which should never contain secrets.
+    pub fn redacted(&self) -> Cow<'_, str> {
+        if self.username().is_empty() && self.password().is_none() {
+            return self.verbatim();
+        }
+        let mut url = (**self).clone();
+        let _ = url.set_username("");
+        let _ = url.set_password(None);
+        Cow::Owned(url.to_string())
+    }
+}
+
 impl FromStr for IndexUrl {
     type Err = url::ParseError;
 
@@ -141,6 +157,23 @@ impl FromStr for FlatIndexLocation {
     }
 }
 
+impl FlatIndexLocation {
+    /// Return the representation of the location, with any credentials removed from a URL.
+    ///
+    /// Used when writing the location to an output file, which should never contain secrets.
+    pub fn redacted(&self) -> Cow<'_, str> {
+        match self {
+            Self::Path(path) => path.to_string_lossy(),
+            Self::Url(url) => {
+                let mut url = url.clone();
+                let _ = url.set_username("");
+                let _ = url.set_password(None);
+                Cow::Owned(url.to_string())
+            }
+        }
+    }
+}
+
 impl Display for FlatIndexLocation {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -150,6 +183,75 @@ impl Display for FlatIndexLocation {
     }
 }
 
+/// A package that's pinned to a specific index, e.g., `torch=https://download.pytorch.org/whl/cpu`.
+///
+/// A pinned package is only ever fetched from its index, regardless of the other configured
+/// indexes and `--find-links` locations, and the index is never consulted for any other package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageIndex {
+    pub package: PackageName,
+    pub index: IndexUrl,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum PackageIndexError {
+    #[error("Expected a package pinned to an index (`<package>=<url>`), found: `{0}`")]
+    MissingSeparator(String),
+
+    #[error(transparent)]
+    InvalidName(#[from] InvalidNameError),
+
+    #[error(transparent)]
+    InvalidUrl(#[from] url::ParseError),
+}
+
+impl FromStr for PackageIndex {
+    type Err = PackageIndexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((package, index)) = s.split_once('=') else {
+            return Err(PackageIndexError::MissingSeparator(s.to_string()));
+        };
+        Ok(Self {
+            package: PackageName::from_str(package.trim())?,
+            index: IndexUrl::from_str(index.trim())?,
+        })
+    }
+}
+
+impl Display for PackageIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.package, self.index)
+    }
+}
+
+/// The strategy to use when resolving a package against multiple indexes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum IndexStrategy {
+    /// Only use results from the first index that returns a match for a given package name.
+    ///
+    /// While this differs from pip's behavior, it's the default index strategy as it's the most
+    /// secure.
+    #[default]
+    FirstMatch,
+    /// Search for every package name across all indexes, exhausting the versions from the first
+    /// index before moving on to the next.
+    ///
+    /// In this strategy, we look for every package across all indexes. When resolving, we prefer
+    /// the distributions of a given version from the first index that provides it, and ignore
+    /// the same version on any subsequent index.
+    UnsafeAnyMatch,
+    /// Search for every package name across all indexes, preferring the "best" version found. If a
+    /// package version is in multiple indexes, the files from every index are considered.
+    ///
+    /// In this strategy, we look for every package across all indexes. When resolving, we
+    /// consider all versions from all indexes, choosing the "best" version found (typically, the
+    /// highest compatible version).
+    UnsafeBestMatch,
+}
+
 /// The index locations to use for fetching packages.
 ///
 /// By default, uses the PyPI index.
@@ -167,6 +269,8 @@ pub struct IndexLocations {
     extra_index: Vec<IndexUrl>,
     flat_index: Vec<FlatIndexLocation>,
     no_index: bool,
+    index_strategy: IndexStrategy,
+    package_indexes: Vec<PackageIndex>,
 }
 
 impl Default for IndexLocations {
@@ -177,6 +281,8 @@ impl Default for IndexLocations {
             extra_index: Vec::new(),
             flat_index: Vec::new(),
             no_index: false,
+            index_strategy: IndexStrategy::default(),
+            package_indexes: Vec::new(),
         }
     }
 }
@@ -194,6 +300,28 @@ impl IndexLocations {
             extra_index,
             flat_index,
             no_index,
+            index_strategy: IndexStrategy::default(),
+            package_indexes: Vec::new(),
+        }
+    }
+
+    /// Set the [`IndexStrategy`] to use when resolving a package against multiple indexes.
+    #[must_use]
+    pub fn with_index_strategy(self, index_strategy: IndexStrategy) -> Self {
+        Self {
+            index_strategy,
+            ..self
+        }
+    }
+
+    /// Set the packages that are pinned to a specific index.
+    ///
+    /// If a package is pinned more than once, the last pin takes precedence.
+    #[must_use]
+    pub fn with_package_indexes(self, package_indexes: Vec<PackageIndex>) -> Self {
+        Self {
+            package_indexes,
+            ..self
         }
     }
 
@@ -216,6 +344,8 @@ impl IndexLocations {
             extra_index: self.extra_index.into_iter().chain(extra_index).collect(),
             flat_index: self.flat_index.into_iter().chain(flat_index).collect(),
             no_index: self.no_index || no_index,
+            index_strategy: self.index_strategy,
+            package_indexes: self.package_indexes,
         }
     }
 }
@@ -256,12 +386,24 @@ impl<'a> IndexLocations {
         self.flat_index.iter()
     }
 
+    /// Return the [`IndexStrategy`] to use when resolving a package against multiple indexes.
+    pub fn index_strategy(&'a self) -> IndexStrategy {
+        self.index_strategy
+    }
+
+    /// Return the packages that are pinned to a specific index.
+    pub fn package_indexes(&'a self) -> &'a [PackageIndex] {
+        &self.package_indexes
+    }
+
     /// Clone the index locations into a [`IndexUrls`] instance.
     pub fn index_urls(&'a self) -> IndexUrls {
         IndexUrls {
             index: self.index.clone(),
             extra_index: self.extra_index.clone(),
             no_index: self.no_index,
+            index_strategy: self.index_strategy,
+            package_indexes: self.package_indexes.clone(),
         }
     }
 }
@@ -274,6 +416,8 @@ pub struct IndexUrls {
     index: Option<IndexUrl>,
     extra_index: Vec<IndexUrl>,
     no_index: bool,
+    index_strategy: IndexStrategy,
+    package_indexes: Vec<PackageIndex>,
 }
 
 impl Default for IndexUrls {
@@ -283,6 +427,8 @@ impl Default for IndexUrls {
             index: Some(DEFAULT_INDEX_URL.clone()),
             extra_index: Vec::new(),
             no_index: false,
+            index_strategy: IndexStrategy::default(),
+            package_indexes: Vec::new(),
         }
     }
 }
@@ -320,6 +466,26 @@ impl<'a> IndexUrls {
     pub fn indexes(&'a self) -> impl Iterator<Item = &'a IndexUrl> + 'a {
         self.extra_index().chain(self.index())
     }
+
+    /// Return the [`IndexStrategy`] to use when resolving a package against multiple indexes.
+    pub fn index_strategy(&'a self) -> IndexStrategy {
+        self.index_strategy
+    }
+
+    /// Return the index to which the given package is pinned, if any.
+    ///
+    /// If `no_index` was enabled, then this always returns `None`.
+    pub fn package_index(&'a self, package_name: &PackageName) -> Option<&'a IndexUrl> {
+        if self.no_index {
+            None
+        } else {
+            self.package_indexes
+                .iter()
+                .rev()
+                .find(|package_index| package_index.package == *package_name)
+                .map(|package_index| &package_index.index)
+        }
+    }
 }
 
 impl From<IndexLocations> for IndexUrls {
@@ -328,6 +494,8 @@ impl From<IndexLocations> for IndexUrls {
             index: locations.index,
             extra_index: locations.extra_index,
             no_index: locations.no_index,
+            index_strategy: locations.index_strategy,
+            package_indexes: locations.package_indexes,
         }
     }
 }
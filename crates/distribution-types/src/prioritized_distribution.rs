@@ -17,6 +17,8 @@ struct PrioritizedDistInner {
     wheel: Option<(DistMetadata, WheelCompatibility)>,
     /// The hashes for each distribution.
     hashes: Vec<Hashes>,
+    /// Every distribution for the package version, including those that weren't prioritized.
+    dists: Vec<Dist>,
     /// If exclude newer filtered files from this distribution
     exclude_newer: bool,
 }
@@ -71,6 +73,7 @@ impl PrioritizedDist {
     ) -> Self {
         Self(Box::new(PrioritizedDistInner {
             source: None,
+            dists: vec![dist.clone()],
             wheel: Some((
                 DistMetadata {
                     dist,
@@ -92,6 +95,7 @@ impl PrioritizedDist {
         hash: Option<Hashes>,
     ) -> Self {
         Self(Box::new(PrioritizedDistInner {
+            dists: vec![dist.clone()],
             source: Some(DistMetadata {
                 dist,
                 requires_python,
@@ -112,6 +116,8 @@ impl PrioritizedDist {
         hash: Option<Hashes>,
         compatibility: WheelCompatibility,
     ) {
+        self.0.dists.push(dist.clone());
+
         // Track the highest-priority wheel.
         if let Some((.., existing_compatibility)) = &self.0.wheel {
             if compatibility > *existing_compatibility {
@@ -148,6 +154,8 @@ impl PrioritizedDist {
         yanked: Yanked,
         hash: Option<Hashes>,
     ) {
+        self.0.dists.push(dist.clone());
+
         if self.0.source.is_none() {
             self.0.source = Some(DistMetadata {
                 dist,
@@ -223,6 +231,12 @@ impl PrioritizedDist {
         &self.0.hashes
     }
 
+    /// Return every distribution for the package version, including those that weren't
+    /// prioritized.
+    pub fn dists(&self) -> &[Dist] {
+        &self.0.dists
+    }
+
     /// Returns true if and only if this distribution does not contain any
     /// source distributions or wheels.
     pub fn is_empty(&self) -> bool {
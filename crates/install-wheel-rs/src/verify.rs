@@ -0,0 +1,51 @@
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+
+use crate::wheel::{parse_key_value_file, read_record_file};
+use crate::Error;
+
+/// Read the tags (like `cp312-cp312-manylinux_2_17_x86_64`) from the `WHEEL` file of the given
+/// `dist_info` directory.
+///
+/// Returns an empty list if the `WHEEL` file is missing or doesn't declare any tags.
+pub fn read_wheel_tags(dist_info: &Path) -> Result<Vec<String>, Error> {
+    let wheel_file = match fs::File::open(dist_info.join("WHEEL")) {
+        Ok(wheel_file) => wheel_file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut data = parse_key_value_file(wheel_file, "WHEEL")?;
+    Ok(data.remove("Tag").unwrap_or_default())
+}
+
+/// Return the paths listed in the `RECORD` file of the given `dist_info` directory that don't
+/// exist on disk.
+///
+/// Bytecode in `__pycache__` directories is ignored, since it may be removed and regenerated at
+/// any time. Returns an empty list if the `RECORD` file itself is missing.
+pub fn missing_record_files(dist_info: &Path) -> Result<Vec<PathBuf>, Error> {
+    let Some(site_packages) = dist_info.parent() else {
+        return Err(Error::BrokenVenv(
+            "dist-info directory is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    let mut record_file = match fs::File::open(dist_info.join("RECORD")) {
+        Ok(record_file) => record_file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let record = read_record_file(&mut record_file)?;
+
+    Ok(record
+        .into_iter()
+        .map(|entry| site_packages.join(entry.path))
+        .filter(|path| {
+            !path
+                .components()
+                .any(|component| component.as_os_str() == "__pycache__")
+        })
+        .filter(|path| !path.exists())
+        .collect())
+}
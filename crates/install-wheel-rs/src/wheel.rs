@@ -651,7 +651,7 @@ pub(crate) fn read_record_file(record: &mut impl Read) -> Result<Vec<RecordEntry
 }
 
 /// Parse a file with `Key: value` entries such as WHEEL and METADATA
-fn parse_key_value_file(
+pub(crate) fn parse_key_value_file(
     file: impl Read,
     debug_filename: &str,
 ) -> Result<FxHashMap<String, Vec<String>>, Error> {
@@ -358,6 +358,22 @@ impl Requirement {
         }
     }
 
+    /// Returns whether the markers apply for the given environment.
+    ///
+    /// If no environment is provided, only the `extra` markers are evaluated, i.e., the requirement
+    /// applies if there is potentially an environment that could activate it.
+    pub fn evaluate_optional_environment(
+        &self,
+        env: Option<&MarkerEnvironment>,
+        extras: &[ExtraName],
+    ) -> bool {
+        if let Some(marker) = &self.marker {
+            marker.evaluate_optional_environment(env, extras)
+        } else {
+            true
+        }
+    }
+
     /// Returns whether the requirement would be satisfied, independent of environment markers, i.e.
     /// if there is potentially an environment that could activate this requirement.
     ///
@@ -880,6 +880,134 @@ impl MarkerExpression {
         }
     }
 
+    /// Evaluates only the `extra` part of the marker, treating all other expressions as
+    /// satisfied.
+    fn evaluate_extras(&self, extras: &[ExtraName]) -> bool {
+        match (&self.l_value, &self.operator, &self.r_value) {
+            // `extra == '...'`
+            (MarkerValue::Extra, MarkerOperator::Equal, MarkerValue::QuotedString(r_string)) => {
+                ExtraName::from_str(r_string).is_ok_and(|r_extra| extras.contains(&r_extra))
+            }
+            // `'...' == extra`
+            (MarkerValue::QuotedString(l_string), MarkerOperator::Equal, MarkerValue::Extra) => {
+                ExtraName::from_str(l_string).is_ok_and(|l_extra| extras.contains(&l_extra))
+            }
+            // `extra != '...'`
+            (MarkerValue::Extra, MarkerOperator::NotEqual, MarkerValue::QuotedString(r_string)) => {
+                ExtraName::from_str(r_string).is_ok_and(|r_extra| !extras.contains(&r_extra))
+            }
+            // `'...' != extra`
+            (MarkerValue::QuotedString(l_string), MarkerOperator::NotEqual, MarkerValue::Extra) => {
+                ExtraName::from_str(l_string).is_ok_and(|l_extra| !extras.contains(&l_extra))
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns `true` if the expression compares against `extra`.
+    fn is_extra(&self) -> bool {
+        matches!(self.l_value, MarkerValue::Extra) || matches!(self.r_value, MarkerValue::Extra)
+    }
+
+    /// Returns the negation of this expression.
+    fn negate(&self) -> MarkerTree {
+        let operator = match self.operator {
+            MarkerOperator::Equal => MarkerOperator::NotEqual,
+            MarkerOperator::NotEqual => MarkerOperator::Equal,
+            MarkerOperator::GreaterThan => MarkerOperator::LessEqual,
+            MarkerOperator::GreaterEqual => MarkerOperator::LessThan,
+            MarkerOperator::LessThan => MarkerOperator::GreaterEqual,
+            MarkerOperator::LessEqual => MarkerOperator::GreaterThan,
+            MarkerOperator::In => MarkerOperator::NotIn,
+            MarkerOperator::NotIn => MarkerOperator::In,
+            MarkerOperator::TildeEqual => {
+                // `~= 3.8` is equivalent to `>= 3.8, == 3.*`, so its negation is
+                // `< 3.8 or != 3.*`.
+                if let (MarkerValue::MarkerEnvVersion(_), MarkerValue::QuotedString(r_string)) =
+                    (&self.l_value, &self.r_value)
+                {
+                    if let Ok(version) = Version::from_str(r_string) {
+                        let release = version.release();
+                        if release.len() >= 2 {
+                            let prefix = release[..release.len() - 1]
+                                .iter()
+                                .map(ToString::to_string)
+                                .collect::<Vec<_>>()
+                                .join(".");
+                            return MarkerTree::Or(vec![
+                                MarkerTree::Expression(Self {
+                                    l_value: self.l_value.clone(),
+                                    operator: MarkerOperator::LessThan,
+                                    r_value: self.r_value.clone(),
+                                }),
+                                MarkerTree::Expression(Self {
+                                    l_value: self.l_value.clone(),
+                                    operator: MarkerOperator::NotEqual,
+                                    r_value: MarkerValue::QuotedString(format!("{prefix}.*")),
+                                }),
+                            ]);
+                        }
+                    }
+                }
+                MarkerOperator::NotEqual
+            }
+        };
+        MarkerTree::Expression(Self {
+            l_value: self.l_value.clone(),
+            operator,
+            r_value: self.r_value.clone(),
+        })
+    }
+
+    /// Returns `true` if there is provably no environment in which both expressions are
+    /// satisfied.
+    ///
+    /// This is a conservative check: a return value of `false` does not imply that the
+    /// expressions overlap.
+    fn is_disjoint(&self, other: &Self) -> bool {
+        match (&self.l_value, &self.r_value, &other.l_value, &other.r_value) {
+            // E.g., `sys_platform == 'win32'` and `sys_platform == 'linux'`.
+            (
+                MarkerValue::MarkerEnvString(l_key),
+                MarkerValue::QuotedString(l_string),
+                MarkerValue::MarkerEnvString(r_key),
+                MarkerValue::QuotedString(r_string),
+            ) if l_key == r_key => match (&self.operator, &other.operator) {
+                (MarkerOperator::Equal, MarkerOperator::Equal) => l_string != r_string,
+                (MarkerOperator::Equal, MarkerOperator::NotEqual)
+                | (MarkerOperator::NotEqual, MarkerOperator::Equal) => l_string == r_string,
+                _ => false,
+            },
+            // E.g., `python_version < '3.10'` and `python_version >= '3.10'`.
+            (
+                MarkerValue::MarkerEnvVersion(l_key),
+                MarkerValue::QuotedString(l_string),
+                MarkerValue::MarkerEnvVersion(r_key),
+                MarkerValue::QuotedString(r_string),
+            ) if l_key == r_key => {
+                let (Ok(l_version), Ok(r_version)) =
+                    (Version::from_str(l_string), Version::from_str(r_string))
+                else {
+                    return false;
+                };
+                match (&self.operator, &other.operator) {
+                    (MarkerOperator::Equal, MarkerOperator::NotEqual)
+                    | (MarkerOperator::NotEqual, MarkerOperator::Equal) => l_version == r_version,
+                    (l_operator, r_operator) => {
+                        let (Some(l_bounds), Some(r_bounds)) = (
+                            VersionBounds::new(l_operator, l_version),
+                            VersionBounds::new(r_operator, r_version),
+                        ) else {
+                            return false;
+                        };
+                        l_bounds.is_disjoint(&r_bounds)
+                    }
+                }
+            }
+            _ => false,
+        }
+    }
+
     // The `marker <op> '...'` comparison
     fn marker_compare(
         &self,
@@ -898,6 +1026,57 @@ impl MarkerExpression {
     }
 }
 
+/// The interval of versions matched by a single version comparison, e.g., `python_version >= '3.8'`.
+struct VersionBounds {
+    /// The lower bound, and whether it is inclusive.
+    lower: Option<(Version, bool)>,
+    /// The upper bound, and whether it is inclusive.
+    upper: Option<(Version, bool)>,
+}
+
+impl VersionBounds {
+    /// Returns the bounds for the given operator, or `None` if the operator doesn't map to a single
+    /// interval.
+    fn new(operator: &MarkerOperator, version: Version) -> Option<Self> {
+        match operator {
+            MarkerOperator::Equal => Some(Self {
+                lower: Some((version.clone(), true)),
+                upper: Some((version, true)),
+            }),
+            MarkerOperator::GreaterThan => Some(Self {
+                lower: Some((version, false)),
+                upper: None,
+            }),
+            MarkerOperator::GreaterEqual => Some(Self {
+                lower: Some((version, true)),
+                upper: None,
+            }),
+            MarkerOperator::LessThan => Some(Self {
+                lower: None,
+                upper: Some((version, false)),
+            }),
+            MarkerOperator::LessEqual => Some(Self {
+                lower: None,
+                upper: Some((version, true)),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the two intervals have no versions in common.
+    fn is_disjoint(&self, other: &Self) -> bool {
+        fn below(upper: &Option<(Version, bool)>, lower: &Option<(Version, bool)>) -> bool {
+            match (upper, lower) {
+                (Some((upper, upper_inclusive)), Some((lower, lower_inclusive))) => {
+                    upper < lower || (upper == lower && !(*upper_inclusive && *lower_inclusive))
+                }
+                _ => false,
+            }
+        }
+        below(&self.upper, &other.lower) || below(&other.upper, &self.lower)
+    }
+}
+
 impl FromStr for MarkerExpression {
     type Err = Pep508Error;
 
@@ -946,6 +1125,27 @@ impl FromStr for MarkerTree {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for MarkerTree {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for MarkerTree {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        Self::from_str(&string).map_err(de::Error::custom)
+    }
+}
+
 impl MarkerTree {
     /// Does this marker apply in the given environment?
     pub fn evaluate(&self, env: &MarkerEnvironment, extras: &[ExtraName]) -> bool {
@@ -1038,6 +1238,168 @@ impl MarkerTree {
         (result, warnings)
     }
 
+    /// Does this marker apply in the given environment?
+    ///
+    /// If no environment is provided, only the `extra` expressions are evaluated, and all other
+    /// expressions are assumed to be satisfied, i.e., the marker applies if there is potentially
+    /// an environment that could activate it.
+    pub fn evaluate_optional_environment(
+        &self,
+        env: Option<&MarkerEnvironment>,
+        extras: &[ExtraName],
+    ) -> bool {
+        match env {
+            Some(env) => self.evaluate(env, extras),
+            None => self.evaluate_extras(extras),
+        }
+    }
+
+    /// Evaluates only the `extra` expressions of the marker, treating all other expressions as
+    /// satisfied.
+    fn evaluate_extras(&self, extras: &[ExtraName]) -> bool {
+        match self {
+            Self::Expression(expression) => expression.evaluate_extras(extras),
+            Self::And(expressions) => expressions.iter().all(|x| x.evaluate_extras(extras)),
+            Self::Or(expressions) => expressions.iter().any(|x| x.evaluate_extras(extras)),
+        }
+    }
+
+    /// Remove any `extra` expressions that are satisfied by the given set of extras.
+    ///
+    /// Returns `None` if the marker is always satisfied once the extras are accounted for.
+    #[must_use]
+    pub fn simplify_extras(self, extras: &[ExtraName]) -> Option<Self> {
+        match self {
+            Self::Expression(expression) => {
+                if expression.is_extra() && expression.evaluate_extras(extras) {
+                    None
+                } else {
+                    Some(Self::Expression(expression))
+                }
+            }
+            Self::And(expressions) => {
+                let mut expressions = expressions
+                    .into_iter()
+                    .filter_map(|x| x.simplify_extras(extras))
+                    .collect::<Vec<_>>();
+                match expressions.len() {
+                    0 => None,
+                    1 => expressions.pop(),
+                    _ => Some(Self::And(expressions)),
+                }
+            }
+            Self::Or(expressions) => {
+                let mut simplified = Vec::with_capacity(expressions.len());
+                for expression in expressions {
+                    simplified.push(expression.simplify_extras(extras)?);
+                }
+                match simplified.len() {
+                    1 => simplified.pop(),
+                    _ => Some(Self::Or(simplified)),
+                }
+            }
+        }
+    }
+
+    /// Combine two markers with a logical `and`, flattening nested conjunctions and removing
+    /// duplicate terms.
+    #[must_use]
+    pub fn and(self, other: Self) -> Self {
+        let mut expressions: Vec<Self> = Vec::new();
+        for tree in [self, other] {
+            let children = match tree {
+                Self::And(children) => children,
+                tree => vec![tree],
+            };
+            for child in children {
+                if !expressions.contains(&child) {
+                    expressions.push(child);
+                }
+            }
+        }
+        if expressions.len() == 1 {
+            expressions.remove(0)
+        } else {
+            Self::And(expressions)
+        }
+    }
+
+    /// Combine two markers with a logical `or`, flattening nested disjunctions and removing
+    /// duplicate or absorbed terms.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        let mut expressions: Vec<Self> = Vec::new();
+        for tree in [self, other] {
+            let children = match tree {
+                Self::Or(children) => children,
+                tree => vec![tree],
+            };
+            for child in children {
+                if !expressions.contains(&child) {
+                    expressions.push(child);
+                }
+            }
+        }
+
+        // Drop any conjunction that is absorbed by a weaker term, e.g., `a or (a and b)` is `a`.
+        let conjuncts = |tree: &Self| match tree {
+            Self::And(children) => children.clone(),
+            tree => vec![tree.clone()],
+        };
+        let mut absorbed = vec![false; expressions.len()];
+        for (i, left) in expressions.iter().enumerate() {
+            let left_conjuncts = conjuncts(left);
+            for (j, right) in expressions.iter().enumerate() {
+                if i == j || absorbed[j] {
+                    continue;
+                }
+                if conjuncts(right)
+                    .iter()
+                    .all(|conjunct| left_conjuncts.contains(conjunct))
+                {
+                    absorbed[i] = true;
+                    break;
+                }
+            }
+        }
+        let mut expressions = expressions
+            .into_iter()
+            .zip(absorbed)
+            .filter_map(|(expression, absorbed)| (!absorbed).then_some(expression))
+            .collect::<Vec<_>>();
+
+        if expressions.len() == 1 {
+            expressions.remove(0)
+        } else {
+            Self::Or(expressions)
+        }
+    }
+
+    /// Returns the logical negation of this marker.
+    #[must_use]
+    pub fn negate(&self) -> Self {
+        match self {
+            Self::Expression(expression) => expression.negate(),
+            Self::And(expressions) => Self::Or(expressions.iter().map(Self::negate).collect()),
+            Self::Or(expressions) => Self::And(expressions.iter().map(Self::negate).collect()),
+        }
+    }
+
+    /// Returns `true` if there is provably no environment in which both markers are satisfied,
+    /// e.g., `sys_platform == 'win32'` and `sys_platform == 'linux'`.
+    ///
+    /// This is a conservative check: a return value of `false` does not imply that the markers
+    /// overlap.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Or(expressions), _) => expressions.iter().all(|x| x.is_disjoint(other)),
+            (_, Self::Or(expressions)) => expressions.iter().all(|x| self.is_disjoint(x)),
+            (Self::And(expressions), _) => expressions.iter().any(|x| x.is_disjoint(other)),
+            (_, Self::And(expressions)) => expressions.iter().any(|x| self.is_disjoint(x)),
+            (Self::Expression(left), Self::Expression(right)) => left.is_disjoint(right),
+        }
+    }
+
     /// Report the deprecated marker from <https://peps.python.org/pep-0345/#environment-markers>
     fn report_deprecated_options(
         &self,
@@ -1365,6 +1727,7 @@ mod test {
     use crate::{MarkerExpression, MarkerOperator, MarkerTree, MarkerValue, MarkerValueString};
     use indoc::indoc;
     use std::str::FromStr;
+    use uv_normalize::ExtraName;
 
     fn assert_err(input: &str, error: &str) {
         assert_eq!(MarkerTree::from_str(input).unwrap_err().to_string(), error);
@@ -1612,4 +1975,116 @@ mod test {
         )
         .unwrap();
     }
+
+    #[test]
+    fn test_marker_disjoint() {
+        let disjoint = [
+            ("sys_platform == 'win32'", "sys_platform == 'linux'"),
+            ("sys_platform == 'win32'", "sys_platform != 'win32'"),
+            ("python_version < '3.10'", "python_version >= '3.10'"),
+            ("python_version <= '3.9'", "python_version > '3.9'"),
+            (
+                "python_version >= '3.8' and sys_platform == 'darwin'",
+                "sys_platform == 'win32'",
+            ),
+            (
+                "sys_platform == 'win32' or sys_platform == 'darwin'",
+                "sys_platform == 'linux'",
+            ),
+        ];
+        for (a, b) in disjoint {
+            let a = MarkerTree::from_str(a).unwrap();
+            let b = MarkerTree::from_str(b).unwrap();
+            assert!(a.is_disjoint(&b), "{a} {b}");
+            assert!(b.is_disjoint(&a), "{b} {a}");
+        }
+
+        let overlapping = [
+            ("sys_platform == 'win32'", "sys_platform == 'win32'"),
+            ("python_version < '3.10'", "python_version >= '3.9'"),
+            ("python_version <= '3.10'", "python_version >= '3.10'"),
+            ("sys_platform == 'win32'", "python_version >= '3.10'"),
+            (
+                "sys_platform == 'win32' or sys_platform == 'linux'",
+                "sys_platform == 'linux'",
+            ),
+        ];
+        for (a, b) in overlapping {
+            let a = MarkerTree::from_str(a).unwrap();
+            let b = MarkerTree::from_str(b).unwrap();
+            assert!(!a.is_disjoint(&b), "{a} {b}");
+            assert!(!b.is_disjoint(&a), "{b} {a}");
+        }
+    }
+
+    #[test]
+    fn test_marker_negate() {
+        let values = [
+            ("sys_platform == 'win32'", "sys_platform != 'win32'"),
+            ("python_version < '3.10'", "python_version >= '3.10'"),
+            (
+                "sys_platform == 'win32' or python_version > '3.8'",
+                "sys_platform != 'win32' and python_version <= '3.8'",
+            ),
+            (
+                "python_version ~= '3.8'",
+                "python_version < '3.8' or python_version != '3.*'",
+            ),
+        ];
+        for (a, b) in values {
+            assert_eq!(
+                MarkerTree::from_str(a).unwrap().negate(),
+                MarkerTree::from_str(b).unwrap(),
+                "{a} {b}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_marker_combine() {
+        let win32 = MarkerTree::from_str("sys_platform == 'win32'").unwrap();
+        let py38 = MarkerTree::from_str("python_version >= '3.8'").unwrap();
+        assert_eq!(
+            win32
+                .clone()
+                .and(py38.clone())
+                .and(win32.clone())
+                .to_string(),
+            "sys_platform == 'win32' and python_version >= '3.8'"
+        );
+        assert_eq!(
+            win32.clone().or(py38.clone()).or(py38.clone()).to_string(),
+            "sys_platform == 'win32' or python_version >= '3.8'"
+        );
+        assert_eq!(
+            win32.clone().or(win32.clone().and(py38)).to_string(),
+            "sys_platform == 'win32'"
+        );
+    }
+
+    #[test]
+    fn test_marker_simplify_extras() {
+        let extras = [ExtraName::from_str("dev").unwrap()];
+        let marker = MarkerTree::from_str("extra == 'dev' and sys_platform == 'win32'").unwrap();
+        assert_eq!(
+            marker.simplify_extras(&extras).unwrap().to_string(),
+            "sys_platform == 'win32'"
+        );
+        let marker = MarkerTree::from_str("extra == 'dev'").unwrap();
+        assert_eq!(marker.simplify_extras(&extras), None);
+        let marker = MarkerTree::from_str("extra == 'test' or sys_platform == 'win32'").unwrap();
+        assert_eq!(
+            marker.simplify_extras(&extras).unwrap().to_string(),
+            "extra == 'test' or sys_platform == 'win32'"
+        );
+    }
+
+    #[test]
+    fn test_marker_evaluate_optional_environment() {
+        let extras = [ExtraName::from_str("dev").unwrap()];
+        let marker = MarkerTree::from_str("extra == 'dev' and sys_platform == 'win32'").unwrap();
+        assert!(marker.evaluate_optional_environment(None, &extras));
+        assert!(!marker.evaluate_optional_environment(None, &[]));
+        assert!(!marker.evaluate_optional_environment(Some(&env37()), &extras));
+    }
 }
@@ -0,0 +1,144 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// A hash algorithm supported for verifying distributions, as accepted by `--hash`.
+///
+/// Mirrors the set of algorithms that pip accepts in hash-checking mode.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// Return the name of the algorithm, as used in `--hash` (e.g., `sha256`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Sha384 => "sha384",
+            Self::Sha512 => "sha512",
+        }
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = HashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(Self::Sha256),
+            "sha384" => Ok(Self::Sha384),
+            "sha512" => Ok(Self::Sha512),
+            _ => Err(HashError::UnsupportedAlgorithm(s.to_string())),
+        }
+    }
+}
+
+impl Display for HashAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A hex-encoded digest of a file, along with the algorithm used to compute it.
+///
+/// Serialized in the `--hash` format, e.g., `sha256:a1b2c3...`.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HashDigest {
+    pub algorithm: HashAlgorithm,
+    pub digest: String,
+}
+
+impl HashDigest {
+    /// Return the [`HashAlgorithm`] of the digest.
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+}
+
+impl FromStr for HashDigest {
+    type Err = HashError;
+
+    /// Parse a digest in the `--hash` format, e.g., `sha256:a1b2c3...`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((algorithm, digest)) = s.split_once(':') else {
+            return Err(HashError::InvalidStructure(s.to_string()));
+        };
+        if digest.is_empty() || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(HashError::InvalidStructure(s.to_string()));
+        }
+        Ok(Self {
+            algorithm: HashAlgorithm::from_str(algorithm)?,
+            digest: digest.to_ascii_lowercase(),
+        })
+    }
+}
+
+impl Display for HashDigest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.digest)
+    }
+}
+
+impl Serialize for HashDigest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for HashDigest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        Self::from_str(&string).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum HashError {
+    #[error("Unexpected hash (expected `<algorithm>:<hash>`): {0}")]
+    InvalidStructure(String),
+
+    #[error(
+        "Unsupported hash algorithm (expected one of: `sha256`, `sha384`, or `sha512`) on: `{0}`"
+    )]
+    UnsupportedAlgorithm(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{HashAlgorithm, HashDigest};
+
+    #[test]
+    fn parse_digest() {
+        let digest = HashDigest::from_str(
+            "sha256:2E4FD1E5A0BFF3CB8B4B3EB1C5F0F5EF2DD1A6B8C0B0BC2D1DDAE7D3D8C0A2E1",
+        )
+        .unwrap();
+        assert_eq!(digest.algorithm(), HashAlgorithm::Sha256);
+        assert_eq!(
+            digest.to_string(),
+            "sha256:2e4fd1e5a0bff3cb8b4b3eb1c5f0f5ef2dd1a6b8c0b0bc2d1ddae7d3d8c0a2e1"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_digest() {
+        assert!(HashDigest::from_str("sha256").is_err());
+        assert!(HashDigest::from_str("sha256:").is_err());
+        assert!(HashDigest::from_str("sha256:xyz").is_err());
+        assert!(HashDigest::from_str("md5:d41d8cd98f00b204e9800998ecf8427e").is_err());
+    }
+}
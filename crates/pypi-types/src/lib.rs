@@ -1,5 +1,6 @@
 pub use base_url::*;
 pub use direct_url::*;
+pub use hash::*;
 pub use lenient_requirement::*;
 pub use metadata::*;
 pub use scheme::*;
@@ -7,6 +8,7 @@ pub use simple_json::*;
 
 mod base_url;
 mod direct_url;
+mod hash;
 mod lenient_requirement;
 mod metadata;
 mod scheme;
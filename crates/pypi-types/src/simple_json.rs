@@ -3,7 +3,7 @@ use std::str::FromStr;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
 
-use pep440_rs::{VersionSpecifiers, VersionSpecifiersParseError};
+use pep440_rs::{Version, VersionSpecifiers, VersionSpecifiersParseError};
 
 use crate::lenient_requirement::LenientVersionSpecifiers;
 
@@ -13,6 +13,10 @@ pub struct SimpleJson {
     /// The list of [`File`]s available for download sorted by filename.
     #[serde(deserialize_with = "sorted_simple_json_files")]
     pub files: Vec<File>,
+    /// The [PEP 700](https://peps.python.org/pep-0700/) list of all versions of the project,
+    /// including those without any files. Empty if the index predates PEP 700.
+    #[serde(default, deserialize_with = "deserialize_versions_lenient")]
+    pub versions: Vec<Version>,
 }
 
 /// Deserializes a sequence of "simple" files from `PyPI` and ensures that they
@@ -31,21 +35,31 @@ fn sorted_simple_json_files<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<File>
     Ok(files)
 }
 
+/// Deserializes the project-level list of versions, skipping any that aren't valid PEP 440
+/// versions (e.g., legacy versions that remain on `PyPI`).
+fn deserialize_versions_lenient<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Version>, D::Error> {
+    let versions = <Vec<String>>::deserialize(d)?;
+    Ok(versions
+        .iter()
+        .filter_map(|version| Version::from_str(version).ok())
+        .collect())
+}
+
 /// A single (remote) file belonging to a package, either a wheel or a source distribution.
 ///
 /// <https://peps.python.org/pep-0691/#project-detail>
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[serde(from = "FileWire")]
 pub struct File {
-    // Non-PEP 691-compliant alias used by PyPI.
-    #[serde(alias = "data-dist-info-metadata")]
+    /// The [PEP 658](https://peps.python.org/pep-0658/) metadata for the file, read from the
+    /// [PEP 714](https://peps.python.org/pep-0714/) `core-metadata` key, or from the
+    /// `dist-info-metadata` key that it superseded.
     pub dist_info_metadata: Option<DistInfoMetadata>,
     pub filename: String,
     pub hashes: Hashes,
     /// There are a number of invalid specifiers on pypi, so we first try to parse it into a [`VersionSpecifiers`]
     /// according to spec (PEP 440), then a [`LenientVersionSpecifiers`] with fixup for some common problems and if this
     /// still fails, we skip the file when creating a version map.
-    #[serde(default, deserialize_with = "deserialize_version_specifiers_lenient")]
     pub requires_python: Option<Result<VersionSpecifiers, VersionSpecifiersParseError>>,
     pub size: Option<u64>,
     pub upload_time: Option<DateTime<Utc>>,
@@ -53,6 +67,42 @@ pub struct File {
     pub yanked: Option<Yanked>,
 }
 
+/// The serialized form of a [`File`], as returned by the index.
+///
+/// Indexes may include both the `core-metadata` key and its predecessor (e.g., PyPI includes both
+/// `core-metadata` and `data-dist-info-metadata`), so each key is read separately.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct FileWire {
+    core_metadata: Option<DistInfoMetadata>,
+    // Non-PEP 691-compliant alias used by PyPI.
+    #[serde(alias = "data-dist-info-metadata")]
+    dist_info_metadata: Option<DistInfoMetadata>,
+    filename: String,
+    hashes: Hashes,
+    #[serde(default, deserialize_with = "deserialize_version_specifiers_lenient")]
+    requires_python: Option<Result<VersionSpecifiers, VersionSpecifiersParseError>>,
+    size: Option<u64>,
+    upload_time: Option<DateTime<Utc>>,
+    url: String,
+    yanked: Option<Yanked>,
+}
+
+impl From<FileWire> for File {
+    fn from(file: FileWire) -> Self {
+        Self {
+            dist_info_metadata: file.core_metadata.or(file.dist_info_metadata),
+            filename: file.filename,
+            hashes: file.hashes,
+            requires_python: file.requires_python,
+            size: file.size,
+            upload_time: file.upload_time,
+            url: file.url,
+            yanked: file.yanked,
+        }
+    }
+}
+
 fn deserialize_version_specifiers_lenient<'de, D>(
     deserializer: D,
 ) -> Result<Option<Result<VersionSpecifiers, VersionSpecifiersParseError>>, D::Error>
@@ -106,6 +156,15 @@ impl Yanked {
             Self::Reason(_) => true,
         }
     }
+
+    /// Return the reason the file was yanked, if one was provided.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Bool(_) => None,
+            Self::Reason(reason) if reason.trim().is_empty() => None,
+            Self::Reason(reason) => Some(reason.trim()),
+        }
+    }
 }
 
 impl Default for Yanked {
@@ -154,3 +213,74 @@ impl Hashes {
         self.sha256.as_deref().or(self.md5.as_deref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn core_metadata() {
+        // PyPI includes both the PEP 714 key and the legacy key.
+        let file: File = serde_json::from_str(
+            r#"{
+                "core-metadata": {"sha256": "abc"},
+                "data-dist-info-metadata": {"sha256": "def"},
+                "filename": "anyio-4.0.0-py3-none-any.whl",
+                "hashes": {"sha256": "cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f"},
+                "url": "https://files.pythonhosted.org/anyio-4.0.0-py3-none-any.whl",
+                "yanked": "Broken release"
+            }"#,
+        )
+        .unwrap();
+        let Some(DistInfoMetadata::Hashes(hashes)) = file.dist_info_metadata else {
+            panic!("expected metadata hashes");
+        };
+        assert_eq!(hashes.sha256.as_deref(), Some("abc"));
+        assert_eq!(
+            file.yanked.as_ref().and_then(Yanked::reason),
+            Some("Broken release")
+        );
+
+        // The legacy keys are still accepted on their own.
+        let file: File = serde_json::from_str(
+            r#"{
+                "dist-info-metadata": true,
+                "filename": "anyio-4.0.0-py3-none-any.whl",
+                "hashes": {},
+                "url": "https://files.pythonhosted.org/anyio-4.0.0-py3-none-any.whl",
+                "yanked": true
+            }"#,
+        )
+        .unwrap();
+        assert!(file
+            .dist_info_metadata
+            .is_some_and(|metadata| metadata.is_available()));
+        assert!(file.yanked.as_ref().is_some_and(Yanked::is_yanked));
+        assert_eq!(file.yanked.as_ref().and_then(Yanked::reason), None);
+    }
+
+    #[test]
+    fn versions() {
+        // Invalid versions are skipped, rather than failing the entire response.
+        let simple: SimpleJson = serde_json::from_str(
+            r#"{
+                "meta": {"api-version": "1.1"},
+                "name": "anyio",
+                "files": [],
+                "versions": ["3.7.1", "4.0.0", "not-a-version"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            simple.versions,
+            [
+                Version::from_str("3.7.1").unwrap(),
+                Version::from_str("4.0.0").unwrap()
+            ]
+        );
+
+        // Indexes that predate PEP 700 omit the key entirely.
+        let simple: SimpleJson = serde_json::from_str(r#"{"files": []}"#).unwrap();
+        assert!(simple.versions.is_empty());
+    }
+}
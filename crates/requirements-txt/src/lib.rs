@@ -49,7 +49,7 @@ use pep508_rs::{
     expand_path_vars, split_scheme, Extras, Pep508Error, Pep508ErrorSource, Requirement, Scheme,
     VerbatimUrl,
 };
-use uv_client::RegistryClient;
+use uv_client::{RegistryClient, TrustedHost, TrustedHostError};
 use uv_fs::{normalize_url_path, Simplified};
 use uv_normalize::ExtraName;
 use uv_warnings::warn_user;
@@ -78,6 +78,8 @@ enum RequirementsTxtStatement {
     ExtraIndexUrl(VerbatimUrl),
     /// `--find-links`
     FindLinks(FindLink),
+    /// `--trusted-host`
+    TrustedHost(TrustedHost),
     /// `--no-index`
     NoIndex,
 }
@@ -318,6 +320,8 @@ pub struct RequirementsTxt {
     pub find_links: Vec<FindLink>,
     /// Whether to ignore the index, specified with `--no-index`.
     pub no_index: bool,
+    /// The hosts to allow insecure connections to, specified with `--trusted-host`.
+    pub trusted_hosts: Vec<TrustedHost>,
 }
 
 impl RequirementsTxt {
@@ -497,6 +501,9 @@ impl RequirementsTxt {
                 RequirementsTxtStatement::NoIndex => {
                     data.no_index = true;
                 }
+                RequirementsTxtStatement::TrustedHost(trusted_host) => {
+                    data.trusted_hosts.push(trusted_host);
+                }
             }
         }
         Ok(data)
@@ -512,6 +519,7 @@ impl RequirementsTxt {
             extra_index_urls,
             find_links,
             no_index,
+            trusted_hosts,
         } = other;
         self.requirements.extend(requirements);
         self.constraints.extend(constraints);
@@ -522,6 +530,7 @@ impl RequirementsTxt {
         self.extra_index_urls.extend(extra_index_urls);
         self.find_links.extend(find_links);
         self.no_index = self.no_index || no_index;
+        self.trusted_hosts.extend(trusted_hosts);
     }
 }
 
@@ -601,6 +610,17 @@ fn parse_entry(
             }
         })?;
         RequirementsTxtStatement::FindLinks(path_or_url)
+    } else if s.eat_if("--trusted-host") {
+        let given = parse_value(content, s, |c: char| !['\n', '\r'].contains(&c))?;
+        let trusted_host = given.parse::<TrustedHost>().map_err(|err| {
+            RequirementsTxtParserError::TrustedHost {
+                source: err,
+                host: given.to_string(),
+                start,
+                end: s.cursor(),
+            }
+        })?;
+        RequirementsTxtStatement::TrustedHost(trusted_host)
     } else if s.at(char::is_ascii_alphanumeric) {
         let (requirement, hashes) = parse_requirement_and_hashes(s, content, working_dir)?;
         RequirementsTxtStatement::RequirementEntry(RequirementEntry {
@@ -860,6 +880,12 @@ pub enum RequirementsTxtParserError {
         start: usize,
         end: usize,
     },
+    TrustedHost {
+        source: TrustedHostError,
+        host: String,
+        start: usize,
+        end: usize,
+    },
     InvalidEditablePath(String),
     UnsupportedUrl(String),
     MissingRequirementPrefix(String),
@@ -909,6 +935,17 @@ impl RequirementsTxtParserError {
                 start: start + offset,
                 end: end + offset,
             },
+            Self::TrustedHost {
+                source,
+                host,
+                start,
+                end,
+            } => Self::TrustedHost {
+                source,
+                host,
+                start: start + offset,
+                end: end + offset,
+            },
             Self::UnsupportedUrl(url) => Self::UnsupportedUrl(url),
             Self::MissingRequirementPrefix(given) => Self::MissingRequirementPrefix(given),
             Self::MissingEditablePrefix(given) => Self::MissingEditablePrefix(given),
@@ -953,6 +990,9 @@ impl Display for RequirementsTxtParserError {
             Self::Url { url, start, .. } => {
                 write!(f, "Invalid URL at position {start}: `{url}`")
             }
+            Self::TrustedHost { host, start, .. } => {
+                write!(f, "Invalid trusted host at position {start}: `{host}`")
+            }
             Self::UnsupportedUrl(url) => {
                 write!(f, "Unsupported URL (expected a `file://` scheme): `{url}`")
             }
@@ -1001,6 +1041,7 @@ impl std::error::Error for RequirementsTxtParserError {
         match &self {
             Self::IO(err) => err.source(),
             Self::Url { source, .. } => Some(source),
+            Self::TrustedHost { source, .. } => Some(source),
             Self::InvalidEditablePath(_) => None,
             Self::UnsupportedUrl(_) => None,
             Self::MissingRequirementPrefix(_) => None,
@@ -1027,6 +1068,13 @@ impl Display for RequirementsTxtFileError {
                     self.file.simplified_display(),
                 )
             }
+            RequirementsTxtParserError::TrustedHost { host, start, .. } => {
+                write!(
+                    f,
+                    "Invalid trusted host in `{}` at position {start}: `{host}`",
+                    self.file.simplified_display(),
+                )
+            }
             RequirementsTxtParserError::InvalidEditablePath(given) => {
                 write!(
                     f,
@@ -1413,6 +1461,38 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn invalid_trusted_host() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {"
+            --trusted-host devpi.internal:port
+        "})?;
+
+        let error = RequirementsTxt::parse(requirements_txt.path(), temp_dir.path(), None)
+            .await
+            .unwrap_err();
+        let errors = anyhow::Error::new(error).chain().join("\n");
+
+        let requirement_txt =
+            regex::escape(&requirements_txt.path().simplified_display().to_string());
+        let filters = vec![
+            (requirement_txt.as_str(), "<REQUIREMENTS_TXT>"),
+            (r"\\", "/"),
+        ];
+        insta::with_settings!({
+            filters => filters
+        }, {
+            insta::assert_snapshot!(errors, @r###"
+            Invalid trusted host in `<REQUIREMENTS_TXT>` at position 0: `devpi.internal:port`
+            Invalid port in trusted host: `devpi.internal:port`
+            invalid digit found in string
+            "###);
+        });
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn missing_r() -> Result<()> {
         let temp_dir = assert_fs::TempDir::new()?;
@@ -1494,6 +1574,7 @@ mod test {
             extra_index_urls: [],
             find_links: [],
             no_index: false,
+            trusted_hosts: [],
         }
         "###);
 
@@ -1555,6 +1636,7 @@ mod test {
                 extra_index_urls: [],
                 find_links: [],
                 no_index: true,
+                trusted_hosts: [],
             }
             "###);
 
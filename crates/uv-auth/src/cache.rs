@@ -0,0 +1,29 @@
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
+
+use crate::{Credentials, Realm};
+
+/// The credentials discovered during the current session, shared across all clients.
+pub static CREDENTIALS_CACHE: Lazy<CredentialsCache> = Lazy::new(CredentialsCache::default);
+
+/// A cache of credentials, keyed by [`Realm`].
+///
+/// The cache lives in memory only; credentials are never persisted to disk.
+#[derive(Debug, Default)]
+pub struct CredentialsCache {
+    realms: Mutex<FxHashMap<Realm, Arc<Credentials>>>,
+}
+
+impl CredentialsCache {
+    /// Return the credentials for the given realm, if any.
+    pub fn get(&self, realm: &Realm) -> Option<Arc<Credentials>> {
+        self.realms.lock().unwrap().get(realm).cloned()
+    }
+
+    /// Store the credentials for the given realm, replacing any existing credentials.
+    pub fn insert(&self, realm: Realm, credentials: Arc<Credentials>) {
+        self.realms.lock().unwrap().insert(realm, credentials);
+    }
+}
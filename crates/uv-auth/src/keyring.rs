@@ -0,0 +1,74 @@
+use std::process::Stdio;
+
+use serde::Deserialize;
+use tokio::process::Command;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::Credentials;
+
+/// The keyring provider to use when looking up credentials for an index.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum KeyringProvider {
+    /// Don't use a keyring for credential lookup.
+    #[default]
+    Disabled,
+    /// Use the `keyring` command for credential lookup.
+    ///
+    /// A username is required, either embedded in the index URL or provided via the `.netrc` file
+    /// or the environment.
+    Subprocess,
+}
+
+impl KeyringProvider {
+    /// Fetch the password for the given URL and username from the keyring.
+    ///
+    /// The lookup is first attempted with the URL itself, and then with its host.
+    pub async fn fetch(self, url: &Url, username: &str) -> Option<Credentials> {
+        match self {
+            Self::Disabled => None,
+            Self::Subprocess => {
+                let mut url = url.clone();
+                let _ = url.set_username("");
+                let _ = url.set_password(None);
+
+                let password = match Self::subprocess(url.as_str(), username).await {
+                    Some(password) => Some(password),
+                    None => match url.host_str() {
+                        Some(host) => Self::subprocess(host, username).await,
+                        None => None,
+                    },
+                }?;
+                Some(Credentials::new(username.to_string(), Some(password)))
+            }
+        }
+    }
+
+    /// Run `keyring get <service> <username>`, returning the password if one was found.
+    async fn subprocess(service: &str, username: &str) -> Option<String> {
+        debug!("Checking keyring for credentials for `{username}` at `{service}`");
+        let output = match Command::new("keyring")
+            .arg("get")
+            .arg(service)
+            .arg(username)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .await
+        {
+            Ok(output) => output,
+            Err(err) => {
+                warn!("Failed to run `keyring`: {err}");
+                return None;
+            }
+        };
+        if !output.status.success() {
+            return None;
+        }
+        let password = String::from_utf8(output.stdout).ok()?;
+        let password = password.trim_end_matches(['\r', '\n']);
+        (!password.is_empty()).then(|| password.to_string())
+    }
+}
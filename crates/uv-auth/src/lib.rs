@@ -2,6 +2,18 @@
 use tracing::warn;
 use url::Url;
 
+pub use cache::{CredentialsCache, CREDENTIALS_CACHE};
+pub use credentials::Credentials;
+pub use keyring::KeyringProvider;
+pub use netrc::{Netrc, NetrcError};
+pub use realm::Realm;
+
+mod cache;
+mod credentials;
+mod keyring;
+mod netrc;
+mod realm;
+
 /// Optimized version of [`safe_copy_url_auth`] which avoids parsing a string
 /// into a URL unless the given URL has authentication to copy. Useful for patterns
 /// where the returned URL would immediately be cast into a string.
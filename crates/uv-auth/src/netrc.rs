@@ -0,0 +1,217 @@
+use std::env;
+use std::io;
+use std::path::PathBuf;
+
+use directories::BaseDirs;
+use thiserror::Error;
+
+use crate::Credentials;
+
+#[derive(Debug, Error)]
+pub enum NetrcError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Expected a value after `{0}`")]
+    MissingValue(String),
+    #[error("Unexpected token `{0}`")]
+    UnexpectedToken(String),
+}
+
+/// A single `machine` (or `default`) entry in a `.netrc` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NetrcEntry {
+    /// The host to which the entry applies, or `None` for the `default` entry.
+    machine: Option<String>,
+    login: Option<String>,
+    password: Option<String>,
+}
+
+/// The parsed contents of a `.netrc` file.
+///
+/// See: <https://www.gnu.org/software/inetutils/manual/html_node/The-_002enetrc-file.html>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Netrc {
+    entries: Vec<NetrcEntry>,
+}
+
+impl Netrc {
+    /// Read the `.netrc` file at the path given by `NETRC`, or at `~/.netrc` by default.
+    ///
+    /// Returns `Ok(None)` if the file doesn't exist.
+    pub fn from_env() -> Result<Option<Self>, NetrcError> {
+        let path = if let Some(path) = env::var_os("NETRC") {
+            PathBuf::from(path)
+        } else if let Some(base_dirs) = BaseDirs::new() {
+            base_dirs.home_dir().join(".netrc")
+        } else {
+            return Ok(None);
+        };
+        match fs_err::read_to_string(path) {
+            Ok(content) => Ok(Some(Self::parse(&content)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Parse the contents of a `.netrc` file.
+    pub fn parse(content: &str) -> Result<Self, NetrcError> {
+        // Tokenize the file, dropping comments and macro definitions. A macro definition runs
+        // from the line after `macdef <name>` to the next empty line.
+        let mut tokens = Vec::new();
+        let mut in_macdef = false;
+        for line in content.lines() {
+            if in_macdef {
+                in_macdef = !line.trim().is_empty();
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            while let Some(word) = words.next() {
+                if word.starts_with('#') {
+                    break;
+                }
+                if word == "macdef" {
+                    words
+                        .next()
+                        .ok_or_else(|| NetrcError::MissingValue(word.to_string()))?;
+                    in_macdef = true;
+                    break;
+                }
+                tokens.push(word);
+            }
+        }
+
+        let mut entries: Vec<NetrcEntry> = Vec::new();
+        let mut tokens = tokens.into_iter();
+        while let Some(token) = tokens.next() {
+            match token {
+                "machine" => {
+                    let machine = tokens
+                        .next()
+                        .ok_or_else(|| NetrcError::MissingValue(token.to_string()))?;
+                    entries.push(NetrcEntry {
+                        machine: Some(machine.to_string()),
+                        login: None,
+                        password: None,
+                    });
+                }
+                "default" => {
+                    entries.push(NetrcEntry {
+                        machine: None,
+                        login: None,
+                        password: None,
+                    });
+                }
+                "login" | "password" | "account" => {
+                    let value = tokens
+                        .next()
+                        .ok_or_else(|| NetrcError::MissingValue(token.to_string()))?;
+                    let entry = entries
+                        .last_mut()
+                        .ok_or_else(|| NetrcError::UnexpectedToken(token.to_string()))?;
+                    match token {
+                        "login" => entry.login = Some(value.to_string()),
+                        "password" => entry.password = Some(value.to_string()),
+                        _ => {}
+                    }
+                }
+                _ => return Err(NetrcError::UnexpectedToken(token.to_string())),
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Return the credentials for the given host, falling back to the `default` entry.
+    pub fn find(&self, host: &str) -> Option<Credentials> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.machine.as_deref() == Some(host))
+            .or_else(|| self.entries.iter().find(|entry| entry.machine.is_none()))?;
+        if entry.login.is_none() && entry.password.is_none() {
+            return None;
+        }
+        Some(Credentials::new(
+            entry.login.clone().unwrap_or_default(),
+            entry.password.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Credentials, Netrc, NetrcError};
+
+    #[test]
+    fn find_machine() -> Result<(), NetrcError> {
+        let netrc = Netrc::parse(
+            "machine pypi.example.com login user password secret\n\
+             machine other.example.com\n  login other\n  password hunter2\n",
+        )?;
+        assert_eq!(
+            netrc.find("pypi.example.com"),
+            Some(Credentials::new(
+                "user".to_string(),
+                Some("secret".to_string())
+            ))
+        );
+        assert_eq!(
+            netrc.find("other.example.com"),
+            Some(Credentials::new(
+                "other".to_string(),
+                Some("hunter2".to_string())
+            ))
+        );
+        assert_eq!(netrc.find("example.com"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn find_default() -> Result<(), NetrcError> {
+        let netrc = Netrc::parse(
+            "machine pypi.example.com login user password secret\n\
+             default login anonymous password guest\n",
+        )?;
+        assert_eq!(
+            netrc.find("example.com"),
+            Some(Credentials::new(
+                "anonymous".to_string(),
+                Some("guest".to_string())
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn comments_and_macros() -> Result<(), NetrcError> {
+        let netrc = Netrc::parse(
+            "# A comment\n\
+             machine pypi.example.com login user password secret # trailing\n\
+             macdef init\n\
+             cd /pub\n\
+             machine ignored\n\
+             \n\
+             machine other.example.com login other password hunter2\n",
+        )?;
+        assert!(netrc.find("pypi.example.com").is_some());
+        assert!(netrc.find("other.example.com").is_some());
+        assert_eq!(netrc.find("ignored"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            Netrc::parse("machine"),
+            Err(NetrcError::MissingValue(_))
+        ));
+        assert!(matches!(
+            Netrc::parse("login user"),
+            Err(NetrcError::UnexpectedToken(_))
+        ));
+        assert!(matches!(
+            Netrc::parse("machine example.com port 22"),
+            Err(NetrcError::UnexpectedToken(_))
+        ));
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt::{Display, Formatter};
+
+use url::Url;
+
+/// The protection space for a set of credentials, composed of a scheme, host, and port.
+///
+/// Credentials that were found for one URL are only ever attached to requests for URLs in the same
+/// realm. Per RFC 7235, the path is not considered.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Realm {
+    scheme: String,
+    host: Option<String>,
+    port: Option<u16>,
+}
+
+impl From<&Url> for Realm {
+    fn from(url: &Url) -> Self {
+        Self {
+            scheme: url.scheme().to_string(),
+            host: url.host_str().map(str::to_string),
+            port: url.port_or_known_default(),
+        }
+    }
+}
+
+impl Display for Realm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}://{}",
+            self.scheme,
+            self.host.as_deref().unwrap_or_default()
+        )?;
+        if let Some(port) = self.port {
+            write!(f, ":{port}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::{ParseError, Url};
+
+    use crate::Realm;
+
+    #[test]
+    fn realm_ignores_path_and_default_port() -> Result<(), ParseError> {
+        assert_eq!(
+            Realm::from(&Url::parse("https://example.com/simple/")?),
+            Realm::from(&Url::parse("https://example.com:443/files/foo.whl")?)
+        );
+        assert_ne!(
+            Realm::from(&Url::parse("https://example.com")?),
+            Realm::from(&Url::parse("http://example.com")?)
+        );
+        assert_ne!(
+            Realm::from(&Url::parse("https://example.com")?),
+            Realm::from(&Url::parse("https://example.com:8443")?)
+        );
+        Ok(())
+    }
+}
@@ -5,9 +5,9 @@
 use std::ffi::OsString;
 use std::fmt::{Display, Formatter};
 use std::io;
-use std::io::BufRead;
+use std::io::{BufRead, Write};
 use std::path::{Path, PathBuf};
-use std::process::{ExitStatus, Output};
+use std::process::{ExitStatus, Output, Stdio};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::{env, iter};
@@ -23,14 +23,17 @@ use serde::de::{value, SeqAccess, Visitor};
 use serde::{de, Deserialize, Deserializer, Serialize};
 use tempfile::{tempdir_in, TempDir};
 use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncRead};
 use tokio::process::Command;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore, SemaphorePermit};
 use tracing::{debug, info_span, instrument, Instrument};
 
 use distribution_types::Resolution;
 use pep508_rs::Requirement;
+use uv_cache::{Cache, CacheBucket};
 use uv_fs::Simplified;
 use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_normalize::PackageName;
 use uv_traits::{BuildContext, BuildKind, ConfigSettings, SetupPyStrategy, SourceBuildTrait};
 
 /// e.g. `pygraphviz/graphviz_wrap.c:3020:10: fatal error: graphviz/cgraph.h: No such file or directory`
@@ -78,20 +81,22 @@ pub enum Error {
     Virtualenv(#[from] uv_virtualenv::Error),
     #[error("Failed to run {0}")]
     CommandFailed(PathBuf, #[source] io::Error),
-    #[error("{message} with {exit_code}\n--- stdout:\n{stdout}\n--- stderr:\n{stderr}\n---")]
+    #[error("{message} with {exit_code}\n--- stdout:\n{stdout}\n--- stderr:\n{stderr}\n---{}", display_build_log(.build_log.as_deref()))]
     BuildBackend {
         message: String,
         exit_code: ExitStatus,
         stdout: String,
         stderr: String,
+        build_log: Option<PathBuf>,
     },
     /// Nudge the user towards installing the missing dev library
-    #[error("{message} with {exit_code}\n--- stdout:\n{stdout}\n--- stderr:\n{stderr}\n---")]
+    #[error("{message} with {exit_code}\n--- stdout:\n{stdout}\n--- stderr:\n{stderr}\n---{}", display_build_log(.build_log.as_deref()))]
     MissingHeader {
         message: String,
         exit_code: ExitStatus,
         stdout: String,
         stderr: String,
+        build_log: Option<PathBuf>,
         #[source]
         missing_header_cause: MissingHeaderCause,
     },
@@ -133,11 +138,19 @@ impl Display for MissingHeaderCause {
     }
 }
 
+/// Point the user to the full build log, if one was written.
+fn display_build_log(build_log: Option<&Path>) -> String {
+    build_log
+        .map(|path| format!("\nFull build log: {}", path.simplified_display()))
+        .unwrap_or_default()
+}
+
 impl Error {
     fn from_command_output(
         message: String,
         output: &Output,
         package_id: impl Into<String>,
+        build_log: Option<&Path>,
     ) -> Self {
         let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
@@ -163,6 +176,7 @@ impl Error {
                 exit_code: output.status,
                 stdout,
                 stderr,
+                build_log: build_log.map(Path::to_path_buf),
                 missing_header_cause: MissingHeaderCause {
                     missing_library,
                     package_id: package_id.into(),
@@ -175,6 +189,7 @@ impl Error {
             exit_code: output.status,
             stdout,
             stderr,
+            build_log: build_log.map(Path::to_path_buf),
         }
     }
 }
@@ -301,6 +316,206 @@ impl Pep517Backend {
     }
 }
 
+/// The number of build logs to retain in the cache for each package.
+const MAX_CACHED_BUILD_LOGS: usize = 10;
+
+/// Where and how to record the output of build backends.
+#[derive(Debug, Default, Clone)]
+pub struct BuildLogOptions {
+    /// The directory in which to write build logs. Defaults to the cache.
+    pub directory: Option<PathBuf>,
+    /// The reporter to stream the output of build backends to while they run, if any.
+    pub reporter: Option<Arc<dyn BuildOutputReporter>>,
+}
+
+/// Receives the output of build backends while they run.
+pub trait BuildOutputReporter: Send + Sync + std::fmt::Debug {
+    /// Callback to invoke for each line of output from the build backend of the given package.
+    fn on_build_output(&self, package_id: &str, line: &str);
+}
+
+/// The log of a single source distribution build: the build environment, and every command
+/// invoked alongside its output.
+///
+/// A log is written for every build, whether it succeeds or not. Failing to write the log is not
+/// fatal to the build.
+#[derive(Debug)]
+struct BuildLog {
+    /// The log file, if it could be created.
+    path: Option<PathBuf>,
+    /// The reporter to stream the output of build backends to while they run, if any.
+    reporter: Option<Arc<dyn BuildOutputReporter>>,
+    /// Package id such as `foo-1.2.3`, to prefix the streamed output
+    package_id: String,
+}
+
+impl BuildLog {
+    /// Create a new, uniquely named log file for the given package.
+    ///
+    /// In the cache, logs are grouped by package name, and only the most recent logs for each
+    /// package are retained.
+    fn create(
+        options: &BuildLogOptions,
+        cache: &Cache,
+        package_name: Option<&PackageName>,
+        package_id: &str,
+    ) -> Self {
+        let directory = options.directory.clone().unwrap_or_else(|| {
+            let bucket = cache.bucket(CacheBucket::BuildLogs);
+            match package_name {
+                Some(package_name) => bucket.join(package_name.as_ref()),
+                None => bucket,
+            }
+        });
+        let path = fs::create_dir_all(&directory)
+            .and_then(|()| {
+                tempfile::Builder::new()
+                    .prefix(&format!("{}-", build_log_prefix(package_id)))
+                    .suffix(".log")
+                    .tempfile_in(&directory)?
+                    .keep()
+                    .map_err(|err| err.error)
+            })
+            .map(|(_, path)| path);
+        let path = match path {
+            Ok(path) => {
+                debug!("Writing build log to: {}", path.simplified_display());
+                if options.directory.is_none() {
+                    prune_build_logs(&directory, &path);
+                }
+                Some(path)
+            }
+            Err(err) => {
+                debug!(
+                    "Failed to create build log in {}: {err}",
+                    directory.simplified_display()
+                );
+                None
+            }
+        };
+        Self {
+            path,
+            reporter: options.reporter.clone(),
+            package_id: package_id.to_string(),
+        }
+    }
+
+    /// The path to the log file, if any.
+    fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Append to the log file.
+    fn write(&self, contents: &str) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()));
+        if let Err(err) = result {
+            debug!(
+                "Failed to write build log {}: {err}",
+                path.simplified_display()
+            );
+        }
+    }
+
+    /// Record the packages installed into the build environment.
+    fn write_requirements(&self, header: &str, resolution: &Resolution) {
+        let requirements = resolution
+            .requirements()
+            .iter()
+            .map(|requirement| format!("    {requirement}\n"))
+            .join("");
+        self.write(&format!("{header}:\n{requirements}\n"));
+    }
+
+    /// Record a command and its output.
+    fn write_command(&self, invocation: &str, output: &Output) {
+        self.write(&format!(
+            "--- Command: {invocation}\n--- Exit status: {}\n--- stdout:\n{}\n--- stderr:\n{}\n---\n\n",
+            output.status,
+            String::from_utf8_lossy(&output.stdout).trim(),
+            String::from_utf8_lossy(&output.stderr).trim(),
+        ));
+    }
+
+    /// Pass the output of a build backend to the reporter, line by line, while collecting it.
+    async fn stream(
+        &self,
+        reporter: &dyn BuildOutputReporter,
+        reader: impl AsyncRead + Unpin,
+    ) -> io::Result<Vec<u8>> {
+        let mut reader = tokio::io::BufReader::new(reader);
+        let mut buffer = Vec::new();
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line).await? == 0 {
+                break;
+            }
+            reporter.on_build_output(
+                &self.package_id,
+                String::from_utf8_lossy(&line).trim_end_matches(['\r', '\n']),
+            );
+            buffer.extend_from_slice(&line);
+        }
+        Ok(buffer)
+    }
+}
+
+/// Remove all but the most recent build logs in the given directory, retaining `current`.
+///
+/// Pruning is best-effort: failures are logged, but not fatal.
+fn prune_build_logs(directory: &Path, current: &Path) {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(err) => {
+            debug!(
+                "Failed to read build logs in {}: {err}",
+                directory.simplified_display()
+            );
+            return;
+        }
+    };
+    let mut logs = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path != current && path.extension().is_some_and(|ext| ext == "log"))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|metadata| metadata.modified());
+            modified.ok().map(|modified| (modified, path))
+        })
+        .collect::<Vec<_>>();
+    logs.sort_unstable_by(|a, b| b.cmp(a));
+    for (_, path) in logs.into_iter().skip(MAX_CACHED_BUILD_LOGS - 1) {
+        debug!("Removing old build log: {}", path.simplified_display());
+        if let Err(err) = fs::remove_file(&path) {
+            debug!(
+                "Failed to remove build log {}: {err}",
+                path.simplified_display()
+            );
+        }
+    }
+}
+
+/// Turn a package id, which may be a URL or a path, into a file name prefix.
+fn build_log_prefix(package_id: &str) -> String {
+    package_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect()
+}
+
 /// Uses an [`Arc`] internally, clone freely
 #[derive(Debug, Default, Clone)]
 pub struct SourceBuildContext {
@@ -334,18 +549,32 @@ pub struct SourceBuild {
     metadata_directory: Option<PathBuf>,
     /// Package id such as `foo-1.2.3`, for error reporting
     package_id: String,
-    /// Whether we do a regular PEP 517 build or an PEP 660 editable build
+    /// Whether we do a regular PEP 517 build, a PEP 660 editable build or a source distribution
+    /// build
     build_kind: BuildKind,
     /// Modified PATH that contains the `venv_bin`, `user_path` and `system_path` variables in that order
     modified_path: OsString,
     /// Environment variables to be passed in during metadata or wheel building
     environment_variables: FxHashMap<OsString, OsString>,
+    /// Bounds the number of build backends running at the same time, across all builds
+    build_semaphore: Arc<Semaphore>,
+    /// The log of the commands run for this build, and their output
+    build_log: BuildLog,
+    /// The resolved requirements installed into the build environment
+    build_requirements: Vec<Requirement>,
 }
 
 impl SourceBuild {
     /// Create a virtual environment in which to build a source distribution, extracting the
     /// contents from an archive if necessary.
     ///
+    /// If a `shared_environment` is provided, the build backend runs in that environment instead,
+    /// without isolation: the build requirements are assumed to be installed already.
+    ///
+    /// The build environment and the output of every build backend invocation are written to a
+    /// log file, as configured by `build_log_options`. The `package_name`, if known, is used to
+    /// group the logs in the cache.
+    ///
     /// `source_dist` is for error reporting only.
     #[allow(clippy::too_many_arguments)]
     pub async fn setup(
@@ -354,13 +583,28 @@ impl SourceBuild {
         interpreter: &Interpreter,
         build_context: &impl BuildContext,
         source_build_context: SourceBuildContext,
+        package_name: Option<&PackageName>,
         package_id: String,
         setup_py: SetupPyStrategy,
         config_settings: ConfigSettings,
         build_kind: BuildKind,
         mut environment_variables: FxHashMap<OsString, OsString>,
+        shared_environment: Option<&PythonEnvironment>,
+        build_log_options: &BuildLogOptions,
     ) -> Result<Self, Error> {
         let temp_dir = tempdir_in(build_context.cache().root())?;
+        let build_log = BuildLog::create(
+            build_log_options,
+            build_context.cache(),
+            package_name,
+            &package_id,
+        );
+        build_log.write(&format!(
+            "Build log for {package_id} ({})\nPython: {} ({})\n\n",
+            build_kind.description(),
+            interpreter.python_version(),
+            interpreter.sys_executable().simplified_display(),
+        ));
 
         let metadata = match fs::metadata(source) {
             Ok(metadata) => metadata,
@@ -402,27 +646,50 @@ impl SourceBuild {
         let pep517_backend = Self::get_pep517_backend(setup_py, &source_tree, &default_backend)
             .map_err(|err| *err)?;
 
-        let venv = uv_virtualenv::create_venv(
-            &temp_dir.path().join(".venv"),
-            interpreter.clone(),
-            uv_virtualenv::Prompt::None,
-            false,
-            Vec::new(),
-        )?;
+        let mut build_requirements = Vec::new();
+        let venv = if let Some(venv) = shared_environment {
+            debug!(
+                "Building without isolation in: {}",
+                venv.root().simplified_display()
+            );
+            build_log.write(&format!(
+                "Build environment (without isolation): {}\n\n",
+                venv.root().simplified_display()
+            ));
+            venv.clone()
+        } else {
+            let venv = uv_virtualenv::create_venv(
+                &temp_dir.path().join(".venv"),
+                interpreter.clone(),
+                uv_virtualenv::Prompt::None,
+                false,
+                Vec::new(),
+            )?;
+
+            // Setup the build environment.
+            let resolved_requirements = Self::get_resolved_requirements(
+                build_context,
+                source_build_context,
+                &default_backend,
+                pep517_backend.as_ref(),
+            )
+            .await?;
+            build_log.write(&format!(
+                "Build environment: {}\n\n",
+                venv.root().simplified_display()
+            ));
+            build_log.write_requirements("Build requirements", &resolved_requirements);
+            build_requirements = resolved_requirements.requirements();
 
-        // Setup the build environment.
-        let resolved_requirements = Self::get_resolved_requirements(
-            build_context,
-            source_build_context,
-            &default_backend,
-            pep517_backend.as_ref(),
-        )
-        .await?;
+            build_context
+                .install(&resolved_requirements, &venv)
+                .await
+                .map_err(|err| {
+                    Error::RequirementsInstall("build-system.requires (install)", err)
+                })?;
 
-        build_context
-            .install(&resolved_requirements, &venv)
-            .await
-            .map_err(|err| Error::RequirementsInstall("build-system.requires (install)", err))?;
+            venv
+        };
 
         // Figure out what the modified path should be
         // Remove the PATH variable from the environment variables if it's there
@@ -454,8 +721,10 @@ impl SourceBuild {
             OsString::from(venv.scripts())
         };
 
-        if let Some(pep517_backend) = &pep517_backend {
-            create_pep517_build_environment(
+        // Without isolation, the build environment is assumed to contain the extra requirements,
+        // too.
+        if let (Some(pep517_backend), None) = (&pep517_backend, shared_environment) {
+            if let Some(resolution) = create_pep517_build_environment(
                 &source_tree,
                 &venv,
                 pep517_backend,
@@ -465,8 +734,12 @@ impl SourceBuild {
                 &config_settings,
                 &environment_variables,
                 &modified_path,
+                &build_log,
             )
-            .await?;
+            .await?
+            {
+                build_requirements = resolution.requirements();
+            }
         }
 
         Ok(Self {
@@ -480,6 +753,9 @@ impl SourceBuild {
             package_id,
             environment_variables,
             modified_path,
+            build_semaphore: build_context.build_semaphore().clone(),
+            build_log,
+            build_requirements,
         })
     }
 
@@ -620,12 +896,14 @@ impl SourceBuild {
             script="prepare_metadata_for_build_wheel",
             python_version = %self.venv.interpreter().python_version()
         );
+        let _permit = acquire_build_permit(&self.build_semaphore).await;
         let output = run_python_script(
             &self.venv,
             &script,
             &self.source_tree,
             &self.environment_variables,
             &self.modified_path,
+            &self.build_log,
         )
         .instrument(span)
         .await?;
@@ -634,6 +912,7 @@ impl SourceBuild {
                 "Build backend failed to determine metadata through `prepare_metadata_for_build_wheel`".to_string(),
                 &output,
                 &self.package_id,
+                self.build_log.path(),
             ));
         }
         let message = output
@@ -650,6 +929,7 @@ impl SourceBuild {
                     ),
                     &output,
                     &self.package_id,
+                    self.build_log.path(),
                 )
             })?;
         if message.is_empty() {
@@ -660,7 +940,7 @@ impl SourceBuild {
     }
 
     /// Build a source distribution from an archive (`.zip` or `.tar.gz`), return the location of the
-    /// built wheel.
+    /// built wheel (or, for [`BuildKind::Sdist`], the rebuilt source distribution).
     ///
     /// The location will be inside `temp_dir`, i.e. you must use the wheel before dropping the temp
     /// dir.
@@ -681,28 +961,41 @@ impl SourceBuild {
             fs_err::rename(from, to)?;
             Ok(filename)
         } else {
-            if self.build_kind != BuildKind::Wheel {
-                return Err(Error::EditableSetupPy);
-            }
+            let command = match self.build_kind {
+                BuildKind::Wheel => "bdist_wheel",
+                BuildKind::Sdist => "sdist",
+                BuildKind::Editable => return Err(Error::EditableSetupPy),
+            };
             // We checked earlier that setup.py exists.
             let python_interpreter = self.venv.python_executable();
             let span = info_span!(
                 "run_python_script",
-                script="setup.py bdist_wheel",
+                script=format!("setup.py {command}"),
                 python_version = %self.venv.interpreter().python_version()
             );
-            let output = Command::new(python_interpreter)
-                .args(["setup.py", "bdist_wheel"])
-                .current_dir(self.source_tree.simplified())
-                .output()
+            let _permit = acquire_build_permit(&self.build_semaphore).await;
+            let mut setup_py = Command::new(python_interpreter);
+            setup_py
+                .args(["setup.py", command])
+                .current_dir(self.source_tree.simplified());
+            let invocation = format!(
+                "{} setup.py {command}\n--- Working directory: {}",
+                python_interpreter.simplified_display(),
+                self.source_tree.simplified_display()
+            );
+            let output = run_command(setup_py, &invocation, &self.build_log)
                 .instrument(span)
                 .await
                 .map_err(|err| Error::CommandFailed(python_interpreter.to_path_buf(), err))?;
             if !output.status.success() {
                 return Err(Error::from_command_output(
-                    "Failed building wheel through setup.py".to_string(),
+                    format!(
+                        "Failed building {} through setup.py",
+                        self.build_kind.description()
+                    ),
                     &output,
                     &self.package_id,
+                    self.build_log.path(),
                 ));
             }
             let dist = fs::read_dir(self.source_tree.join("dist"))?;
@@ -710,10 +1003,12 @@ impl SourceBuild {
             let [dist_wheel] = dist_dir.as_slice() else {
                 return Err(Error::from_command_output(
                     format!(
-                        "Expected exactly wheel in `dist/` after invoking setup.py, found {dist_dir:?}"
+                        "Expected exactly one {} in `dist/` after invoking setup.py, found {dist_dir:?}",
+                        self.build_kind.description()
                     ),
                     &output,
-                    &self.package_id)
+                    &self.package_id,
+                    self.build_log.path())
                 );
             };
 
@@ -741,40 +1036,59 @@ impl SourceBuild {
             pep517_backend.backend, self.build_kind, metadata_directory
         );
         let escaped_wheel_dir = escape_path_for_python(wheel_dir);
-        let script = formatdoc! {
-            r#"
-            {}
-
-            print(backend.build_{}("{}", metadata_directory={}, config_settings={}))
-            "#,
-            pep517_backend.backend_import(),
-            self.build_kind,
-            escaped_wheel_dir,
-            metadata_directory,
-            self.config_settings.escape_for_python()
+        // Unlike `build_wheel` and `build_editable`, `build_sdist` doesn't accept a metadata
+        // directory.
+        let script = if self.build_kind == BuildKind::Sdist {
+            formatdoc! {
+                r#"
+                {}
+
+                print(backend.build_sdist("{}", config_settings={}))
+                "#,
+                pep517_backend.backend_import(),
+                escaped_wheel_dir,
+                self.config_settings.escape_for_python()
+            }
+        } else {
+            formatdoc! {
+                r#"
+                {}
+
+                print(backend.build_{}("{}", metadata_directory={}, config_settings={}))
+                "#,
+                pep517_backend.backend_import(),
+                self.build_kind,
+                escaped_wheel_dir,
+                metadata_directory,
+                self.config_settings.escape_for_python()
+            }
         };
         let span = info_span!(
             "run_python_script",
             script=format!("build_{}", self.build_kind),
             python_version = %self.venv.interpreter().python_version()
         );
+        let _permit = acquire_build_permit(&self.build_semaphore).await;
         let output = run_python_script(
             &self.venv,
             &script,
             &self.source_tree,
             &self.environment_variables,
             &self.modified_path,
+            &self.build_log,
         )
         .instrument(span)
         .await?;
         if !output.status.success() {
             return Err(Error::from_command_output(
                 format!(
-                    "Build backend failed to build wheel through `build_{}()`",
+                    "Build backend failed to build {} through `build_{}()`",
+                    self.build_kind.description(),
                     self.build_kind
                 ),
                 &output,
                 &self.package_id,
+                self.build_log.path(),
             ));
         }
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -784,11 +1098,13 @@ impl SourceBuild {
         else {
             return Err(Error::from_command_output(
                 format!(
-                    "Build backend failed to build wheel through `build_{}()`",
+                    "Build backend failed to build {} through `build_{}()`",
+                    self.build_kind.description(),
                     self.build_kind
                 ),
                 &output,
                 &self.package_id,
+                self.build_log.path(),
             ));
         };
         Ok(distribution_filename.to_string())
@@ -803,6 +1119,10 @@ impl SourceBuildTrait for SourceBuild {
     async fn wheel<'a>(&'a self, wheel_dir: &'a Path) -> anyhow::Result<String> {
         Ok(self.build(wheel_dir).await?)
     }
+
+    fn build_requirements(&self) -> &[Requirement] {
+        &self.build_requirements
+    }
 }
 
 fn escape_path_for_python(path: &Path) -> String {
@@ -812,6 +1132,8 @@ fn escape_path_for_python(path: &Path) -> String {
 }
 
 /// Not a method because we call it before the builder is completely initialized
+///
+/// Returns the resolution of the build environment, if extra requirements had to be installed.
 #[allow(clippy::too_many_arguments)]
 async fn create_pep517_build_environment(
     source_tree: &Path,
@@ -823,7 +1145,8 @@ async fn create_pep517_build_environment(
     config_settings: &ConfigSettings,
     environment_variables: &FxHashMap<OsString, OsString>,
     modified_path: &OsString,
-) -> Result<(), Error> {
+    build_log: &BuildLog,
+) -> Result<Option<Resolution>, Error> {
     debug!(
         "Calling `{}.get_requires_for_build_{}()`",
         pep517_backend.backend, build_kind
@@ -846,20 +1169,27 @@ async fn create_pep517_build_environment(
         script=format!("get_requires_for_build_{}", build_kind),
         python_version = %venv.interpreter().python_version()
     );
-    let output = run_python_script(
-        venv,
-        &script,
-        source_tree,
-        environment_variables,
-        modified_path,
-    )
-    .instrument(span)
-    .await?;
+    // Release the permit before installing any extra requirements, which may require building
+    // further source distributions.
+    let output = {
+        let _permit = acquire_build_permit(build_context.build_semaphore()).await;
+        run_python_script(
+            venv,
+            &script,
+            source_tree,
+            environment_variables,
+            modified_path,
+            build_log,
+        )
+        .instrument(span)
+        .await?
+    };
     if !output.status.success() {
         return Err(Error::from_command_output(
             format!("Build backend failed to determine extra requires with `build_{build_kind}()`"),
             &output,
             package_id,
+            build_log.path(),
         ));
     }
     let extra_requires = output
@@ -878,6 +1208,7 @@ async fn create_pep517_build_environment(
             ),
             &output,
             package_id,
+            build_log.path(),
         )
     })?;
 
@@ -900,14 +1231,28 @@ async fn create_pep517_build_environment(
             .resolve(&requirements)
             .await
             .map_err(|err| Error::RequirementsInstall("build-system.requires (resolve)", err))?;
+        build_log.write_requirements(
+            &format!("Build requirements (including `get_requires_for_build_{build_kind}`)"),
+            &resolution,
+        );
 
         build_context
             .install(&resolution, venv)
             .await
             .map_err(|err| Error::RequirementsInstall("build-system.requires (install)", err))?;
+
+        return Ok(Some(resolution));
     }
 
-    Ok(())
+    Ok(None)
+}
+
+/// Wait until fewer than the configured number of build backends are running.
+async fn acquire_build_permit(semaphore: &Semaphore) -> SemaphorePermit<'_> {
+    semaphore
+        .acquire()
+        .await
+        .expect("The build semaphore is never closed")
 }
 
 /// It is the caller's responsibility to create an informative span.
@@ -917,8 +1262,10 @@ async fn run_python_script(
     source_tree: &Path,
     environment_variables: &FxHashMap<OsString, OsString>,
     modified_path: &OsString,
+    build_log: &BuildLog,
 ) -> Result<Output, Error> {
-    Command::new(venv.python_executable())
+    let mut command = Command::new(venv.python_executable());
+    command
         .args(["-c", script])
         .current_dir(source_tree.simplified())
         // Pass in remaining environment variables
@@ -926,12 +1273,50 @@ async fn run_python_script(
         // Set the modified PATH
         .env("PATH", modified_path)
         // Activate the venv
-        .env("VIRTUAL_ENV", venv.root())
-        .output()
+        .env("VIRTUAL_ENV", venv.root());
+    let invocation = format!(
+        "{} -c <script>\n--- Working directory: {}\n--- Script:\n{}",
+        venv.python_executable().simplified_display(),
+        source_tree.simplified_display(),
+        script.trim()
+    );
+    run_command(command, &invocation, build_log)
         .await
         .map_err(|err| Error::CommandFailed(venv.python_executable().to_path_buf(), err))
 }
 
+/// Run a build backend command, recording it and its output in the build log.
+///
+/// If a reporter is configured, the output is streamed to it while the command runs.
+async fn run_command(
+    mut command: Command,
+    invocation: &str,
+    build_log: &BuildLog,
+) -> io::Result<Output> {
+    let output = if let Some(reporter) = build_log.reporter.as_deref() {
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
+        let (stdout, stderr, status) = tokio::try_join!(
+            build_log.stream(reporter, stdout),
+            build_log.stream(reporter, stderr),
+            child.wait()
+        )?;
+        Output {
+            status,
+            stdout,
+            stderr,
+        }
+    } else {
+        command.output().await?
+    };
+    build_log.write_command(invocation, &output);
+    Ok(output)
+}
+
 #[cfg(test)]
 mod test {
     use std::process::{ExitStatus, Output};
@@ -970,6 +1355,7 @@ mod test {
             "Failed building wheel through setup.py".to_string(),
             &output,
             "pygraphviz-1.11",
+            None,
         );
         assert!(matches!(err, Error::MissingHeader { .. }));
         // Unix uses exit status, Windows uses exit code.
@@ -1022,6 +1408,7 @@ mod test {
             "Failed building wheel through setup.py".to_string(),
             &output,
             "pygraphviz-1.11",
+            None,
         );
         assert!(matches!(err, Error::MissingHeader { .. }));
         // Unix uses exit status, Windows uses exit code.
@@ -276,6 +276,7 @@ impl Cache {
             CacheBucket::Git,
             CacheBucket::Interpreter,
             CacheBucket::Simple,
+            CacheBucket::BuildLogs,
         ] {
             summary += bucket.remove(self, name)?;
         }
@@ -362,7 +363,7 @@ pub enum CacheBucket {
     /// If we run first `pip compile` and then `pip sync` on the same machine, we get both:
     ///
     /// ```text
-    /// wheels-v0
+    /// wheels-v1
     /// ├── pypi
     /// │   ├── ...
     /// │   ├── pandas
@@ -403,15 +404,15 @@ pub enum CacheBucket {
     /// directories in the cache.
     ///
     /// Cache structure:
-    ///  * `built-wheels-v0/pypi/foo/34a17436ed1e9669/{manifest.msgpack, metadata.msgpack, foo-1.0.0.zip, foo-1.0.0-py3-none-any.whl, ...other wheels}`
-    ///  * `built-wheels-v0/<digest(index-url)>/foo/foo-1.0.0.zip/{manifest.msgpack, metadata.msgpack, foo-1.0.0-py3-none-any.whl, ...other wheels}`
-    ///  * `built-wheels-v0/url/<digest(url)>/foo/foo-1.0.0.zip/{manifest.msgpack, metadata.msgpack, foo-1.0.0-py3-none-any.whl, ...other wheels}`
-    ///  * `built-wheels-v0/git/<digest(url)>/<git sha>/foo/foo-1.0.0.zip/{metadata.msgpack, foo-1.0.0-py3-none-any.whl, ...other wheels}`
+    ///  * `built-wheels-v1/pypi/foo/34a17436ed1e9669/{manifest.msgpack, metadata.msgpack, foo-1.0.0.zip, foo-1.0.0-py3-none-any.whl, ...other wheels}`
+    ///  * `built-wheels-v1/<digest(index-url)>/foo/foo-1.0.0.zip/{manifest.msgpack, metadata.msgpack, foo-1.0.0-py3-none-any.whl, ...other wheels}`
+    ///  * `built-wheels-v1/url/<digest(url)>/foo/foo-1.0.0.zip/{manifest.msgpack, metadata.msgpack, foo-1.0.0-py3-none-any.whl, ...other wheels}`
+    ///  * `built-wheels-v1/git/<digest(url)>/<git sha>/foo/foo-1.0.0.zip/{metadata.msgpack, foo-1.0.0-py3-none-any.whl, ...other wheels}`
     ///
     /// But the url filename does not need to be a valid source dist filename
     /// (<https://github.com/search?q=path%3A**%2Frequirements.txt+master.zip&type=code>),
     /// so it could also be the following and we have to take any string as filename:
-    ///  * `built-wheels-v0/url/<sha256(url)>/master.zip/metadata.msgpack`
+    ///  * `built-wheels-v1/url/<sha256(url)>/master.zip/metadata.msgpack`
     ///
     /// # Example
     ///
@@ -427,7 +428,7 @@ pub enum CacheBucket {
     ///
     /// ...may be cached as:
     /// ```text
-    /// built-wheels-v0/
+    /// built-wheels-v1/
     /// ├── git
     /// │   └── a67db8ed076e3814
     /// │       └── 843b753e9e8cb74e83cac55598719b39a4d5ef1f
@@ -515,18 +516,31 @@ pub enum CacheBucket {
     /// that cache entries can be atomically replaced and removed, as storing directories in the
     /// other buckets directly would make atomic operations impossible.
     Archive,
+    /// Logs of the source distribution builds, including the resolved build requirements, the
+    /// commands invoked and their output.
+    ///
+    /// Cache structure:
+    ///  * `build-logs-v0/<package_name>/<package_id>-<random>.log`
+    ///  * `build-logs-v0/<package_id>-<random>.log`, for builds of packages whose name isn't known
+    ///    upfront (e.g., editables)
+    ///
+    /// A log is written for every build, whether it succeeded or not, such that the output of the
+    /// build backend can be inspected after the fact. Only the most recent logs for each package
+    /// are retained.
+    BuildLogs,
 }
 
 impl CacheBucket {
     fn to_str(self) -> &'static str {
         match self {
-            Self::BuiltWheels => "built-wheels-v0",
+            Self::BuiltWheels => "built-wheels-v1",
             Self::FlatIndex => "flat-index-v0",
             Self::Git => "git-v0",
             Self::Interpreter => "interpreter-v0",
             Self::Simple => "simple-v3",
-            Self::Wheels => "wheels-v0",
+            Self::Wheels => "wheels-v1",
             Self::Archive => "archive-v0",
+            Self::BuildLogs => "build-logs-v0",
         }
     }
 
@@ -617,6 +631,11 @@ impl CacheBucket {
             Self::Archive => {
                 // Nothing to do.
             }
+            Self::BuildLogs => {
+                // We expect a directory per package (indexed by name).
+                let root = cache.bucket(self);
+                summary += rm_rf(root.join(name.to_string()))?;
+            }
         }
         Ok(summary)
     }
@@ -9,7 +9,8 @@ use distribution_filename::{WheelFilename, WheelFilenameError};
 use uv_normalize::PackageName;
 
 use crate::html;
-use crate::middleware::OfflineError;
+use crate::middleware::{OfflineError, RetriesExhausted};
+use crate::proxy::ProxyConfig;
 
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
@@ -33,6 +34,21 @@ impl Error {
     pub(crate) fn from_html_err(err: html::Error, url: Url) -> Self {
         ErrorKind::BadHtml { source: err, url }.into()
     }
+
+    /// Record how the failed request was routed, given the client's proxy configuration.
+    pub(crate) fn with_proxy_route(mut self, proxy: &ProxyConfig) -> Self {
+        self.kind.set_proxy_route(proxy);
+        self
+    }
+
+    /// Returns `true` if the error is due to a request timing out (including after retrying).
+    pub fn is_timeout(&self) -> bool {
+        match &*self.kind {
+            ErrorKind::ReqwestError(err) => err.is_timeout(),
+            ErrorKind::RetriesExhausted { source, .. } => source.is_timeout(),
+            _ => false,
+        }
+    }
 }
 
 impl From<ErrorKind> for Error {
@@ -71,6 +87,15 @@ pub enum ErrorKind {
     #[error("Couldn't parse metadata of {0} from {1}")]
     MetadataParseError(WheelFilename, String, #[source] Box<pypi_types::Error>),
 
+    /// The metadata file didn't match the hash provided by the registry.
+    #[error("Hash mismatch for metadata of {filename} from {url}\n\nExpected:\n  sha256:{expected}\n\nComputed:\n  sha256:{actual}")]
+    MetadataHashMismatch {
+        filename: WheelFilename,
+        url: String,
+        expected: String,
+        actual: String,
+    },
+
     /// The metadata file was not found in the wheel.
     #[error("Metadata file `{0}` was not found in {1}")]
     MetadataNotFound(WheelFilename, String),
@@ -84,6 +109,15 @@ pub enum ErrorKind {
     #[error(transparent)]
     ReqwestError(#[from] BetterReqwestError),
 
+    /// A request failed with a transient error (e.g., a dropped connection or a `5xx` response),
+    /// and continued to fail after retrying.
+    #[error("Request failed after {attempts} attempts")]
+    RetriesExhausted {
+        attempts: u32,
+        #[source]
+        source: Box<Error>,
+    },
+
     /// A generic request middleware error happened while making a request.
     /// Refer to the error message for more details.
     #[error(transparent)]
@@ -146,6 +180,12 @@ pub enum ErrorKind {
 
     #[error("Network connectivity is disabled, but the requested data wasn't found in the cache for: `{0}`")]
     Offline(String),
+
+    #[error("Expected a file URL, but received: {0}")]
+    NonFileUrl(Url),
+
+    #[error("Refusing to connect to `{0}` over plain HTTP; to allow it, mark the host as trusted with `--trusted-host {1}`")]
+    InsecureUrl(Url, String),
 }
 
 impl ErrorKind {
@@ -205,6 +245,27 @@ impl ErrorKind {
 
         false
     }
+
+    /// Record how the failed request was routed, for any underlying connection error.
+    fn set_proxy_route(&mut self, proxy: &ProxyConfig) {
+        match self {
+            Self::ReqwestError(err) | Self::FileNotFound(_, err) => err.set_proxy_route(proxy),
+            Self::RetriesExhausted { source, .. } => source.kind.set_proxy_route(proxy),
+            _ => {}
+        }
+    }
+
+    /// Returns `true` if the error is due to a range request that was interrupted (e.g., by a
+    /// dropped connection), such that it may succeed if retried.
+    pub(crate) fn is_http_range_request_interrupted(&self) -> bool {
+        if self.is_http_range_requests_unsupported() {
+            return false;
+        }
+        matches!(
+            self,
+            Self::AsyncHttpRangeReader(_) | Self::Zip(_, ZipError::UpstreamReadError(_))
+        )
+    }
 }
 
 impl From<reqwest::Error> for ErrorKind {
@@ -221,7 +282,15 @@ impl From<reqwest_middleware::Error> for ErrorKind {
         }
 
         match error {
-            reqwest_middleware::Error::Middleware(err) => Self::ReqwestMiddlewareError(err),
+            reqwest_middleware::Error::Middleware(err) => {
+                match err.downcast::<RetriesExhausted>() {
+                    Ok(RetriesExhausted { attempts, source }) => Self::RetriesExhausted {
+                        attempts,
+                        source: Box::new(Self::from(source).into()),
+                    },
+                    Err(err) => Self::ReqwestMiddlewareError(err),
+                }
+            }
             reqwest_middleware::Error::Reqwest(err) => Self::from(err),
         }
     }
@@ -230,25 +299,36 @@ impl From<reqwest_middleware::Error> for ErrorKind {
 /// Handle the case with no internet by explicitly telling the user instead of showing an obscure
 /// DNS error.
 #[derive(Debug)]
-pub struct BetterReqwestError(reqwest::Error);
+pub struct BetterReqwestError {
+    err: reqwest::Error,
+    /// How the request was routed with respect to the configured proxy, if any.
+    route: Option<String>,
+}
 
 impl BetterReqwestError {
+    /// Record how the request was routed, if it failed to connect or timed out.
+    fn set_proxy_route(&mut self, proxy: &ProxyConfig) {
+        if self.err.is_connect() || self.err.is_timeout() {
+            self.route = self.err.url().and_then(|url| proxy.describe_route(url));
+        }
+    }
+
     fn is_likely_offline(&self) -> bool {
-        if !self.0.is_connect() {
+        if !self.err.is_connect() {
             return false;
         }
         // Self is "error sending request for url", the first source is "error trying to connect",
         // the second source is "dns error". We have to check for the string because hyper errors
         // are opaque.
-        std::error::Error::source(&self.0)
+        std::error::Error::source(&self.err)
             .and_then(|err| err.source())
             .is_some_and(|err| err.to_string().starts_with("dns error: "))
     }
 }
 
 impl From<reqwest::Error> for BetterReqwestError {
-    fn from(error: reqwest::Error) -> Self {
-        Self(error)
+    fn from(err: reqwest::Error) -> Self {
+        Self { err, route: None }
     }
 }
 
@@ -256,26 +336,31 @@ impl Deref for BetterReqwestError {
     type Target = reqwest::Error;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.err
     }
 }
 
 impl Display for BetterReqwestError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if self.is_likely_offline() {
-            f.write_str("Could not connect, are you offline?")
+            f.write_str("Could not connect, are you offline?")?;
         } else {
-            Display::fmt(&self.0, f)
+            Display::fmt(&self.err, f)?;
+        }
+        // Note whether the request went through a proxy, or bypassed one.
+        if let Some(route) = &self.route {
+            write!(f, " ({route})")?;
         }
+        Ok(())
     }
 }
 
 impl std::error::Error for BetterReqwestError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         if self.is_likely_offline() {
-            Some(&self.0)
+            Some(&self.err)
         } else {
-            self.0.source()
+            self.err.source()
         }
     }
 }
@@ -11,11 +11,11 @@ use url::Url;
 use distribution_filename::DistFilename;
 use distribution_types::{
     BuiltDist, Dist, File, FileLocation, FlatIndexLocation, IndexUrl, PrioritizedDist,
-    RegistryBuiltDist, RegistrySourceDist, SourceDist,
+    RegistryBuiltDist, RegistrySourceDist, SourceDist, WheelCompatibility,
 };
 use pep440_rs::Version;
 use pep508_rs::VerbatimUrl;
-use platform_tags::Tags;
+use platform_tags::{TagPriority, Tags};
 use pypi_types::{Hashes, Yanked};
 use uv_auth::safe_copy_url_auth;
 use uv_cache::{Cache, CacheBucket};
@@ -130,10 +130,15 @@ impl<'a> FlatIndexClient<'a> {
 
     /// Read a flat remote index from a `--find-links` URL.
     async fn read_from_url(&self, url: &Url) -> Result<FlatIndexEntries, Error> {
+        self.client.require_secure(url)?;
+
         let cache_entry = self.cache.entry(
             CacheBucket::FlatIndex,
             "html",
-            format!("{}.msgpack", cache_key::digest(&url.to_string())),
+            format!(
+                "{}.msgpack",
+                cache_key::digest(&cache_key::CanonicalUrl::new(url))
+            ),
         );
         let cache_control = match self.client.connectivity() {
             Connectivity::Online => CacheControl::from(
@@ -271,8 +276,11 @@ pub struct FlatIndex {
 
 impl FlatIndex {
     /// Collect all files from a `--find-links` target into a [`FlatIndex`].
+    ///
+    /// If `tags` is `None`, wheels are considered compatible with any environment (as in a
+    /// universal resolution).
     #[instrument(skip_all)]
-    pub fn from_entries(entries: FlatIndexEntries, tags: &Tags) -> Self {
+    pub fn from_entries(entries: FlatIndexEntries, tags: Option<&Tags>) -> Self {
         // Collect compatible distributions.
         let mut index = FxHashMap::default();
         for (filename, file, url) in entries.entries {
@@ -290,14 +298,19 @@ impl FlatIndex {
         distributions: &mut FlatDistributions,
         file: File,
         filename: DistFilename,
-        tags: &Tags,
+        tags: Option<&Tags>,
         index: IndexUrl,
     ) {
         // No `requires-python` here: for source distributions, we don't have that information;
         // for wheels, we read it lazily only when selected.
         match filename {
             DistFilename::WheelFilename(filename) => {
-                let compatibility = filename.compatibility(tags);
+                let compatibility = match tags {
+                    Some(tags) => WheelCompatibility::from(filename.compatibility(tags)),
+                    None => WheelCompatibility::Compatible(
+                        TagPriority::try_from(0).expect("valid tag priority"),
+                    ),
+                };
                 let version = filename.version.clone();
 
                 let dist = Dist::Built(BuiltDist::Registry(RegistryBuiltDist {
@@ -312,7 +325,7 @@ impl FlatIndex {
                             None,
                             Yanked::default(),
                             None,
-                            compatibility.into(),
+                            compatibility,
                         );
                     }
                     Entry::Vacant(entry) => {
@@ -321,7 +334,7 @@ impl FlatIndex {
                             None,
                             Yanked::default(),
                             None,
-                            compatibility.into(),
+                            compatibility,
                         ));
                     }
                 }
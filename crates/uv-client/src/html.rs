@@ -156,10 +156,13 @@ impl SimpleHtml {
             None
         };
 
-        // Extract the `data-dist-info-metadata` field, which should be set on
-        // the `data-dist-info-metadata` attribute.
-        let dist_info_metadata = if let Some(dist_info_metadata) =
-            link.attributes().get("data-dist-info-metadata").flatten()
+        // Extract the `core-metadata` field, which should be set on the `data-core-metadata`
+        // attribute (PEP 714), or on the `data-dist-info-metadata` attribute that it superseded.
+        let dist_info_metadata = if let Some(dist_info_metadata) = link
+            .attributes()
+            .get("data-core-metadata")
+            .flatten()
+            .or_else(|| link.attributes().get("data-dist-info-metadata").flatten())
         {
             let dist_info_metadata = std::str::from_utf8(dist_info_metadata.as_bytes())?;
             let dist_info_metadata = html_escape::decode_html_entities(dist_info_metadata);
@@ -173,13 +176,16 @@ impl SimpleHtml {
         };
 
         // Extract the `yanked` field, which should be set on the `data-yanked`
-        // attribute.
-        let yanked = if let Some(yanked) = link.attributes().get("data-yanked").flatten() {
-            let yanked = std::str::from_utf8(yanked.as_bytes())?;
-            let yanked = html_escape::decode_html_entities(yanked);
-            Some(Yanked::Reason(yanked.to_string()))
-        } else {
-            None
+        // attribute. The attribute may be present without a value, in which case no reason was
+        // provided.
+        let yanked = match link.attributes().get("data-yanked") {
+            Some(Some(yanked)) => {
+                let yanked = std::str::from_utf8(yanked.as_bytes())?;
+                let yanked = html_escape::decode_html_entities(yanked);
+                Some(Yanked::Reason(yanked.to_string()))
+            }
+            Some(None) => Some(Yanked::Bool(true)),
+            None => None,
         };
 
         Ok(File {
@@ -909,4 +915,33 @@ mod tests {
         }
         "###);
     }
+
+    #[test]
+    fn parse_core_metadata_and_yanked() {
+        let text = r#"
+<!DOCTYPE html>
+<html>
+  <body>
+    <h1>Links for jinja2</h1>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl" data-core-metadata="sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61" data-dist-info-metadata="true" data-yanked>Jinja2-3.1.2-py3-none-any.whl</a><br/>
+    <a href="/whl/Jinja2-3.1.3-py3-none-any.whl" data-yanked="Broken release">Jinja2-3.1.3-py3-none-any.whl</a><br/>
+  </body>
+</html>
+        "#;
+        let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
+        let result = SimpleHtml::parse(text, &base).unwrap();
+
+        let Some(DistInfoMetadata::Hashes(hashes)) = &result.files[0].dist_info_metadata else {
+            panic!("expected metadata hashes");
+        };
+        assert_eq!(
+            hashes.sha256.as_deref(),
+            Some("6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61")
+        );
+        assert!(matches!(result.files[0].yanked, Some(Yanked::Bool(true))));
+        assert_eq!(
+            result.files[1].yanked.as_ref().and_then(Yanked::reason),
+            Some("Broken release")
+        );
+    }
 }
@@ -1,11 +1,14 @@
 pub use cached_client::{CacheControl, CachedClient, CachedClientError, DataWithCachePolicy};
 pub use error::{BetterReqwestError, Error, ErrorKind};
 pub use flat_index::{FlatDistributions, FlatIndex, FlatIndexClient, FlatIndexError};
+pub use proxy::{IndexProxy, ProxyBypass, ProxyConfig, ProxyError, ProxyRoute, ProxyUrl};
 pub use registry_client::{
     Connectivity, RegistryClient, RegistryClientBuilder, SimpleMetadata, SimpleMetadatum,
     VersionFiles,
 };
 pub use rkyvutil::OwnedArchive;
+pub use tls::{TlsConfig, TlsError};
+pub use trusted_host::{TrustedHost, TrustedHostError};
 
 mod cached_client;
 mod error;
@@ -13,6 +16,10 @@ mod flat_index;
 mod html;
 mod httpcache;
 mod middleware;
+mod proxy;
 mod registry_client;
 mod remote_metadata;
+mod resume;
 mod rkyvutil;
+mod tls;
+mod trusted_host;
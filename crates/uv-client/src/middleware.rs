@@ -1,10 +1,19 @@
 use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
 
-use reqwest::{Request, Response};
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use reqwest::{Client, Request, Response, StatusCode};
 use reqwest_middleware::{Middleware, Next};
 use task_local_extensions::Extensions;
+use tracing::{debug, warn};
 use url::Url;
 
+use uv_auth::{Credentials, KeyringProvider, Netrc, Realm, CREDENTIALS_CACHE};
+use uv_warnings::warn_user_once;
+
+use crate::TrustedHost;
+
 /// A custom error type for the offline middleware.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct OfflineError {
@@ -45,3 +54,283 @@ impl Middleware for OfflineMiddleware {
         ))
     }
 }
+
+/// The error returned when a request continues to fail with a transient error after retrying.
+#[derive(Debug)]
+pub(crate) struct RetriesExhausted {
+    /// The total number of attempts, including the initial request.
+    pub(crate) attempts: u32,
+    /// The error from the final attempt.
+    pub(crate) source: reqwest::Error,
+}
+
+impl std::fmt::Display for RetriesExhausted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Request failed after {} attempts", self.attempts)
+    }
+}
+
+impl std::error::Error for RetriesExhausted {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// A middleware that retries requests that fail with a transient error (e.g., a connection reset,
+/// a timeout, or a `5xx` response), with an exponential backoff between attempts.
+///
+/// If the final attempt also fails, the error records the number of attempts that were made.
+pub(crate) struct RetryMiddleware {
+    retries: u32,
+}
+
+impl RetryMiddleware {
+    pub(crate) fn new(retries: u32) -> Self {
+        Self { retries }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RetryMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            // Requests with streaming bodies can't be cloned, and so can't be retried.
+            let Some(request) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+
+            let result = next.clone().run(request, extensions).await;
+            let transient = match &result {
+                Ok(response) => is_transient_status(response.status()),
+                Err(reqwest_middleware::Error::Reqwest(err)) => is_transient_error(err),
+                Err(reqwest_middleware::Error::Middleware(_)) => false,
+            };
+            if !transient {
+                return result;
+            }
+
+            if attempt >= self.retries {
+                if attempt == 0 {
+                    return result;
+                }
+                let source = match result {
+                    Ok(response) => match response.error_for_status() {
+                        Ok(response) => return Ok(response),
+                        Err(err) => err,
+                    },
+                    Err(reqwest_middleware::Error::Reqwest(err)) => err,
+                    Err(err) => return Err(err),
+                };
+                return Err(reqwest_middleware::Error::Middleware(
+                    RetriesExhausted {
+                        attempts: attempt + 1,
+                        source,
+                    }
+                    .into(),
+                ));
+            }
+
+            attempt += 1;
+            let delay = backoff(attempt);
+            match &result {
+                Ok(response) => debug!(
+                    "Request for {} failed with status {}; retrying in {delay:?} ({attempt}/{})",
+                    req.url(),
+                    response.status(),
+                    self.retries
+                ),
+                Err(err) => debug!(
+                    "Request for {} failed ({err}); retrying in {delay:?} ({attempt}/{})",
+                    req.url(),
+                    self.retries
+                ),
+            }
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Returns `true` if a response with the given status may succeed if the request is retried.
+pub(crate) fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Returns `true` if a request that failed with the given error may succeed if retried, e.g.,
+/// if the connection was reset or timed out.
+pub(crate) fn is_transient_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect() || err.is_request() || err.is_body() || err.is_decode()
+}
+
+/// The delay before the given retry (starting from `1`), doubling with each attempt.
+pub(crate) fn backoff(attempt: u32) -> Duration {
+    const INITIAL: Duration = Duration::from_millis(500);
+    const MAX: Duration = Duration::from_secs(10);
+    INITIAL
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(MAX)
+}
+
+/// A middleware that sends requests for trusted hosts through a client that skips certificate
+/// verification.
+///
+/// This must be the innermost middleware, as it bypasses the default client for any trusted host.
+pub(crate) struct TrustedHostMiddleware {
+    trusted_hosts: Vec<TrustedHost>,
+    client: Client,
+}
+
+impl TrustedHostMiddleware {
+    pub(crate) fn new(trusted_hosts: Vec<TrustedHost>, client: Client) -> Self {
+        Self {
+            trusted_hosts,
+            client,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for TrustedHostMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let Some(trusted_host) = self
+            .trusted_hosts
+            .iter()
+            .find(|trusted_host| trusted_host.matches(req.url()))
+        else {
+            return next.run(req, extensions).await;
+        };
+
+        if req.url().scheme() == "http" {
+            warn_user_once!(
+                "Connecting to `{trusted_host}` over plain HTTP, since it's marked as a trusted host. Traffic to this host is unencrypted and can be read or modified in transit."
+            );
+        } else {
+            warn_user_once!(
+                "Skipping certificate verification for `{trusted_host}`, since it's marked as a trusted host. Connections to this host are vulnerable to man-in-the-middle attacks."
+            );
+        }
+        Ok(self.client.execute(req).await?)
+    }
+}
+
+/// A middleware that attaches credentials to outgoing requests.
+///
+/// Credentials are taken from the request URL, the session cache, the environment, and the
+/// `.netrc` file, in that order. If the server responds with a `401 Unauthorized`, the keyring (if
+/// enabled) is consulted and the request is retried once.
+///
+/// Any credentials that are found are cached for the remainder of the session, and attached to
+/// subsequent requests within the same [`Realm`].
+pub(crate) struct AuthMiddleware {
+    netrc: Option<Netrc>,
+    keyring_provider: KeyringProvider,
+}
+
+impl AuthMiddleware {
+    pub(crate) fn new(keyring_provider: KeyringProvider) -> Self {
+        let netrc = Netrc::from_env().unwrap_or_else(|err| {
+            warn!("Failed to read `.netrc` file: {err}");
+            None
+        });
+        Self {
+            netrc,
+            keyring_provider,
+        }
+    }
+
+    /// Find the credentials for a URL without making any network requests or spawning any
+    /// subprocesses.
+    fn credentials(&self, url: &Url, realm: &Realm) -> Option<Arc<Credentials>> {
+        if let Some(credentials) = CREDENTIALS_CACHE.get(realm) {
+            return Some(credentials);
+        }
+        let host = url.host_str()?;
+        let credentials = Credentials::from_env(host)
+            .or_else(|| self.netrc.as_ref().and_then(|netrc| netrc.find(host)))?;
+        debug!("Found credentials for {realm}");
+        let credentials = Arc::new(credentials);
+        if credentials.password().is_some() {
+            CREDENTIALS_CACHE.insert(realm.clone(), credentials.clone());
+        }
+        Some(credentials)
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for AuthMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let url = req.url().clone();
+        let realm = Realm::from(&url);
+
+        // `reqwest` moves any credentials embedded in the URL into the `Authorization` header.
+        let credentials = if let Some(credentials) = req
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Credentials::from_header_value)
+        {
+            let credentials = Arc::new(credentials);
+            if credentials.password().is_some() {
+                CREDENTIALS_CACHE.insert(realm.clone(), credentials.clone());
+            }
+            Some(credentials)
+        } else if req.headers().contains_key(AUTHORIZATION) {
+            // Leave any other authorization schemes (e.g., bearer tokens) untouched.
+            return next.run(req, extensions).await;
+        } else {
+            let credentials = self.credentials(&url, &realm);
+            if let Some(credentials) = credentials.as_deref() {
+                authenticate(&mut req, credentials);
+            }
+            credentials
+        };
+
+        let retry = req.try_clone();
+        let response = next.clone().run(req, extensions).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        // As a last resort, consult the keyring, which requires a username.
+        let (Some(mut retry), Some(username)) =
+            (retry, credentials.as_deref().map(Credentials::username))
+        else {
+            return Ok(response);
+        };
+        let Some(credentials) = self.keyring_provider.fetch(&url, username).await else {
+            return Ok(response);
+        };
+        debug!("Found credentials for {realm} in keyring; retrying request");
+        authenticate(&mut retry, &credentials);
+        let response = next.run(retry, extensions).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            CREDENTIALS_CACHE.insert(realm, Arc::new(credentials));
+        }
+        Ok(response)
+    }
+}
+
+/// Attach the credentials to a request as a `Basic` `Authorization` header.
+fn authenticate(req: &mut Request, credentials: &Credentials) {
+    if let Ok(mut value) = HeaderValue::from_str(&credentials.to_header_value()) {
+        value.set_sensitive(true);
+        req.headers_mut().insert(AUTHORIZATION, value);
+    }
+}
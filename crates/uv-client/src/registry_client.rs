@@ -5,39 +5,49 @@ use std::path::Path;
 use std::str::FromStr;
 
 use async_http_range_reader::AsyncHttpRangeReader;
+use bytes::Bytes;
+use futures::stream::BoxStream;
 use futures::{FutureExt, TryStreamExt};
 use http::HeaderMap;
 use reqwest::{Client, ClientBuilder, Response, StatusCode};
-use reqwest_retry::policies::ExponentialBackoff;
-use reqwest_retry::RetryTransientMiddleware;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tokio::io::AsyncReadExt;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 use url::Url;
 
 use distribution_filename::{DistFilename, SourceDistFilename, WheelFilename};
-use distribution_types::{BuiltDist, File, FileLocation, IndexUrl, IndexUrls, Name};
+use distribution_types::{BuiltDist, File, FileLocation, IndexStrategy, IndexUrl, IndexUrls, Name};
 use install_wheel_rs::{find_dist_info, is_metadata_entry};
 use pep440_rs::Version;
-use pypi_types::{Metadata21, SimpleJson};
-use uv_auth::safe_copy_url_auth;
-use uv_cache::{Cache, CacheBucket, WheelCache};
+use pypi_types::{DistInfoMetadata, Metadata21, SimpleJson};
+use uv_auth::{safe_copy_url_auth, KeyringProvider, Realm, CREDENTIALS_CACHE};
+use uv_cache::{Cache, CacheBucket, CacheEntry, WheelCache};
 use uv_normalize::PackageName;
 use uv_version::version;
 use uv_warnings::warn_user_once;
 
 use crate::cached_client::CacheControl;
 use crate::html::SimpleHtml;
-use crate::middleware::OfflineMiddleware;
+use crate::middleware::{
+    backoff, AuthMiddleware, OfflineMiddleware, RetryMiddleware, TrustedHostMiddleware,
+};
+use crate::proxy::ProxyConfig;
 use crate::remote_metadata::wheel_metadata_from_remote_zip;
+use crate::resume::resumable_stream;
 use crate::rkyvutil::OwnedArchive;
-use crate::{CachedClient, CachedClientError, Error, ErrorKind};
+use crate::tls::TlsConfig;
+use crate::{CachedClient, CachedClientError, Error, ErrorKind, TrustedHost};
 
 /// A builder for an [`RegistryClient`].
 #[derive(Debug, Clone)]
 pub struct RegistryClientBuilder {
     index_urls: IndexUrls,
+    keyring_provider: KeyringProvider,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    trusted_hosts: Vec<TrustedHost>,
     retries: u32,
     connectivity: Connectivity,
     cache: Cache,
@@ -45,12 +55,19 @@ pub struct RegistryClientBuilder {
 }
 
 impl RegistryClientBuilder {
+    /// The default number of times to retry a request that fails with a transient error.
+    pub const DEFAULT_RETRIES: u32 = 3;
+
     pub fn new(cache: Cache) -> Self {
         Self {
             index_urls: IndexUrls::default(),
+            keyring_provider: KeyringProvider::default(),
+            tls: TlsConfig::default(),
+            proxy: ProxyConfig::default(),
+            trusted_hosts: Vec::new(),
             cache,
             connectivity: Connectivity::Online,
-            retries: 3,
+            retries: Self::DEFAULT_RETRIES,
             client: None,
         }
     }
@@ -63,12 +80,38 @@ impl RegistryClientBuilder {
         self
     }
 
+    #[must_use]
+    pub fn keyring_provider(mut self, keyring_provider: KeyringProvider) -> Self {
+        self.keyring_provider = keyring_provider;
+        self
+    }
+
+    #[must_use]
+    pub fn tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    #[must_use]
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    #[must_use]
+    pub fn trusted_hosts(mut self, trusted_hosts: Vec<TrustedHost>) -> Self {
+        self.trusted_hosts = trusted_hosts;
+        self
+    }
+
     #[must_use]
     pub fn connectivity(mut self, connectivity: Connectivity) -> Self {
         self.connectivity = connectivity;
         self
     }
 
+    /// Set the number of times to retry a request (or resume a download) that fails with a
+    /// transient error.
     #[must_use]
     pub fn retries(mut self, retries: u32) -> Self {
         self.retries = retries;
@@ -108,24 +151,47 @@ impl RegistryClientBuilder {
             .unwrap_or(default_timeout);
         debug!("Using registry request timeout of {}s", timeout);
 
-        let client_raw = self.client.unwrap_or_else(|| {
-            // Disallow any connections.
+        let client_core = || {
             let client_core = ClientBuilder::new()
-                .user_agent(user_agent_string)
+                .user_agent(user_agent_string.as_str())
                 .pool_max_idle_per_host(20)
                 .timeout(std::time::Duration::from_secs(timeout));
 
-            client_core.build().expect("Failed to build HTTP client.")
-        });
+            // Configure the trusted roots and client certificate.
+            let client_core = self.tls.apply(client_core);
+
+            // Configure the proxies, for both the cached and the raw clients.
+            self.proxy.apply(client_core)
+        };
+
+        // Build a separate client for trusted hosts, which skips certificate verification.
+        let client_insecure = if self.trusted_hosts.is_empty() {
+            None
+        } else {
+            Some(
+                client_core()
+                    .danger_accept_invalid_certs(true)
+                    .build()
+                    .expect("Failed to build HTTP client."),
+            )
+        };
+
+        let client_raw = self
+            .client
+            .unwrap_or_else(|| client_core().build().expect("Failed to build HTTP client."));
 
         let uncached_client = match self.connectivity {
             Connectivity::Online => {
-                let retry_policy =
-                    ExponentialBackoff::builder().build_with_max_retries(self.retries);
-                let retry_strategy = RetryTransientMiddleware::new_with_policy(retry_policy);
-                reqwest_middleware::ClientBuilder::new(client_raw.clone())
-                    .with(retry_strategy)
-                    .build()
+                let mut client = reqwest_middleware::ClientBuilder::new(client_raw.clone())
+                    .with(RetryMiddleware::new(self.retries))
+                    .with(AuthMiddleware::new(self.keyring_provider));
+                if let Some(client_insecure) = client_insecure.clone() {
+                    client = client.with(TrustedHostMiddleware::new(
+                        self.trusted_hosts.clone(),
+                        client_insecure,
+                    ));
+                }
+                client.build()
             }
             Connectivity::Offline => reqwest_middleware::ClientBuilder::new(client_raw.clone())
                 .with(OfflineMiddleware)
@@ -136,9 +202,14 @@ impl RegistryClientBuilder {
             index_urls: self.index_urls,
             cache: self.cache,
             connectivity: self.connectivity,
+            tls: self.tls,
+            proxy: self.proxy,
+            trusted_hosts: self.trusted_hosts,
             client_raw,
+            client_insecure,
             client: CachedClient::new(uncached_client),
             timeout,
+            retries: self.retries,
         }
     }
 }
@@ -153,12 +224,22 @@ pub struct RegistryClient {
     /// Don't use this client, it only exists because `async_http_range_reader` needs.
     /// [`reqwest::Client] instead of [`reqwest_middleware::Client`]
     client_raw: Client,
+    /// The raw client used for trusted hosts, if any, which skips certificate verification.
+    client_insecure: Option<Client>,
     /// Used for the remote wheel METADATA cache.
     cache: Cache,
     /// The connectivity mode to use.
     connectivity: Connectivity,
+    /// The TLS configuration used by the client.
+    tls: TlsConfig,
+    /// The proxy configuration used by the client.
+    proxy: ProxyConfig,
+    /// The hosts that may be reached over plain HTTP, or without certificate verification.
+    trusted_hosts: Vec<TrustedHost>,
     /// Configured client timeout, in seconds.
     timeout: u64,
+    /// The number of times to retry a request that fails with a transient error.
+    retries: u32,
 }
 
 impl RegistryClient {
@@ -177,6 +258,64 @@ impl RegistryClient {
         self.timeout
     }
 
+    /// Return the number of times this client retries a request that fails with a transient
+    /// error.
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Return the [`TlsConfig`] used by this client.
+    pub fn tls(&self) -> &TlsConfig {
+        &self.tls
+    }
+
+    /// Return the [`ProxyConfig`] used by this client.
+    pub fn proxy(&self) -> &ProxyConfig {
+        &self.proxy
+    }
+
+    /// Return the hosts that may be reached over plain HTTP, or without certificate verification.
+    pub fn trusted_hosts(&self) -> &[TrustedHost] {
+        &self.trusted_hosts
+    }
+
+    /// Returns an error if the URL is served over plain HTTP, unless its host is trusted (or is the
+    /// local machine).
+    pub(crate) fn require_secure(&self, url: &Url) -> Result<(), Error> {
+        if url.scheme() != "http" {
+            return Ok(());
+        }
+        let is_loopback = match url.host() {
+            Some(url::Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+            Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+            None => false,
+        };
+        if is_loopback
+            || self
+                .trusted_hosts
+                .iter()
+                .any(|trusted_host| trusted_host.matches(url))
+        {
+            return Ok(());
+        }
+        let host = url.host_str().unwrap_or_default().to_string();
+        let mut url = url.clone();
+        let _ = url.set_username("");
+        let _ = url.set_password(None);
+        Err(ErrorKind::InsecureUrl(url, host).into())
+    }
+
+    /// Return the [`IndexStrategy`] this client uses to query multiple indexes.
+    pub fn index_strategy(&self) -> IndexStrategy {
+        self.index_urls.index_strategy()
+    }
+
+    /// Return the index to which the given package is pinned, if any.
+    pub fn package_index(&self, package_name: &PackageName) -> Option<&IndexUrl> {
+        self.index_urls.package_index(package_name)
+    }
+
     /// Set the index URLs to use for fetching packages.
     #[must_use]
     pub fn with_index_url(self, index_urls: IndexUrls) -> Self {
@@ -188,37 +327,62 @@ impl RegistryClient {
     /// "simple" here refers to [PEP 503 – Simple Repository API](https://peps.python.org/pep-0503/)
     /// and [PEP 691 – JSON-based Simple API for Python Package Indexes](https://peps.python.org/pep-0691/),
     /// which the pypi json api approximately implements.
+    ///
+    /// Returns the metadata from each index that provides the package, in index order. Under
+    /// [`IndexStrategy::FirstMatch`], only the first such index is queried; otherwise, every index
+    /// is searched. If the package is pinned to an index, only that index is queried.
     #[instrument("simple_api", skip_all, fields(package = % package_name))]
     pub async fn simple(
         &self,
         package_name: &PackageName,
-    ) -> Result<(IndexUrl, OwnedArchive<SimpleMetadata>), Error> {
-        let mut it = self.index_urls.indexes().peekable();
-        if it.peek().is_none() {
+    ) -> Result<Vec<(IndexUrl, OwnedArchive<SimpleMetadata>)>, Error> {
+        let indexes = if let Some(index) = self.index_urls.package_index(package_name) {
+            trace!("Package {package_name} is pinned to {index}");
+            vec![index]
+        } else {
+            self.index_urls.indexes().collect::<Vec<_>>()
+        };
+        if indexes.is_empty() {
             return Err(ErrorKind::NoIndex(package_name.as_ref().to_string()).into());
         }
 
-        for index in it {
+        let mut results = Vec::new();
+        for index in indexes {
+            self.require_secure(index)?;
             let result = self.simple_single_index(package_name, index).await?;
 
-            return match result {
-                Ok(metadata) => Ok((index.clone(), metadata)),
+            match result {
+                Ok(metadata) => {
+                    results.push((index.clone(), metadata));
+
+                    // If we're only using the first match, we can stop here.
+                    if self.index_urls.index_strategy() == IndexStrategy::FirstMatch {
+                        break;
+                    }
+                }
                 Err(CachedClientError::Client(err)) => match err.into_kind() {
                     ErrorKind::Offline(_) => continue,
+                    ErrorKind::PackageNotFound(_) => continue,
                     ErrorKind::ReqwestError(err) => {
                         if err.status() == Some(StatusCode::NOT_FOUND)
                             || err.status() == Some(StatusCode::FORBIDDEN)
                         {
                             continue;
                         }
-                        Err(ErrorKind::from(err).into())
+                        return Err(Error::from(ErrorKind::from(err)).with_proxy_route(&self.proxy));
                     }
-                    other => Err(other.into()),
+                    other => return Err(Error::from(other).with_proxy_route(&self.proxy)),
                 },
-                Err(CachedClientError::Callback(err)) => Err(err),
+                Err(CachedClientError::Callback(err)) => {
+                    return Err(err.with_proxy_route(&self.proxy))
+                }
             };
         }
 
+        if !results.is_empty() {
+            return Ok(results);
+        }
+
         match self.connectivity {
             Connectivity::Online => {
                 Err(ErrorKind::PackageNotFound(package_name.to_string()).into())
@@ -244,6 +408,14 @@ impl RegistryClient {
 
         trace!("Fetching metadata for {package_name} from {url}");
 
+        // A `file://` index is read directly from disk, bypassing the HTTP cache.
+        if url.scheme() == "file" {
+            return Ok(self
+                .simple_local(package_name, &url)
+                .await
+                .map_err(CachedClientError::Client));
+        }
+
         let cache_entry = self.cache.entry(
             CacheBucket::Simple,
             Path::new(&match index {
@@ -324,6 +496,42 @@ impl RegistryClient {
         Ok(result)
     }
 
+    /// Read the metadata for a package from a local `file://` index.
+    ///
+    /// The index must be laid out as `<root>/<package>/index.html` or, for the PEP 691 JSON format,
+    /// `<root>/<package>/index.json`. Relative file URLs are resolved against the package directory.
+    async fn simple_local(
+        &self,
+        package_name: &PackageName,
+        url: &Url,
+    ) -> Result<OwnedArchive<SimpleMetadata>, Error> {
+        let directory = url
+            .to_file_path()
+            .map_err(|()| ErrorKind::NonFileUrl(url.clone()))?;
+
+        let unarchived = match fs_err::tokio::read(directory.join("index.json")).await {
+            Ok(bytes) => {
+                let data: SimpleJson = serde_json::from_slice(&bytes)
+                    .map_err(|err| Error::from_json_err(err, url.clone()))?;
+                SimpleMetadata::from_files(data.files, package_name, url)
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                let text = match fs_err::tokio::read_to_string(directory.join("index.html")).await {
+                    Ok(text) => text,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                        return Err(ErrorKind::PackageNotFound(package_name.to_string()).into());
+                    }
+                    Err(err) => return Err(ErrorKind::Io(err).into()),
+                };
+                let SimpleHtml { base, files } = SimpleHtml::parse(&text, url)
+                    .map_err(|err| Error::from_html_err(err, url.clone()))?;
+                SimpleMetadata::from_files(files, package_name, &base.into_url())
+            }
+            Err(err) => return Err(ErrorKind::Io(err).into()),
+        };
+        OwnedArchive::from_unarchived(&unarchived)
+    }
+
     /// Fetch the metadata for a remote wheel file.
     ///
     /// For a remote wheel, we try the following ways to fetch the metadata:
@@ -338,12 +546,14 @@ impl RegistryClient {
                     let url = pypi_types::base_url_join_relative(base, url)
                         .map_err(ErrorKind::JoinRelativeError)?;
                     self.wheel_metadata_registry(&wheel.index, &wheel.file, &url)
-                        .await?
+                        .await
+                        .map_err(|err| err.with_proxy_route(&self.proxy))?
                 }
                 FileLocation::AbsoluteUrl(url) => {
                     let url = Url::parse(url).map_err(ErrorKind::UrlParseError)?;
                     self.wheel_metadata_registry(&wheel.index, &wheel.file, &url)
-                        .await?
+                        .await
+                        .map_err(|err| err.with_proxy_route(&self.proxy))?
                 }
                 FileLocation::Path(path) => {
                     let file = fs_err::tokio::File::open(&path)
@@ -354,14 +564,10 @@ impl RegistryClient {
                         .await?
                 }
             },
-            BuiltDist::DirectUrl(wheel) => {
-                self.wheel_metadata_no_pep658(
-                    &wheel.filename,
-                    &wheel.url,
-                    WheelCache::Url(&wheel.url),
-                )
-                .await?
-            }
+            BuiltDist::DirectUrl(wheel) => self
+                .wheel_metadata_no_pep658(&wheel.filename, &wheel.url, WheelCache::Url(&wheel.url))
+                .await
+                .map_err(|err| err.with_proxy_route(&self.proxy))?,
             BuiltDist::Path(wheel) => {
                 let file = fs_err::tokio::File::open(&wheel.path)
                     .await
@@ -393,7 +599,7 @@ impl RegistryClient {
         if file
             .dist_info_metadata
             .as_ref()
-            .is_some_and(pypi_types::DistInfoMetadata::is_available)
+            .is_some_and(DistInfoMetadata::is_available)
         {
             let mut url = url.clone();
             url.set_path(&format!("{}.metadata", url.path()));
@@ -412,9 +618,27 @@ impl RegistryClient {
                 Connectivity::Offline => CacheControl::AllowStale,
             };
 
+            // If the index provides a hash for the metadata file, verify it before parsing.
+            let expected = match file.dist_info_metadata.as_ref() {
+                Some(DistInfoMetadata::Hashes(hashes)) => hashes.sha256.clone(),
+                _ => None,
+            };
+
             let response_callback = |response: Response| async {
                 let bytes = response.bytes().await.map_err(ErrorKind::from)?;
 
+                if let Some(expected) = expected {
+                    let actual = format!("{:x}", Sha256::digest(&bytes));
+                    if !actual.eq_ignore_ascii_case(&expected) {
+                        return Err(Error::from(ErrorKind::MetadataHashMismatch {
+                            filename: filename.clone(),
+                            url: url.to_string(),
+                            expected,
+                            actual,
+                        }));
+                    }
+                }
+
                 info_span!("parse_metadata21")
                     .in_scope(|| Metadata21::parse(bytes.as_ref()))
                     .map_err(|err| {
@@ -465,7 +689,71 @@ impl RegistryClient {
             Connectivity::Offline => CacheControl::AllowStale,
         };
 
-        let client = self.client_raw.clone();
+        // Read the metadata via range requests, retrying if the requests are interrupted.
+        let mut attempt = 0;
+        let result = loop {
+            let result = self
+                .wheel_metadata_range_request(filename, url, &cache_entry, cache_control)
+                .await;
+            match result {
+                Err(err)
+                    if attempt < self.retries && err.kind().is_http_range_request_interrupted() =>
+                {
+                    attempt += 1;
+                    let delay = backoff(attempt);
+                    warn!(
+                        "Range request for {filename} was interrupted ({err}); retrying in {delay:?} ({attempt}/{})",
+                        self.retries
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(err) if attempt > 0 && err.kind().is_http_range_request_interrupted() => {
+                    break Err(Error::from(ErrorKind::RetriesExhausted {
+                        attempts: attempt + 1,
+                        source: Box::new(err),
+                    }));
+                }
+                result => break result,
+            }
+        };
+        match result {
+            Ok(metadata) => return Ok(metadata),
+            Err(err) => {
+                if err.kind().is_http_range_requests_unsupported() {
+                    // The range request version failed. Fall back to downloading the entire file
+                    // and the reading the file from the zip the regular way.
+                    warn!("Range requests not supported for {filename}; downloading wheel");
+                } else {
+                    return Err(err);
+                }
+            }
+        }
+
+        // Stream the file, searching for the METADATA.
+        let reader = self.stream_external(url).await?;
+        read_metadata_async_stream(filename, url.to_string(), reader).await
+    }
+
+    /// Read the metadata for a wheel with HTTP range requests, reading only the
+    /// `.dist-info/METADATA` file from the remote zip.
+    async fn wheel_metadata_range_request(
+        &self,
+        filename: &WheelFilename,
+        url: &Url,
+        cache_entry: &CacheEntry,
+        cache_control: CacheControl,
+    ) -> Result<Metadata21, Error> {
+        let client = match self.client_insecure.as_ref() {
+            Some(client_insecure)
+                if self
+                    .trusted_hosts
+                    .iter()
+                    .any(|trusted_host| trusted_host.matches(url)) =>
+            {
+                client_insecure.clone()
+            }
+            _ => self.client_raw.clone(),
+        };
         let req = self
             .client
             .uncached()
@@ -481,6 +769,14 @@ impl RegistryClient {
         let mut headers = HeaderMap::default();
         if let Some(authorization) = req.headers().get("authorization") {
             headers.append("authorization", authorization.clone());
+        } else if let Some(credentials) = CREDENTIALS_CACHE.get(&Realm::from(url)) {
+            // The range requests bypass the middleware, so attach any cached credentials directly.
+            if let Ok(mut authorization) =
+                http::HeaderValue::from_str(&credentials.to_header_value())
+            {
+                authorization.set_sensitive(true);
+                headers.append("authorization", authorization);
+            }
         }
 
         // This response callback is special, we actually make a number of subsequent requests to
@@ -506,54 +802,40 @@ impl RegistryClient {
             .instrument(info_span!("read_metadata_range_request", wheel = %filename))
         };
 
-        let result = self
-            .client
-            .get_serde(
-                req,
-                &cache_entry,
-                cache_control,
-                read_metadata_range_request,
-            )
+        self.client
+            .get_serde(req, cache_entry, cache_control, read_metadata_range_request)
             .await
-            .map_err(crate::Error::from);
-
-        match result {
-            Ok(metadata) => return Ok(metadata),
-            Err(err) => {
-                if err.kind().is_http_range_requests_unsupported() {
-                    // The range request version failed. Fall back to downloading the entire file
-                    // and the reading the file from the zip the regular way.
-                    warn!("Range requests not supported for {filename}; downloading wheel");
-                } else {
-                    return Err(err);
-                }
-            }
-        }
-
-        // Stream the file, searching for the METADATA.
-        let reader = self.stream_external(url).await?;
-        read_metadata_async_stream(filename, url.to_string(), reader).await
+            .map_err(crate::Error::from)
     }
 
     /// Stream a file from an external URL.
     pub async fn stream_external(
         &self,
         url: &Url,
-    ) -> Result<Box<dyn futures::AsyncRead + Unpin + Send + Sync>, Error> {
+    ) -> Result<Box<dyn futures::AsyncRead + Unpin + Send>, Error> {
+        let response = self
+            .client
+            .uncached()
+            .get(url.to_string())
+            .send()
+            .await
+            .map_err(|err| Error::from(ErrorKind::from(err)).with_proxy_route(&self.proxy))?
+            .error_for_status()
+            .map_err(|err| Error::from(ErrorKind::from(err)).with_proxy_route(&self.proxy))?;
         Ok(Box::new(
-            self.client
-                .uncached()
-                .get(url.to_string())
-                .send()
-                .await
-                .map_err(ErrorKind::from)?
-                .error_for_status()
-                .map_err(ErrorKind::from)?
-                .bytes_stream()
+            self.stream_response(response)
                 .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
                 .into_async_read(),
         ))
     }
+
+    /// Stream the body of a response.
+    ///
+    /// If the connection is interrupted, the download is resumed with an HTTP range request,
+    /// up to the configured number of retries.
+    pub fn stream_response(&self, response: Response) -> BoxStream<'static, Result<Bytes, Error>> {
+        resumable_stream(self.client.uncached(), response, self.retries)
+    }
 }
 
 /// Read a wheel's `METADATA` file from a zip file.
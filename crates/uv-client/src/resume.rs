@@ -0,0 +1,162 @@
+//! Resume interrupted downloads with HTTP range requests.
+
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use reqwest::header::{HeaderValue, ACCEPT_ENCODING, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::{Response, StatusCode};
+use reqwest_middleware::ClientWithMiddleware;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::middleware::{backoff, is_transient_error};
+use crate::{Error, ErrorKind};
+
+type Body = Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>;
+
+/// Stream the body of a response. If the connection is interrupted mid-body, the download is
+/// resumed from the last byte received with a `Range` request, up to `retries` times.
+///
+/// The `Range` request is conditioned on the `ETag` (or `Last-Modified` date) of the original
+/// response via `If-Range`, such that the server returns the entire file if it has changed in the
+/// meantime. In that case, or if the server ignores the `Range` header altogether, the bytes that
+/// were already received are skipped.
+pub(crate) fn resumable_stream(
+    client: ClientWithMiddleware,
+    response: Response,
+    retries: u32,
+) -> BoxStream<'static, Result<Bytes, Error>> {
+    let state = Download {
+        client,
+        url: response.url().clone(),
+        validator: validator(&response),
+        body: Some(Box::pin(response.bytes_stream())),
+        offset: 0,
+        skip: 0,
+        attempts: 0,
+        retries,
+    };
+
+    futures::stream::unfold(state, |mut state| async move {
+        loop {
+            let body = state.body.as_mut()?;
+            match body.next().await {
+                None => return None,
+                Some(Ok(mut bytes)) => {
+                    // Discard any bytes that we received prior to resuming.
+                    if state.skip > 0 {
+                        let skip = state.skip.min(bytes.len() as u64);
+                        bytes = bytes.slice(usize::try_from(skip).unwrap_or(usize::MAX)..);
+                        state.skip -= skip;
+                        if bytes.is_empty() {
+                            continue;
+                        }
+                    }
+                    state.offset += bytes.len() as u64;
+                    return Some((Ok(bytes), state));
+                }
+                Some(Err(err)) => {
+                    if !is_transient_error(&err) || state.attempts >= state.retries {
+                        state.body = None;
+                        let err = if state.attempts > 0 {
+                            ErrorKind::RetriesExhausted {
+                                attempts: state.attempts + 1,
+                                source: Box::new(ErrorKind::from(err).into()),
+                            }
+                        } else {
+                            ErrorKind::from(err)
+                        };
+                        return Some((Err(err.into()), state));
+                    }
+
+                    state.attempts += 1;
+                    let delay = backoff(state.attempts);
+                    warn!(
+                        "Download of {} was interrupted after {} bytes ({err}); resuming in {delay:?} ({}/{})",
+                        state.url, state.offset, state.attempts, state.retries
+                    );
+                    tokio::time::sleep(delay).await;
+
+                    if let Err(err) = state.resume().await {
+                        state.body = None;
+                        return Some((Err(err), state));
+                    }
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+/// The state of a resumable download.
+struct Download {
+    client: ClientWithMiddleware,
+    url: Url,
+    /// The `If-Range` validator for the file, if the server provided one.
+    validator: Option<HeaderValue>,
+    /// The body of the current response, or `None` if the download has failed.
+    body: Option<Body>,
+    /// The number of bytes received so far.
+    offset: u64,
+    /// The number of bytes to discard from the start of the current body, if the server
+    /// ignored the requested range.
+    skip: u64,
+    /// The number of times the download has been resumed.
+    attempts: u32,
+    /// The maximum number of times to resume the download.
+    retries: u32,
+}
+
+impl Download {
+    /// Request the remainder of the file, starting from the current offset.
+    ///
+    /// Without a validator, there's no way to ensure that the range refers to the same file, so
+    /// the entire file is requested instead.
+    async fn resume(&mut self) -> Result<(), Error> {
+        // Request the unencoded representation, such that the byte offsets match the decoded
+        // bytes received so far.
+        let mut request = self
+            .client
+            .get(self.url.clone())
+            .header(ACCEPT_ENCODING, "identity");
+        if let Some(validator) = &self.validator {
+            request = request
+                .header(RANGE, format!("bytes={}-", self.offset))
+                .header(IF_RANGE, validator.clone());
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(ErrorKind::from)?
+            .error_for_status()
+            .map_err(ErrorKind::from)?;
+
+        if response.status() == StatusCode::PARTIAL_CONTENT {
+            debug!("Resuming download of {} at byte {}", self.url, self.offset);
+            self.skip = 0;
+        } else {
+            debug!(
+                "Server returned the entire file for {}; skipping {} bytes",
+                self.url, self.offset
+            );
+            self.skip = self.offset;
+        }
+        self.body = Some(Box::pin(response.bytes_stream()));
+        Ok(())
+    }
+}
+
+/// Extract a validator for an `If-Range` header from a response: a strong `ETag`, or else the
+/// `Last-Modified` date.
+///
+/// Weak `ETag`s can't be used with `If-Range`, per RFC 9110.
+fn validator(response: &Response) -> Option<HeaderValue> {
+    response
+        .headers()
+        .get(ETAG)
+        .filter(|etag| !etag.as_bytes().starts_with(b"W/"))
+        .or_else(|| response.headers().get(LAST_MODIFIED))
+        .cloned()
+}
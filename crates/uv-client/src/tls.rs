@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use reqwest::{Certificate, ClientBuilder, Identity};
+use tracing::debug;
+
+use uv_fs::Simplified;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse CA certificate bundle: `{}`", _0.simplified_display())]
+    CaBundle(PathBuf, #[source] reqwest::Error),
+
+    #[error("Failed to parse client certificate (expected a PEM file containing a certificate and private key): `{}`", _0.simplified_display())]
+    ClientCert(PathBuf, #[source] reqwest::Error),
+}
+
+/// The TLS configuration shared by all HTTP clients, including those used for Git fetches.
+///
+/// By default, the certificates in the platform's native certificate store are trusted.
+/// Alternatively, the bundled Mozilla root certificates, or a custom CA bundle, can be trusted
+/// instead.
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    /// The root certificates to trust, in lieu of the native roots.
+    roots: Option<Vec<Certificate>>,
+    /// The client certificate and private key to present to servers that request one.
+    identity: Option<Identity>,
+    /// The path to the CA bundle, if provided.
+    ca_bundle: Option<PathBuf>,
+    /// The path to the client certificate, if provided.
+    client_cert: Option<PathBuf>,
+}
+
+impl TlsConfig {
+    /// Load the TLS configuration.
+    ///
+    /// If a CA bundle is provided, only the certificates in the bundle are trusted. Otherwise, if
+    /// `webpki_roots` is set, the bundled Mozilla root certificates are trusted.
+    pub fn new(
+        webpki_roots: bool,
+        ca_bundle: Option<PathBuf>,
+        client_cert: Option<PathBuf>,
+    ) -> Result<Self, TlsError> {
+        let roots = if let Some(ca_bundle) = ca_bundle.as_ref() {
+            debug!(
+                "Using CA certificate bundle: {}",
+                ca_bundle.simplified_display()
+            );
+            let pem = fs_err::read(ca_bundle)?;
+            let certificates = Certificate::from_pem_bundle(&pem)
+                .map_err(|err| TlsError::CaBundle(ca_bundle.clone(), err))?;
+            Some(certificates)
+        } else if webpki_roots {
+            debug!("Using the bundled Mozilla root certificates");
+            let certificates = webpki_root_certs::TLS_SERVER_ROOT_CERTS
+                .iter()
+                .filter_map(|certificate| Certificate::from_der(certificate.as_ref()).ok())
+                .collect();
+            Some(certificates)
+        } else {
+            None
+        };
+
+        let identity = if let Some(client_cert) = client_cert.as_ref() {
+            debug!(
+                "Using client certificate: {}",
+                client_cert.simplified_display()
+            );
+            let pem = fs_err::read(client_cert)?;
+            let identity = Identity::from_pem(&pem)
+                .map_err(|err| TlsError::ClientCert(client_cert.clone(), err))?;
+            Some(identity)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            roots,
+            identity,
+            ca_bundle,
+            client_cert,
+        })
+    }
+
+    /// The path to the CA bundle, if one was provided.
+    pub fn ca_bundle(&self) -> Option<&Path> {
+        self.ca_bundle.as_deref()
+    }
+
+    /// The path to the client certificate, if one was provided.
+    pub fn client_cert(&self) -> Option<&Path> {
+        self.client_cert.as_deref()
+    }
+
+    /// Apply the TLS configuration to a [`ClientBuilder`].
+    pub(crate) fn apply(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        if let Some(roots) = self.roots.as_ref() {
+            builder = builder.tls_built_in_root_certs(false);
+            for certificate in roots {
+                builder = builder.add_root_certificate(certificate.clone());
+            }
+        }
+        if let Some(identity) = self.identity.as_ref() {
+            builder = builder.identity(identity.clone());
+        }
+        builder
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt::{Display, Formatter};
+use std::net::Ipv6Addr;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use url::{Host, Url};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TrustedHostError {
+    #[error("Expected a host or `<host>:<port>`, found: `{0}`")]
+    InvalidHost(String),
+
+    #[error("Invalid port in trusted host: `{0}`")]
+    InvalidPort(String, #[source] ParseIntError),
+}
+
+/// A host (and, optionally, a port) that's allowed to be reached over plain HTTP, or over HTTPS
+/// without certificate verification.
+///
+/// Also known as `--trusted-host`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrustedHost {
+    host: String,
+    port: Option<u16>,
+}
+
+impl TrustedHost {
+    /// Returns `true` if the [`Url`] points to this host (and port, if one was provided).
+    pub fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        if !host.eq_ignore_ascii_case(&self.host) {
+            return false;
+        }
+        match self.port {
+            Some(port) => url.port_or_known_default() == Some(port),
+            None => true,
+        }
+    }
+
+    /// Returns the host, without the port.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+}
+
+impl FromStr for TrustedHost {
+    type Err = TrustedHostError;
+
+    /// Parse a trusted host, e.g., `devpi.internal`, `devpi.internal:3141`, `::1`, or `[::1]:3141`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || s.contains(['/', '@', '?', '#']) {
+            return Err(TrustedHostError::InvalidHost(s.to_string()));
+        }
+
+        // Bare IPv6 addresses contain colons, but only a bracketed address can be followed by a
+        // port.
+        let (host, port) = match s.rsplit_once(':') {
+            Some((host, port)) if !host.contains(':') || host.ends_with(']') => {
+                let port = port
+                    .parse::<u16>()
+                    .map_err(|err| TrustedHostError::InvalidPort(s.to_string(), err))?;
+                (host, Some(port))
+            }
+            _ => (s, None),
+        };
+        if host.is_empty() {
+            return Err(TrustedHostError::InvalidHost(s.to_string()));
+        }
+
+        // Normalize IPv6 addresses to the bracketed form returned by `Url::host_str`.
+        let ipv6 = host
+            .strip_prefix('[')
+            .and_then(|host| host.strip_suffix(']'))
+            .unwrap_or(host);
+        let host = match Ipv6Addr::from_str(ipv6) {
+            Ok(addr) => Host::<String>::Ipv6(addr).to_string(),
+            Err(_) => host.to_ascii_lowercase(),
+        };
+
+        Ok(Self { host, port })
+    }
+}
+
+impl Display for TrustedHost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.port {
+            Some(port) => write!(f, "{}:{port}", self.host),
+            None => write!(f, "{}", self.host),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use url::Url;
+
+    use super::TrustedHost;
+
+    #[test]
+    fn parse() {
+        let host = TrustedHost::from_str("Devpi.Internal").unwrap();
+        assert_eq!(host.to_string(), "devpi.internal");
+
+        let host = TrustedHost::from_str("devpi.internal:3141").unwrap();
+        assert_eq!(host.to_string(), "devpi.internal:3141");
+
+        let host = TrustedHost::from_str("[::1]:3141").unwrap();
+        assert_eq!(host.to_string(), "[::1]:3141");
+
+        let host = TrustedHost::from_str("0:0::1").unwrap();
+        assert_eq!(host.to_string(), "[::1]");
+
+        assert!(TrustedHost::from_str("").is_err());
+        assert!(TrustedHost::from_str("devpi.internal:port").is_err());
+        assert!(TrustedHost::from_str("https://devpi.internal/simple").is_err());
+    }
+
+    #[test]
+    fn matches() {
+        let host = TrustedHost::from_str("devpi.internal").unwrap();
+        assert!(host.matches(&Url::parse("http://devpi.internal/simple").unwrap()));
+        assert!(host.matches(&Url::parse("https://DEVPI.internal:3141/simple").unwrap()));
+        assert!(!host.matches(&Url::parse("https://pypi.org/simple").unwrap()));
+
+        let host = TrustedHost::from_str("devpi.internal:3141").unwrap();
+        assert!(host.matches(&Url::parse("http://devpi.internal:3141/simple").unwrap()));
+        assert!(!host.matches(&Url::parse("http://devpi.internal/simple").unwrap()));
+
+        let host = TrustedHost::from_str("devpi.internal:443").unwrap();
+        assert!(host.matches(&Url::parse("https://devpi.internal/simple").unwrap()));
+
+        let host = TrustedHost::from_str("::1").unwrap();
+        assert!(host.matches(&Url::parse("http://[::1]:3141/simple").unwrap()));
+        assert!(host.matches(&Url::parse("http://[0:0::1]/simple").unwrap()));
+        assert!(!host.matches(&Url::parse("http://[::2]/simple").unwrap()));
+
+        let host = TrustedHost::from_str("[::1]:3141").unwrap();
+        assert!(host.matches(&Url::parse("http://[::1]:3141/simple").unwrap()));
+        assert!(!host.matches(&Url::parse("http://[::1]/simple").unwrap()));
+    }
+}
@@ -8,7 +8,7 @@ use fs_err as fs;
 use distribution_types::IndexLocations;
 use platform_host::Platform;
 use rustc_hash::FxHashMap;
-use uv_build::{SourceBuild, SourceBuildContext};
+use uv_build::{BuildLogOptions, SourceBuild, SourceBuildContext};
 use uv_cache::{Cache, CacheArgs};
 use uv_client::{FlatIndex, RegistryClientBuilder};
 use uv_dispatch::BuildDispatch;
@@ -84,11 +84,14 @@ pub(crate) async fn build(args: BuildArgs) -> Result<PathBuf> {
         build_dispatch.interpreter(),
         &build_dispatch,
         SourceBuildContext::default(),
+        None,
         args.sdist.display().to_string(),
         setup_py,
         config_settings.clone(),
         build_kind,
         FxHashMap::default(),
+        None,
+        &BuildLogOptions::default(),
     )
     .await?;
     Ok(wheel_dir.join(builder.build(&wheel_dir).await?))
@@ -17,7 +17,7 @@ use uv_client::{FlatIndex, FlatIndexClient, RegistryClientBuilder};
 use uv_dispatch::BuildDispatch;
 use uv_installer::NoBinary;
 use uv_interpreter::PythonEnvironment;
-use uv_resolver::{InMemoryIndex, Manifest, Options, Resolver};
+use uv_resolver::{InMemoryIndex, Manifest, Options, PythonRequirement, Resolver};
 use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 
 #[derive(ValueEnum, Default, Clone)]
@@ -64,7 +64,7 @@ pub(crate) async fn resolve_cli(args: ResolveCliArgs) -> Result<()> {
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, venv.interpreter().tags()?)
+        FlatIndex::from_entries(entries, Some(venv.interpreter().tags()?))
     };
     let index = InMemoryIndex::default();
     let in_flight = InFlight::default();
@@ -94,8 +94,8 @@ pub(crate) async fn resolve_cli(args: ResolveCliArgs) -> Result<()> {
     let resolver = Resolver::new(
         Manifest::simple(args.requirements.clone()),
         Options::default(),
-        venv.interpreter().markers(),
-        venv.interpreter(),
+        Some(venv.interpreter().markers()),
+        PythonRequirement::new(venv.interpreter(), venv.interpreter().markers()),
         tags,
         &client,
         &flat_index,
@@ -48,10 +48,17 @@ async fn find_latest_version(
     client: &RegistryClient,
     package_name: &PackageName,
 ) -> Option<Version> {
-    let (_, raw_simple_metadata) = client.simple(package_name).await.ok()?;
-    let simple_metadata = OwnedArchive::deserialize(&raw_simple_metadata);
-    let version = simple_metadata.into_iter().next()?.version;
-    Some(version)
+    let results = client.simple(package_name).await.ok()?;
+    results
+        .iter()
+        .filter_map(|(_, raw_simple_metadata)| {
+            let simple_metadata = OwnedArchive::deserialize(raw_simple_metadata);
+            simple_metadata
+                .into_iter()
+                .next()
+                .map(|metadatum| metadatum.version)
+        })
+        .max()
 }
 
 pub(crate) async fn resolve_many(args: ResolveManyArgs) -> Result<()> {
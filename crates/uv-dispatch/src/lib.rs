@@ -4,23 +4,28 @@
 
 use std::ffi::OsStr;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::{ffi::OsString, future::Future};
 
 use anyhow::{bail, Context, Result};
 use futures::FutureExt;
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
+use tokio::sync::Semaphore;
 use tracing::{debug, instrument};
 
 use distribution_types::{IndexLocations, Name, Resolution, SourceDist};
 use pep508_rs::Requirement;
-use uv_build::{SourceBuild, SourceBuildContext};
+use uv_build::{BuildLogOptions, SourceBuild, SourceBuildContext};
 use uv_cache::Cache;
 use uv_client::{FlatIndex, RegistryClient};
 use uv_installer::{Downloader, Installer, NoBinary, Plan, Planner, Reinstall, SitePackages};
 use uv_interpreter::{Interpreter, PythonEnvironment};
-use uv_resolver::{InMemoryIndex, Manifest, Options, Resolver};
-use uv_traits::{BuildContext, BuildKind, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_resolver::{InMemoryIndex, Manifest, Options, PythonRequirement, Resolver};
+use uv_traits::{
+    BuildContext, BuildIsolation, BuildKind, Concurrency, ConfigSettings, InFlight, NoBuild,
+    PackageBuildSettings, SetupPyStrategy,
+};
 
 /// The main implementation of [`BuildContext`], used by the CLI, see [`BuildContext`]
 /// documentation.
@@ -36,9 +41,16 @@ pub struct BuildDispatch<'a> {
     no_build: &'a NoBuild,
     no_binary: &'a NoBinary,
     config_settings: &'a ConfigSettings,
+    package_build_settings: PackageBuildSettings,
+    build_isolation: BuildIsolation<'a>,
+    build_constraints: &'a [Requirement],
+    build_requirements: Mutex<Vec<Requirement>>,
+    build_log_options: BuildLogOptions,
     source_build_context: SourceBuildContext,
     options: Options,
     build_extra_env_vars: FxHashMap<OsString, OsString>,
+    concurrency: Concurrency,
+    build_semaphore: Arc<Semaphore>,
 }
 
 impl<'a> BuildDispatch<'a> {
@@ -56,6 +68,7 @@ impl<'a> BuildDispatch<'a> {
         no_build: &'a NoBuild,
         no_binary: &'a NoBinary,
     ) -> Self {
+        let concurrency = Concurrency::default();
         Self {
             client,
             cache,
@@ -68,9 +81,16 @@ impl<'a> BuildDispatch<'a> {
             config_settings,
             no_build,
             no_binary,
+            package_build_settings: PackageBuildSettings::default(),
+            build_isolation: BuildIsolation::default(),
+            build_constraints: &[],
+            build_requirements: Mutex::default(),
+            build_log_options: BuildLogOptions::default(),
             source_build_context: SourceBuildContext::default(),
             options: Options::default(),
             build_extra_env_vars: FxHashMap::default(),
+            concurrency,
+            build_semaphore: Arc::new(Semaphore::new(concurrency.builds)),
         }
     }
 
@@ -80,6 +100,56 @@ impl<'a> BuildDispatch<'a> {
         self
     }
 
+    /// Set the limits on the number of concurrent downloads, builds and installs.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.build_semaphore = Arc::new(Semaphore::new(concurrency.builds));
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Share the given semaphore to limit concurrent builds, e.g., with another [`BuildDispatch`].
+    ///
+    /// Must be called after [`BuildDispatch::with_concurrency`], which creates a new semaphore.
+    #[must_use]
+    pub fn with_build_semaphore(mut self, build_semaphore: Arc<Semaphore>) -> Self {
+        self.build_semaphore = build_semaphore;
+        self
+    }
+
+    /// Set the config settings and environment variables that only apply when building specific
+    /// packages.
+    #[must_use]
+    pub fn with_package_build_settings(
+        mut self,
+        package_build_settings: PackageBuildSettings,
+    ) -> Self {
+        self.package_build_settings = package_build_settings;
+        self
+    }
+
+    /// Set the environment in which to run build backends, to disable build isolation.
+    #[must_use]
+    pub fn with_build_isolation(mut self, build_isolation: BuildIsolation<'a>) -> Self {
+        self.build_isolation = build_isolation;
+        self
+    }
+
+    /// Set the constraints to apply when resolving the requirements of build environments.
+    #[must_use]
+    pub fn with_build_constraints(mut self, build_constraints: &'a [Requirement]) -> Self {
+        self.build_constraints = build_constraints;
+        self
+    }
+
+    /// Set where to write the logs of source distribution builds, and whether to stream the
+    /// output of build backends while they run.
+    #[must_use]
+    pub fn with_build_log_options(mut self, build_log_options: BuildLogOptions) -> Self {
+        self.build_log_options = build_log_options;
+        self
+    }
+
     /// Set the environment variables to be used when building a source distribution.
     #[must_use]
     pub fn with_build_extra_env_vars<I, K, V>(mut self, sdist_build_env_variables: I) -> Self
@@ -94,6 +164,12 @@ impl<'a> BuildDispatch<'a> {
             .collect();
         self
     }
+
+    /// Return the requirements of the build environments used through this dispatch, including
+    /// those of builds that were read from the cache, in the order in which they were recorded.
+    pub fn build_requirements(&self) -> Vec<Requirement> {
+        self.build_requirements.lock().unwrap().clone()
+    }
 }
 
 impl<'a> BuildContext for BuildDispatch<'a> {
@@ -123,14 +199,49 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         self.setup_py
     }
 
+    fn build_isolation(&self) -> BuildIsolation<'_> {
+        self.build_isolation
+    }
+
+    fn config_settings(&self) -> &ConfigSettings {
+        self.config_settings
+    }
+
+    fn package_build_settings(&self) -> &PackageBuildSettings {
+        &self.package_build_settings
+    }
+
+    fn concurrency(&self) -> &Concurrency {
+        &self.concurrency
+    }
+
+    fn build_semaphore(&self) -> &Arc<Semaphore> {
+        &self.build_semaphore
+    }
+
+    fn record_build_requirements(&self, requirements: &[Requirement]) {
+        self.build_requirements
+            .lock()
+            .unwrap()
+            .extend_from_slice(requirements);
+    }
+
     async fn resolve<'data>(&'data self, requirements: &'data [Requirement]) -> Result<Resolution> {
         let markers = self.interpreter.markers();
         let tags = self.interpreter.tags()?;
+        let manifest = Manifest::new(
+            requirements.to_vec(),
+            self.build_constraints.to_vec(),
+            Vec::new(),
+            Vec::new(),
+            None,
+            Vec::new(),
+        );
         let resolver = Resolver::new(
-            Manifest::simple(requirements.to_vec()),
+            manifest,
             self.options,
-            markers,
-            self.interpreter,
+            Some(markers),
+            PythonRequirement::new(self.interpreter, markers),
             tags,
             self.client,
             self.flat_index,
@@ -286,17 +397,36 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             }
         }
 
+        // Apply any settings specific to the package being built.
+        let package = dist.map(SourceDist::name);
+        let config_settings = self
+            .package_build_settings
+            .config_settings(package, self.config_settings);
+        let mut environment_variables = self.build_extra_env_vars.clone();
+        if let Some(package_environment_variables) =
+            self.package_build_settings.environment_variables(package)
+        {
+            environment_variables.extend(
+                package_environment_variables
+                    .iter()
+                    .map(|(name, value)| (OsString::from(name), OsString::from(value))),
+            );
+        }
+
         let builder = SourceBuild::setup(
             source,
             subdirectory,
             self.interpreter,
             self,
             self.source_build_context.clone(),
+            package,
             package_id.to_string(),
             self.setup_py,
-            self.config_settings.clone(),
+            config_settings,
             build_kind,
-            self.build_extra_env_vars.clone(),
+            environment_variables,
+            self.build_isolation.shared_environment(package),
+            &self.build_log_options,
         )
         .boxed()
         .await?;
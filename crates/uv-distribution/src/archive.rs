@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use pypi_types::{HashAlgorithm, HashDigest};
+
+use crate::Error;
+
+/// An archive (unzipped wheel) that exists in the local cache, along with the digests of the
+/// file from which it was unzipped.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Archive {
+    /// The path to the archive entry in the wheel's archive bucket.
+    pub(crate) path: PathBuf,
+    /// The computed hashes of the archive.
+    pub(crate) hashes: Vec<HashDigest>,
+}
+
+impl Archive {
+    /// Create a new [`Archive`] with the given path and hashes.
+    pub(crate) fn new(path: PathBuf, hashes: Vec<HashDigest>) -> Self {
+        Self { path, hashes }
+    }
+
+    /// Returns `true` if the archive includes a digest for every algorithm in `hashes`.
+    pub(crate) fn has_digests(&self, hashes: &[HashDigest]) -> bool {
+        has_digests(&self.hashes, hashes)
+    }
+}
+
+/// Returns the set of [`HashAlgorithm`]s to compute for a file, given the expected digests.
+///
+/// SHA-256 is always included, such that cached entries can be verified against the most common
+/// `--hash` algorithm without needing to be re-downloaded.
+pub(crate) fn hash_algorithms(hashes: &[HashDigest]) -> Vec<HashAlgorithm> {
+    let mut algorithms = vec![HashAlgorithm::Sha256];
+    for hash in hashes {
+        if !algorithms.contains(&hash.algorithm()) {
+            algorithms.push(hash.algorithm());
+        }
+    }
+    algorithms
+}
+
+/// Returns `true` if `computed` includes a digest for every algorithm in `expected`.
+pub(crate) fn has_digests(computed: &[HashDigest], expected: &[HashDigest]) -> bool {
+    expected.iter().all(|expected| {
+        computed
+            .iter()
+            .any(|computed| computed.algorithm() == expected.algorithm())
+    })
+}
+
+/// Verify that the `computed` digests of a file satisfy the `expected` digests.
+///
+/// As in pip, a file is considered valid if any of its digests matches any of the expected
+/// digests. If no digests are expected, the file is always considered valid.
+pub(crate) fn verify_hashes(
+    filename: &str,
+    expected: &[HashDigest],
+    computed: &[HashDigest],
+) -> Result<(), Error> {
+    if expected.is_empty() || expected.iter().any(|hash| computed.contains(hash)) {
+        return Ok(());
+    }
+
+    let format = |hashes: &[HashDigest]| {
+        hashes
+            .iter()
+            .map(|hash| format!("  {hash}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let computed = computed
+        .iter()
+        .filter(|computed| {
+            expected
+                .iter()
+                .any(|expected| expected.algorithm() == computed.algorithm())
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    Err(Error::HashMismatch {
+        filename: filename.to_string(),
+        expected: format(expected),
+        actual: format(&computed),
+    })
+}
+
+/// Compute the digests of a local file, for each of the given algorithms.
+pub(crate) async fn hash_file(
+    path: &Path,
+    algorithms: &[HashAlgorithm],
+) -> Result<Vec<HashDigest>, Error> {
+    let file = fs_err::tokio::File::open(path)
+        .await
+        .map_err(Error::HashExhaustion)?;
+    let mut hashers = algorithms
+        .iter()
+        .copied()
+        .map(uv_extract::Hasher::from)
+        .collect::<Vec<_>>();
+    let mut reader = uv_extract::HashReader::new(file, &mut hashers);
+    reader.finish().await.map_err(Error::HashExhaustion)?;
+    Ok(hashers
+        .into_iter()
+        .map(uv_extract::Hasher::finalize)
+        .collect())
+}
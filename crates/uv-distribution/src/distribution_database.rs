@@ -8,20 +8,24 @@ use tokio_util::compat::FuturesAsyncReadCompatExt;
 use tracing::{info_span, instrument, Instrument};
 use url::Url;
 
+use distribution_filename::WheelFilename;
 use distribution_types::{
-    BuiltDist, DirectGitUrl, Dist, FileLocation, IndexLocations, LocalEditable, Name, SourceDist,
+    BuiltDist, DirectArchiveUrl, DirectGitUrl, Dist, File, FileLocation, IndexLocations,
+    LocalEditable, Name, RemoteSource, SourceDist,
 };
 use platform_tags::Tags;
-use pypi_types::Metadata21;
-use uv_cache::{ArchiveTarget, ArchiveTimestamp, Cache, CacheBucket, WheelCache};
+use pypi_types::{HashDigest, Metadata21};
+use uv_cache::{ArchiveTarget, ArchiveTimestamp, Cache, CacheBucket, CacheEntry, WheelCache};
 use uv_client::{CacheControl, CachedClientError, Connectivity, RegistryClient};
-
+use uv_extract::{HashReader, Hasher};
 use uv_git::GitSource;
-use uv_traits::{BuildContext, NoBinary, NoBuild};
+use uv_traits::{BuildContext, NoBinary, NoBuild, RequiredHashes};
 
-use crate::download::{BuiltWheel, UnzippedWheel};
+use crate::archive::{hash_algorithms, hash_file, verify_hashes, Archive};
+use crate::download::{BuiltWheel, DownloadedArchive, UnzippedWheel};
 use crate::locks::Locks;
 use crate::reporter::Facade;
+use crate::source::git_http;
 use crate::{DiskWheel, Error, LocalWheel, Reporter, SourceDistCachedBuilder};
 
 /// A cached high-level interface to convert distributions (a requirement resolved to a location)
@@ -43,6 +47,8 @@ pub struct DistributionDatabase<'a, Context: BuildContext + Send + Sync> {
     client: &'a RegistryClient,
     build_context: &'a Context,
     builder: SourceDistCachedBuilder<'a, Context>,
+    /// The hashes that distributions are required to match, if any.
+    hashes: Option<&'a RequiredHashes>,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context> {
@@ -59,6 +65,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
             client,
             build_context,
             builder: SourceDistCachedBuilder::new(build_context, client, tags),
+            hashes: None,
         }
     }
 
@@ -73,8 +80,29 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         }
     }
 
-    /// Handle a specific `reqwest` error, and convert it to [`io::Error`].
-    fn handle_response_errors(&self, err: reqwest::Error) -> io::Error {
+    /// Set the hashes that distributions are required to match.
+    ///
+    /// Source distributions are verified against these hashes before their metadata is built. In
+    /// `--require-hashes` mode, distributions without any hashes are rejected before they're
+    /// downloaded or built.
+    #[must_use]
+    pub fn with_hashes(self, hashes: &'a RequiredHashes) -> Self {
+        Self {
+            hashes: Some(hashes),
+            ..self
+        }
+    }
+
+    /// In `--require-hashes` mode, reject a distribution that lacks any hashes.
+    fn check_required_hashes(&self, dist: &Dist, hashes: &[HashDigest]) -> Result<(), Error> {
+        if hashes.is_empty() && self.hashes.is_some_and(RequiredHashes::is_strict) {
+            return Err(Error::MissingHashes(dist.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Handle an error while streaming a download, and convert it to [`io::Error`].
+    fn handle_response_errors(&self, err: uv_client::Error) -> io::Error {
         if err.is_timeout() {
             io::Error::new(
                 io::ErrorKind::TimedOut,
@@ -91,15 +119,24 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
     ///
     /// If `no_remote_wheel` is set, the wheel will be built from a source distribution
     /// even if compatible pre-built wheels are available.
-    #[instrument(skip(self))]
-    pub async fn get_or_build_wheel(&self, dist: Dist) -> Result<LocalWheel, Error> {
+    ///
+    /// If `hashes` is non-empty, the downloaded (or local) distribution must match at least one of
+    /// the given digests.
+    #[instrument(skip(self, hashes))]
+    pub async fn get_or_build_wheel(
+        &self,
+        dist: Dist,
+        hashes: &[HashDigest],
+    ) -> Result<LocalWheel, Error> {
+        self.check_required_hashes(&dist, hashes)?;
+
         let no_binary = match self.build_context.no_binary() {
             NoBinary::None => false,
             NoBinary::All => true,
             NoBinary::Packages(packages) => packages.contains(dist.name()),
         };
         match &dist {
-            Dist::Built(BuiltDist::Registry(wheel)) => {
+            Dist::Built(built_dist @ BuiltDist::Registry(wheel)) => {
                 if no_binary {
                     return Err(Error::NoBinary);
                 }
@@ -112,6 +149,12 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                         Url::parse(url).map_err(|err| Error::Url(url.clone(), err))?
                     }
                     FileLocation::Path(path) => {
+                        // Verify the local file against the expected hashes.
+                        if !hashes.is_empty() {
+                            let computed = hash_file(path, &hash_algorithms(hashes)).await?;
+                            verify_hashes(&wheel.file.filename, hashes, &computed)?;
+                        }
+
                         let url = Url::from_file_path(path).expect("path is absolute");
                         let cache_entry = self.cache.entry(
                             CacheBucket::Wheels,
@@ -156,58 +199,19 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     WheelCache::Index(&wheel.index).remote_wheel_dir(wheel.name().as_ref()),
                     wheel.filename.stem(),
                 );
-                let http_entry = wheel_entry.with_file(format!("{}.http", wheel.filename.stem()));
-
-                let download = |response: reqwest::Response| {
-                    async {
-                        let reader = response
-                            .bytes_stream()
-                            .map_err(|err| self.handle_response_errors(err))
-                            .into_async_read();
-
-                        // Download and unzip the wheel to a temporary directory.
-                        let temp_dir =
-                            tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
-                        uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
-
-                        // Persist the temporary directory to the directory store.
-                        let archive = self
-                            .cache
-                            .persist(temp_dir.into_path(), wheel_entry.path())
-                            .map_err(Error::CacheRead)?;
-                        Ok(archive)
-                    }
-                    .instrument(info_span!("download", wheel = %wheel))
-                };
-
-                let req = self.client.cached_client().uncached().get(url).build()?;
-                let cache_control = match self.client.connectivity() {
-                    Connectivity::Online => CacheControl::from(
-                        self.cache
-                            .freshness(&http_entry, Some(wheel.name()))
-                            .map_err(Error::CacheRead)?,
-                    ),
-                    Connectivity::Offline => CacheControl::AllowStale,
-                };
 
                 let archive = self
-                    .client
-                    .cached_client()
-                    .get_serde(req, &http_entry, cache_control, download)
-                    .await
-                    .map_err(|err| match err {
-                        CachedClientError::Callback(err) => err,
-                        CachedClientError::Client(err) => Error::Client(err),
-                    })?;
+                    .stream_wheel(url, &wheel.filename, &wheel_entry, built_dist, hashes)
+                    .await?;
 
                 Ok(LocalWheel::Unzipped(UnzippedWheel {
                     dist: dist.clone(),
-                    archive,
+                    archive: archive.path,
                     filename: wheel.filename.clone(),
                 }))
             }
 
-            Dist::Built(BuiltDist::DirectUrl(wheel)) => {
+            Dist::Built(built_dist @ BuiltDist::DirectUrl(wheel)) => {
                 if no_binary {
                     return Err(Error::NoBinary);
                 }
@@ -218,57 +222,20 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     WheelCache::Url(&wheel.url).remote_wheel_dir(wheel.name().as_ref()),
                     wheel.filename.stem(),
                 );
-                let http_entry = wheel_entry.with_file(format!("{}.http", wheel.filename.stem()));
-
-                let download = |response: reqwest::Response| {
-                    async {
-                        let reader = response
-                            .bytes_stream()
-                            .map_err(|err| self.handle_response_errors(err))
-                            .into_async_read();
-
-                        // Download and unzip the wheel to a temporary directory.
-                        let temp_dir =
-                            tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
-                        uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
-
-                        // Persist the temporary directory to the directory store.
-                        let archive = self
-                            .cache
-                            .persist(temp_dir.into_path(), wheel_entry.path())
-                            .map_err(Error::CacheRead)?;
-                        Ok(archive)
-                    }
-                    .instrument(info_span!("download", wheel = %wheel))
-                };
 
-                let req = self
-                    .client
-                    .cached_client()
-                    .uncached()
-                    .get(wheel.url.raw().clone())
-                    .build()?;
-                let cache_control = match self.client.connectivity() {
-                    Connectivity::Online => CacheControl::from(
-                        self.cache
-                            .freshness(&http_entry, Some(wheel.name()))
-                            .map_err(Error::CacheRead)?,
-                    ),
-                    Connectivity::Offline => CacheControl::AllowStale,
-                };
                 let archive = self
-                    .client
-                    .cached_client()
-                    .get_serde(req, &http_entry, cache_control, download)
-                    .await
-                    .map_err(|err| match err {
-                        CachedClientError::Callback(err) => err,
-                        CachedClientError::Client(err) => Error::Client(err),
-                    })?;
+                    .stream_wheel(
+                        wheel.url.raw().clone(),
+                        &wheel.filename,
+                        &wheel_entry,
+                        built_dist,
+                        hashes,
+                    )
+                    .await?;
 
                 Ok(LocalWheel::Unzipped(UnzippedWheel {
                     dist: dist.clone(),
-                    archive,
+                    archive: archive.path,
                     filename: wheel.filename.clone(),
                 }))
             }
@@ -284,6 +251,12 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     wheel.filename.stem(),
                 );
 
+                // Verify the local file against the expected hashes.
+                if !hashes.is_empty() {
+                    let computed = hash_file(&wheel.path, &hash_algorithms(hashes)).await?;
+                    verify_hashes(&wheel.filename.to_string(), hashes, &computed)?;
+                }
+
                 // If the file is already unzipped, and the unzipped directory is fresh,
                 // return it.
                 match cache_entry.path().canonicalize() {
@@ -317,7 +290,11 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                 let lock = self.locks.acquire(&dist).await;
                 let _guard = lock.lock().await;
 
-                let built_wheel = self.builder.download_and_build(source_dist).boxed().await?;
+                let built_wheel = self
+                    .builder
+                    .download_and_build(source_dist, hashes)
+                    .boxed()
+                    .await?;
 
                 // If the wheel was unzipped previously, respect it. Source distributions are
                 // cached under a unique build ID, so unzipped directories are never stale.
@@ -341,6 +318,311 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         }
     }
 
+    /// Stream a wheel from a URL, unzipping it into the cache as it's downloaded.
+    ///
+    /// The digests of the wheel are computed during the download and stored alongside the HTTP
+    /// cache entry, such that cached wheels can be verified against `hashes` without being
+    /// re-downloaded.
+    async fn stream_wheel(
+        &self,
+        url: Url,
+        filename: &WheelFilename,
+        wheel_entry: &CacheEntry,
+        dist: &BuiltDist,
+        hashes: &[HashDigest],
+    ) -> Result<Archive, Error> {
+        let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
+
+        let download = |response: reqwest::Response| {
+            async {
+                let reader = self
+                    .client
+                    .stream_response(response)
+                    .map_err(|err| self.handle_response_errors(err))
+                    .into_async_read();
+
+                // Create a hasher for each hash algorithm.
+                let mut hashers = hash_algorithms(hashes)
+                    .into_iter()
+                    .map(Hasher::from)
+                    .collect::<Vec<_>>();
+                let mut hasher = HashReader::new(reader.compat(), &mut hashers);
+
+                // Download and unzip the wheel to a temporary directory.
+                let temp_dir =
+                    tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
+                uv_extract::stream::unzip(&mut hasher, temp_dir.path()).await?;
+
+                // Exhaust the reader, such that the digests cover the entire file.
+                hasher.finish().await.map_err(Error::HashExhaustion)?;
+
+                let computed = hashers
+                    .into_iter()
+                    .map(Hasher::finalize)
+                    .collect::<Vec<_>>();
+
+                // Reject the wheel before persisting it, if it doesn't match the expected hashes.
+                verify_hashes(&filename.to_string(), hashes, &computed)?;
+
+                // Persist the temporary directory to the directory store.
+                let path = self
+                    .cache
+                    .persist(temp_dir.into_path(), wheel_entry.path())
+                    .map_err(Error::CacheRead)?;
+                Ok(Archive::new(path, computed))
+            }
+            .instrument(info_span!("download", wheel = %dist))
+        };
+
+        let req = self.client.cached_client().uncached().get(url).build()?;
+        let cache_control = match self.client.connectivity() {
+            Connectivity::Online => CacheControl::from(
+                self.cache
+                    .freshness(&http_entry, Some(dist.name()))
+                    .map_err(Error::CacheRead)?,
+            ),
+            Connectivity::Offline => CacheControl::AllowStale,
+        };
+
+        let archive = self
+            .client
+            .cached_client()
+            .get_serde(
+                req.try_clone().expect("HTTP request must be cloneable"),
+                &http_entry,
+                cache_control,
+                download,
+            )
+            .await
+            .map_err(|err| match err {
+                CachedClientError::Callback(err) => err,
+                CachedClientError::Client(err) => Error::Client(err),
+            })?;
+
+        // If the cached archive is missing any of the required digests, download it again.
+        let archive = if archive.has_digests(hashes) {
+            archive
+        } else {
+            self.client
+                .cached_client()
+                .skip_cache(req, &http_entry, download)
+                .await
+                .map_err(|err| match err {
+                    CachedClientError::Callback(err) => err,
+                    CachedClientError::Client(err) => Error::Client(err),
+                })?
+        };
+
+        // Verify the (possibly cached) archive against the expected hashes.
+        verify_hashes(&filename.to_string(), hashes, &archive.hashes)?;
+
+        Ok(archive)
+    }
+
+    /// Save the archive for a distribution to the given directory, under its original filename.
+    ///
+    /// Wheels and source distributions are copied (or downloaded) as-is, without being unzipped
+    /// into the cache. If `build` is set, source distributions are instead built, and the
+    /// resulting wheel is saved in their place.
+    ///
+    /// If `hashes` is non-empty, the distribution must match at least one of the given digests.
+    #[instrument(skip(self, directory, hashes))]
+    pub async fn download_archive(
+        &self,
+        dist: &Dist,
+        directory: &Path,
+        build: bool,
+        hashes: &[HashDigest],
+    ) -> Result<DownloadedArchive, Error> {
+        self.check_required_hashes(dist, hashes)?;
+
+        match dist {
+            Dist::Source(source_dist) if build => {
+                let no_build = match self.build_context.no_build() {
+                    NoBuild::All => true,
+                    NoBuild::None => false,
+                    NoBuild::Packages(packages) => packages.contains(source_dist.name()),
+                };
+                if no_build {
+                    return Err(Error::NoBuild);
+                }
+
+                let lock = self.locks.acquire(dist).await;
+                let _guard = lock.lock().await;
+
+                let built_wheel = self
+                    .builder
+                    .download_and_build(source_dist, hashes)
+                    .boxed()
+                    .await?;
+
+                // The built wheel has already been verified against the source distribution's
+                // hashes, so there's nothing left to verify.
+                let target = directory.join(built_wheel.filename.to_string());
+                self.copy_archive(&built_wheel.path, &target, dist, &[])
+                    .await
+            }
+            Dist::Built(BuiltDist::Registry(wheel)) => {
+                self.download_file(&wheel.file, directory, dist, hashes)
+                    .await
+            }
+            Dist::Built(BuiltDist::DirectUrl(wheel)) => {
+                let target = directory.join(wheel.filename.to_string());
+                self.stream_archive(wheel.url.raw().clone(), &target, dist, hashes)
+                    .await
+            }
+            Dist::Built(BuiltDist::Path(wheel)) => {
+                let target = directory.join(wheel.filename.to_string());
+                self.copy_archive(&wheel.path, &target, dist, hashes).await
+            }
+            Dist::Source(SourceDist::Registry(source_dist)) => {
+                self.download_file(&source_dist.file, directory, dist, hashes)
+                    .await
+            }
+            Dist::Source(SourceDist::DirectUrl(source_dist)) => {
+                let filename = source_dist
+                    .filename()
+                    .expect("Distribution must have a filename");
+                let DirectArchiveUrl { url, .. } = DirectArchiveUrl::from(source_dist.url.raw());
+                let target = directory.join(filename.as_ref());
+                self.stream_archive(url, &target, dist, hashes).await
+            }
+            Dist::Source(SourceDist::Git(_)) => {
+                Err(Error::ArchiveNotSupportedGit(dist.to_string()))
+            }
+            Dist::Source(SourceDist::Path(source_dist)) => {
+                if source_dist.path.is_dir() {
+                    return Err(Error::ArchiveNotSupportedSourceTree(dist.to_string()));
+                }
+                let filename = source_dist
+                    .filename()
+                    .expect("Distribution must have a filename");
+                let target = directory.join(filename.as_ref());
+                self.copy_archive(&source_dist.path, &target, dist, hashes)
+                    .await
+            }
+        }
+    }
+
+    /// Save a registry [`File`] to the given directory, whether it's hosted remotely or locally.
+    async fn download_file(
+        &self,
+        file: &File,
+        directory: &Path,
+        dist: &Dist,
+        hashes: &[HashDigest],
+    ) -> Result<DownloadedArchive, Error> {
+        let target = directory.join(&file.filename);
+        let url = match &file.url {
+            FileLocation::RelativeUrl(base, url) => pypi_types::base_url_join_relative(base, url)?,
+            FileLocation::AbsoluteUrl(url) => {
+                Url::parse(url).map_err(|err| Error::Url(url.clone(), err))?
+            }
+            FileLocation::Path(path) => {
+                return self.copy_archive(path, &target, dist, hashes).await;
+            }
+        };
+        self.stream_archive(url, &target, dist, hashes).await
+    }
+
+    /// Copy a local archive to the target path, verifying it against the expected hashes first.
+    async fn copy_archive(
+        &self,
+        path: &Path,
+        target: &Path,
+        dist: &Dist,
+        hashes: &[HashDigest],
+    ) -> Result<DownloadedArchive, Error> {
+        let computed = hash_file(path, &hash_algorithms(hashes)).await?;
+        verify_hashes(&dist.to_string(), hashes, &computed)?;
+
+        // Avoid copying a file onto itself, e.g., when saving distributions from a `--find-links`
+        // directory back to that same directory.
+        let is_same_file = matches!(
+            (path.canonicalize(), target.canonicalize()),
+            (Ok(path), Ok(target)) if path == target
+        );
+        if !is_same_file {
+            fs_err::tokio::copy(path, target)
+                .await
+                .map_err(Error::ArchiveWrite)?;
+        }
+
+        Ok(DownloadedArchive {
+            dist: dist.clone(),
+            path: target.to_path_buf(),
+            hashes: computed,
+        })
+    }
+
+    /// Stream an archive from a URL to the target path, computing its digests as it's downloaded.
+    ///
+    /// The archive is written to a temporary file alongside the target, and only persisted once
+    /// it's been verified against the expected hashes.
+    async fn stream_archive(
+        &self,
+        url: Url,
+        target: &Path,
+        dist: &Dist,
+        hashes: &[HashDigest],
+    ) -> Result<DownloadedArchive, Error> {
+        let response = self
+            .client
+            .cached_client()
+            .uncached()
+            .get(url)
+            .send()
+            .await
+            .map_err(|err| Error::Client(uv_client::ErrorKind::from(err).into()))?
+            .error_for_status()?;
+
+        let reader = self
+            .client
+            .stream_response(response)
+            .map_err(|err| self.handle_response_errors(err))
+            .into_async_read();
+
+        // Create a hasher for each hash algorithm.
+        let mut hashers = hash_algorithms(hashes)
+            .into_iter()
+            .map(Hasher::from)
+            .collect::<Vec<_>>();
+        let mut hasher = HashReader::new(reader.compat(), &mut hashers);
+
+        // Download the archive to a temporary file in the target directory.
+        let directory = target
+            .parent()
+            .expect("Archive must be written to a directory");
+        let (file, temp_path) = tempfile::NamedTempFile::new_in(directory)
+            .map_err(Error::ArchiveWrite)?
+            .into_parts();
+        let mut file = tokio::fs::File::from_std(file);
+        tokio::io::copy(&mut hasher, &mut file)
+            .instrument(info_span!("download", archive = %dist))
+            .await
+            .map_err(Error::ArchiveWrite)?;
+        file.sync_all().await.map_err(Error::ArchiveWrite)?;
+        drop(file);
+
+        let computed = hashers
+            .into_iter()
+            .map(Hasher::finalize)
+            .collect::<Vec<_>>();
+
+        // Reject the archive before persisting it, if it doesn't match the expected hashes.
+        verify_hashes(&dist.to_string(), hashes, &computed)?;
+
+        temp_path
+            .persist(target)
+            .map_err(|err| Error::ArchiveWrite(err.error))?;
+
+        Ok(DownloadedArchive {
+            dist: dist.clone(),
+            path: target.to_path_buf(),
+            hashes: computed,
+        })
+    }
+
     /// Either fetch the only wheel metadata (directly from the index or with range requests) or
     /// fetch and build the source distribution.
     ///
@@ -367,6 +649,13 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     return Err(Error::NoBuild);
                 }
 
+                // Verify the source distribution before building it.
+                let hashes = self
+                    .hashes
+                    .map(|hashes| hashes.get(source_dist.name()))
+                    .unwrap_or_default();
+                self.check_required_hashes(dist, hashes)?;
+
                 let lock = self.locks.acquire(dist).await;
                 let _guard = lock.lock().await;
 
@@ -380,7 +669,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
 
                 let metadata = self
                     .builder
-                    .download_and_build_metadata(&source_dist)
+                    .download_and_build_metadata(&source_dist, hashes)
                     .boxed()
                     .await?;
                 Ok((metadata, precise))
@@ -432,10 +721,13 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
 
         // Fetch the precise SHA of the Git reference (which could be a branch, a tag, a partial
         // commit, etc.).
+        let http = git_http(self.client, url.repository());
+        let source =
+            GitSource::new(url, self.client.cached_client().uncached(), git_dir).with_http(http);
         let source = if let Some(reporter) = self.reporter.clone() {
-            GitSource::new(url, git_dir).with_reporter(Facade::from(reporter))
+            source.with_reporter(Facade::from(reporter))
         } else {
-            GitSource::new(url, git_dir)
+            source
         };
         let precise = tokio::task::spawn_blocking(move || source.fetch())
             .await?
@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 
 use distribution_filename::WheelFilename;
 use distribution_types::{CachedDist, Dist};
+use pypi_types::HashDigest;
 
 /// A wheel that's been unzipped while downloading
 #[derive(Debug, Clone)]
@@ -43,6 +44,18 @@ pub struct BuiltWheel {
     pub(crate) target: PathBuf,
 }
 
+/// A distribution archive (a wheel or source distribution) that's been saved to a directory
+/// outside the cache, under its original filename.
+#[derive(Debug, Clone)]
+pub struct DownloadedArchive {
+    /// The remote distribution from which this archive was downloaded (or built).
+    pub(crate) dist: Dist,
+    /// The path to the saved archive.
+    pub(crate) path: PathBuf,
+    /// The computed hashes of the archive.
+    pub(crate) hashes: Vec<HashDigest>,
+}
+
 /// A downloaded or built wheel.
 #[derive(Debug, Clone)]
 pub enum LocalWheel {
@@ -116,6 +129,23 @@ impl BuiltWheel {
     }
 }
 
+impl DownloadedArchive {
+    /// Return the [`Dist`] from which this archive was downloaded.
+    pub fn remote(&self) -> &Dist {
+        &self.dist
+    }
+
+    /// Return the path to the saved archive.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the computed hashes of the archive, which always include a SHA-256 digest.
+    pub fn hashes(&self) -> &[HashDigest] {
+        &self.hashes
+    }
+}
+
 impl std::fmt::Display for LocalWheel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.remote())
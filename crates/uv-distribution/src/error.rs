@@ -57,6 +57,30 @@ pub enum Error {
     #[error("Failed to extract source distribution")]
     Extract(#[from] uv_extract::Error),
 
+    // Archive download error
+    #[error("Failed to save distribution archive")]
+    ArchiveWrite(#[source] std::io::Error),
+    #[error("Git dependencies can only be saved as built wheels: `{0}`")]
+    ArchiveNotSupportedGit(String),
+    #[error("Local directories can only be saved as built wheels: `{0}`")]
+    ArchiveNotSupportedSourceTree(String),
+
+    // Hash verification error
+    #[error("Hash mismatch for `{filename}`\n\nExpected:\n{expected}\n\nComputed:\n{actual}")]
+    HashMismatch {
+        filename: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Failed to hash distribution")]
+    HashExhaustion(#[source] std::io::Error),
+    #[error("Hash-checking is not supported for Git dependencies: `{0}`")]
+    HashesNotSupportedGit(String),
+    #[error("Hash-checking is not supported for local directories: `{0}`")]
+    HashesNotSupportedSourceTree(String),
+    #[error("In `--require-hashes` mode, all requirements must have a hash, but none were provided for: {0}")]
+    MissingHashes(String),
+
     /// Should not occur; only seen when another task panicked.
     #[error("The task executor is broken, did some other task panic?")]
     Join(#[from] JoinError),
@@ -1,11 +1,12 @@
 pub use distribution_database::DistributionDatabase;
-pub use download::{BuiltWheel, DiskWheel, LocalWheel};
+pub use download::{BuiltWheel, DiskWheel, DownloadedArchive, LocalWheel};
 pub use error::Error;
 pub use index::{BuiltWheelIndex, RegistryWheelIndex};
 pub use reporter::Reporter;
 pub use source::SourceDistCachedBuilder;
 pub use unzip::Unzip;
 
+mod archive;
 mod distribution_database;
 mod download;
 mod error;
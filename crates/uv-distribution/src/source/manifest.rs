@@ -1,17 +1,37 @@
 use serde::{Deserialize, Serialize};
 
-/// The [`Manifest`] is a thin wrapper around a unique identifier for the source distribution.
+use pypi_types::HashDigest;
+
+/// The [`Manifest`] is a thin wrapper around a unique identifier for the source distribution,
+/// along with the digests of the source distribution archive (if it was downloaded).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct Manifest(String);
+pub(crate) struct Manifest {
+    id: String,
+    hashes: Vec<HashDigest>,
+}
 
 impl Manifest {
     /// Initialize a new [`Manifest`] with a random UUID.
     pub(crate) fn new() -> Self {
-        Self(nanoid::nanoid!())
+        Self {
+            id: nanoid::nanoid!(),
+            hashes: Vec::new(),
+        }
+    }
+
+    /// Set the digests of the source distribution archive.
+    #[must_use]
+    pub(crate) fn with_hashes(self, hashes: Vec<HashDigest>) -> Self {
+        Self { hashes, ..self }
     }
 
     /// Return the unique ID of the manifest.
     pub(crate) fn id(&self) -> &str {
-        &self.0
+        &self.id
+    }
+
+    /// Return the digests of the source distribution archive.
+    pub(crate) fn hashes(&self) -> &[HashDigest] {
+        &self.hashes
     }
 }
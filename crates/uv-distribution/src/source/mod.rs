@@ -1,5 +1,6 @@
 //! Fetch and build source distributions from remote sources.
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -15,23 +16,25 @@ use zip::ZipArchive;
 
 use distribution_filename::WheelFilename;
 use distribution_types::{
-    DirectArchiveUrl, DirectGitUrl, Dist, FileLocation, GitSourceDist, LocalEditable, Name,
-    PathSourceDist, RemoteSource, SourceDist,
+    DirectArchiveUrl, DirectGitUrl, Dist, FileLocation, GitSourceDist, InstalledDist,
+    LocalEditable, Name, PathSourceDist, RemoteSource, SourceDist,
 };
 use install_wheel_rs::read_dist_info;
-use pep508_rs::VerbatimUrl;
+use pep508_rs::{Requirement, VerbatimUrl};
 use platform_tags::Tags;
-use pypi_types::Metadata21;
+use pypi_types::{HashDigest, Metadata21};
 use uv_cache::{
     ArchiveTimestamp, CacheBucket, CacheEntry, CacheShard, CachedByTimestamp, Freshness, WheelCache,
 };
 use uv_client::{
-    CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
+    CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, ProxyRoute, RegistryClient,
 };
+use uv_extract::{HashReader, Hasher};
 use uv_fs::{write_atomic, LockedFile};
-use uv_git::{Fetch, GitSource};
+use uv_git::{Fetch, GitHttp, GitProxy, GitSource};
 use uv_traits::{BuildContext, BuildKind, NoBuild, SourceBuildTrait};
 
+use crate::archive::{has_digests, hash_algorithms, hash_file, verify_hashes};
 use crate::error::Error;
 use crate::reporter::Facade;
 use crate::source::built_wheel_metadata::BuiltWheelMetadata;
@@ -55,6 +58,10 @@ pub(crate) const MANIFEST: &str = "manifest.msgpack";
 /// The name of the file that contains the cached distribution metadata, encoded via `MsgPack`.
 pub(crate) const METADATA: &str = "metadata.msgpack";
 
+/// The name of the file that contains the resolved requirements of the build environment, encoded
+/// via `MsgPack`.
+pub(crate) const BUILD_REQUIREMENTS: &str = "build-requirements.msgpack";
+
 impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
     /// Initialize a [`SourceDistCachedBuilder`] from a [`BuildContext`].
     pub fn new(build_context: &'a T, client: &'a RegistryClient, tags: &'a Tags) -> Self {
@@ -76,9 +83,13 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
     }
 
     /// Download and build a [`SourceDist`].
+    ///
+    /// If `hashes` is non-empty, the source distribution archive must match at least one of the
+    /// given digests.
     pub async fn download_and_build(
         &self,
         source_dist: &SourceDist,
+        hashes: &[HashDigest],
     ) -> Result<BuiltWheelMetadata, Error> {
         let built_wheel_metadata = match &source_dist {
             SourceDist::DirectUrl(direct_url_source_dist) => {
@@ -100,6 +111,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                     &url,
                     &cache_shard,
                     subdirectory.as_deref(),
+                    hashes,
                 )
                 .boxed()
                 .await?
@@ -121,7 +133,10 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                             path: path.clone(),
                             editable: false,
                         };
-                        return self.path(source_dist, &path_source_dist).boxed().await;
+                        return self
+                            .path(source_dist, &path_source_dist, hashes)
+                            .boxed()
+                            .await;
                     }
                 };
 
@@ -139,15 +154,21 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                     &url,
                     &cache_shard,
                     None,
+                    hashes,
                 )
                 .boxed()
                 .await?
             }
             SourceDist::Git(git_source_dist) => {
+                if !hashes.is_empty() {
+                    return Err(Error::HashesNotSupportedGit(source_dist.to_string()));
+                }
                 self.git(source_dist, git_source_dist).boxed().await?
             }
             SourceDist::Path(path_source_dist) => {
-                self.path(source_dist, path_source_dist).boxed().await?
+                self.path(source_dist, path_source_dist, hashes)
+                    .boxed()
+                    .await?
             }
         };
 
@@ -157,9 +178,13 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
     /// Download a [`SourceDist`] and determine its metadata. This typically involves building the
     /// source distribution into a wheel; however, some build backends support determining the
     /// metadata without building the source distribution.
+    ///
+    /// If `hashes` is non-empty, the source distribution archive must match at least one of the
+    /// given digests before any build runs.
     pub async fn download_and_build_metadata(
         &self,
         source_dist: &SourceDist,
+        hashes: &[HashDigest],
     ) -> Result<Metadata21, Error> {
         let metadata = match &source_dist {
             SourceDist::DirectUrl(direct_url_source_dist) => {
@@ -181,6 +206,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                     &url,
                     &cache_shard,
                     subdirectory.as_deref(),
+                    hashes,
                 )
                 .boxed()
                 .await?
@@ -203,7 +229,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                             editable: false,
                         };
                         return self
-                            .path_metadata(source_dist, &path_source_dist)
+                            .path_metadata(source_dist, &path_source_dist, hashes)
                             .boxed()
                             .await;
                     }
@@ -223,17 +249,21 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                     &url,
                     &cache_shard,
                     None,
+                    hashes,
                 )
                 .boxed()
                 .await?
             }
             SourceDist::Git(git_source_dist) => {
+                if !hashes.is_empty() {
+                    return Err(Error::HashesNotSupportedGit(source_dist.to_string()));
+                }
                 self.git_metadata(source_dist, git_source_dist)
                     .boxed()
                     .await?
             }
             SourceDist::Path(path_source_dist) => {
-                self.path_metadata(source_dist, path_source_dist)
+                self.path_metadata(source_dist, path_source_dist, hashes)
                     .boxed()
                     .await?
             }
@@ -251,6 +281,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         url: &'data Url,
         cache_shard: &CacheShard,
         subdirectory: Option<&'data Path>,
+        hashes: &'data [HashDigest],
     ) -> Result<BuiltWheelMetadata, Error> {
         let cache_entry = cache_shard.entry(MANIFEST);
         let cache_control = match self.client.connectivity() {
@@ -272,10 +303,17 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 // Download the source distribution.
                 debug!("Downloading source distribution: {source_dist}");
                 let source_dist_entry = cache_shard.shard(manifest.id()).entry(filename);
-                self.persist_source_dist_url(response, source_dist, filename, &source_dist_entry)
+                let computed = self
+                    .persist_source_dist_url(
+                        response,
+                        source_dist,
+                        filename,
+                        &source_dist_entry,
+                        hashes,
+                    )
                     .await?;
 
-                Ok(manifest)
+                Ok(manifest.with_hashes(computed))
             }
             .boxed()
             .instrument(info_span!("download", source_dist = %source_dist))
@@ -289,21 +327,46 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         let manifest = self
             .client
             .cached_client()
-            .get_serde(req, &cache_entry, cache_control, download)
+            .get_serde(
+                req.try_clone().expect("HTTP request must be cloneable"),
+                &cache_entry,
+                cache_control,
+                download,
+            )
             .await
             .map_err(|err| match err {
                 CachedClientError::Callback(err) => err,
                 CachedClientError::Client(err) => Error::Client(err),
             })?;
 
+        // If the cached manifest is missing any of the required digests, download the source
+        // distribution again.
+        let manifest = if has_digests(manifest.hashes(), hashes) {
+            manifest
+        } else {
+            self.client
+                .cached_client()
+                .skip_cache(req, &cache_entry, download)
+                .await
+                .map_err(|err| match err {
+                    CachedClientError::Callback(err) => err,
+                    CachedClientError::Client(err) => Error::Client(err),
+                })?
+        };
+
+        // Verify the (possibly cached) source distribution against the expected hashes.
+        verify_hashes(filename, hashes, manifest.hashes())?;
+
         // From here on, scope all operations to the current build. Within the manifest shard,
         // there's no need to check for freshness, since entries have to be fresher than the
         // manifest itself. There's also no need to lock, since we never replace entries within the
         // shard.
         let cache_shard = cache_shard.shard(manifest.id());
+        let build_shard = self.build_shard(source_dist, &cache_shard);
 
         // If the cache contains a compatible wheel, return it.
-        if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
+        if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &build_shard) {
+            self.restore_build_requirements(&build_shard).await?;
             return Ok(built_wheel);
         }
 
@@ -319,7 +382,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 source_dist,
                 source_dist_entry.path(),
                 subdirectory,
-                &cache_shard,
+                &build_shard,
             )
             .await?;
 
@@ -330,14 +393,14 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         }
 
         // Store the metadata.
-        let metadata_entry = cache_shard.entry(METADATA);
+        let metadata_entry = build_shard.entry(METADATA);
         write_atomic(metadata_entry.path(), rmp_serde::to_vec(&metadata)?)
             .await
             .map_err(Error::CacheWrite)?;
 
         Ok(BuiltWheelMetadata {
-            path: cache_shard.join(&disk_filename),
-            target: cache_shard.join(wheel_filename.stem()),
+            path: build_shard.join(&disk_filename),
+            target: build_shard.join(wheel_filename.stem()),
             filename: wheel_filename,
         })
     }
@@ -354,6 +417,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         url: &'data Url,
         cache_shard: &CacheShard,
         subdirectory: Option<&'data Path>,
+        hashes: &'data [HashDigest],
     ) -> Result<Metadata21, Error> {
         let cache_entry = cache_shard.entry(MANIFEST);
         let cache_control = match self.client.connectivity() {
@@ -375,10 +439,17 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 // Download the source distribution.
                 debug!("Downloading source distribution: {source_dist}");
                 let source_dist_entry = cache_shard.shard(manifest.id()).entry(filename);
-                self.persist_source_dist_url(response, source_dist, filename, &source_dist_entry)
+                let computed = self
+                    .persist_source_dist_url(
+                        response,
+                        source_dist,
+                        filename,
+                        &source_dist_entry,
+                        hashes,
+                    )
                     .await?;
 
-                Ok(manifest)
+                Ok(manifest.with_hashes(computed))
             }
             .boxed()
             .instrument(info_span!("download", source_dist = %source_dist))
@@ -392,23 +463,48 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         let manifest = self
             .client
             .cached_client()
-            .get_serde(req, &cache_entry, cache_control, download)
+            .get_serde(
+                req.try_clone().expect("HTTP request must be cloneable"),
+                &cache_entry,
+                cache_control,
+                download,
+            )
             .await
             .map_err(|err| match err {
                 CachedClientError::Callback(err) => err,
                 CachedClientError::Client(err) => Error::Client(err),
             })?;
 
+        // If the cached manifest is missing any of the required digests, download the source
+        // distribution again.
+        let manifest = if has_digests(manifest.hashes(), hashes) {
+            manifest
+        } else {
+            self.client
+                .cached_client()
+                .skip_cache(req, &cache_entry, download)
+                .await
+                .map_err(|err| match err {
+                    CachedClientError::Callback(err) => err,
+                    CachedClientError::Client(err) => Error::Client(err),
+                })?
+        };
+
+        // Verify the (possibly cached) source distribution against the expected hashes.
+        verify_hashes(filename, hashes, manifest.hashes())?;
+
         // From here on, scope all operations to the current build. Within the manifest shard,
         // there's no need to check for freshness, since entries have to be fresher than the
         // manifest itself. There's also no need to lock, since we never replace entries within the
         // shard.
         let cache_shard = cache_shard.shard(manifest.id());
+        let build_shard = self.build_shard(source_dist, &cache_shard);
 
         // If the cache contains compatible metadata, return it.
-        let metadata_entry = cache_shard.entry(METADATA);
+        let metadata_entry = build_shard.entry(METADATA);
         if let Some(metadata) = read_cached_metadata(&metadata_entry).await? {
             debug!("Using cached metadata for {source_dist}");
+            self.restore_build_requirements(&build_shard).await?;
             return Ok(metadata);
         }
 
@@ -417,12 +513,17 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         // If the backend supports `prepare_metadata_for_build_wheel`, use it.
         if let Some(metadata) = self
-            .build_source_dist_metadata(source_dist, source_dist_entry.path(), subdirectory)
+            .build_source_dist_metadata(
+                source_dist,
+                source_dist_entry.path(),
+                subdirectory,
+                &build_shard,
+            )
             .boxed()
             .await?
         {
             // Store the metadata.
-            let cache_entry = cache_shard.entry(METADATA);
+            let cache_entry = build_shard.entry(METADATA);
             fs::create_dir_all(cache_entry.dir())
                 .await
                 .map_err(Error::CacheWrite)?;
@@ -444,12 +545,12 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 source_dist,
                 source_dist_entry.path(),
                 subdirectory,
-                &cache_shard,
+                &build_shard,
             )
             .await?;
 
         // Store the metadata.
-        let cache_entry = cache_shard.entry(METADATA);
+        let cache_entry = build_shard.entry(METADATA);
         write_atomic(cache_entry.path(), rmp_serde::to_vec(&metadata)?)
             .await
             .map_err(Error::CacheWrite)?;
@@ -468,7 +569,11 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         &self,
         source_dist: &SourceDist,
         path_source_dist: &PathSourceDist,
+        hashes: &[HashDigest],
     ) -> Result<BuiltWheelMetadata, Error> {
+        // Verify the local archive against the expected hashes.
+        verify_path_hashes(source_dist, path_source_dist, hashes).await?;
+
         let cache_shard = self.build_context.cache().shard(
             CacheBucket::BuiltWheels,
             WheelCache::Path(&path_source_dist.url)
@@ -496,10 +601,11 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         // there's no need to check for freshness, since entries have to be fresher than the
         // manifest itself. There's also no need to lock, since we never replace entries within the
         // shard.
-        let cache_shard = cache_shard.shard(manifest.id());
+        let cache_shard = self.build_shard(source_dist, &cache_shard.shard(manifest.id()));
 
         // If the cache contains a compatible wheel, return it.
         if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
+            self.restore_build_requirements(&cache_shard).await?;
             return Ok(built_wheel);
         }
 
@@ -540,7 +646,11 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         &self,
         source_dist: &SourceDist,
         path_source_dist: &PathSourceDist,
+        hashes: &[HashDigest],
     ) -> Result<Metadata21, Error> {
+        // Verify the local archive against the expected hashes.
+        verify_path_hashes(source_dist, path_source_dist, hashes).await?;
+
         let cache_shard = self.build_context.cache().shard(
             CacheBucket::BuiltWheels,
             WheelCache::Path(&path_source_dist.url)
@@ -568,7 +678,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         // there's no need to check for freshness, since entries have to be fresher than the
         // manifest itself. There's also no need to lock, since we never replace entries within the
         // shard.
-        let cache_shard = cache_shard.shard(manifest.id());
+        let cache_shard = self.build_shard(source_dist, &cache_shard.shard(manifest.id()));
 
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
@@ -580,13 +690,14 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         {
             if let Some(metadata) = read_cached_metadata(&metadata_entry).await? {
                 debug!("Using cached metadata for {source_dist}");
+                self.restore_build_requirements(&cache_shard).await?;
                 return Ok(metadata);
             }
         }
 
         // If the backend supports `prepare_metadata_for_build_wheel`, use it.
         if let Some(metadata) = self
-            .build_source_dist_metadata(source_dist, &path_source_dist.path, None)
+            .build_source_dist_metadata(source_dist, &path_source_dist.path, None, &cache_shard)
             .boxed()
             .await?
         {
@@ -641,9 +752,11 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             WheelCache::Git(&git_source_dist.url, &git_sha.to_short_string())
                 .remote_wheel_dir(git_source_dist.name().as_ref()),
         );
+        let cache_shard = self.build_shard(source_dist, &cache_shard);
 
         // If the cache contains a compatible wheel, return it.
         if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
+            self.restore_build_requirements(&cache_shard).await?;
             return Ok(built_wheel);
         }
 
@@ -697,6 +810,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             WheelCache::Git(&git_source_dist.url, &git_sha.to_short_string())
                 .remote_wheel_dir(git_source_dist.name().as_ref()),
         );
+        let cache_shard = self.build_shard(source_dist, &cache_shard);
 
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
@@ -708,13 +822,19 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         {
             if let Some(metadata) = read_cached_metadata(&metadata_entry).await? {
                 debug!("Using cached metadata for {source_dist}");
+                self.restore_build_requirements(&cache_shard).await?;
                 return Ok(metadata);
             }
         }
 
         // If the backend supports `prepare_metadata_for_build_wheel`, use it.
         if let Some(metadata) = self
-            .build_source_dist_metadata(source_dist, fetch.path(), subdirectory.as_deref())
+            .build_source_dist_metadata(
+                source_dist,
+                fetch.path(),
+                subdirectory.as_deref(),
+                &cache_shard,
+            )
             .boxed()
             .await?
         {
@@ -760,32 +880,98 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         Ok(metadata)
     }
 
+    /// Return the shard in which to store the wheels and metadata built from a source
+    /// distribution.
+    ///
+    /// Builds without isolation depend on the packages installed in the shared build environment,
+    /// so they're stored separately for each environment and set of installed distributions, and
+    /// never shared with isolated builds. Upgrading a package in the environment (e.g., `numpy`)
+    /// thus triggers a rebuild against the new version.
+    ///
+    /// Similarly, builds with config settings or extra environment variables are stored separately
+    /// for each set of settings, such that changing a setting triggers a rebuild.
+    fn build_shard(&self, source_dist: &SourceDist, cache_shard: &CacheShard) -> CacheShard {
+        let package = source_dist.name();
+
+        let cache_shard = match self
+            .build_context
+            .build_isolation()
+            .shared_environment(Some(package))
+        {
+            Some(venv) => cache_shard.shard(cache_key::digest(&(
+                venv.root(),
+                venv.interpreter().python_version().to_string(),
+                installed_distributions(venv.site_packages()),
+            ))),
+            None => cache_shard.clone(),
+        };
+
+        let package_build_settings = self.build_context.package_build_settings();
+        let config_settings = package_build_settings
+            .config_settings(Some(package), self.build_context.config_settings());
+        let environment_variables = package_build_settings.environment_variables(Some(package));
+        if config_settings.is_empty() && environment_variables.is_none() {
+            cache_shard
+        } else {
+            cache_shard.shard(cache_key::digest(&(config_settings, environment_variables)))
+        }
+    }
+
     /// Download and unzip a source distribution into the cache from an HTTP response.
-    async fn persist_source_dist_url<'data>(
+    ///
+    /// Returns the digests of the downloaded archive, which must match at least one of the
+    /// expected `hashes` (if any).
+    async fn persist_source_dist_url(
         &self,
         response: Response,
         source_dist: &SourceDist,
         filename: &str,
-        cache_entry: &'data CacheEntry,
-    ) -> Result<&'data Path, Error> {
-        let cache_path = cache_entry.path();
-        if cache_path.is_dir() {
-            debug!("Distribution is already cached: {source_dist}");
-            return Ok(cache_path);
-        }
-
+        cache_entry: &CacheEntry,
+        hashes: &[HashDigest],
+    ) -> Result<Vec<HashDigest>, Error> {
         // Download and unzip the source distribution into a temporary directory.
         let span =
             info_span!("download_source_dist", filename = filename, source_dist = %source_dist);
         let temp_dir =
             tempfile::tempdir_in(self.build_context.cache().root()).map_err(Error::CacheWrite)?;
-        let reader = response
-            .bytes_stream()
+        let reader = self
+            .client
+            .stream_response(response)
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
             .into_async_read();
-        uv_extract::stream::archive(reader.compat(), filename, temp_dir.path()).await?;
+
+        // Create a hasher for each hash algorithm.
+        let mut hashers = hash_algorithms(hashes)
+            .into_iter()
+            .map(Hasher::from)
+            .collect::<Vec<_>>();
+        let mut hasher = HashReader::new(reader.compat(), &mut hashers);
+        uv_extract::stream::archive(
+            tokio::io::BufReader::new(&mut hasher),
+            filename,
+            temp_dir.path(),
+        )
+        .await?;
+
+        // Exhaust the reader, such that the digests cover the entire file.
+        hasher.finish().await.map_err(Error::HashExhaustion)?;
         drop(span);
 
+        let computed = hashers
+            .into_iter()
+            .map(Hasher::finalize)
+            .collect::<Vec<_>>();
+
+        // Reject the source distribution before persisting it, if it doesn't match the expected
+        // hashes.
+        verify_hashes(filename, hashes, &computed)?;
+
+        let cache_path = cache_entry.path();
+        if cache_path.is_dir() {
+            debug!("Distribution is already cached: {source_dist}");
+            return Ok(computed);
+        }
+
         // Extract the top-level directory.
         let extracted = match uv_extract::strip_component(temp_dir.path()) {
             Ok(top_level) => top_level,
@@ -801,7 +987,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .await
             .map_err(Error::CacheWrite)?;
 
-        Ok(cache_path)
+        Ok(computed)
     }
 
     /// Download a source distribution from a Git repository.
@@ -823,10 +1009,13 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         let DirectGitUrl { url, subdirectory } = DirectGitUrl::try_from(url).map_err(Error::Git)?;
 
+        let http = git_http(self.client, url.repository());
+        let source =
+            GitSource::new(url, self.client.cached_client().uncached(), git_dir).with_http(http);
         let source = if let Some(reporter) = &self.reporter {
-            GitSource::new(url, git_dir).with_reporter(Facade::from(reporter.clone()))
+            source.with_reporter(Facade::from(reporter.clone()))
         } else {
-            GitSource::new(url, git_dir)
+            source
         };
         let fetch = tokio::task::spawn_blocking(move || source.fetch())
             .await?
@@ -861,7 +1050,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         fs::create_dir_all(&cache_shard)
             .await
             .map_err(Error::CacheWrite)?;
-        let disk_filename = self
+        let builder = self
             .build_context
             .setup_build(
                 source_dist,
@@ -871,10 +1060,13 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 BuildKind::Wheel,
             )
             .await
-            .map_err(|err| Error::Build(dist.to_string(), err))?
+            .map_err(|err| Error::Build(dist.to_string(), err))?;
+        let disk_filename = builder
             .wheel(cache_shard)
             .await
             .map_err(|err| Error::Build(dist.to_string(), err))?;
+        self.persist_build_requirements(builder.build_requirements(), cache_shard)
+            .await?;
 
         // Read the metadata from the wheel.
         let filename = WheelFilename::from_str(&disk_filename)?;
@@ -899,6 +1091,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         dist: &SourceDist,
         source_dist: &Path,
         subdirectory: Option<&Path>,
+        cache_shard: &CacheShard,
     ) -> Result<Option<Metadata21>, Error> {
         debug!("Preparing metadata for: {dist}");
 
@@ -939,9 +1132,53 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             });
         }
 
+        self.persist_build_requirements(builder.build_requirements(), cache_shard)
+            .await?;
+
         Ok(Some(metadata))
     }
 
+    /// Record the resolved requirements of a build environment, and store them alongside the
+    /// artifacts built in it, such that they can be recorded again when the artifacts are read
+    /// from the cache.
+    async fn persist_build_requirements(
+        &self,
+        requirements: &[Requirement],
+        cache_shard: &CacheShard,
+    ) -> Result<(), Error> {
+        self.build_context.record_build_requirements(requirements);
+
+        let requirements = requirements
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let cache_entry = cache_shard.entry(BUILD_REQUIREMENTS);
+        fs::create_dir_all(cache_entry.dir())
+            .await
+            .map_err(Error::CacheWrite)?;
+        write_atomic(cache_entry.path(), rmp_serde::to_vec(&requirements)?)
+            .await
+            .map_err(Error::CacheWrite)?;
+        Ok(())
+    }
+
+    /// Record the resolved requirements of the build environment of a cached build, if they were
+    /// stored alongside it.
+    async fn restore_build_requirements(&self, cache_shard: &CacheShard) -> Result<(), Error> {
+        let cache_entry = cache_shard.entry(BUILD_REQUIREMENTS);
+        let requirements = match fs::read(cache_entry.path()).await {
+            Ok(cached) => rmp_serde::from_slice::<Vec<String>>(&cached)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(Error::CacheRead(err)),
+        };
+        let requirements = requirements
+            .iter()
+            .filter_map(|requirement| Requirement::from_str(requirement).ok())
+            .collect::<Vec<_>>();
+        self.build_context.record_build_requirements(&requirements);
+        Ok(())
+    }
+
     /// Build a single directory into an editable wheel
     pub async fn build_editable(
         &self,
@@ -978,6 +1215,43 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
     }
 }
 
+/// Return the name and version of every distribution installed in the given `site-packages`
+/// directory.
+fn installed_distributions(site_packages: &Path) -> BTreeMap<String, String> {
+    let Ok(entries) = fs_err::read_dir(site_packages) else {
+        return BTreeMap::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let dist = InstalledDist::try_from_path(&entry.ok()?.path()).ok()??;
+            Some((dist.name().to_string(), dist.version().to_string()))
+        })
+        .collect()
+}
+
+/// Verify a local source distribution archive against the expected hashes.
+///
+/// Source trees can't be hashed, and so are rejected if any hashes are expected.
+async fn verify_path_hashes(
+    source_dist: &SourceDist,
+    path_source_dist: &PathSourceDist,
+    hashes: &[HashDigest],
+) -> Result<(), Error> {
+    if hashes.is_empty() {
+        return Ok(());
+    }
+    if path_source_dist.path.is_dir() {
+        return Err(Error::HashesNotSupportedSourceTree(source_dist.to_string()));
+    }
+    let computed = hash_file(&path_source_dist.path, &hash_algorithms(hashes)).await?;
+    let filename = path_source_dist
+        .path
+        .file_name()
+        .map(|filename| filename.to_string_lossy())
+        .unwrap_or_default();
+    verify_hashes(&filename, hashes, &computed)
+}
+
 /// Read an existing HTTP-cached [`Manifest`], if it exists.
 pub(crate) fn read_http_manifest(cache_entry: &CacheEntry) -> Result<Option<Manifest>, Error> {
     match fs_err::File::open(cache_entry.path()) {
@@ -1065,3 +1339,21 @@ fn read_wheel_metadata(
     let dist_info = read_dist_info(filename, &mut archive)?;
     Ok(Metadata21::parse(&dist_info)?)
 }
+
+/// Return the [`GitHttp`] configuration that mirrors the client's TLS and proxy configuration,
+/// such that Git fetches trust the same certificates, and use the same proxy, as all other
+/// requests.
+///
+/// The proxy is determined by the repository URL, and also applies to any submodules.
+pub(crate) fn git_http(client: &RegistryClient, repository: &Url) -> GitHttp {
+    let proxy = match client.proxy().route(repository) {
+        ProxyRoute::Proxy(proxy) => GitProxy::Proxy(proxy.url().clone()),
+        ProxyRoute::Bypass(_) => GitProxy::Direct,
+        ProxyRoute::Direct => GitProxy::Inherit,
+    };
+    GitHttp {
+        ca_bundle: client.tls().ca_bundle().map(Path::to_path_buf),
+        client_cert: client.tls().client_cert().map(Path::to_path_buf),
+        proxy,
+    }
+}
@@ -0,0 +1,89 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use sha2::digest::DynDigest;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use tokio::io::{AsyncReadExt, ReadBuf};
+
+use pypi_types::{HashAlgorithm, HashDigest};
+
+/// An incremental hasher for a single [`HashAlgorithm`].
+pub struct Hasher {
+    algorithm: HashAlgorithm,
+    inner: Box<dyn DynDigest + Send>,
+}
+
+impl Hasher {
+    /// Update the hasher with the given data.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Consume the hasher, returning the computed [`HashDigest`].
+    pub fn finalize(self) -> HashDigest {
+        let digest = self.inner.finalize();
+        HashDigest {
+            algorithm: self.algorithm,
+            digest: hex::encode(digest),
+        }
+    }
+}
+
+impl From<HashAlgorithm> for Hasher {
+    fn from(algorithm: HashAlgorithm) -> Self {
+        let inner: Box<dyn DynDigest + Send> = match algorithm {
+            HashAlgorithm::Sha256 => Box::new(Sha256::new()),
+            HashAlgorithm::Sha384 => Box::new(Sha384::new()),
+            HashAlgorithm::Sha512 => Box::new(Sha512::new()),
+        };
+        Self { algorithm, inner }
+    }
+}
+
+/// An [`tokio::io::AsyncRead`] adapter that computes one or more digests of the underlying
+/// stream as it's consumed.
+pub struct HashReader<'a, R> {
+    reader: R,
+    hashers: &'a mut [Hasher],
+}
+
+impl<'a, R> HashReader<'a, R>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    pub fn new(reader: R, hashers: &'a mut [Hasher]) -> Self {
+        Self { reader, hashers }
+    }
+
+    /// Exhaust the underlying reader, such that the digests cover the entire stream, even if the
+    /// consumer stopped reading early (e.g., at the end of a zip archive's central directory).
+    pub async fn finish(&mut self) -> Result<(), std::io::Error> {
+        let mut buffer = vec![0; 8192];
+        while self.read(&mut buffer).await? > 0 {}
+        Ok(())
+    }
+}
+
+impl<R> tokio::io::AsyncRead for HashReader<'_, R>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let reader = Pin::new(&mut self.reader);
+        let filled = buf.filled().len();
+        match reader.poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                let data = &buf.filled()[filled..];
+                for hasher in self.hashers.iter_mut() {
+                    hasher.update(data);
+                }
+                Poll::Ready(Ok(()))
+            }
+            other => other,
+        }
+    }
+}
@@ -1,7 +1,9 @@
 pub use error::Error;
+pub use hash::{HashReader, Hasher};
 pub use sync::*;
 
 mod error;
+mod hash;
 pub mod stream;
 mod sync;
 mod tar;
@@ -9,14 +9,14 @@ use std::{env, str};
 use anyhow::{anyhow, Context, Result};
 use cargo_util::{paths, ProcessBuilder};
 use git2::{ErrorClass, ObjectType};
-use reqwest::Client;
 use reqwest::StatusCode;
+use reqwest_middleware::ClientWithMiddleware;
 use tracing::{debug, warn};
 use url::Url;
 use uv_fs::Simplified;
 
 use crate::util::retry;
-use crate::FetchStrategy;
+use crate::{FetchStrategy, GitHttp, GitProxy};
 
 /// A file indicates that if present, `git reset` has been done and a repo
 /// checkout is ready to go. See [`GitCheckout::reset`] for why we need this.
@@ -164,13 +164,21 @@ impl GitRemote {
         reference: &GitReference,
         locked_rev: Option<git2::Oid>,
         strategy: FetchStrategy,
-        client: &Client,
+        client: &ClientWithMiddleware,
+        http: &GitHttp,
     ) -> Result<(GitDatabase, git2::Oid)> {
         let locked_ref = locked_rev.map(|oid| GitReference::FullCommit(oid.to_string()));
         let reference = locked_ref.as_ref().unwrap_or(reference);
         if let Some(mut db) = db {
-            fetch(&mut db.repo, self.url.as_str(), reference, strategy, client)
-                .with_context(|| format!("failed to fetch into: {}", into.simplified_display()))?;
+            fetch(
+                &mut db.repo,
+                self.url.as_str(),
+                reference,
+                strategy,
+                client,
+                http,
+            )
+            .with_context(|| format!("failed to fetch into: {}", into.simplified_display()))?;
 
             let resolved_commit_hash = match locked_rev {
                 Some(rev) => db.contains(rev).then_some(rev),
@@ -189,8 +197,15 @@ impl GitRemote {
         }
         paths::create_dir_all(into)?;
         let mut repo = init(into, true)?;
-        fetch(&mut repo, self.url.as_str(), reference, strategy, client)
-            .with_context(|| format!("failed to clone into: {}", into.simplified_display()))?;
+        fetch(
+            &mut repo,
+            self.url.as_str(),
+            reference,
+            strategy,
+            client,
+            http,
+        )
+        .with_context(|| format!("failed to clone into: {}", into.simplified_display()))?;
         let rev = match locked_rev {
             Some(rev) => rev,
             None => reference.resolve(&repo)?,
@@ -224,7 +239,8 @@ impl GitDatabase {
         rev: git2::Oid,
         destination: &Path,
         strategy: FetchStrategy,
-        client: &Client,
+        client: &ClientWithMiddleware,
+        http: &GitHttp,
     ) -> Result<GitCheckout<'_>> {
         // If the existing checkout exists, and it is fresh, use it.
         // A non-fresh checkout can happen if the checkout operation was
@@ -238,7 +254,7 @@ impl GitDatabase {
             Some(co) => co,
             None => GitCheckout::clone_into(destination, self, rev)?,
         };
-        checkout.update_submodules(strategy, client)?;
+        checkout.update_submodules(strategy, client, http)?;
         Ok(checkout)
     }
 
@@ -450,7 +466,12 @@ impl<'a> GitCheckout<'a> {
     /// Submodules set to `none` won't be fetched.
     ///
     /// [^1]: <https://git-scm.com/docs/git-submodule#Documentation/git-submodule.txt-none>
-    fn update_submodules(&self, strategy: FetchStrategy, client: &Client) -> Result<()> {
+    fn update_submodules(
+        &self,
+        strategy: FetchStrategy,
+        client: &ClientWithMiddleware,
+        http: &GitHttp,
+    ) -> Result<()> {
         /// Like `Cow`, but without a requirement on `Clone`.
         enum Repo<'a> {
             Borrowed(&'a git2::Repository),
@@ -528,15 +549,21 @@ impl<'a> GitCheckout<'a> {
                 // Fetch data from origin and reset to the head commit
                 debug!("Updating Git submodule: {}", child_remote_url);
                 let reference = GitReference::FullCommit(head.to_string());
-                fetch(&mut repo, &child_remote_url, &reference, strategy, client).with_context(
-                    || {
-                        format!(
-                            "failed to fetch submodule `{}` from {}",
-                            child.name().unwrap_or(""),
-                            child_remote_url
-                        )
-                    },
-                )?;
+                fetch(
+                    &mut repo,
+                    &child_remote_url,
+                    &reference,
+                    strategy,
+                    client,
+                    http,
+                )
+                .with_context(|| {
+                    format!(
+                        "failed to fetch submodule `{}` from {}",
+                        child.name().unwrap_or(""),
+                        child_remote_url
+                    )
+                })?;
 
                 let obj = repo.find_object(head, None)?;
                 reset(&repo, &obj)?;
@@ -909,7 +936,8 @@ pub(crate) fn fetch(
     remote_url: &str,
     reference: &GitReference,
     strategy: FetchStrategy,
-    client: &Client,
+    client: &ClientWithMiddleware,
+    http: &GitHttp,
 ) -> Result<()> {
     let oid_to_fetch = match github_fast_path(repo, remote_url, reference, client) {
         Ok(FastPathRev::UpToDate) => return Ok(()),
@@ -994,14 +1022,16 @@ pub(crate) fn fetch(
     match strategy {
         FetchStrategy::Cli => {
             let result = match refspec_strategy {
-                RefspecStrategy::All => fetch_with_cli(repo, remote_url, refspecs.as_slice(), tags),
+                RefspecStrategy::All => {
+                    fetch_with_cli(repo, remote_url, refspecs.as_slice(), tags, http)
+                }
                 RefspecStrategy::First => {
                     // Try each refspec
                     let mut errors = refspecs
                         .iter()
                         .map_while(|refspec| {
                             let fetch_result =
-                                fetch_with_cli(repo, remote_url, &[refspec.clone()], tags);
+                                fetch_with_cli(repo, remote_url, &[refspec.clone()], tags, http);
 
                             // Stop after the first success and log failures
                             match fetch_result {
@@ -1045,6 +1075,11 @@ pub(crate) fn fetch(
 
             let git_config = git2::Config::open_default()?;
             with_fetch_options(&git_config, remote_url, &mut |mut opts| {
+                if let GitProxy::Proxy(proxy) = &http.proxy {
+                    let mut proxy_options = git2::ProxyOptions::new();
+                    proxy_options.url(proxy.as_str());
+                    opts.proxy_options(proxy_options);
+                }
                 if tags {
                     opts.download_tags(git2::AutotagOption::All);
                 }
@@ -1108,6 +1143,7 @@ fn fetch_with_cli(
     url: &str,
     refspecs: &[String],
     tags: bool,
+    http: &GitHttp,
 ) -> Result<()> {
     let mut cmd = ProcessBuilder::new("git");
     cmd.arg("fetch");
@@ -1131,6 +1167,32 @@ fn fetch_with_cli(
         .env_remove("GIT_ALTERNATE_OBJECT_DIRECTORIES")
         .cwd(repo.path());
 
+    // Pass along any custom certificates, which `git` reads from the environment.
+    if let Some(ca_bundle) = http.ca_bundle.as_ref() {
+        cmd.env("GIT_SSL_CAINFO", ca_bundle);
+    }
+    if let Some(client_cert) = http.client_cert.as_ref() {
+        cmd.env("GIT_SSL_CERT", client_cert)
+            .env("GIT_SSL_KEY", client_cert);
+    }
+
+    // Route the fetch through the configured proxy, overriding `http.proxy` in the user's Git
+    // configuration. The proxy is passed through the environment, rather than as a `-c` argument,
+    // to keep any credentials out of the process list.
+    match &http.proxy {
+        GitProxy::Inherit => {}
+        GitProxy::Proxy(proxy) => {
+            cmd.env("GIT_CONFIG_COUNT", "1")
+                .env("GIT_CONFIG_KEY_0", "http.proxy")
+                .env("GIT_CONFIG_VALUE_0", proxy.as_str())
+                .env_remove("NO_PROXY")
+                .env_remove("no_proxy");
+        }
+        GitProxy::Direct => {
+            cmd.env("NO_PROXY", "*").env("no_proxy", "*");
+        }
+    }
+
     // We capture the output to avoid streaming it to the user's console during clones.
     // The required `on...line` callbacks currently do nothing.
     // The output appears to be included in error messages by default.
@@ -1304,7 +1366,7 @@ fn github_fast_path(
     repo: &mut git2::Repository,
     url: &str,
     reference: &GitReference,
-    client: &Client,
+    client: &ClientWithMiddleware,
 ) -> Result<FastPathRev> {
     let url = Url::parse(url)?;
     if !is_github(&url) {
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::str::FromStr;
 use url::Url;
 
@@ -120,6 +121,30 @@ impl std::fmt::Display for GitUrl {
     }
 }
 
+/// The HTTP configuration (TLS and proxy settings) to use when fetching repositories.
+#[derive(Debug, Clone, Default)]
+pub struct GitHttp {
+    /// A PEM bundle of CA certificates to trust, passed to `git` as `GIT_SSL_CAINFO`.
+    pub ca_bundle: Option<PathBuf>,
+    /// A PEM file containing a client certificate and private key, passed to `git` as
+    /// `GIT_SSL_CERT` and `GIT_SSL_KEY`.
+    pub client_cert: Option<PathBuf>,
+    /// The proxy to fetch the repository through.
+    pub proxy: GitProxy,
+}
+
+/// How to route Git fetches with respect to proxies.
+#[derive(Debug, Clone, Default)]
+pub enum GitProxy {
+    /// Defer to Git's own proxy configuration.
+    #[default]
+    Inherit,
+    /// Fetch through the given proxy, whose URL may include credentials.
+    Proxy(Url),
+    /// Connect directly, ignoring any proxy configured for Git.
+    Direct,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum FetchStrategy {
     /// Fetch Git repositories using libgit2.
@@ -4,21 +4,23 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use reqwest::Client;
+use reqwest_middleware::ClientWithMiddleware;
 use tracing::{debug, instrument};
 use url::Url;
 
 use cache_key::{digest, RepositoryUrl};
 
 use crate::git::GitRemote;
-use crate::{FetchStrategy, GitSha, GitUrl};
+use crate::{FetchStrategy, GitHttp, GitSha, GitUrl};
 
 /// A remote Git source that can be checked out locally.
 pub struct GitSource {
     /// The Git reference from the manifest file.
     git: GitUrl,
     /// The HTTP client to use for fetching.
-    client: Client,
+    client: ClientWithMiddleware,
+    /// The TLS configuration to pass to the `git` CLI.
+    http: GitHttp,
     /// The fetch strategy to use when cloning.
     strategy: FetchStrategy,
     /// The path to the Git source database.
@@ -29,10 +31,11 @@ pub struct GitSource {
 
 impl GitSource {
     /// Initialize a new Git source.
-    pub fn new(git: GitUrl, cache: impl Into<PathBuf>) -> Self {
+    pub fn new(git: GitUrl, client: ClientWithMiddleware, cache: impl Into<PathBuf>) -> Self {
         Self {
             git,
-            client: Client::new(),
+            client,
+            http: GitHttp::default(),
             strategy: FetchStrategy::Cli,
             cache: cache.into(),
             reporter: None,
@@ -48,6 +51,12 @@ impl GitSource {
         }
     }
 
+    /// Set the [`GitHttp`] configuration to use for this Git source.
+    #[must_use]
+    pub fn with_http(self, http: GitHttp) -> Self {
+        Self { http, ..self }
+    }
+
     /// Fetch the underlying Git repository at the given revision.
     #[instrument(skip(self))]
     pub fn fetch(self) -> Result<Fetch> {
@@ -80,6 +89,7 @@ impl GitSource {
                     locked_rev.map(git2::Oid::from),
                     self.strategy,
                     &self.client,
+                    &self.http,
                 )?;
 
                 (db, GitSha::from(actual_rev), task)
@@ -103,6 +113,7 @@ impl GitSource {
             &checkout_path,
             self.strategy,
             &self.client,
+            &self.http,
         )?;
 
         // Report the checkout operation to the reporter.
@@ -7,12 +7,14 @@ use tokio::task::JoinError;
 use tracing::instrument;
 use url::Url;
 
-use distribution_types::{CachedDist, Dist, Identifier, LocalEditable, RemoteSource, SourceDist};
+use distribution_types::{
+    CachedDist, Dist, Identifier, LocalEditable, Name, RemoteSource, SourceDist,
+};
 use platform_tags::Tags;
 use uv_cache::Cache;
 use uv_client::RegistryClient;
-use uv_distribution::{DistributionDatabase, LocalWheel, Unzip};
-use uv_traits::{BuildContext, InFlight};
+use uv_distribution::{DistributionDatabase, DownloadedArchive, LocalWheel, Unzip};
+use uv_traits::{BuildContext, InFlight, RequiredHashes};
 
 use crate::editable::BuiltEditable;
 
@@ -22,6 +24,8 @@ pub enum Error {
     Unzip(Dist, #[source] uv_extract::Error),
     #[error("Failed to fetch wheel: {0}")]
     Fetch(Dist, #[source] uv_distribution::Error),
+    #[error("Failed to download distribution: {0}")]
+    Archive(Dist, #[source] uv_distribution::Error),
     /// Should not occur; only seen when another task panicked.
     #[error("The task executor is broken, did some other task panic?")]
     Join(#[from] JoinError),
@@ -35,7 +39,10 @@ pub enum Error {
 pub struct Downloader<'a, Context: BuildContext + Send + Sync> {
     database: DistributionDatabase<'a, Context>,
     cache: &'a Cache,
+    hashes: Option<&'a RequiredHashes>,
     reporter: Option<Arc<dyn Reporter>>,
+    /// The maximum number of concurrent downloads.
+    concurrency: usize,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
@@ -48,7 +55,9 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
         Self {
             database: DistributionDatabase::new(cache, tags, client, build_context),
             reporter: None,
+            hashes: None,
             cache,
+            concurrency: build_context.concurrency().downloads,
         }
     }
 
@@ -59,7 +68,19 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
         Self {
             reporter: Some(reporter.clone()),
             database: self.database.with_reporter(Facade::from(reporter.clone())),
+            hashes: self.hashes,
             cache: self.cache,
+            concurrency: self.concurrency,
+        }
+    }
+
+    /// Set the hashes that downloaded distributions are required to match.
+    #[must_use]
+    pub fn with_hashes(self, hashes: &'a RequiredHashes) -> Self {
+        Self {
+            database: self.database.with_hashes(hashes),
+            hashes: Some(hashes),
+            ..self
         }
     }
 
@@ -77,9 +98,8 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<CachedDist, Error>(wheel)
             })
-            // TODO(charlie): The number of concurrent fetches, such that we limit the number of
-            // concurrent builds to the number of cores, while allowing more concurrent downloads.
-            .buffer_unordered(50)
+            // Builds are additionally bounded by the build context's semaphore.
+            .buffer_unordered(self.concurrency)
     }
 
     /// Download, build, and unzip a set of downloaded wheels.
@@ -105,6 +125,34 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
         Ok(wheels)
     }
 
+    /// Save the archives for a set of distributions to the given directory, in parallel.
+    ///
+    /// If `build` is set, source distributions are built, and the resulting wheels are saved in
+    /// their place.
+    #[instrument(skip_all, fields(total = distributions.len()))]
+    pub async fn download_archives(
+        &self,
+        distributions: Vec<Dist>,
+        directory: &Path,
+        build: bool,
+    ) -> Result<Vec<DownloadedArchive>, Error> {
+        futures::stream::iter(distributions)
+            .map(|dist| async move {
+                let hashes = self
+                    .hashes
+                    .map(|hashes| hashes.get(dist.name()))
+                    .unwrap_or_default();
+                self.database
+                    .download_archive(&dist, directory, build, hashes)
+                    .boxed()
+                    .await
+                    .map_err(|err| Error::Archive(dist.clone(), err))
+            })
+            .buffer_unordered(self.concurrency)
+            .try_collect()
+            .await
+    }
+
     /// Build a set of editables
     #[instrument(skip_all)]
     pub async fn build_editables(
@@ -133,7 +181,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<_, Error>((editable, cached_dist, metadata))
             })
-            .buffer_unordered(50);
+            .buffer_unordered(self.concurrency);
 
         while let Some((editable, wheel, metadata)) = fetches.next().await.transpose()? {
             if let Some(reporter) = self.reporter.as_ref() {
@@ -158,9 +206,13 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
     pub async fn get_wheel(&self, dist: Dist, in_flight: &InFlight) -> Result<CachedDist, Error> {
         let id = dist.distribution_id();
         if in_flight.downloads.register(id.clone()) {
+            let hashes = self
+                .hashes
+                .map(|hashes| hashes.get(dist.name()))
+                .unwrap_or_default();
             let download: LocalWheel = self
                 .database
-                .get_or_build_wheel(dist.clone())
+                .get_or_build_wheel(dist.clone(), hashes)
                 .boxed()
                 .map_err(|err| Error::Fetch(dist.clone(), err))
                 .await?;
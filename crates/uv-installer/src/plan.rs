@@ -17,7 +17,7 @@ use uv_distribution::{BuiltWheelIndex, RegistryWheelIndex};
 use uv_fs::Simplified;
 use uv_interpreter::PythonEnvironment;
 use uv_normalize::PackageName;
-use uv_traits::NoBinary;
+use uv_traits::{NoBinary, RequiredHashes};
 
 use crate::{ResolvedEditable, SitePackages};
 
@@ -26,6 +26,7 @@ use crate::{ResolvedEditable, SitePackages};
 pub struct Planner<'a> {
     requirements: &'a [Requirement],
     editable_requirements: &'a [ResolvedEditable],
+    hashes: Option<&'a RequiredHashes>,
 }
 
 impl<'a> Planner<'a> {
@@ -35,6 +36,7 @@ impl<'a> Planner<'a> {
         Self {
             requirements,
             editable_requirements: &[],
+            hashes: None,
         }
     }
 
@@ -47,6 +49,18 @@ impl<'a> Planner<'a> {
         }
     }
 
+    /// Set the hashes that the requirements in the [`Plan`] are required to match.
+    ///
+    /// Requirements with required hashes are never linked directly from the cache; instead,
+    /// they're marked as remote, such that the distribution database can verify them.
+    #[must_use]
+    pub fn with_hashes(self, hashes: &'a RequiredHashes) -> Self {
+        Self {
+            hashes: Some(hashes),
+            ..self
+        }
+    }
+
     /// Partition a set of requirements into those that should be linked from the cache, those that
     /// need to be downloaded, and those that should be removed.
     ///
@@ -222,6 +236,15 @@ impl<'a> Planner<'a> {
                 continue;
             }
 
+            if self
+                .hashes
+                .is_some_and(|hashes| hashes.contains(&requirement.name))
+            {
+                debug!("Must verify hashes for requirement: {requirement}");
+                remote.push(requirement.clone());
+                continue;
+            }
+
             // Identify any locally-available distributions that satisfy the requirement.
             match requirement.version_or_url.as_ref() {
                 None => {
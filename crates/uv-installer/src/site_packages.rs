@@ -208,37 +208,8 @@ impl<'a> SitePackages<'a> {
                         continue;
                     }
 
-                    let installed = self.get_packages(&dependency.name);
-                    match installed.as_slice() {
-                        [] => {
-                            // No version installed.
-                            diagnostics.push(Diagnostic::MissingDependency {
-                                package: package.clone(),
-                                requirement: dependency.clone(),
-                            });
-                        }
-                        [installed] => {
-                            match &dependency.version_or_url {
-                                None | Some(pep508_rs::VersionOrUrl::Url(_)) => {
-                                    // Nothing to do (accept any installed version).
-                                }
-                                Some(pep508_rs::VersionOrUrl::VersionSpecifier(
-                                    version_specifier,
-                                )) => {
-                                    // The installed version doesn't satisfy the requirement.
-                                    if !version_specifier.contains(installed.version()) {
-                                        diagnostics.push(Diagnostic::IncompatibleDependency {
-                                            package: package.clone(),
-                                            version: installed.version().clone(),
-                                            requirement: dependency.clone(),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                        _ => {
-                            // There are multiple installed distributions for the same package.
-                        }
+                    if let Some(diagnostic) = self.diagnose_requirement(package, dependency) {
+                        diagnostics.push(diagnostic);
                     }
                 }
             }
@@ -247,6 +218,93 @@ impl<'a> SitePackages<'a> {
         Ok(diagnostics)
     }
 
+    /// Validate the installed packages in the virtual environment, as in
+    /// [`SitePackages::diagnostics`], additionally verifying that each distribution was built for
+    /// a platform compatible with the interpreter, and that the files listed in its `RECORD` are
+    /// present.
+    pub fn check(&self) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = self.diagnostics()?;
+
+        let tags = self.venv.interpreter().tags()?;
+        for distribution in self.iter() {
+            // Verify that the wheel tags are compatible with the current platform.
+            let wheel_tags = install_wheel_rs::read_wheel_tags(distribution.path())
+                .with_context(|| format!("Failed to read WHEEL file for: {distribution}"))?;
+            let parsed = wheel_tags
+                .iter()
+                .filter_map(|tag| parse_wheel_tag(tag))
+                .collect::<Vec<_>>();
+            if !parsed.is_empty()
+                && !parsed
+                    .iter()
+                    .any(|(python, abi, platform)| tags.is_compatible(python, abi, platform))
+            {
+                diagnostics.push(Diagnostic::IncompatiblePlatform {
+                    package: distribution.name().clone(),
+                    tags: wheel_tags,
+                });
+            }
+
+            // Verify that the files listed in the `RECORD` are present.
+            let missing = install_wheel_rs::missing_record_files(distribution.path())
+                .with_context(|| format!("Failed to read RECORD file for: {distribution}"))?;
+            if !missing.is_empty() {
+                diagnostics.push(Diagnostic::MissingFiles {
+                    package: distribution.name().clone(),
+                    paths: missing,
+                });
+            }
+        }
+
+        Ok(diagnostics)
+    }
+
+    /// Validate a single requirement of the given package against the installed distributions,
+    /// returning a [`Diagnostic`] if the requirement is missing or unsatisfied.
+    ///
+    /// Markers are not evaluated; the caller is responsible for determining whether the
+    /// requirement applies to the current environment.
+    pub fn diagnose_requirement(
+        &self,
+        package: &PackageName,
+        requirement: &Requirement,
+    ) -> Option<Diagnostic> {
+        let installed = self.get_packages(&requirement.name);
+        match installed.as_slice() {
+            [] => {
+                // No version installed.
+                Some(Diagnostic::MissingDependency {
+                    package: package.clone(),
+                    requirement: requirement.clone(),
+                })
+            }
+            [installed] => {
+                match &requirement.version_or_url {
+                    None | Some(pep508_rs::VersionOrUrl::Url(_)) => {
+                        // Nothing to do (accept any installed version).
+                        None
+                    }
+                    Some(pep508_rs::VersionOrUrl::VersionSpecifier(version_specifier)) => {
+                        // The installed version doesn't satisfy the requirement.
+                        if version_specifier.contains(installed.version()) {
+                            None
+                        } else {
+                            Some(Diagnostic::IncompatibleDependency {
+                                package: package.clone(),
+                                version: installed.version().clone(),
+                                requirement: requirement.clone(),
+                            })
+                        }
+                    }
+                }
+            }
+            _ => {
+                // There are multiple installed distributions for the same package.
+                None
+            }
+        }
+    }
+
     /// Returns `true` if the installed packages satisfy the given requirements.
     pub fn satisfies(
         &self,
@@ -435,6 +493,19 @@ impl IntoIterator for SitePackages<'_> {
     }
 }
 
+/// Parse a wheel tag (like `py2.py3-none-any`) into its Python, ABI, and platform components,
+/// expanding any compressed tag sets.
+fn parse_wheel_tag(tag: &str) -> Option<(Vec<String>, Vec<String>, Vec<String>)> {
+    let mut parts = tag.split('-');
+    let (Some(python), Some(abi), Some(platform), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    let split = |part: &str| part.split('.').map(ToString::to_string).collect();
+    Some((split(python), split(abi), split(platform)))
+}
+
 #[derive(Debug)]
 pub enum Diagnostic {
     IncompletePackage {
@@ -471,6 +542,18 @@ pub enum Diagnostic {
         /// The installed versions of the package.
         paths: Vec<PathBuf>,
     },
+    IncompatiblePlatform {
+        /// The package that was built for an incompatible platform.
+        package: PackageName,
+        /// The tags declared in the package's `WHEEL` file.
+        tags: Vec<String>,
+    },
+    MissingFiles {
+        /// The package that is missing files.
+        package: PackageName,
+        /// The files listed in the package's `RECORD` that are missing.
+        paths: Vec<PathBuf>,
+    },
 }
 
 impl Diagnostic {
@@ -508,6 +591,18 @@ impl Diagnostic {
                     paths.iter().fold(String::new(), |acc, path| acc + &format!("\n  - {}", path.display()))
                 )
             },
+            Self::IncompatiblePlatform { package, tags } => format!(
+                "The package `{package}` was built for an incompatible platform (tags: {}).",
+                tags.iter().map(|tag| format!("`{tag}`")).collect::<Vec<_>>().join(", ")
+            ),
+            Self::MissingFiles { package, paths } => {
+                let mut paths = paths.clone();
+                paths.sort();
+                format!(
+                    "The package `{package}` is missing files listed in its `RECORD`:{}",
+                    paths.iter().fold(String::new(), |acc, path| acc + &format!("\n  - {}", path.display()))
+                )
+            },
         }
     }
 
@@ -523,6 +618,8 @@ impl Diagnostic {
                 ..
             } => name == package || &requirement.name == name,
             Self::DuplicatePackage { package, .. } => name == package,
+            Self::IncompatiblePlatform { package, .. } => name == package,
+            Self::MissingFiles { package, .. } => name == package,
         }
     }
 }
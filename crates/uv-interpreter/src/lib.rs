@@ -10,6 +10,7 @@ pub use crate::interpreter::Interpreter;
 pub use crate::python_environment::PythonEnvironment;
 pub use crate::python_query::{find_default_python, find_requested_python};
 pub use crate::python_version::PythonVersion;
+pub use crate::target_triple::TargetTriple;
 pub use crate::virtualenv::Virtualenv;
 
 mod cfg;
@@ -17,6 +18,7 @@ mod interpreter;
 mod python_environment;
 mod python_query;
 mod python_version;
+mod target_triple;
 mod virtualenv;
 
 #[derive(Debug, Error)]
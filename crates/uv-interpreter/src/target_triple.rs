@@ -0,0 +1,227 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use pep508_rs::MarkerEnvironment;
+use platform_host::{Arch, Os, Platform};
+
+/// A target triple for which to resolve, as an alternative to the current platform (e.g.,
+/// `x86_64-manylinux_2_28` or `aarch64-apple-darwin`).
+///
+/// Unlike a full Rust target triple, the set of supported targets is limited to those for which
+/// Python distributions are commonly published.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TargetTriple {
+    /// An alias for `x86_64-pc-windows-msvc`, the default target for Windows.
+    Windows,
+    /// An alias for `x86_64-unknown-linux-gnu`, the default target for Linux.
+    Linux,
+    /// An alias for `aarch64-apple-darwin`, the default target for macOS.
+    Macos,
+    /// An x86 Windows target.
+    X8664PcWindowsMsvc,
+    /// A 32-bit x86 Windows target.
+    I686PcWindowsMsvc,
+    /// An x86 Linux target, equivalent to `x86_64-manylinux_2_17`.
+    X8664UnknownLinuxGnu,
+    /// An ARM-based macOS target, as seen on Apple Silicon devices (macOS 12.0 and later).
+    Aarch64AppleDarwin,
+    /// An x86 macOS target (macOS 10.12 and later).
+    X8664AppleDarwin,
+    /// An ARM64 Linux target, equivalent to `aarch64-manylinux_2_17`.
+    Aarch64UnknownLinuxGnu,
+    /// An ARM64 Linux target with musl (musllinux 1.2).
+    Aarch64UnknownLinuxMusl,
+    /// An x86 Linux target with musl (musllinux 1.2).
+    X8664UnknownLinuxMusl,
+    /// An x86 Linux target with glibc 2.17 or later.
+    X8664Manylinux217,
+    /// An x86 Linux target with glibc 2.28 or later.
+    X8664Manylinux228,
+    /// An x86 Linux target with glibc 2.31 or later.
+    X8664Manylinux231,
+    /// An ARM64 Linux target with glibc 2.17 or later.
+    Aarch64Manylinux217,
+    /// An ARM64 Linux target with glibc 2.28 or later.
+    Aarch64Manylinux228,
+    /// An ARM64 Linux target with glibc 2.31 or later.
+    Aarch64Manylinux231,
+}
+
+impl TargetTriple {
+    /// All supported target triples.
+    pub const ALL: &'static [Self] = &[
+        Self::Windows,
+        Self::Linux,
+        Self::Macos,
+        Self::X8664PcWindowsMsvc,
+        Self::I686PcWindowsMsvc,
+        Self::X8664UnknownLinuxGnu,
+        Self::Aarch64AppleDarwin,
+        Self::X8664AppleDarwin,
+        Self::Aarch64UnknownLinuxGnu,
+        Self::Aarch64UnknownLinuxMusl,
+        Self::X8664UnknownLinuxMusl,
+        Self::X8664Manylinux217,
+        Self::X8664Manylinux228,
+        Self::X8664Manylinux231,
+        Self::Aarch64Manylinux217,
+        Self::Aarch64Manylinux228,
+        Self::Aarch64Manylinux231,
+    ];
+
+    /// Return the name of the target triple, as accepted on the command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Windows => "windows",
+            Self::Linux => "linux",
+            Self::Macos => "macos",
+            Self::X8664PcWindowsMsvc => "x86_64-pc-windows-msvc",
+            Self::I686PcWindowsMsvc => "i686-pc-windows-msvc",
+            Self::X8664UnknownLinuxGnu => "x86_64-unknown-linux-gnu",
+            Self::Aarch64AppleDarwin => "aarch64-apple-darwin",
+            Self::X8664AppleDarwin => "x86_64-apple-darwin",
+            Self::Aarch64UnknownLinuxGnu => "aarch64-unknown-linux-gnu",
+            Self::Aarch64UnknownLinuxMusl => "aarch64-unknown-linux-musl",
+            Self::X8664UnknownLinuxMusl => "x86_64-unknown-linux-musl",
+            Self::X8664Manylinux217 => "x86_64-manylinux_2_17",
+            Self::X8664Manylinux228 => "x86_64-manylinux_2_28",
+            Self::X8664Manylinux231 => "x86_64-manylinux_2_31",
+            Self::Aarch64Manylinux217 => "aarch64-manylinux_2_17",
+            Self::Aarch64Manylinux228 => "aarch64-manylinux_2_28",
+            Self::Aarch64Manylinux231 => "aarch64-manylinux_2_31",
+        }
+    }
+
+    /// Return the [`Platform`] for the target, used to determine the compatible wheel tags.
+    pub fn platform(self) -> Platform {
+        match self {
+            Self::Windows | Self::X8664PcWindowsMsvc => Platform::new(Os::Windows, Arch::X86_64),
+            Self::I686PcWindowsMsvc => Platform::new(Os::Windows, Arch::X86),
+            Self::Linux | Self::X8664UnknownLinuxGnu | Self::X8664Manylinux217 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::X86_64,
+            ),
+            Self::Macos | Self::Aarch64AppleDarwin => Platform::new(
+                Os::Macos {
+                    major: 12,
+                    minor: 0,
+                },
+                Arch::Aarch64,
+            ),
+            Self::X8664AppleDarwin => Platform::new(
+                Os::Macos {
+                    major: 10,
+                    minor: 12,
+                },
+                Arch::X86_64,
+            ),
+            Self::Aarch64UnknownLinuxGnu | Self::Aarch64Manylinux217 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64UnknownLinuxMusl => {
+                Platform::new(Os::Musllinux { major: 1, minor: 2 }, Arch::Aarch64)
+            }
+            Self::X8664UnknownLinuxMusl => {
+                Platform::new(Os::Musllinux { major: 1, minor: 2 }, Arch::X86_64)
+            }
+            Self::X8664Manylinux228 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 28,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux231 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 31,
+                },
+                Arch::X86_64,
+            ),
+            Self::Aarch64Manylinux228 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 28,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux231 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 31,
+                },
+                Arch::Aarch64,
+            ),
+        }
+    }
+
+    /// Return a [`MarkerEnvironment`] for the target, based on a base [`MarkerEnvironment`].
+    ///
+    /// The returned [`MarkerEnvironment`] will preserve the base environment's Python version and
+    /// implementation markers, but override its platform markers.
+    pub fn markers(self, base: &MarkerEnvironment) -> MarkerEnvironment {
+        let platform = self.platform();
+        let (os_name, platform_system, sys_platform) = match platform.os() {
+            Os::Windows => ("nt", "Windows", "win32"),
+            Os::Macos { .. } => ("posix", "Darwin", "darwin"),
+            _ => ("posix", "Linux", "linux"),
+        };
+        let platform_machine = match (platform.os(), platform.arch()) {
+            (Os::Windows, Arch::X86_64) => "AMD64".to_string(),
+            (Os::Windows, Arch::X86) => "x86".to_string(),
+            (Os::Macos { .. }, Arch::Aarch64) => "arm64".to_string(),
+            (_, arch) => arch.to_string(),
+        };
+
+        let mut markers = base.clone();
+
+        // Ex) `sys_platform == "linux"`
+        markers.os_name = os_name.to_string();
+        markers.platform_system = platform_system.to_string();
+        markers.sys_platform = sys_platform.to_string();
+
+        // Ex) `platform_machine == "x86_64"`
+        markers.platform_machine = platform_machine;
+
+        // The release and version of the target operating system are unknown.
+        markers.platform_release = String::new();
+        markers.platform_version = String::new();
+
+        markers
+    }
+}
+
+impl FromStr for TargetTriple {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|target| target.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Unsupported target `{s}` (expected one of: {})",
+                    Self::ALL
+                        .iter()
+                        .copied()
+                        .map(Self::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+impl Display for TargetTriple {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -24,7 +24,7 @@ impl CandidateSelector {
     pub(crate) fn for_resolution(
         options: Options,
         manifest: &Manifest,
-        markers: &MarkerEnvironment,
+        markers: Option<&MarkerEnvironment>,
     ) -> Self {
         Self {
             resolution_strategy: ResolutionStrategy::from_mode(
@@ -60,12 +60,15 @@ struct Preferences(FxHashMap<PackageName, Version>);
 
 impl Preferences {
     /// Create a set of [`Preferences`] from a set of requirements.
-    fn from_requirements(requirements: &[Requirement], markers: &MarkerEnvironment) -> Self {
+    fn from_requirements(
+        requirements: &[Requirement],
+        markers: Option<&MarkerEnvironment>,
+    ) -> Self {
         Self(
             requirements
                 .iter()
                 .filter_map(|requirement| {
-                    if !requirement.evaluate_markers(markers, &[]) {
+                    if !requirement.evaluate_optional_environment(markers, &[]) {
                         return None;
                     }
                     let Some(VersionOrUrl::VersionSpecifier(version_specifiers)) =
@@ -5,10 +5,10 @@
 use anyhow::Result;
 use futures::{stream, Stream, StreamExt, TryStreamExt};
 use rustc_hash::FxHashMap;
-use uv_traits::NoBinary;
+use uv_traits::{Concurrency, NoBinary};
 
 use distribution_filename::DistFilename;
-use distribution_types::{Dist, IndexUrl, Resolution};
+use distribution_types::{Dist, IndexStrategy, IndexUrl, Resolution};
 use pep508_rs::{Requirement, VersionOrUrl};
 use platform_tags::{TagCompatibility, Tags};
 use uv_client::{
@@ -26,6 +26,8 @@ pub struct DistFinder<'a> {
     interpreter: &'a Interpreter,
     flat_index: &'a FlatIndex,
     no_binary: &'a NoBinary,
+    /// The maximum number of concurrent metadata fetches.
+    concurrency: usize,
 }
 
 impl<'a> DistFinder<'a> {
@@ -44,6 +46,16 @@ impl<'a> DistFinder<'a> {
             interpreter,
             flat_index,
             no_binary,
+            concurrency: Concurrency::DEFAULT_DOWNLOADS,
+        }
+    }
+
+    /// Set the maximum number of concurrent metadata fetches.
+    #[must_use]
+    pub fn with_concurrency(self, concurrency: usize) -> Self {
+        Self {
+            concurrency,
+            ..self
         }
     }
 
@@ -67,11 +79,24 @@ impl<'a> DistFinder<'a> {
         match requirement.version_or_url.as_ref() {
             None | Some(VersionOrUrl::VersionSpecifier(_)) => {
                 // Query the index(es) (cached) to get the URLs for the available files.
-                let (index, raw_metadata) = self.client.simple(&requirement.name).await?;
-                let metadata = OwnedArchive::deserialize(&raw_metadata);
+                let results = self.client.simple(&requirement.name).await?;
 
-                // Pick a version that satisfies the requirement.
-                let Some(dist) = self.select(requirement, metadata, &index, flat_index) else {
+                // Pick a version that satisfies the requirement from each index, in order.
+                let mut candidates = results.iter().filter_map(|(index, raw_metadata)| {
+                    let metadata = OwnedArchive::deserialize(raw_metadata);
+                    self.select(requirement, metadata, index, flat_index)
+                });
+                let dist = match self.client.index_strategy() {
+                    IndexStrategy::FirstMatch | IndexStrategy::UnsafeAnyMatch => candidates.next(),
+                    IndexStrategy::UnsafeBestMatch => candidates.reduce(|best, dist| {
+                        if dist.version() > best.version() {
+                            dist
+                        } else {
+                            best
+                        }
+                    }),
+                };
+                let Some(dist) = dist else {
                     return Err(ResolveError::NotFound(requirement.clone()));
                 };
 
@@ -98,9 +123,16 @@ impl<'a> DistFinder<'a> {
     ) -> impl Stream<Item = Result<(PackageName, Dist), ResolveError>> + 'data {
         stream::iter(requirements)
             .map(move |requirement| {
-                self.resolve_requirement(requirement, self.flat_index.get(&requirement.name))
+                // A package that's pinned to an index is never resolved from the `--find-links`
+                // entries.
+                let flat_index = if self.client.package_index(&requirement.name).is_some() {
+                    None
+                } else {
+                    self.flat_index.get(&requirement.name)
+                };
+                self.resolve_requirement(requirement, flat_index)
             })
-            .buffer_unordered(32)
+            .buffer_unordered(self.concurrency)
     }
 
     /// Resolve a set of pinned packages into a set of wheels.
@@ -1,8 +1,10 @@
 pub use dependency_mode::DependencyMode;
 pub use error::ResolveError;
 pub use finder::{DistFinder, Reporter as FinderReporter};
+pub use lock::{Artifact, Dependency, Distribution, Lock, LockError, Source};
 pub use manifest::Manifest;
 pub use options::{Options, OptionsBuilder};
+pub use prefetch::{PrefetchReport, Prefetcher, Uncached, UncachedReason};
 pub use prerelease_mode::PreReleaseMode;
 pub use python_requirement::PythonRequirement;
 pub use resolution::{AnnotationStyle, Diagnostic, DisplayResolutionGraph, ResolutionGraph};
@@ -20,10 +22,12 @@ mod dependency_mode;
 mod editables;
 mod error;
 mod finder;
+mod lock;
 mod manifest;
 mod options;
 mod overrides;
 mod pins;
+mod prefetch;
 mod prerelease_mode;
 mod pubgrub;
 mod python_requirement;
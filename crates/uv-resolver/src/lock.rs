@@ -0,0 +1,517 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use chrono::{DateTime, TimeZone, Utc};
+use petgraph::Direction;
+use rustc_hash::FxHashMap;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use url::Url;
+
+use distribution_filename::{DistFilename, SourceDistFilename, WheelFilename};
+use distribution_types::{
+    BuiltDist, Dist, DistributionMetadata, File, FileLocation, IndexUrl, LocalEditable, Name,
+    RemoteSource, Resolution, SourceDist,
+};
+use pep440_rs::{Version, VersionSpecifier, VersionSpecifiers};
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement, VerbatimUrl, VersionOrUrl};
+use platform_tags::{TagCompatibility, Tags};
+use pypi_types::{HashAlgorithm, HashDigest, Hashes};
+use uv_normalize::PackageName;
+
+use crate::ResolutionGraph;
+
+/// The current version of the lockfile format.
+const VERSION: u32 = 1;
+
+/// A lockfile, which records every distribution in a resolution, along with its source, its
+/// available artifacts, and its dependencies.
+///
+/// Unlike a `requirements.txt` file, a [`Lock`] can be installed without querying the index for
+/// any metadata, since it includes the URL and hash of every wheel and source distribution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lock {
+    version: u32,
+    #[serde(rename = "distribution", default)]
+    distributions: Vec<Distribution>,
+}
+
+impl Lock {
+    /// Create a [`Lock`] from a [`ResolutionGraph`].
+    pub fn from_resolution_graph(graph: &ResolutionGraph) -> Result<Self, LockError> {
+        let petgraph = graph.petgraph();
+
+        let mut distributions = Vec::with_capacity(petgraph.node_count());
+        for index in petgraph.node_indices() {
+            let dist = &petgraph[index];
+            let package_id = dist.package_id();
+
+            let mut dependencies = petgraph
+                .neighbors_directed(index, Direction::Outgoing)
+                .map(|dependency| {
+                    let dependency = &petgraph[dependency];
+                    let dependency_id = dependency.package_id();
+                    Dependency {
+                        name: dependency.name().clone(),
+                        version: resolved_version(graph, dependency),
+                        marker: graph
+                            .dependency_marker(&package_id, &dependency_id)
+                            .cloned(),
+                    }
+                })
+                .collect::<Vec<_>>();
+            dependencies.sort_unstable_by(|a, b| {
+                a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version))
+            });
+
+            // For registry distributions, record every available artifact, falling back to the
+            // selected artifact if the index wasn't queried (as for a pinned preference).
+            let mut sdist = None;
+            let mut wheels = Vec::new();
+            if dist.file().is_some() {
+                let files = graph.files(&package_id);
+                let files = if files.is_empty() {
+                    std::slice::from_ref(dist)
+                } else {
+                    files
+                };
+                for file in files {
+                    match file {
+                        Dist::Built(BuiltDist::Registry(wheel)) => {
+                            wheels.push(Artifact::from_file(&wheel.file)?);
+                        }
+                        Dist::Source(SourceDist::Registry(source_dist)) => {
+                            if sdist.is_none() {
+                                sdist = Some(Artifact::from_file(&source_dist.file)?);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            distributions.push(Distribution {
+                name: dist.name().clone(),
+                version: resolved_version(graph, dist),
+                source: Source::from_dist(dist),
+                marker: graph.marker(&package_id).cloned(),
+                sdist,
+                wheels,
+                dependencies,
+            });
+        }
+        distributions
+            .sort_unstable_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+
+        Ok(Self {
+            version: VERSION,
+            distributions,
+        })
+    }
+
+    /// Parse a [`Lock`] from the contents of a TOML lockfile.
+    pub fn from_toml(contents: &str) -> Result<Self, LockError> {
+        let lock: Self = toml::from_str(contents)?;
+        if lock.version != VERSION {
+            return Err(LockError::UnsupportedVersion(lock.version));
+        }
+        Ok(lock)
+    }
+
+    /// Serialize the [`Lock`] to TOML.
+    pub fn to_toml(&self) -> Result<String, LockError> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Return the distributions in the lockfile.
+    pub fn distributions(&self) -> &[Distribution] {
+        &self.distributions
+    }
+
+    /// Convert the [`Lock`] to a [`Resolution`] for the given environment, selecting the
+    /// distributions whose markers apply and, for each, the most compatible artifact.
+    pub fn to_resolution(
+        &self,
+        markers: &MarkerEnvironment,
+        tags: &Tags,
+    ) -> Result<Resolution, LockError> {
+        let mut resolution = FxHashMap::default();
+        for distribution in &self.distributions {
+            if distribution
+                .marker
+                .as_ref()
+                .is_some_and(|marker| !marker.evaluate(markers, &[]))
+            {
+                continue;
+            }
+            resolution.insert(distribution.name.clone(), distribution.to_dist(tags)?);
+        }
+        Ok(Resolution::new(resolution))
+    }
+}
+
+/// A pinned distribution in a [`Lock`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Distribution {
+    /// The name of the package.
+    pub name: PackageName,
+    /// The resolved version of the package.
+    pub version: Version,
+    /// The source from which the package was resolved.
+    pub source: Source,
+    /// The markers under which the package is required, if it's only required conditionally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<MarkerTree>,
+    /// The source distribution available for the package, for registry-based packages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sdist: Option<Artifact>,
+    /// The wheels available for the package, for registry-based packages.
+    #[serde(default, rename = "wheel", skip_serializing_if = "Vec::is_empty")]
+    pub wheels: Vec<Artifact>,
+    /// The dependencies of the package.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<Dependency>,
+}
+
+impl Distribution {
+    /// Return `true` if the distribution is a local editable.
+    pub fn is_editable(&self) -> bool {
+        matches!(self.source, Source::Editable(_))
+    }
+
+    /// Return the hashes of every artifact for the distribution.
+    pub fn hashes(&self) -> impl Iterator<Item = &HashDigest> {
+        self.sdist
+            .iter()
+            .chain(self.wheels.iter())
+            .filter_map(|artifact| artifact.hash.as_ref())
+    }
+
+    /// Convert the [`Distribution`] to a [`Requirement`] that pins it to its resolved version or
+    /// URL.
+    pub fn to_requirement(&self) -> Requirement {
+        let version_or_url = match &self.source {
+            Source::Registry(_) => VersionOrUrl::VersionSpecifier(VersionSpecifiers::from(
+                VersionSpecifier::equals_version(self.version.clone()),
+            )),
+            Source::Direct(url) | Source::Git(url) => {
+                VersionOrUrl::Url(VerbatimUrl::from_url(url.clone()))
+            }
+            Source::Path(path) | Source::Editable(path) => {
+                VersionOrUrl::Url(VerbatimUrl::from_path(path))
+            }
+        };
+        Requirement {
+            name: self.name.clone(),
+            extras: vec![],
+            version_or_url: Some(version_or_url),
+            marker: self.marker.clone(),
+        }
+    }
+
+    /// Convert the [`Distribution`] to a [`Dist`], selecting the most compatible wheel for the
+    /// given tags, or the source distribution if no wheel is compatible.
+    pub fn to_dist(&self, tags: &Tags) -> Result<Dist, LockError> {
+        match &self.source {
+            Source::Registry(index) => {
+                let mut best: Option<(TagCompatibility, WheelFilename, &Artifact)> = None;
+                for wheel in &self.wheels {
+                    let filename = WheelFilename::from_str(&wheel.filename()?)?;
+                    let compatibility = filename.compatibility(tags);
+                    if !compatibility.is_compatible() {
+                        continue;
+                    }
+                    if best
+                        .as_ref()
+                        .map_or(true, |(existing, ..)| compatibility > *existing)
+                    {
+                        best = Some((compatibility, filename, wheel));
+                    }
+                }
+
+                let index = IndexUrl::from(VerbatimUrl::from_url(index.clone()));
+                if let Some((_, filename, wheel)) = best {
+                    return Ok(Dist::from_registry(
+                        DistFilename::WheelFilename(filename),
+                        wheel.to_file()?,
+                        index,
+                    ));
+                }
+                if let Some(sdist) = &self.sdist {
+                    let filename = SourceDistFilename::parse(&sdist.filename()?, &self.name)?;
+                    return Ok(Dist::from_registry(
+                        DistFilename::SourceDistFilename(filename),
+                        sdist.to_file()?,
+                        index,
+                    ));
+                }
+                Err(LockError::NoCompatibleDistribution(format!(
+                    "{}=={}",
+                    self.name, self.version
+                )))
+            }
+            Source::Direct(url) | Source::Git(url) => Ok(Dist::from_url(
+                self.name.clone(),
+                VerbatimUrl::from_url(url.clone()),
+            )?),
+            Source::Path(path) => Ok(Dist::from_url(
+                self.name.clone(),
+                VerbatimUrl::from_path(path),
+            )?),
+            Source::Editable(path) => Ok(Dist::from_editable(
+                self.name.clone(),
+                LocalEditable {
+                    url: VerbatimUrl::from_path(path),
+                    path: path.clone(),
+                    extras: vec![],
+                },
+            )?),
+        }
+    }
+}
+
+/// The source of a [`Distribution`], serialized as `<kind>+<location>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// A package index, e.g., `registry+https://pypi.org/simple`.
+    Registry(Url),
+    /// A direct URL to a wheel or source distribution, e.g.,
+    /// `direct+https://example.com/flask-3.0.0.tar.gz`.
+    Direct(Url),
+    /// A Git repository, pinned to a commit, e.g., `git+https://github.com/pallets/flask@<commit>`.
+    Git(Url),
+    /// A local wheel, source distribution, or source tree, e.g., `path+/home/user/flask`.
+    Path(PathBuf),
+    /// A local source tree, installed in editable mode, e.g., `editable+/home/user/flask`.
+    Editable(PathBuf),
+}
+
+impl Source {
+    /// Determine the [`Source`] of a resolved [`Dist`].
+    fn from_dist(dist: &Dist) -> Self {
+        match dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => {
+                Self::Registry(redact_url(Url::from(wheel.index.clone())))
+            }
+            Dist::Source(SourceDist::Registry(sdist)) => {
+                Self::Registry(redact_url(Url::from(sdist.index.clone())))
+            }
+            Dist::Built(BuiltDist::DirectUrl(wheel)) => {
+                Self::Direct(redact_url(wheel.url.to_url()))
+            }
+            Dist::Source(SourceDist::DirectUrl(sdist)) => {
+                Self::Direct(redact_url(sdist.url.to_url()))
+            }
+            Dist::Source(SourceDist::Git(sdist)) => Self::Git(redact_url(sdist.url.to_url())),
+            Dist::Built(BuiltDist::Path(wheel)) => Self::Path(wheel.path.clone()),
+            Dist::Source(SourceDist::Path(sdist)) => {
+                if sdist.editable {
+                    Self::Editable(sdist.path.clone())
+                } else {
+                    Self::Path(sdist.path.clone())
+                }
+            }
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Registry(url) => write!(f, "registry+{url}"),
+            Self::Direct(url) => write!(f, "direct+{url}"),
+            // Git URLs already include the `git+` prefix.
+            Self::Git(url) => write!(f, "{url}"),
+            Self::Path(path) => write!(f, "path+{}", path.display()),
+            Self::Editable(path) => write!(f, "editable+{}", path.display()),
+        }
+    }
+}
+
+impl FromStr for Source {
+    type Err = LockError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_url =
+            |url: &str| Url::parse(url).map_err(|err| LockError::Url(url.to_string(), err));
+        if let Some(url) = s.strip_prefix("registry+") {
+            Ok(Self::Registry(parse_url(url)?))
+        } else if let Some(url) = s.strip_prefix("direct+") {
+            Ok(Self::Direct(parse_url(url)?))
+        } else if s.starts_with("git+") {
+            Ok(Self::Git(parse_url(s)?))
+        } else if let Some(path) = s.strip_prefix("path+") {
+            Ok(Self::Path(PathBuf::from(path)))
+        } else if let Some(path) = s.strip_prefix("editable+") {
+            Ok(Self::Editable(PathBuf::from(path)))
+        } else {
+            Err(LockError::InvalidSource(s.to_string()))
+        }
+    }
+}
+
+impl Serialize for Source {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Source {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        Self::from_str(&string).map_err(de::Error::custom)
+    }
+}
+
+/// A wheel or source distribution available for a [`Distribution`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Artifact {
+    /// The URL from which the artifact can be downloaded.
+    pub url: Url,
+    /// The hash of the artifact, as reported by the index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<HashDigest>,
+    /// The size of the artifact in bytes, as reported by the index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// The time at which the artifact was uploaded to the index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_time: Option<DateTime<Utc>>,
+}
+
+impl Artifact {
+    /// Create an [`Artifact`] from a registry [`File`].
+    fn from_file(file: &File) -> Result<Self, LockError> {
+        let url = match &file.url {
+            FileLocation::RelativeUrl(base, url) => pypi_types::base_url_join_relative(base, url)?,
+            FileLocation::AbsoluteUrl(url) => {
+                Url::parse(url).map_err(|err| LockError::Url(url.clone(), err))?
+            }
+            FileLocation::Path(path) => {
+                Url::from_file_path(path).map_err(|()| LockError::Path(path.clone()))?
+            }
+        };
+        Ok(Self {
+            url: redact_url(url),
+            hash: file.hashes.sha256.as_ref().map(|digest| HashDigest {
+                algorithm: HashAlgorithm::Sha256,
+                digest: digest.clone(),
+            }),
+            size: file.size,
+            upload_time: file
+                .upload_time_utc_ms
+                .and_then(|timestamp| Utc.timestamp_millis_opt(timestamp).single()),
+        })
+    }
+
+    /// Return the filename of the artifact.
+    fn filename(&self) -> Result<String, LockError> {
+        Ok(self.url.filename()?.into_owned())
+    }
+
+    /// Convert the [`Artifact`] back to a registry [`File`].
+    fn to_file(&self) -> Result<File, LockError> {
+        let location = if self.url.scheme() == "file" {
+            FileLocation::Path(
+                self.url
+                    .to_file_path()
+                    .map_err(|()| LockError::InvalidFileUrl(self.url.clone()))?,
+            )
+        } else {
+            FileLocation::AbsoluteUrl(self.url.to_string())
+        };
+        Ok(File {
+            dist_info_metadata: None,
+            filename: self.filename()?,
+            hashes: Hashes {
+                md5: None,
+                sha256: self
+                    .hash
+                    .as_ref()
+                    .filter(|hash| hash.algorithm() == HashAlgorithm::Sha256)
+                    .map(|hash| hash.digest.clone()),
+            },
+            requires_python: None,
+            size: self.size,
+            upload_time_utc_ms: self.upload_time.map(|time| time.timestamp_millis()),
+            url: location,
+            yanked: None,
+        })
+    }
+}
+
+/// A dependency of a [`Distribution`], identifying another [`Distribution`] in the [`Lock`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Dependency {
+    /// The name of the dependency.
+    pub name: PackageName,
+    /// The resolved version of the dependency.
+    pub version: Version,
+    /// The markers under which the dependency is required, if it's only required conditionally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<MarkerTree>,
+}
+
+/// Return the resolved version of a [`Dist`] in the [`ResolutionGraph`].
+fn resolved_version(graph: &ResolutionGraph, dist: &Dist) -> Version {
+    graph
+        .version(&dist.package_id())
+        .or_else(|| dist.version())
+        .cloned()
+        .expect("every distribution should have a resolved version")
+}
+
+/// Remove any credentials from a URL, to avoid persisting them in the lockfile.
+fn redact_url(mut url: Url) -> Url {
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LockError {
+    #[error("Failed to parse lockfile")]
+    Deserialize(#[from] toml::de::Error),
+
+    #[error("Failed to serialize lockfile")]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error("Unsupported lockfile version `{0}`; the lockfile may have been generated by a newer version of uv")]
+    UnsupportedVersion(u32),
+
+    #[error("Invalid source in lockfile: `{0}`")]
+    InvalidSource(String),
+
+    #[error("Failed to parse URL: `{0}`")]
+    Url(String, #[source] url::ParseError),
+
+    #[error(transparent)]
+    JoinRelativeUrl(#[from] pypi_types::JoinRelativeError),
+
+    #[error("Failed to convert path to URL: `{}`", _0.display())]
+    Path(PathBuf),
+
+    #[error("Failed to convert URL to path: `{0}`")]
+    InvalidFileUrl(Url),
+
+    #[error(transparent)]
+    WheelFilename(#[from] distribution_filename::WheelFilenameError),
+
+    #[error(transparent)]
+    SourceDistFilename(#[from] distribution_filename::SourceDistFilenameError),
+
+    #[error(transparent)]
+    DistributionType(#[from] distribution_types::Error),
+
+    #[error("No compatible wheel or source distribution found in lockfile for `{0}`")]
+    NoCompatibleDistribution(String),
+}
@@ -0,0 +1,334 @@
+//! Populate the cache with every piece of index metadata that a resolution could require.
+//!
+//! Unlike the resolver, which only fetches metadata for the versions it actually visits, the
+//! prefetcher walks every candidate version of every reachable package, such that a subsequent
+//! `--offline` resolution can be answered entirely from the cache.
+
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, Utc};
+use futures::{stream, StreamExt};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use distribution_types::{Dist, PrioritizedDist};
+use pep440_rs::Version;
+use pep508_rs::{MarkerEnvironment, Requirement, VerbatimUrl, VersionOrUrl};
+use platform_tags::Tags;
+use pypi_types::Metadata21;
+use uv_client::{FlatIndex, RegistryClient};
+use uv_distribution::DistributionDatabase;
+use uv_normalize::{ExtraName, PackageName};
+use uv_traits::{BuildContext, Concurrency};
+
+use crate::constraints::Constraints;
+use crate::error::ResolveError;
+use crate::overrides::Overrides;
+use crate::python_requirement::PythonRequirement;
+use crate::resolver::{DefaultResolverProvider, ResolverProvider, VersionsResponse};
+use crate::version_map::VersionMap;
+
+pub struct Prefetcher<'a, Provider: ResolverProvider> {
+    markers: &'a MarkerEnvironment,
+    python_requirement: PythonRequirement,
+    provider: Provider,
+    /// The maximum number of concurrent metadata fetches.
+    concurrency: usize,
+}
+
+impl<'a, Context: BuildContext + Send + Sync> Prefetcher<'a, DefaultResolverProvider<'a, Context>> {
+    /// Initialize a new prefetcher using the default backend doing real requests.
+    ///
+    /// Reads the flat index entries.
+    pub fn new(
+        markers: &'a MarkerEnvironment,
+        python_requirement: PythonRequirement,
+        tags: &'a Tags,
+        client: &'a RegistryClient,
+        flat_index: &'a FlatIndex,
+        exclude_newer: Option<DateTime<Utc>>,
+        build_context: &'a Context,
+    ) -> Self {
+        let provider = DefaultResolverProvider::new(
+            client,
+            DistributionDatabase::new(build_context.cache(), tags, client, build_context),
+            flat_index,
+            Some(tags),
+            python_requirement.clone(),
+            exclude_newer,
+            build_context.no_binary(),
+        );
+        let mut prefetcher = Self::new_custom_io(markers, python_requirement, provider);
+        prefetcher.concurrency = build_context.concurrency().downloads;
+        prefetcher
+    }
+}
+
+impl<'a, Provider: ResolverProvider> Prefetcher<'a, Provider> {
+    /// Initialize a new prefetcher using a user provided backend.
+    pub fn new_custom_io(
+        markers: &'a MarkerEnvironment,
+        python_requirement: PythonRequirement,
+        provider: Provider,
+    ) -> Self {
+        Self {
+            markers,
+            python_requirement,
+            provider,
+            concurrency: Concurrency::DEFAULT_DOWNLOADS,
+        }
+    }
+
+    /// Fetch the simple pages and wheel metadata for every version of every package that could
+    /// participate in a resolution of the given requirements.
+    pub async fn prefetch(
+        &self,
+        requirements: Vec<Requirement>,
+        constraints: Vec<Requirement>,
+        overrides: Vec<Requirement>,
+    ) -> Result<PrefetchReport, ResolveError> {
+        let constraints = Constraints::from_requirements(constraints);
+        let overrides = Overrides::from_requirements(overrides);
+
+        let mut report = PrefetchReport::default();
+        let mut packages: FxHashMap<PackageName, PackageState> = FxHashMap::default();
+        let mut direct_urls: FxHashSet<(PackageName, String)> = FxHashSet::default();
+
+        let mut pending: Vec<Requirement> = overrides.apply(&requirements).cloned().collect();
+        while !pending.is_empty() {
+            // Fetch the simple pages for any packages we haven't seen yet.
+            let names = pending
+                .iter()
+                .filter(|requirement| {
+                    !matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_)))
+                })
+                .map(|requirement| &requirement.name)
+                .filter(|name| !packages.contains_key(*name))
+                .collect::<FxHashSet<_>>();
+            let responses = stream::iter(names)
+                .map(|name| async move {
+                    (name.clone(), self.provider.get_package_versions(name).await)
+                })
+                .buffer_unordered(self.concurrency)
+                .collect::<Vec<_>>()
+                .await;
+            for (name, response) in responses {
+                match response? {
+                    VersionsResponse::Found(version_map) => {
+                        report.packages += 1;
+                        packages.insert(name, PackageState::new(Some(version_map)));
+                    }
+                    response => {
+                        let reason = match response {
+                            VersionsResponse::NoIndex => UncachedReason::NoIndex,
+                            VersionsResponse::Offline => UncachedReason::Offline,
+                            _ => UncachedReason::NotFound,
+                        };
+                        report.uncached.push(Uncached {
+                            name: name.clone(),
+                            version: None,
+                            reason,
+                        });
+                        packages.insert(name, PackageState::new(None));
+                    }
+                }
+            }
+
+            // Determine the versions that each requirement could select, along with any
+            // dependencies unlocked by newly requested extras.
+            let mut next = Vec::new();
+            let mut fetches = Vec::new();
+            for requirement in pending.drain(..) {
+                if let Some(VersionOrUrl::Url(url)) = &requirement.version_or_url {
+                    if direct_urls.insert((requirement.name.clone(), url.to_string())) {
+                        report.uncached.push(Uncached {
+                            name: requirement.name.clone(),
+                            version: None,
+                            reason: UncachedReason::DirectUrl(url.clone()),
+                        });
+                    }
+                    continue;
+                }
+
+                let Some(state) = packages.get_mut(&requirement.name) else {
+                    continue;
+                };
+                let Some(version_map) = state.version_map.as_ref() else {
+                    continue;
+                };
+
+                // If the requirement enables new extras, revisit the versions we've already
+                // fetched.
+                if requirement
+                    .extras
+                    .iter()
+                    .any(|extra| !state.extras.contains(extra))
+                {
+                    state.extras.extend(requirement.extras.iter().cloned());
+                    let extras = state.extras.iter().cloned().collect::<Vec<_>>();
+                    for metadata in state.metadata.values().flatten() {
+                        next.extend(self.dependencies(metadata, &extras, &overrides));
+                    }
+                }
+
+                for (version, handle) in version_map.iter() {
+                    if !requirement.is_satisfied_by(version) {
+                        continue;
+                    }
+                    if !constraints
+                        .get(&requirement.name)
+                        .into_iter()
+                        .flatten()
+                        .filter(|constraint| constraint.evaluate_markers(self.markers, &[]))
+                        .all(|constraint| constraint.is_satisfied_by(version))
+                    {
+                        continue;
+                    }
+                    if state.metadata.contains_key(version) {
+                        continue;
+                    }
+                    state.metadata.insert(version.clone(), None);
+
+                    // Select the distribution that the resolver would read metadata from.
+                    let Some(dist) = handle.prioritized_dist().and_then(PrioritizedDist::get)
+                    else {
+                        continue;
+                    };
+                    if self.python_requirement.validate_dist(&dist).is_some() {
+                        continue;
+                    }
+                    match &dist.for_resolution().dist {
+                        dist @ Dist::Built(_) => {
+                            fetches.push((requirement.name.clone(), version.clone(), dist.clone()));
+                        }
+                        Dist::Source(_) => {
+                            report.uncached.push(Uncached {
+                                name: requirement.name.clone(),
+                                version: Some(version.clone()),
+                                reason: UncachedReason::SourceDist,
+                            });
+                        }
+                    }
+                }
+            }
+
+            // Fetch the metadata for each selected wheel, which stores it in the cache.
+            let responses = stream::iter(fetches)
+                .map(|(name, version, dist)| async move {
+                    let metadata = self.provider.get_or_build_wheel_metadata(&dist).await;
+                    (name, version, metadata)
+                })
+                .buffer_unordered(self.concurrency)
+                .collect::<Vec<_>>()
+                .await;
+            for (name, version, metadata) in responses {
+                // Report the failure, but continue prefetching everything else.
+                let metadata = match metadata {
+                    Ok((metadata, _)) => metadata,
+                    Err(err) => {
+                        report.uncached.push(Uncached {
+                            name,
+                            version: Some(version),
+                            reason: UncachedReason::Metadata(err.to_string()),
+                        });
+                        continue;
+                    }
+                };
+                report.metadata += 1;
+
+                let state = packages
+                    .get_mut(&name)
+                    .expect("metadata is only fetched for known packages");
+                let extras = state.extras.iter().cloned().collect::<Vec<_>>();
+                next.extend(self.dependencies(&metadata, &extras, &overrides));
+                state.metadata.insert(version, Some(metadata));
+            }
+
+            pending = next;
+        }
+
+        Ok(report)
+    }
+
+    /// Return the dependencies of a distribution that apply to the target environment.
+    fn dependencies(
+        &self,
+        metadata: &Metadata21,
+        extras: &[ExtraName],
+        overrides: &Overrides,
+    ) -> Vec<Requirement> {
+        overrides
+            .apply(&metadata.requires_dist)
+            .filter(|requirement| requirement.evaluate_markers(self.markers, extras))
+            .cloned()
+            .collect()
+    }
+}
+
+/// The versions of a package that have been visited during a prefetch.
+struct PackageState {
+    /// The available versions of the package, or `None` if the package could not be found.
+    version_map: Option<VersionMap>,
+    /// The extras that have been requested for the package.
+    extras: FxHashSet<ExtraName>,
+    /// The metadata for each visited version, or `None` if no metadata could be fetched.
+    metadata: FxHashMap<Version, Option<Metadata21>>,
+}
+
+impl PackageState {
+    fn new(version_map: Option<VersionMap>) -> Self {
+        Self {
+            version_map,
+            extras: FxHashSet::default(),
+            metadata: FxHashMap::default(),
+        }
+    }
+}
+
+/// A summary of a prefetch.
+#[derive(Debug, Default)]
+pub struct PrefetchReport {
+    /// The number of packages whose simple pages were cached.
+    pub packages: usize,
+    /// The number of wheels whose metadata was cached.
+    pub metadata: usize,
+    /// The packages and versions whose metadata could not be cached.
+    pub uncached: Vec<Uncached>,
+}
+
+/// A package or version whose metadata could not be cached.
+#[derive(Debug)]
+pub struct Uncached {
+    pub name: PackageName,
+    pub version: Option<Version>,
+    pub reason: UncachedReason,
+}
+
+#[derive(Debug)]
+pub enum UncachedReason {
+    /// The version is only available as a source distribution, which must be built to determine
+    /// its metadata.
+    SourceDist,
+    /// The package was requested by URL, and so is fetched directly during resolution.
+    DirectUrl(VerbatimUrl),
+    /// The package was not found in the registry.
+    NotFound,
+    /// The package was not found, and `--no-index` was provided.
+    NoIndex,
+    /// The package was not found in the cache, and the network is not available.
+    Offline,
+    /// The metadata for the version could not be fetched.
+    Metadata(String),
+}
+
+impl Display for UncachedReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SourceDist => write!(f, "only available as a source distribution"),
+            Self::DirectUrl(url) => write!(f, "requested by URL ({url})"),
+            Self::NotFound => write!(f, "not found in the package registry"),
+            Self::NoIndex => write!(f, "not found in the provided package locations"),
+            Self::Offline => write!(f, "not found in the cache, and network access is disabled"),
+            Self::Metadata(err) => write!(f, "failed to fetch metadata ({err})"),
+        }
+    }
+}
@@ -5,7 +5,8 @@ use uv_normalize::PackageName;
 
 use crate::Manifest;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum PreReleaseMode {
     /// Disallow all pre-release versions.
@@ -53,7 +54,7 @@ impl PreReleaseStrategy {
     pub(crate) fn from_mode(
         mode: PreReleaseMode,
         manifest: &Manifest,
-        markers: &MarkerEnvironment,
+        markers: Option<&MarkerEnvironment>,
     ) -> Self {
         match mode {
             PreReleaseMode::Disallow => Self::Disallow,
@@ -65,10 +66,10 @@ impl PreReleaseStrategy {
                     .iter()
                     .chain(manifest.constraints.iter())
                     .chain(manifest.overrides.iter())
-                    .filter(|requirement| requirement.evaluate_markers(markers, &[]))
+                    .filter(|requirement| requirement.evaluate_optional_environment(markers, &[]))
                     .chain(manifest.editables.iter().flat_map(|(editable, metadata)| {
                         metadata.requires_dist.iter().filter(|requirement| {
-                            requirement.evaluate_markers(markers, &editable.extras)
+                            requirement.evaluate_optional_environment(markers, &editable.extras)
                         })
                     }))
                     .filter(|requirement| {
@@ -94,10 +95,10 @@ impl PreReleaseStrategy {
                     .iter()
                     .chain(manifest.constraints.iter())
                     .chain(manifest.overrides.iter())
-                    .filter(|requirement| requirement.evaluate_markers(markers, &[]))
+                    .filter(|requirement| requirement.evaluate_optional_environment(markers, &[]))
                     .chain(manifest.editables.iter().flat_map(|(editable, metadata)| {
                         metadata.requires_dist.iter().filter(|requirement| {
-                            requirement.evaluate_markers(markers, &editable.extras)
+                            requirement.evaluate_optional_environment(markers, &editable.extras)
                         })
                     }))
                     .filter(|requirement| {
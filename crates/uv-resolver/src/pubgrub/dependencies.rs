@@ -1,10 +1,10 @@
 use itertools::Itertools;
 use pubgrub::range::Range;
-use tracing::warn;
+use tracing::{debug, warn};
 
 use distribution_types::Verbatim;
 use pep440_rs::Version;
-use pep508_rs::{MarkerEnvironment, Requirement, VersionOrUrl};
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement, VersionOrUrl};
 use uv_normalize::{ExtraName, PackageName};
 
 use crate::constraints::Constraints;
@@ -14,8 +14,29 @@ use crate::pubgrub::PubGrubPackage;
 use crate::resolver::Urls;
 use crate::ResolveError;
 
+#[derive(Debug, Default)]
+pub struct PubGrubDependencies(Vec<PubGrubDependency>);
+
+/// A single `PubGrub` dependency, along with the markers under which it applies.
+#[derive(Debug, Clone)]
+pub(crate) struct PubGrubDependency {
+    pub(crate) package: PubGrubPackage,
+    pub(crate) version: Range<Version>,
+    /// The markers under which the dependency applies, or `None` if it applies unconditionally.
+    ///
+    /// Only populated during universal resolution; when resolving for a specific environment,
+    /// inapplicable dependencies are dropped up-front.
+    pub(crate) marker: Option<MarkerTree>,
+}
+
+/// A subset of the dependencies of a package that should be solved independently, as the result
+/// of the package declaring conflicting requirements under disjoint markers.
 #[derive(Debug)]
-pub struct PubGrubDependencies(Vec<(PubGrubPackage, Range<Version>)>);
+pub(crate) struct PubGrubFork {
+    /// The markers under which this fork applies, or `None` if it applies unconditionally.
+    pub(crate) markers: Option<MarkerTree>,
+    pub(crate) dependencies: Vec<PubGrubDependency>,
+}
 
 impl PubGrubDependencies {
     /// Generate a set of `PubGrub` dependencies from a set of requirements.
@@ -26,21 +47,21 @@ impl PubGrubDependencies {
         source_name: Option<&PackageName>,
         source_extra: Option<&ExtraName>,
         urls: &Urls,
-        env: &MarkerEnvironment,
+        env: Option<&MarkerEnvironment>,
     ) -> Result<Self, ResolveError> {
         let mut dependencies = Vec::default();
+        let extras = source_extra.map(std::slice::from_ref).unwrap_or_default();
 
         // Iterate over all declared requirements.
         for requirement in overrides.apply(requirements) {
             // If the requirement isn't relevant for the current platform, skip it.
-            if let Some(extra) = source_extra {
-                if !requirement.evaluate_markers(env, std::slice::from_ref(extra)) {
-                    continue;
-                }
-            } else if !requirement.evaluate_markers(env, &[]) {
+            if !requirement.evaluate_optional_environment(env, extras) {
                 continue;
             }
 
+            // In universal mode, retain the markers under which the requirement applies.
+            let marker = marker(requirement, env, extras);
+
             // Add the package, plus any extra variants.
             for result in std::iter::once(to_pubgrub(requirement, None, urls)).chain(
                 requirement
@@ -62,19 +83,25 @@ impl PubGrubDependencies {
                     }
                 }
 
-                dependencies.push((package.clone(), version.clone()));
+                dependencies.push(PubGrubDependency {
+                    package: package.clone(),
+                    version: version.clone(),
+                    marker: marker.clone(),
+                });
 
                 // If the requirement was constrained, add those constraints.
                 for constraint in constraints.get(&requirement.name).into_iter().flatten() {
                     // If the requirement isn't relevant for the current platform, skip it.
-                    if let Some(extra) = source_extra {
-                        if !constraint.evaluate_markers(env, std::slice::from_ref(extra)) {
-                            continue;
-                        }
-                    } else if !constraint.evaluate_markers(env, &[]) {
+                    if !constraint.evaluate_optional_environment(env, extras) {
                         continue;
                     }
 
+                    // The constraint applies wherever both it and the requirement apply.
+                    let marker = match (marker.clone(), self::marker(constraint, env, extras)) {
+                        (Some(left), Some(right)) => Some(left.and(right)),
+                        (left, right) => left.or(right),
+                    };
+
                     // Add the package, plus any extra variants.
                     for result in std::iter::once(to_pubgrub(constraint, None, urls)).chain(
                         constraint
@@ -96,7 +123,11 @@ impl PubGrubDependencies {
                             }
                         }
 
-                        dependencies.push((package.clone(), version.clone()));
+                        dependencies.push(PubGrubDependency {
+                            package: package.clone(),
+                            version: version.clone(),
+                            marker: marker.clone(),
+                        });
                     }
                 }
             }
@@ -107,20 +138,173 @@ impl PubGrubDependencies {
 
     /// Add a [`PubGrubPackage`] and [`PubGrubVersion`] range into the dependencies.
     pub(crate) fn push(&mut self, package: PubGrubPackage, version: Range<Version>) {
-        self.0.push((package, version));
+        self.0.push(PubGrubDependency {
+            package,
+            version,
+            marker: None,
+        });
     }
 
     /// Iterate over the dependencies.
-    pub(crate) fn iter(&self) -> impl Iterator<Item = &(PubGrubPackage, Range<Version>)> {
-        self.0.iter()
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&PubGrubPackage, &Range<Version>)> {
+        self.0
+            .iter()
+            .map(|dependency| (&dependency.package, &dependency.version))
     }
+
+    /// Split the dependencies into forks, given the markers of the fork in which they're being
+    /// resolved.
+    ///
+    /// If the same package is requested with different requirements under provably disjoint
+    /// markers (e.g., `numpy>=1.26; python_version >= '3.9'` and `numpy<1.26; python_version <
+    /// '3.9'`), each set of requirements is solved in its own fork. Together, the forks cover the
+    /// entire marker space.
+    ///
+    /// Dependencies whose markers are disjoint with those of the current fork are dropped. Outside
+    /// of universal resolution, this always returns a single fork.
+    pub(crate) fn fork(self, markers: Option<&MarkerTree>) -> Vec<PubGrubFork> {
+        // Drop any dependencies that can't apply within the current fork.
+        let dependencies = self
+            .0
+            .into_iter()
+            .filter(|dependency| match (markers, dependency.marker.as_ref()) {
+                (Some(markers), Some(marker)) => !markers.is_disjoint(marker),
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+
+        // Group the dependencies by package name, preserving the order of first appearance.
+        let mut groups: Vec<Vec<PubGrubDependency>> = Vec::new();
+        for dependency in dependencies {
+            let group = match &dependency.package {
+                PubGrubPackage::Package(name, ..) => groups.iter_mut().find(|group| {
+                    matches!(&group[0].package, PubGrubPackage::Package(other, ..) if other == name)
+                }),
+                _ => None,
+            };
+            match group {
+                Some(group) => group.push(dependency),
+                None => groups.push(vec![dependency]),
+            }
+        }
+
+        let mut forks = vec![PubGrubFork {
+            markers: markers.cloned(),
+            dependencies: Vec::new(),
+        }];
+        for group in groups {
+            // Collect the distinct markers under which the package is requested.
+            let mut group_markers: Vec<&MarkerTree> = Vec::new();
+            for marker in group
+                .iter()
+                .filter_map(|dependency| dependency.marker.as_ref())
+            {
+                if !group_markers.contains(&marker) {
+                    group_markers.push(marker);
+                }
+            }
+
+            if !requires_fork(&group, &group_markers) {
+                for fork in &mut forks {
+                    fork.dependencies.extend(group.iter().cloned());
+                }
+                continue;
+            }
+
+            if let PubGrubPackage::Package(name, ..) = &group[0].package {
+                debug!(
+                    "Forking on {name} for markers: {}",
+                    group_markers.iter().join(", ")
+                );
+            }
+
+            // The remainder covers any environment in which none of the markers apply. If the
+            // markers are exhaustive (e.g., `python_version < '3.9'` and `python_version >=
+            // '3.9'`), the remainder is unsatisfiable, and can be omitted.
+            let remainder = group_markers
+                .iter()
+                .map(|marker| marker.negate())
+                .reduce(MarkerTree::and)
+                .filter(|remainder| !remainder.is_disjoint(remainder));
+
+            let mut next = Vec::with_capacity(forks.len() * (group_markers.len() + 1));
+            for fork in forks {
+                for marker in group_markers
+                    .iter()
+                    .map(|marker| (*marker).clone())
+                    .chain(remainder.clone())
+                {
+                    if fork
+                        .markers
+                        .as_ref()
+                        .is_some_and(|markers| markers.is_disjoint(&marker))
+                    {
+                        continue;
+                    }
+                    let mut dependencies = fork.dependencies.clone();
+                    dependencies.extend(
+                        group
+                            .iter()
+                            .filter(|dependency| {
+                                dependency
+                                    .marker
+                                    .as_ref()
+                                    .map_or(true, |dependency| *dependency == marker)
+                            })
+                            .cloned(),
+                    );
+                    next.push(PubGrubFork {
+                        markers: Some(match fork.markers.clone() {
+                            Some(markers) => markers.and(marker),
+                            None => marker,
+                        }),
+                        dependencies,
+                    });
+                }
+            }
+            forks = next;
+        }
+
+        forks
+    }
+}
+
+/// Returns `true` if the requirements on a single package must be solved in separate forks: that
+/// is, if the package is requested with differing requirements under pairwise-disjoint markers.
+fn requires_fork(group: &[PubGrubDependency], markers: &[&MarkerTree]) -> bool {
+    if markers.len() < 2 {
+        return false;
+    }
+
+    // If every conditional requirement is identical, there's nothing to fork on.
+    let mut conditional = group
+        .iter()
+        .filter(|dependency| dependency.marker.is_some())
+        .map(|dependency| (&dependency.package, &dependency.version));
+    let Some(first) = conditional.next() else {
+        return false;
+    };
+    if conditional.all(|other| other == first) {
+        return false;
+    }
+
+    markers
+        .iter()
+        .tuple_combinations()
+        .all(|(left, right)| left.is_disjoint(right))
 }
 
-/// Convert a [`PubGrubDependencies`] to a [`DependencyConstraints`].
-impl From<PubGrubDependencies> for Vec<(PubGrubPackage, Range<Version>)> {
-    fn from(dependencies: PubGrubDependencies) -> Self {
-        dependencies.0
+/// Returns the markers under which a requirement applies, for the purpose of universal
+/// resolution, with any `extra` expressions that are satisfied by the given extras removed.
+fn marker(
+    requirement: &Requirement,
+    env: Option<&MarkerEnvironment>,
+    extras: &[ExtraName],
+) -> Option<MarkerTree> {
+    if env.is_some() {
+        return None;
     }
+    requirement.marker.clone()?.simplify_extras(extras)
 }
 
 /// Convert a [`Requirement`] to a `PubGrub`-compatible package and range.
@@ -3,6 +3,7 @@ use std::hash::BuildHasherDefault;
 
 use anyhow::Result;
 use dashmap::DashMap;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
@@ -12,9 +13,12 @@ use pubgrub::type_aliases::SelectedDependencies;
 use rustc_hash::FxHashMap;
 use url::Url;
 
-use distribution_types::{Dist, DistributionMetadata, LocalEditable, Name, PackageId, Verbatim};
+use distribution_types::{
+    Dist, DistributionMetadata, LocalEditable, Name, PackageId, PackageIndex, Verbatim,
+};
 use once_map::OnceMap;
 use pep440_rs::Version;
+use pep508_rs::MarkerTree;
 use pypi_types::{Hashes, Metadata21};
 use uv_normalize::{ExtraName, PackageName};
 
@@ -44,7 +48,18 @@ pub struct ResolutionGraph {
     /// The underlying graph.
     petgraph: petgraph::graph::Graph<Dist, Range<Version>, petgraph::Directed>,
     /// The metadata for every distribution in this resolution.
-    hashes: FxHashMap<PackageName, Vec<Hashes>>,
+    hashes: FxHashMap<PackageId, Vec<Hashes>>,
+    /// The resolved version of every distribution in this resolution.
+    versions: FxHashMap<PackageId, Version>,
+    /// Every available file (e.g., every wheel and source distribution) for each registry-based
+    /// distribution in this resolution, including those that weren't selected.
+    files: FxHashMap<PackageId, Vec<Dist>>,
+    /// The markers under which each distribution is required, for distributions that are only
+    /// required conditionally (as in a universal resolution).
+    markers: FxHashMap<PackageId, MarkerTree>,
+    /// The markers under which each dependency is required by its dependent, for dependencies
+    /// that are only required conditionally, keyed by the dependent and the dependency.
+    dependency_markers: FxHashMap<(PackageId, PackageId), MarkerTree>,
     /// The set of editable requirements in this resolution.
     editables: Editables,
     /// Any diagnostics that were encountered while building the graph.
@@ -53,6 +68,7 @@ pub struct ResolutionGraph {
 
 impl ResolutionGraph {
     /// Create a new graph from the resolved `PubGrub` state.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_state(
         selection: &SelectedDependencies<PubGrubPackage, Version>,
         pins: &FilePins,
@@ -61,12 +77,18 @@ impl ResolutionGraph {
         redirects: &DashMap<Url, Url>,
         state: &State<PubGrubPackage, Range<Version>, PubGrubPriority>,
         editables: Editables,
+        node_markers: &FxHashMap<PackageName, MarkerTree>,
     ) -> Result<Self, ResolveError> {
         // TODO(charlie): petgraph is a really heavy and unnecessary dependency here. We should
         // write our own graph, given that our requirements are so simple.
         let mut petgraph = petgraph::graph::Graph::with_capacity(selection.len(), selection.len());
         let mut hashes =
             FxHashMap::with_capacity_and_hasher(selection.len(), BuildHasherDefault::default());
+        let mut versions =
+            FxHashMap::with_capacity_and_hasher(selection.len(), BuildHasherDefault::default());
+        let mut files = FxHashMap::default();
+        let mut markers = FxHashMap::default();
+        let mut dependency_markers = FxHashMap::default();
         let mut diagnostics = Vec::new();
 
         // Add every package to the graph.
@@ -84,16 +106,35 @@ impl ResolutionGraph {
                             .clone()
                     };
 
-                    // Add its hashes to the index.
+                    // Warn if the distribution was yanked (e.g., because it was pinned directly).
+                    if let Some(yanked) = pinned_package
+                        .file()
+                        .and_then(|file| file.yanked.as_ref())
+                        .filter(|yanked| yanked.is_yanked())
+                    {
+                        diagnostics.push(Diagnostic::YankedVersion {
+                            dist: pinned_package.clone(),
+                            reason: yanked.reason().map(ToString::to_string),
+                        });
+                    }
+
+                    // Add its hashes and files to the index.
                     if let Some(versions_response) = packages.get(package_name) {
                         if let VersionsResponse::Found(ref version_map) = *versions_response {
-                            hashes.insert(package_name.clone(), {
+                            hashes.insert(pinned_package.package_id(), {
                                 let mut hashes = version_map.hashes(version);
                                 hashes.sort_unstable();
                                 hashes
                             });
+                            files.insert(pinned_package.package_id(), version_map.dists(version));
                         }
                     }
+                    versions.insert(pinned_package.package_id(), version.clone());
+
+                    // Add its markers, if it's only required conditionally.
+                    if let Some(marker) = node_markers.get(package_name) {
+                        markers.insert(pinned_package.package_id(), marker.clone());
+                    }
 
                     // Add the distribution to the graph.
                     let index = petgraph.add_node(pinned_package);
@@ -111,16 +152,23 @@ impl ResolutionGraph {
                         Dist::from_url(package_name.clone(), url)?
                     };
 
-                    // Add its hashes to the index.
+                    // Add its hashes and files to the index.
                     if let Some(versions_response) = packages.get(package_name) {
                         if let VersionsResponse::Found(ref version_map) = *versions_response {
-                            hashes.insert(package_name.clone(), {
+                            hashes.insert(pinned_package.package_id(), {
                                 let mut hashes = version_map.hashes(version);
                                 hashes.sort_unstable();
                                 hashes
                             });
+                            files.insert(pinned_package.package_id(), version_map.dists(version));
                         }
                     }
+                    versions.insert(pinned_package.package_id(), version.clone());
+
+                    // Add its markers, if it's only required conditionally.
+                    if let Some(marker) = node_markers.get(package_name) {
+                        markers.insert(pinned_package.package_id(), marker.clone());
+                    }
 
                     // Add the distribution to the graph.
                     let index = petgraph.add_node(pinned_package);
@@ -213,7 +261,10 @@ impl ResolutionGraph {
                     dependency_range,
                 ) = &state.incompatibility_store[*id].kind
                 {
-                    let PubGrubPackage::Package(self_package, _, _) = self_package else {
+                    let Some(self_selected) = selection.get(self_package) else {
+                        continue;
+                    };
+                    let PubGrubPackage::Package(self_package, _, self_url) = self_package else {
                         continue;
                     };
                     let PubGrubPackage::Package(dependency_package, _, _) = dependency_package
@@ -234,6 +285,30 @@ impl ResolutionGraph {
                             *dependency_index,
                             dependency_range.clone(),
                         );
+
+                        // Add the markers under which the dependency is required, if it's only
+                        // required conditionally.
+                        let marker = if let Some((_, metadata)) = editables.get(self_package) {
+                            requirement_markers(metadata, dependency_package)
+                        } else {
+                            let dist = if let Some(url) = self_url {
+                                PubGrubDistribution::from_url(self_package, url)
+                            } else {
+                                PubGrubDistribution::from_registry(self_package, self_selected)
+                            };
+                            distributions.get(&dist.package_id()).and_then(|metadata| {
+                                requirement_markers(&metadata, dependency_package)
+                            })
+                        };
+                        if let Some(marker) = marker {
+                            dependency_markers.insert(
+                                (
+                                    petgraph[*self_index].package_id(),
+                                    petgraph[*dependency_index].package_id(),
+                                ),
+                                marker,
+                            );
+                        }
                     }
                 }
             }
@@ -242,11 +317,113 @@ impl ResolutionGraph {
         Ok(Self {
             petgraph,
             hashes,
+            versions,
+            files,
+            markers,
+            dependency_markers,
             editables,
             diagnostics,
         })
     }
 
+    /// Merge the graphs resolved for each fork into a single graph, given the markers under which
+    /// each fork applies.
+    ///
+    /// A package that was resolved to the same distribution under the same markers in every fork
+    /// retains those markers; otherwise, each distribution is marked with the union of the markers
+    /// of the forks in which it was selected.
+    pub(crate) fn from_forks(forks: Vec<(Option<MarkerTree>, Self)>) -> Self {
+        let fork_count = forks.len();
+        let mut petgraph = petgraph::graph::Graph::new();
+        let mut hashes = FxHashMap::default();
+        let mut versions = FxHashMap::default();
+        let mut files = FxHashMap::default();
+        let mut dependency_markers = FxHashMap::default();
+        let mut editables = Editables::default();
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+        // For every distribution, the markers of each fork in which it was selected, along with
+        // the markers under which it was required within that fork.
+        let mut occurrences: FxHashMap<PackageId, Vec<(Option<MarkerTree>, Option<MarkerTree>)>> =
+            FxHashMap::default();
+        let mut inverse = FxHashMap::default();
+
+        for (fork_markers, graph) in forks {
+            let mut indices = FxHashMap::default();
+            for index in graph.petgraph.node_indices() {
+                let dist = &graph.petgraph[index];
+                let package_id = dist.package_id();
+                let merged = *inverse
+                    .entry(package_id.clone())
+                    .or_insert_with(|| petgraph.add_node(dist.clone()));
+                indices.insert(index, merged);
+                occurrences.entry(package_id.clone()).or_default().push((
+                    fork_markers.clone(),
+                    graph.markers.get(&package_id).cloned(),
+                ));
+            }
+            for edge in graph.petgraph.edge_references() {
+                petgraph.update_edge(
+                    indices[&edge.source()],
+                    indices[&edge.target()],
+                    edge.weight().clone(),
+                );
+            }
+            hashes.extend(graph.hashes);
+            versions.extend(graph.versions);
+            files.extend(graph.files);
+            dependency_markers.extend(graph.dependency_markers);
+            for diagnostic in graph.diagnostics {
+                if !diagnostics
+                    .iter()
+                    .any(|existing| existing.message() == diagnostic.message())
+                {
+                    diagnostics.push(diagnostic);
+                }
+            }
+            editables = graph.editables;
+        }
+
+        let mut markers = FxHashMap::default();
+        for (package_id, occurrences) in occurrences {
+            let marker = if occurrences.len() == fork_count
+                && occurrences.iter().map(|(_, marker)| marker).all_equal()
+            {
+                // The forks are exhaustive, so the fork markers can be dropped.
+                occurrences
+                    .into_iter()
+                    .next()
+                    .and_then(|(_, marker)| marker)
+            } else {
+                occurrences
+                    .into_iter()
+                    .map(|(fork_markers, marker)| match (fork_markers, marker) {
+                        (Some(left), Some(right)) => Some(left.and(right)),
+                        (left, right) => left.or(right),
+                    })
+                    .reduce(|left, right| match (left, right) {
+                        (Some(left), Some(right)) => Some(left.or(right)),
+                        _ => None,
+                    })
+                    .flatten()
+            };
+            if let Some(marker) = marker {
+                markers.insert(package_id, marker);
+            }
+        }
+
+        Self {
+            petgraph,
+            hashes,
+            versions,
+            files,
+            markers,
+            dependency_markers,
+            editables,
+            diagnostics,
+        }
+    }
+
     /// Return the number of packages in the graph.
     pub fn len(&self) -> usize {
         self.petgraph.node_count()
@@ -273,6 +450,49 @@ impl ResolutionGraph {
     pub fn petgraph(&self) -> &petgraph::graph::Graph<Dist, Range<Version>, petgraph::Directed> {
         &self.petgraph
     }
+
+    /// Return the resolved version of the given distribution, if any.
+    pub fn version(&self, package_id: &PackageId) -> Option<&Version> {
+        self.versions.get(package_id)
+    }
+
+    /// Return every available file for the given distribution, including those that weren't
+    /// selected.
+    pub fn files(&self, package_id: &PackageId) -> &[Dist] {
+        self.files.get(package_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Return the markers under which the given distribution is required, if it's only required
+    /// conditionally.
+    pub fn marker(&self, package_id: &PackageId) -> Option<&MarkerTree> {
+        self.markers.get(package_id)
+    }
+
+    /// Return the markers under which `dependency` is required by `dependent`, if it's only
+    /// required conditionally.
+    pub fn dependency_marker(
+        &self,
+        dependent: &PackageId,
+        dependency: &PackageId,
+    ) -> Option<&MarkerTree> {
+        self.dependency_markers
+            .get(&(dependent.clone(), dependency.clone()))
+    }
+}
+
+/// Return the markers under which `metadata` requires the `dependency`, or `None` if it's
+/// required unconditionally.
+fn requirement_markers(metadata: &Metadata21, dependency: &PackageName) -> Option<MarkerTree> {
+    metadata
+        .requires_dist
+        .iter()
+        .filter(|requirement| requirement.name == *dependency)
+        .map(|requirement| requirement.marker.clone())
+        .reduce(|left, right| match (left, right) {
+            (Some(left), Some(right)) => Some(left.or(right)),
+            _ => None,
+        })
+        .flatten()
 }
 
 /// A [`std::fmt::Display`] implementation for the resolution graph.
@@ -290,11 +510,20 @@ pub struct DisplayResolutionGraph<'a> {
     /// The style of annotation comments, used to indicate the dependencies that requested each
     /// package.
     annotation_style: AnnotationStyle,
+    /// The packages that are pinned to a specific index, to annotate with their provenance.
+    package_indexes: &'a [PackageIndex],
 }
 
 impl<'a> From<&'a ResolutionGraph> for DisplayResolutionGraph<'a> {
     fn from(resolution: &'a ResolutionGraph) -> Self {
-        Self::new(resolution, &[], false, true, AnnotationStyle::default())
+        Self::new(
+            resolution,
+            &[],
+            false,
+            true,
+            AnnotationStyle::default(),
+            &[],
+        )
     }
 }
 
@@ -306,6 +535,7 @@ impl<'a> DisplayResolutionGraph<'a> {
         show_hashes: bool,
         include_annotations: bool,
         annotation_style: AnnotationStyle,
+        package_indexes: &'a [PackageIndex],
     ) -> DisplayResolutionGraph<'a> {
         Self {
             resolution: underlying,
@@ -313,6 +543,7 @@ impl<'a> DisplayResolutionGraph<'a> {
             show_hashes,
             include_annotations,
             annotation_style,
+            package_indexes,
         }
     }
 }
@@ -386,13 +617,22 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                 Node::Editable(_, editable) => format!("-e {}", editable.verbatim()),
             };
 
+            // Display the markers under which the distribution is required, if any.
+            let package_id = self.resolution.petgraph[index].package_id();
+            if let Node::Distribution(..) = node {
+                if let Some(marker) = self.resolution.markers.get(&package_id) {
+                    line.push_str(" ; ");
+                    line.push_str(&marker.to_string());
+                }
+            }
+
             // Display the distribution hashes, if any.
             let mut has_hashes = false;
             if self.show_hashes {
                 if let Some(hashes) = self
                     .resolution
                     .hashes
-                    .get(node.name())
+                    .get(&package_id)
                     .filter(|hashes| !hashes.is_empty())
                 {
                     for hash in hashes {
@@ -419,16 +659,50 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                     .collect::<Vec<_>>();
                 edges.sort_unstable_by_key(|package| package.name());
 
+                // Display the reason the package was yanked, if it was selected anyway.
+                let yanked = match node {
+                    Node::Distribution(_, dist) => dist
+                        .file()
+                        .and_then(|file| file.yanked.as_ref())
+                        .filter(|yanked| yanked.is_yanked())
+                        .map(|yanked| match yanked.reason() {
+                            Some(reason) => format!("yanked: {reason}"),
+                            None => "yanked".to_string(),
+                        }),
+                    Node::Editable(..) => None,
+                };
+
+                // Display the index to which the package is pinned, if any.
+                let pinned = match node {
+                    Node::Distribution(name, _) => self
+                        .package_indexes
+                        .iter()
+                        .rev()
+                        .find(|package_index| package_index.package == *name)
+                        .map(|package_index| package_index.index.redacted()),
+                    Node::Editable(..) => None,
+                };
+
                 match self.annotation_style {
                     AnnotationStyle::Line => {
+                        let mut comments = Vec::new();
                         if !edges.is_empty() {
-                            let separator = if has_hashes { "\n    " } else { "  " };
                             let deps = edges
                                 .into_iter()
                                 .map(|dependency| dependency.name().to_string())
                                 .collect::<Vec<_>>()
                                 .join(", ");
-                            let comment = format!("# via {deps}").green().to_string();
+                            comments.push(format!("via {deps}"));
+                        }
+                        if let Some(index) = pinned.as_ref() {
+                            comments.push(format!("from {index}"));
+                        }
+                        if let Some(yanked) = yanked.as_ref() {
+                            comments.push(yanked.clone());
+                        }
+                        if !comments.is_empty() {
+                            let separator = if has_hashes { "\n    " } else { "  " };
+                            let comment = format!("# {}", comments.join("; ")).green().to_string();
                             annotation = Some((separator, comment));
                         }
                     }
@@ -451,6 +725,25 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                         }
                     },
                 }
+
+                if self.annotation_style == AnnotationStyle::Split {
+                    if let Some(index) = pinned {
+                        let from = format!("    # from {index}").green().to_string();
+                        annotation = Some(match annotation {
+                            Some((separator, comment)) => (separator, format!("{comment}\n{from}")),
+                            None => ("\n", from),
+                        });
+                    }
+                    if let Some(yanked) = yanked {
+                        let yanked = format!("    # {yanked}").green().to_string();
+                        annotation = Some(match annotation {
+                            Some((separator, comment)) => {
+                                (separator, format!("{comment}\n{yanked}"))
+                            }
+                            None => ("\n", yanked),
+                        });
+                    }
+                }
             }
 
             if let Some((separator, comment)) = annotation {
@@ -495,6 +788,13 @@ pub enum Diagnostic {
         /// The extra that was requested. For example, `colorama` in `black[colorama]`.
         extra: ExtraName,
     },
+    YankedVersion {
+        /// The distribution that was selected despite being yanked. For example,
+        /// `attrs==21.1.0`.
+        dist: Dist,
+        /// The reason the distribution was yanked, if provided by the index.
+        reason: Option<String>,
+    },
 }
 
 impl Diagnostic {
@@ -504,6 +804,10 @@ impl Diagnostic {
             Self::MissingExtra { dist, extra } => {
                 format!("The package `{dist}` does not have an extra named `{extra}`.")
             }
+            Self::YankedVersion { dist, reason } => match reason {
+                Some(reason) => format!("`{dist}` is yanked (reason: \"{reason}\")."),
+                None => format!("`{dist}` is yanked."),
+            },
         }
     }
 
@@ -511,6 +815,7 @@ impl Diagnostic {
     pub fn includes(&self, name: &PackageName) -> bool {
         match self {
             Self::MissingExtra { dist, .. } => name == dist.name(),
+            Self::YankedVersion { dist, .. } => name == dist.name(),
         }
     }
 }
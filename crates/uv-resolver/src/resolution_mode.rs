@@ -5,7 +5,8 @@ use uv_normalize::PackageName;
 
 use crate::Manifest;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum ResolutionMode {
     /// Resolve the highest compatible version of each package.
@@ -35,7 +36,7 @@ impl ResolutionStrategy {
     pub(crate) fn from_mode(
         mode: ResolutionMode,
         manifest: &Manifest,
-        markers: &MarkerEnvironment,
+        markers: Option<&MarkerEnvironment>,
     ) -> Self {
         match mode {
             ResolutionMode::Highest => Self::Highest,
@@ -45,10 +46,10 @@ impl ResolutionStrategy {
                 manifest
                     .requirements
                     .iter()
-                    .filter(|requirement| requirement.evaluate_markers(markers, &[]))
+                    .filter(|requirement| requirement.evaluate_optional_environment(markers, &[]))
                     .chain(manifest.editables.iter().flat_map(|(editable, metadata)| {
                         metadata.requires_dist.iter().filter(|requirement| {
-                            requirement.evaluate_markers(markers, &editable.extras)
+                            requirement.evaluate_optional_environment(markers, &editable.extras)
                         })
                     }))
                     .map(|requirement| requirement.name.clone())
@@ -11,6 +11,7 @@ use itertools::Itertools;
 use pubgrub::error::PubGrubError;
 use pubgrub::range::Range;
 use pubgrub::solver::{Incompatibility, State};
+use pubgrub::type_aliases::SelectedDependencies;
 use rustc_hash::{FxHashMap, FxHashSet};
 use tokio::select;
 use tokio_stream::wrappers::ReceiverStream;
@@ -23,15 +24,14 @@ use distribution_types::{
     VersionOrUrl,
 };
 use pep440_rs::{Version, VersionSpecifiers, MIN_VERSION};
-use pep508_rs::{MarkerEnvironment, Requirement};
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement};
 use platform_tags::{IncompatibleTag, Tags};
 use pypi_types::{Metadata21, Yanked};
 pub(crate) use urls::Urls;
 use uv_client::{FlatIndex, RegistryClient};
 use uv_distribution::DistributionDatabase;
-use uv_interpreter::Interpreter;
 use uv_normalize::PackageName;
-use uv_traits::BuildContext;
+use uv_traits::{BuildContext, Concurrency, RequiredHashes};
 
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::constraints::Constraints;
@@ -41,8 +41,8 @@ use crate::manifest::Manifest;
 use crate::overrides::Overrides;
 use crate::pins::FilePins;
 use crate::pubgrub::{
-    PubGrubDependencies, PubGrubDistribution, PubGrubPackage, PubGrubPriorities, PubGrubPython,
-    PubGrubSpecifier,
+    PubGrubDependencies, PubGrubDependency, PubGrubDistribution, PubGrubFork, PubGrubPackage,
+    PubGrubPriorities, PubGrubPriority, PubGrubPython, PubGrubSpecifier,
 };
 use crate::python_requirement::PythonRequirement;
 use crate::resolution::ResolutionGraph;
@@ -91,6 +91,185 @@ enum ResolverVersion {
     Unavailable(Version, UnavailableVersion),
 }
 
+/// The state of a single fork of the resolution.
+///
+/// Resolution begins with a single fork. If a package declares conflicting requirements under
+/// disjoint markers, the fork is split, and each resulting fork is solved independently.
+#[derive(Clone)]
+struct ForkState {
+    /// The `PubGrub` solver state for this fork.
+    pubgrub: State<PubGrubPackage, Range<Version>, PubGrubPriority>,
+    /// The next package on which to run unit propagation.
+    next: PubGrubPackage,
+    /// The files pinned for each selected package version.
+    pins: FilePins,
+    /// The priorities of the packages visited in this fork.
+    priorities: PubGrubPriorities,
+    /// The package versions whose dependencies have been added to the solver state.
+    added_dependencies: FxHashMap<PubGrubPackage, FxHashSet<Version>>,
+    /// The dependencies of each package version, along with the markers under which each
+    /// dependency applies.
+    dependencies: FxHashMap<(PubGrubPackage, Version), Vec<(PubGrubPackage, Option<MarkerTree>)>>,
+    /// The markers under which this fork applies, or `None` if it applies unconditionally.
+    markers: Option<MarkerTree>,
+}
+
+impl ForkState {
+    fn new(
+        pubgrub: State<PubGrubPackage, Range<Version>, PubGrubPriority>,
+        root: PubGrubPackage,
+    ) -> Self {
+        Self {
+            pubgrub,
+            next: root,
+            pins: FilePins::default(),
+            priorities: PubGrubPriorities::default(),
+            added_dependencies: FxHashMap::default(),
+            dependencies: FxHashMap::default(),
+            markers: None,
+        }
+    }
+
+    /// Add the dependencies of a package version to the solver state, and add the version to the
+    /// partial solution.
+    fn add_dependencies(
+        &mut self,
+        package: &PubGrubPackage,
+        version: Version,
+        dependencies: Vec<PubGrubDependency>,
+    ) {
+        self.dependencies.insert(
+            (package.clone(), version.clone()),
+            dependencies
+                .iter()
+                .map(|dependency| (dependency.package.clone(), dependency.marker.clone()))
+                .collect(),
+        );
+
+        // Add that package and version if the dependencies are not problematic.
+        let dep_incompats = self.pubgrub.add_incompatibility_from_dependencies(
+            package.clone(),
+            version.clone(),
+            dependencies
+                .into_iter()
+                .map(|dependency| (dependency.package, dependency.version))
+                .collect::<Vec<_>>(),
+        );
+
+        self.pubgrub.partial_solution.add_version(
+            package.clone(),
+            version,
+            dep_incompats,
+            &self.pubgrub.incompatibility_store,
+        );
+    }
+
+    /// Add the dependencies of a package version to the solver state, splitting this fork into one
+    /// fork per set of dependencies (see [`PubGrubDependencies::fork`]).
+    ///
+    /// If there are no forks (i.e., the dependencies can't apply in any environment covered by this
+    /// fork), the version is marked as unavailable, such that the solver backtracks, rather than
+    /// the fork being dropped.
+    fn fork(
+        mut self,
+        package: &PubGrubPackage,
+        version: Version,
+        mut forks: Vec<PubGrubFork>,
+    ) -> Vec<Self> {
+        match forks.len() {
+            0 => {
+                self.pubgrub
+                    .add_incompatibility(Incompatibility::unavailable(
+                        package.clone(),
+                        version,
+                        "its dependencies don't apply in any environment".to_string(),
+                    ));
+                vec![self]
+            }
+            1 => {
+                let fork = forks.pop().expect("a single fork");
+                self.add_dependencies(package, version, fork.dependencies);
+                vec![self]
+            }
+            _ => forks
+                .into_iter()
+                .map(|fork| {
+                    let mut forked = self.clone();
+                    forked.markers = fork.markers;
+                    forked.add_dependencies(package, version.clone(), fork.dependencies);
+                    forked
+                })
+                .collect(),
+        }
+    }
+
+    /// Determine the markers under which each selected package is required, by propagating the
+    /// markers on each dependency edge from the root.
+    ///
+    /// Packages that are required unconditionally are omitted from the returned map.
+    fn node_markers(
+        &self,
+        selection: &SelectedDependencies<PubGrubPackage, Version>,
+    ) -> FxHashMap<PackageName, MarkerTree> {
+        // A value of `None` indicates that the package is required unconditionally.
+        let mut reach: FxHashMap<&PubGrubPackage, Option<MarkerTree>> = FxHashMap::default();
+        let mut queue = Vec::new();
+        if let Some(root) = selection
+            .keys()
+            .find(|package| matches!(package, PubGrubPackage::Root(_)))
+        {
+            reach.insert(root, None);
+            queue.push(root);
+        }
+
+        while let Some(parent) = queue.pop() {
+            let Some(version) = selection.get(parent) else {
+                continue;
+            };
+            let Some(edges) = self.dependencies.get(&(parent.clone(), version.clone())) else {
+                continue;
+            };
+            let parent_marker = reach[parent].clone();
+            for (child, marker) in edges {
+                // The dependency applies wherever both the parent and the edge apply.
+                let marker = match (parent_marker.clone(), marker.clone()) {
+                    (Some(left), Some(right)) => Some(left.and(right)),
+                    (left, right) => left.or(right),
+                };
+                let updated = match reach.get(child) {
+                    None => marker,
+                    Some(None) => continue,
+                    Some(Some(existing)) => marker.map(|marker| existing.clone().or(marker)),
+                };
+                if reach.get(child) != Some(&updated) {
+                    reach.insert(child, updated);
+                    queue.push(child);
+                }
+            }
+        }
+
+        // Combine the markers for each package across its extras.
+        let mut markers: FxHashMap<PackageName, Option<MarkerTree>> = FxHashMap::default();
+        for (package, marker) in reach {
+            let PubGrubPackage::Package(package_name, ..) = package else {
+                continue;
+            };
+            if let Some(existing) = markers.get_mut(package_name) {
+                *existing = match (existing.take(), marker) {
+                    (Some(left), Some(right)) => Some(left.or(right)),
+                    _ => None,
+                };
+            } else {
+                markers.insert(package_name.clone(), marker);
+            }
+        }
+        markers
+            .into_iter()
+            .filter_map(|(package_name, marker)| Some((package_name, marker?)))
+            .collect()
+    }
+}
+
 pub struct Resolver<'a, Provider: ResolverProvider> {
     project: Option<PackageName>,
     requirements: Vec<Requirement>,
@@ -100,7 +279,8 @@ pub struct Resolver<'a, Provider: ResolverProvider> {
     allowed_yanks: AllowedYanks,
     urls: Urls,
     dependency_mode: DependencyMode,
-    markers: &'a MarkerEnvironment,
+    /// The environment for which to resolve, or `None` to resolve for all environments.
+    markers: Option<&'a MarkerEnvironment>,
     python_requirement: PythonRequirement,
     selector: CandidateSelector,
     index: &'a InMemoryIndex,
@@ -110,18 +290,25 @@ pub struct Resolver<'a, Provider: ResolverProvider> {
     visited: DashSet<PackageName>,
     reporter: Option<Arc<dyn Reporter>>,
     provider: Provider,
+    /// The maximum number of concurrent metadata fetches.
+    concurrency: usize,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> Resolver<'a, DefaultResolverProvider<'a, Context>> {
     /// Initialize a new resolver using the default backend doing real requests.
     ///
+    /// If `markers` is `None`, the resolver will perform a universal resolution, producing a
+    /// resolution that is valid across all environments (subject to the given
+    /// [`PythonRequirement`]). In that case, the `tags` are only used for building source
+    /// distributions.
+    ///
     /// Reads the flat index entries.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         manifest: Manifest,
         options: Options,
-        markers: &'a MarkerEnvironment,
-        interpreter: &'a Interpreter,
+        markers: Option<&'a MarkerEnvironment>,
+        python_requirement: PythonRequirement,
         tags: &'a Tags,
         client: &'a RegistryClient,
         flat_index: &'a FlatIndex,
@@ -132,19 +319,33 @@ impl<'a, Context: BuildContext + Send + Sync> Resolver<'a, DefaultResolverProvid
             client,
             DistributionDatabase::new(build_context.cache(), tags, client, build_context),
             flat_index,
-            tags,
-            PythonRequirement::new(interpreter, markers),
+            markers.map(|_| tags),
+            python_requirement.clone(),
             options.exclude_newer,
             build_context.no_binary(),
         );
-        Self::new_custom_io(
+        let mut resolver = Self::new_custom_io(
             manifest,
             options,
             markers,
-            PythonRequirement::new(interpreter, markers),
+            python_requirement,
             index,
             provider,
-        )
+        )?;
+        resolver.concurrency = build_context.concurrency().downloads;
+        Ok(resolver)
+    }
+
+    /// Set the hashes that source distributions are required to match before they're built.
+    ///
+    /// In `--require-hashes` mode, source distributions without any hashes are rejected rather
+    /// than built.
+    #[must_use]
+    pub fn with_hashes(self, hashes: &'a RequiredHashes) -> Self {
+        Self {
+            provider: self.provider.with_hashes(hashes),
+            ..self
+        }
     }
 }
 
@@ -153,7 +354,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     pub fn new_custom_io(
         manifest: Manifest,
         options: Options,
-        markers: &'a MarkerEnvironment,
+        markers: Option<&'a MarkerEnvironment>,
         python_requirement: PythonRequirement,
         index: &'a InMemoryIndex,
         provider: Provider,
@@ -175,6 +376,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             python_requirement,
             reporter: None,
             provider,
+            concurrency: Concurrency::DEFAULT_DOWNLOADS,
         })
     }
 
@@ -194,7 +396,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         // A channel to fetch package metadata (e.g., given `flask`, fetch all versions) and version
         // metadata (e.g., given `flask==1.0.0`, fetch the metadata for that version).
         // Channel size is set to the same size as the task buffer for simplicity.
-        let (request_sink, request_stream) = tokio::sync::mpsc::channel(50);
+        let (request_sink, request_stream) = tokio::sync::mpsc::channel(self.concurrency);
 
         // Run the fetcher.
         let requests_fut = self.fetch(request_stream).fuse();
@@ -239,228 +441,255 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     ) -> Result<ResolutionGraph, ResolveError> {
         let root = PubGrubPackage::Root(self.project.clone());
 
-        // Keep track of the packages for which we've requested metadata.
-        let mut pins = FilePins::default();
-        let mut priorities = PubGrubPriorities::default();
-
         // Start the solve.
-        let mut state = State::init(root.clone(), MIN_VERSION.clone());
-        let mut added_dependencies: FxHashMap<PubGrubPackage, FxHashSet<Version>> =
-            FxHashMap::default();
-        let mut next = root;
+        let mut forks = vec![ForkState::new(
+            State::init(root.clone(), MIN_VERSION.clone()),
+            root,
+        )];
+        let mut resolutions = Vec::new();
 
         debug!(
             "Solving with target Python version {}",
             self.python_requirement.target()
         );
 
-        loop {
-            // Run unit propagation.
-            state.unit_propagation(next)?;
+        'FORK: while let Some(mut state) = forks.pop() {
+            if let Some(markers) = state.markers.as_ref() {
+                debug!("Solving fork: {markers}");
+            }
 
-            // Pre-visit all candidate packages, to allow metadata to be fetched in parallel.
-            Self::pre_visit(state.partial_solution.prioritized_packages(), request_sink).await?;
+            loop {
+                // Run unit propagation.
+                state.pubgrub.unit_propagation(state.next.clone())?;
 
-            // Choose a package version.
-            let Some(highest_priority_pkg) =
-                state
-                    .partial_solution
-                    .pick_highest_priority_pkg(|package, _range| {
-                        priorities.get(package).unwrap_or_default()
-                    })
-            else {
-                let selection = state.partial_solution.extract_solution();
-                return ResolutionGraph::from_state(
-                    &selection,
-                    &pins,
-                    &self.index.packages,
-                    &self.index.distributions,
-                    &self.index.redirects,
-                    &state,
-                    self.editables.clone(),
-                );
-            };
-            next = highest_priority_pkg;
-
-            let term_intersection = state
-                .partial_solution
-                .term_intersection_for_package(&next)
-                .ok_or_else(|| {
-                    PubGrubError::Failure("a package was chosen but we don't have a term.".into())
-                })?;
-            let decision = self
-                .choose_version(
-                    &next,
-                    term_intersection.unwrap_positive(),
-                    &mut pins,
+                // Pre-visit all candidate packages, to allow metadata to be fetched in parallel.
+                Self::pre_visit(
+                    state.pubgrub.partial_solution.prioritized_packages(),
                     request_sink,
                 )
                 .await?;
 
-            // Pick the next compatible version.
-            let version = match decision {
-                None => {
-                    debug!("No compatible version found for: {next}");
+                // Choose a package version.
+                let Some(highest_priority_pkg) = state
+                    .pubgrub
+                    .partial_solution
+                    .pick_highest_priority_pkg(|package, _range| {
+                        state.priorities.get(package).unwrap_or_default()
+                    })
+                else {
+                    let selection = state.pubgrub.partial_solution.extract_solution();
+                    let resolution = ResolutionGraph::from_state(
+                        &selection,
+                        &state.pins,
+                        &self.index.packages,
+                        &self.index.distributions,
+                        &self.index.redirects,
+                        &state.pubgrub,
+                        self.editables.clone(),
+                        &state.node_markers(&selection),
+                    )?;
+                    resolutions.push((state.markers, resolution));
+                    continue 'FORK;
+                };
+                state.next = highest_priority_pkg;
+                let next = state.next.clone();
 
-                    let term_intersection = state
-                        .partial_solution
-                        .term_intersection_for_package(&next)
-                        .expect("a package was chosen but we don't have a term.");
-
-                    let reason = {
-                        if let PubGrubPackage::Package(ref package_name, _, _) = next {
-                            // Check if the decision was due to the package being unavailable
-                            self.unavailable_packages
-                                .get(package_name)
-                                .map(|entry| match *entry {
-                                    UnavailablePackage::NoIndex => {
-                                        "was not found in the provided package locations"
-                                    }
-                                    UnavailablePackage::Offline => "was not found in the cache",
-                                    UnavailablePackage::NotFound => {
-                                        "was not found in the package registry"
-                                    }
-                                })
-                        } else {
-                            None
-                        }
-                    };
+                let term_intersection = state
+                    .pubgrub
+                    .partial_solution
+                    .term_intersection_for_package(&next)
+                    .ok_or_else(|| {
+                        PubGrubError::Failure(
+                            "a package was chosen but we don't have a term.".into(),
+                        )
+                    })?;
+                let decision = self
+                    .choose_version(
+                        &next,
+                        term_intersection.unwrap_positive(),
+                        &mut state.pins,
+                        request_sink,
+                    )
+                    .await?;
+
+                // Pick the next compatible version.
+                let version = match decision {
+                    None => {
+                        debug!("No compatible version found for: {next}");
+
+                        let term_intersection = state
+                            .pubgrub
+                            .partial_solution
+                            .term_intersection_for_package(&next)
+                            .expect("a package was chosen but we don't have a term.");
+
+                        let reason = {
+                            if let PubGrubPackage::Package(ref package_name, _, _) = next {
+                                // Check if the decision was due to the package being unavailable
+                                self.unavailable_packages.get(package_name).map(
+                                    |entry| match *entry {
+                                        UnavailablePackage::NoIndex => {
+                                            "was not found in the provided package locations"
+                                        }
+                                        UnavailablePackage::Offline => "was not found in the cache",
+                                        UnavailablePackage::NotFound => {
+                                            "was not found in the package registry"
+                                        }
+                                    },
+                                )
+                            } else {
+                                None
+                            }
+                        };
 
-                    let inc = Incompatibility::no_versions(
-                        next.clone(),
-                        term_intersection.clone(),
-                        reason.map(ToString::to_string),
-                    );
+                        let inc = Incompatibility::no_versions(
+                            next.clone(),
+                            term_intersection.clone(),
+                            reason.map(ToString::to_string),
+                        );
 
-                    state.add_incompatibility(inc);
-                    continue;
-                }
-                Some(version) => version,
-            };
-            let version = match version {
-                ResolverVersion::Available(version) => version,
-                ResolverVersion::Unavailable(version, unavailable) => {
-                    let reason = match unavailable {
-                        UnavailableVersion::RequiresPython(requires_python) => {
-                            // Incompatible requires-python versions are special in that we track
-                            // them as incompatible dependencies instead of marking the package version
-                            // as unavailable directly
-                            let python_version = requires_python
-                                .iter()
-                                .map(PubGrubSpecifier::try_from)
-                                .fold_ok(Range::full(), |range, specifier| {
-                                    range.intersection(&specifier.into())
-                                })?;
-
-                            let package = &next;
-                            for kind in [PubGrubPython::Installed, PubGrubPython::Target] {
-                                state.add_incompatibility(Incompatibility::from_dependency(
-                                    package.clone(),
-                                    Range::singleton(version.clone()),
-                                    (PubGrubPackage::Python(kind), python_version.clone()),
-                                ));
+                        state.pubgrub.add_incompatibility(inc);
+                        continue;
+                    }
+                    Some(version) => version,
+                };
+                let version = match version {
+                    ResolverVersion::Available(version) => version,
+                    ResolverVersion::Unavailable(version, unavailable) => {
+                        let reason = match unavailable {
+                            UnavailableVersion::RequiresPython(requires_python) => {
+                                // Incompatible requires-python versions are special in that we track
+                                // them as incompatible dependencies instead of marking the package version
+                                // as unavailable directly
+                                let python_version = requires_python
+                                    .iter()
+                                    .map(PubGrubSpecifier::try_from)
+                                    .fold_ok(Range::full(), |range, specifier| {
+                                        range.intersection(&specifier.into())
+                                    })?;
+
+                                let package = &next;
+                                for kind in [PubGrubPython::Installed, PubGrubPython::Target] {
+                                    state.pubgrub.add_incompatibility(
+                                        Incompatibility::from_dependency(
+                                            package.clone(),
+                                            Range::singleton(version.clone()),
+                                            (PubGrubPackage::Python(kind), python_version.clone()),
+                                        ),
+                                    );
+                                }
+                                state
+                                    .pubgrub
+                                    .partial_solution
+                                    .add_decision(next.clone(), version);
+                                continue;
                             }
-                            state.partial_solution.add_decision(next.clone(), version);
-                            continue;
-                        }
-                        UnavailableVersion::Yanked(yanked) => match yanked {
-                            Yanked::Bool(_) => "it was yanked".to_string(),
-                            Yanked::Reason(reason) => format!(
-                                "it was yanked (reason: {})",
-                                reason.trim().trim_end_matches('.')
-                            ),
-                        },
-                        UnavailableVersion::NoDistributions(best_incompatible) => {
-                            if let Some(best_incompatible) = best_incompatible {
-                                match best_incompatible {
-                                    IncompatibleWheel::NoBinary => "no source distribution is available and using wheels is disabled".to_string(),
-                                    IncompatibleWheel::RequiresPython => "no wheels are available that meet your required Python version".to_string(),
-                                    IncompatibleWheel::Tag(tag) => {
-                                        match tag {
-                                            IncompatibleTag::Invalid => "no wheels are available with valid tags".to_string(),
-                                            IncompatibleTag::Python => "no wheels are available with a matching Python implementation".to_string(),
-                                            IncompatibleTag::Abi => "no wheels are available with a matching Python ABI".to_string(),
-                                            IncompatibleTag::Platform => "no wheels are available with a matching platform".to_string(),
+                            UnavailableVersion::Yanked(yanked) => match yanked {
+                                Yanked::Bool(_) => "it was yanked".to_string(),
+                                Yanked::Reason(reason) => format!(
+                                    "it was yanked (reason: {})",
+                                    reason.trim().trim_end_matches('.')
+                                ),
+                            },
+                            UnavailableVersion::NoDistributions(best_incompatible) => {
+                                if let Some(best_incompatible) = best_incompatible {
+                                    match best_incompatible {
+                                        IncompatibleWheel::NoBinary => "no source distribution is available and using wheels is disabled".to_string(),
+                                        IncompatibleWheel::RequiresPython => "no wheels are available that meet your required Python version".to_string(),
+                                        IncompatibleWheel::Tag(tag) => {
+                                            match tag {
+                                                IncompatibleTag::Invalid => "no wheels are available with valid tags".to_string(),
+                                                IncompatibleTag::Python => "no wheels are available with a matching Python implementation".to_string(),
+                                                IncompatibleTag::Abi => "no wheels are available with a matching Python ABI".to_string(),
+                                                IncompatibleTag::Platform => "no wheels are available with a matching platform".to_string(),
+                                            }
                                         }
                                     }
+                                } else {
+                                    // TODO(zanieb): It's unclear why we would encounter this case still
+                                    "no wheels are available for your system".to_string()
                                 }
-                            } else {
-                                // TODO(zanieb): It's unclear why we would encounter this case still
-                                "no wheels are available for your system".to_string()
-                            }
-                        }
-                    };
-                    state.add_incompatibility(Incompatibility::unavailable(
-                        next.clone(),
-                        version.clone(),
-                        reason,
-                    ));
-                    continue;
-                }
-            };
-
-            self.on_progress(&next, &version);
-
-            if added_dependencies
-                .entry(next.clone())
-                .or_default()
-                .insert(version.clone())
-            {
-                // Retrieve that package dependencies.
-                let package = &next;
-                let dependencies = match self
-                    .get_dependencies(package, &version, &mut priorities, request_sink)
-                    .await?
-                {
-                    Dependencies::Unavailable(reason) => {
-                        let message = {
-                            if matches!(package, PubGrubPackage::Root(_)) {
-                                // Including front-matter for the root package is redundant
-                                reason.clone()
-                            } else {
-                                format!("its dependencies are unusable because {reason}")
                             }
                         };
-                        state.add_incompatibility(Incompatibility::unavailable(
-                            package.clone(),
-                            version.clone(),
-                            message,
-                        ));
+                        state
+                            .pubgrub
+                            .add_incompatibility(Incompatibility::unavailable(
+                                next.clone(),
+                                version.clone(),
+                                reason,
+                            ));
                         continue;
                     }
-                    Dependencies::Available(constraints)
-                        if constraints
-                            .iter()
-                            .any(|(dependency, _)| dependency == package) =>
-                    {
-                        return Err(PubGrubError::SelfDependency {
-                            package: package.clone(),
-                            version: version.clone(),
-                        }
-                        .into());
-                    }
-                    Dependencies::Available(constraints) => constraints,
                 };
 
-                // Add that package and version if the dependencies are not problematic.
-                let dep_incompats = state.add_incompatibility_from_dependencies(
-                    package.clone(),
-                    version.clone(),
-                    dependencies,
-                );
+                self.on_progress(&next, &version);
 
-                state.partial_solution.add_version(
-                    package.clone(),
-                    version,
-                    dep_incompats,
-                    &state.incompatibility_store,
-                );
-            } else {
-                // `dep_incompats` are already in `incompatibilities` so we know there are not satisfied
-                // terms and can add the decision directly.
-                state.partial_solution.add_decision(next.clone(), version);
+                if state
+                    .added_dependencies
+                    .entry(next.clone())
+                    .or_default()
+                    .insert(version.clone())
+                {
+                    // Retrieve that package dependencies.
+                    let package = &next;
+                    let dependencies = match self
+                        .get_dependencies(package, &version, &mut state.priorities, request_sink)
+                        .await?
+                    {
+                        Dependencies::Unavailable(reason) => {
+                            let message = {
+                                if matches!(package, PubGrubPackage::Root(_)) {
+                                    // Including front-matter for the root package is redundant
+                                    reason.clone()
+                                } else {
+                                    format!("its dependencies are unusable because {reason}")
+                                }
+                            };
+                            state
+                                .pubgrub
+                                .add_incompatibility(Incompatibility::unavailable(
+                                    package.clone(),
+                                    version.clone(),
+                                    message,
+                                ));
+                            continue;
+                        }
+                        Dependencies::Available(constraints)
+                            if constraints
+                                .iter()
+                                .any(|(dependency, _)| dependency == package) =>
+                        {
+                            return Err(PubGrubError::SelfDependency {
+                                package: package.clone(),
+                                version: version.clone(),
+                            }
+                            .into());
+                        }
+                        Dependencies::Available(constraints) => constraints,
+                    };
+
+                    // If the package declares conflicting requirements under disjoint markers,
+                    // solve each set of requirements in its own fork.
+                    let dependencies = dependencies.fork(state.markers.as_ref());
+                    let mut forked = state.fork(package, version, dependencies);
+                    if forked.len() == 1 {
+                        state = forked.pop().expect("a single fork");
+                    } else {
+                        forks.extend(forked);
+                        continue 'FORK;
+                    }
+                } else {
+                    // `dep_incompats` are already in `incompatibilities` so we know there are not satisfied
+                    // terms and can add the decision directly.
+                    state
+                        .pubgrub
+                        .partial_solution
+                        .add_decision(next.clone(), version);
+                }
             }
         }
+
+        Ok(ResolutionGraph::from_forks(resolutions))
     }
 
     /// Visit a [`PubGrubPackage`] prior to selection. This should be called on a [`PubGrubPackage`]
@@ -663,7 +892,13 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         .allowed_yanks
                         .allowed(package_name, candidate.version())
                     {
-                        warn!("Allowing yanked version: {}", candidate.package_id());
+                        match dist.yanked().reason() {
+                            Some(reason) => warn!(
+                                "Allowing yanked version: {} (reason: {reason})",
+                                candidate.package_id()
+                            ),
+                            None => warn!("Allowing yanked version: {}", candidate.package_id()),
+                        }
                     } else {
                         return Ok(Some(ResolverVersion::Unavailable(
                             candidate.version().clone(),
@@ -775,10 +1010,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     }
                 }
 
-                Ok(Dependencies::Available(constraints.into()))
+                Ok(Dependencies::Available(constraints))
             }
 
-            PubGrubPackage::Python(_) => Ok(Dependencies::Available(Vec::default())),
+            PubGrubPackage::Python(_) => {
+                Ok(Dependencies::Available(PubGrubDependencies::default()))
+            }
 
             PubGrubPackage::Package(package_name, extra, url) => {
                 // If we're excluding transitive dependencies, short-circuit.
@@ -802,7 +1039,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                             .ok_or(ResolveError::Unregistered)?;
                     }
 
-                    return Ok(Dependencies::Available(Vec::default()));
+                    return Ok(Dependencies::Available(PubGrubDependencies::default()));
                 }
 
                 // Determine if the distribution is editable.
@@ -833,7 +1070,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         );
                     }
 
-                    return Ok(Dependencies::Available(constraints.into()));
+                    return Ok(Dependencies::Available(constraints));
                 }
 
                 // Determine the distribution to lookup.
@@ -889,7 +1126,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     );
                 }
 
-                Ok(Dependencies::Available(constraints.into()))
+                Ok(Dependencies::Available(constraints))
             }
         }
     }
@@ -901,7 +1138,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     ) -> Result<(), ResolveError> {
         let mut response_stream = ReceiverStream::new(request_stream)
             .map(|request| self.process_request(request).boxed())
-            .buffer_unordered(50);
+            .buffer_unordered(self.concurrency);
 
         while let Some(response) = response_stream.next().await {
             match response? {
@@ -1140,12 +1377,11 @@ enum Response {
 
 /// An enum used by [`DependencyProvider`] that holds information about package dependencies.
 /// For each [Package] there is a set of versions allowed as a dependency.
-#[derive(Clone)]
 enum Dependencies {
     /// Package dependencies are not available.
     Unavailable(String),
     /// Container for all available package versions.
-    Available(Vec<(PubGrubPackage, Range<Version>)>),
+    Available(PubGrubDependencies),
 }
 
 fn uncapitalize<T: AsRef<str>>(string: T) -> String {
@@ -1155,3 +1391,33 @@ fn uncapitalize<T: AsRef<str>>(string: T) -> String {
         Some(first) => first.to_lowercase().chain(chars).collect(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pubgrub::solver::State;
+
+    use pep440_rs::{Version, MIN_VERSION};
+    use uv_normalize::PackageName;
+
+    use crate::pubgrub::PubGrubPackage;
+
+    use super::ForkState;
+
+    /// If a package version's dependencies yield no forks, the fork is retained (with the version
+    /// marked as unavailable), rather than dropped from the resolution.
+    #[test]
+    fn fork_without_forks() {
+        let root = PubGrubPackage::Root(None);
+        let state = ForkState::new(State::init(root.clone(), MIN_VERSION.clone()), root);
+
+        let package = PubGrubPackage::Package(PackageName::from_str("anyio").unwrap(), None, None);
+        let version = Version::from_str("4.0.0").unwrap();
+        let forks = state.fork(&package, version.clone(), Vec::new());
+
+        assert_eq!(forks.len(), 1);
+        assert_eq!(forks[0].markers, None);
+        assert!(!forks[0].dependencies.contains_key(&(package, version)));
+    }
+}
@@ -10,7 +10,7 @@ use pypi_types::Metadata21;
 use uv_client::{FlatIndex, RegistryClient};
 use uv_distribution::DistributionDatabase;
 use uv_normalize::PackageName;
-use uv_traits::{BuildContext, NoBinary};
+use uv_traits::{BuildContext, NoBinary, RequiredHashes};
 
 use crate::python_requirement::PythonRequirement;
 use crate::version_map::VersionMap;
@@ -64,7 +64,9 @@ pub struct DefaultResolverProvider<'a, Context: BuildContext + Send + Sync> {
     client: RegistryClient,
     /// These are the entries from `--find-links` that act as overrides for index responses.
     flat_index: FlatIndex,
-    tags: Tags,
+    /// The compatibility tags for the target environment, or `None` if wheels should be
+    /// considered compatible with any environment (as in a universal resolution).
+    tags: Option<Tags>,
     python_requirement: PythonRequirement,
     exclude_newer: Option<DateTime<Utc>>,
     no_binary: NoBinary,
@@ -77,7 +79,7 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
         client: &'a RegistryClient,
         fetcher: DistributionDatabase<'a, Context>,
         flat_index: &'a FlatIndex,
-        tags: &'a Tags,
+        tags: Option<&'a Tags>,
         python_requirement: PythonRequirement,
         exclude_newer: Option<DateTime<Utc>>,
         no_binary: &'a NoBinary,
@@ -86,12 +88,21 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
             fetcher,
             client: client.clone(),
             flat_index: flat_index.clone(),
-            tags: tags.clone(),
+            tags: tags.cloned(),
             python_requirement,
             exclude_newer,
             no_binary: no_binary.clone(),
         }
     }
+
+    /// Set the hashes that source distributions are required to match before they're built.
+    #[must_use]
+    pub fn with_hashes(self, hashes: &'a RequiredHashes) -> Self {
+        Self {
+            fetcher: self.fetcher.with_hashes(hashes),
+            ..self
+        }
+    }
 }
 
 impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
@@ -104,29 +115,37 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
     ) -> PackageVersionsResult {
         let result = self.client.simple(package_name).await;
 
+        // A package that's pinned to an index is only ever fetched from that index, so skip any
+        // distributions from the `--find-links` entries.
+        let flat_index = if self.client.package_index(package_name).is_some() {
+            None
+        } else {
+            self.flat_index.get(package_name).cloned()
+        };
+
         // If the "Simple API" request was successful, convert to `VersionMap` on the Tokio
         // threadpool, since it can be slow.
         match result {
-            Ok((index, metadata)) => Ok(VersionsResponse::Found(VersionMap::from_metadata(
-                metadata,
+            Ok(results) => Ok(VersionsResponse::Found(VersionMap::from_metadata(
+                results,
                 package_name,
-                &index,
-                &self.tags,
+                self.client.index_strategy(),
+                self.tags.as_ref(),
                 &self.python_requirement,
                 self.exclude_newer.as_ref(),
-                self.flat_index.get(package_name).cloned(),
+                flat_index,
                 &self.no_binary,
             ))),
             Err(err) => match err.into_kind() {
                 uv_client::ErrorKind::PackageNotFound(_) => {
-                    if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
+                    if let Some(flat_index) = flat_index {
                         Ok(VersionsResponse::Found(VersionMap::from(flat_index)))
                     } else {
                         Ok(VersionsResponse::NotFound)
                     }
                 }
                 uv_client::ErrorKind::NoIndex(_) => {
-                    if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
+                    if let Some(flat_index) = flat_index {
                         Ok(VersionsResponse::Found(VersionMap::from(flat_index)))
                     } else if self.flat_index.offline() {
                         Ok(VersionsResponse::Offline)
@@ -135,7 +154,7 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
                     }
                 }
                 uv_client::ErrorKind::Offline(_) => {
-                    if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
+                    if let Some(flat_index) = flat_index {
                         Ok(VersionsResponse::Found(VersionMap::from(flat_index)))
                     } else {
                         Ok(VersionsResponse::Offline)
@@ -5,9 +5,11 @@ use chrono::{DateTime, Utc};
 use tracing::{instrument, warn};
 
 use distribution_filename::DistFilename;
-use distribution_types::{Dist, IncompatibleWheel, IndexUrl, PrioritizedDist, WheelCompatibility};
+use distribution_types::{
+    Dist, IncompatibleWheel, IndexStrategy, IndexUrl, PrioritizedDist, WheelCompatibility,
+};
 use pep440_rs::Version;
-use platform_tags::Tags;
+use platform_tags::{TagPriority, Tags};
 use pypi_types::Hashes;
 use rkyv::{de::deserializers::SharedDeserializeMap, Deserialize};
 use uv_client::{FlatDistributions, OwnedArchive, SimpleMetadata, VersionFiles};
@@ -25,13 +27,17 @@ pub struct VersionMap {
 
 impl VersionMap {
     /// Initialize a [`VersionMap`] from the given metadata.
+    ///
+    /// The metadata is provided in index order. When the same version is available from multiple
+    /// indexes, the [`IndexStrategy`] determines whether only the first index's files are used, or
+    /// whether the files from every index are merged.
     #[instrument(skip_all, fields(package_name))]
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_metadata(
-        simple_metadata: OwnedArchive<SimpleMetadata>,
+        simple_metadata: Vec<(IndexUrl, OwnedArchive<SimpleMetadata>)>,
         package_name: &PackageName,
-        index: &IndexUrl,
-        tags: &Tags,
+        index_strategy: IndexStrategy,
+        tags: Option<&Tags>,
         python_requirement: &PythonRequirement,
         exclude_newer: Option<&DateTime<Utc>>,
         flat_index: Option<FlatDistributions>,
@@ -41,18 +47,34 @@ impl VersionMap {
         // Create stubs for each entry in simple metadata. The full conversion
         // from a `VersionFiles` to a PrioritizedDist for each version
         // isn't done until that specific version is requested.
-        for (datum_index, datum) in simple_metadata.iter().enumerate() {
-            let version: Version = datum
-                .version
-                .deserialize(&mut SharedDeserializeMap::new())
-                .expect("archived version always deserializes");
-            map.insert(
-                version,
-                LazyPrioritizedDist::OnlySimple(SimplePrioritizedDist {
+        for (metadata_index, (_, metadata)) in simple_metadata.iter().enumerate() {
+            for (datum_index, datum) in metadata.iter().enumerate() {
+                let version: Version = datum
+                    .version
+                    .deserialize(&mut SharedDeserializeMap::new())
+                    .expect("archived version always deserializes");
+                let offset = SimpleDatumOffset {
+                    metadata_index,
                     datum_index,
-                    dist: OnceLock::new(),
-                }),
-            );
+                };
+                match map.entry(version) {
+                    Entry::Vacant(e) => {
+                        e.insert(LazyPrioritizedDist::OnlySimple(SimplePrioritizedDist {
+                            offsets: vec![offset],
+                            dist: OnceLock::new(),
+                        }));
+                    }
+                    // The version was already provided by an earlier index. Unless we're
+                    // merging across indexes, the earlier index takes precedence.
+                    Entry::Occupied(mut e) => {
+                        if index_strategy == IndexStrategy::UnsafeBestMatch {
+                            if let LazyPrioritizedDist::OnlySimple(simple_dist) = e.get_mut() {
+                                simple_dist.offsets.push(offset);
+                            }
+                        }
+                    }
+                }
+            }
         }
         // If a set of flat distributions have been given, we need to add those
         // to our map of entries as well.
@@ -90,8 +112,7 @@ impl VersionMap {
                 map,
                 simple_metadata,
                 no_binary,
-                index: index.clone(),
-                tags: tags.clone(),
+                tags: tags.cloned(),
                 python_requirement: python_requirement.clone(),
                 exclude_newer: exclude_newer.copied(),
             }),
@@ -161,6 +182,20 @@ impl VersionMap {
         }
     }
 
+    /// Return every [`Dist`] for the given version, if any.
+    pub(crate) fn dists(&self, version: &Version) -> Vec<Dist> {
+        match self.inner {
+            VersionMapInner::Eager(ref map) => map
+                .get(version)
+                .map(|file| file.dists().to_vec())
+                .unwrap_or_default(),
+            VersionMapInner::Lazy(ref lazy) => lazy
+                .get(version)
+                .map(|file| file.dists().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+
     /// Returns the total number of distinct versions in this map.
     ///
     /// Note that this may include versions of distributions that are not
@@ -252,15 +287,14 @@ struct VersionMapLazy {
     /// A map from version to possibly-initialized distribution.
     map: BTreeMap<Version, LazyPrioritizedDist>,
     /// The raw simple metadata from which `PrioritizedDist`s should
-    /// be constructed.
-    simple_metadata: OwnedArchive<SimpleMetadata>,
+    /// be constructed, along with the URL of the index it came from, in
+    /// index order.
+    simple_metadata: Vec<(IndexUrl, OwnedArchive<SimpleMetadata>)>,
     /// When true, wheels aren't allowed.
     no_binary: bool,
-    /// The URL of the index where this package came from.
-    index: IndexUrl,
     /// The set of compatibility tags that determines whether a wheel is usable
-    /// in the current environment.
-    tags: Tags,
+    /// in the current environment. If `None`, every wheel is considered usable.
+    tags: Option<Tags>,
     /// The version of Python active in the current environment. This is used
     /// to determine whether a package's Python version constraint (if one
     /// exists) is satisfied or not.
@@ -310,80 +344,16 @@ impl VersionMapLazy {
         simple: &'p SimplePrioritizedDist,
     ) -> Option<&'p PrioritizedDist> {
         let get_or_init = || {
-            let files: VersionFiles = self
-                .simple_metadata
-                .datum(simple.datum_index)
-                .expect("index to lazy dist is correct")
-                .files
-                .deserialize(&mut SharedDeserializeMap::new())
-                .expect("archived version files should deserialize");
             let mut priority_dist = init.cloned().unwrap_or_default();
-            for (filename, file) in files.all() {
-                if let Some(exclude_newer) = self.exclude_newer {
-                    match file.upload_time_utc_ms.as_ref() {
-                        Some(&upload_time) if upload_time >= exclude_newer.timestamp_millis() => {
-                            priority_dist.set_exclude_newer();
-                            continue;
-                        }
-                        None => {
-                            warn_user_once!(
-                                "{} is missing an upload date, but user provided: {exclude_newer}",
-                                file.filename,
-                            );
-                            priority_dist.set_exclude_newer();
-                            continue;
-                        }
-                        _ => {}
-                    }
-                }
-                let yanked = file.yanked.clone().unwrap_or_default();
-                let requires_python = file.requires_python.clone();
-                let hash = file.hashes.clone();
-                match filename {
-                    DistFilename::WheelFilename(filename) => {
-                        // Determine a compatibility for the wheel based on tags
-                        let mut compatibility =
-                            WheelCompatibility::from(filename.compatibility(&self.tags));
-
-                        if compatibility.is_compatible() {
-                            // Check for Python version incompatibility
-                            if let Some(ref requires_python) = file.requires_python {
-                                if !requires_python.contains(self.python_requirement.target()) {
-                                    compatibility = WheelCompatibility::Incompatible(
-                                        IncompatibleWheel::RequiresPython,
-                                    );
-                                }
-                            }
-
-                            // Mark all wheels as incompatibility when binaries are disabled
-                            if self.no_binary {
-                                compatibility =
-                                    WheelCompatibility::Incompatible(IncompatibleWheel::NoBinary);
-                            }
-                        };
-
-                        let dist = Dist::from_registry(
-                            DistFilename::WheelFilename(filename),
-                            file,
-                            self.index.clone(),
-                        );
-                        priority_dist.insert_built(
-                            dist,
-                            requires_python,
-                            yanked,
-                            Some(hash),
-                            compatibility,
-                        );
-                    }
-                    DistFilename::SourceDistFilename(filename) => {
-                        let dist = Dist::from_registry(
-                            DistFilename::SourceDistFilename(filename),
-                            file,
-                            self.index.clone(),
-                        );
-                        priority_dist.insert_source(dist, requires_python, yanked, Some(hash));
-                    }
-                }
+            for offset in &simple.offsets {
+                let (index, simple_metadata) = &self.simple_metadata[offset.metadata_index];
+                let files: VersionFiles = simple_metadata
+                    .datum(offset.datum_index)
+                    .expect("index to lazy dist is correct")
+                    .files
+                    .deserialize(&mut SharedDeserializeMap::new())
+                    .expect("archived version files should deserialize");
+                self.insert_files(&mut priority_dist, files, index);
             }
             if priority_dist.is_empty() {
                 None
@@ -393,6 +363,86 @@ impl VersionMapLazy {
         };
         simple.dist.get_or_init(get_or_init).as_ref()
     }
+
+    /// Insert the given files, from the given index, into a [`PrioritizedDist`].
+    fn insert_files(
+        &self,
+        priority_dist: &mut PrioritizedDist,
+        files: VersionFiles,
+        index: &IndexUrl,
+    ) {
+        for (filename, file) in files.all() {
+            if let Some(exclude_newer) = self.exclude_newer {
+                match file.upload_time_utc_ms.as_ref() {
+                    Some(&upload_time) if upload_time >= exclude_newer.timestamp_millis() => {
+                        priority_dist.set_exclude_newer();
+                        continue;
+                    }
+                    None => {
+                        warn_user_once!(
+                            "{} is missing an upload date, but user provided: {exclude_newer}",
+                            file.filename,
+                        );
+                        priority_dist.set_exclude_newer();
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+            let yanked = file.yanked.clone().unwrap_or_default();
+            let requires_python = file.requires_python.clone();
+            let hash = file.hashes.clone();
+            match filename {
+                DistFilename::WheelFilename(filename) => {
+                    // Determine a compatibility for the wheel based on tags
+                    let mut compatibility = match self.tags.as_ref() {
+                        Some(tags) => WheelCompatibility::from(filename.compatibility(tags)),
+                        None => WheelCompatibility::Compatible(
+                            TagPriority::try_from(0).expect("valid tag priority"),
+                        ),
+                    };
+
+                    if compatibility.is_compatible() {
+                        // Check for Python version incompatibility
+                        if let Some(ref requires_python) = file.requires_python {
+                            if !requires_python.contains(self.python_requirement.target()) {
+                                compatibility = WheelCompatibility::Incompatible(
+                                    IncompatibleWheel::RequiresPython,
+                                );
+                            }
+                        }
+
+                        // Mark all wheels as incompatibility when binaries are disabled
+                        if self.no_binary {
+                            compatibility =
+                                WheelCompatibility::Incompatible(IncompatibleWheel::NoBinary);
+                        }
+                    };
+
+                    let dist = Dist::from_registry(
+                        DistFilename::WheelFilename(filename),
+                        file,
+                        index.clone(),
+                    );
+                    priority_dist.insert_built(
+                        dist,
+                        requires_python,
+                        yanked,
+                        Some(hash),
+                        compatibility,
+                    );
+                }
+                DistFilename::SourceDistFilename(filename) => {
+                    let dist = Dist::from_registry(
+                        DistFilename::SourceDistFilename(filename),
+                        file,
+                        index.clone(),
+                    );
+                    priority_dist.insert_source(dist, requires_python, yanked, Some(hash));
+                }
+            }
+        }
+    }
 }
 
 /// Represents a possibly initialized [`PrioritizedDist`] for
@@ -416,10 +466,13 @@ enum LazyPrioritizedDist {
 /// Represents a lazily initialized `PrioritizedDist`.
 #[derive(Debug)]
 struct SimplePrioritizedDist {
-    /// An offset into `SimpleMetadata` corresponding to a `SimpleMetadatum`.
-    /// This provides access to a `VersionFiles` that is used to construct a
+    /// The offsets of each `SimpleMetadatum` for this version. Each provides
+    /// access to a `VersionFiles` that is used to construct a
     /// `PrioritizedDist`.
-    datum_index: usize,
+    ///
+    /// There's typically a single offset, unless the version is available
+    /// from multiple indexes and the files from each are being merged.
+    offsets: Vec<SimpleDatumOffset>,
     /// A lazily initialized distribution.
     ///
     /// Note that the `Option` does not represent the initialization state.
@@ -429,3 +482,12 @@ struct SimplePrioritizedDist {
     /// of writing, is to use `--exclude-newer 1900-01-01`.)
     dist: OnceLock<Option<PrioritizedDist>>,
 }
+
+/// The location of a `SimpleMetadatum` within a [`VersionMapLazy`].
+#[derive(Debug, Clone, Copy)]
+struct SimpleDatumOffset {
+    /// An offset into the list of `SimpleMetadata`, one per index.
+    metadata_index: usize,
+    /// An offset into `SimpleMetadata` corresponding to a `SimpleMetadatum`.
+    datum_index: usize,
+}
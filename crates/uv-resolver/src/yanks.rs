@@ -12,7 +12,7 @@ use crate::Manifest;
 pub(crate) struct AllowedYanks(FxHashMap<PackageName, FxHashSet<Version>>);
 
 impl AllowedYanks {
-    pub(crate) fn from_manifest(manifest: &Manifest, markers: &MarkerEnvironment) -> Self {
+    pub(crate) fn from_manifest(manifest: &Manifest, markers: Option<&MarkerEnvironment>) -> Self {
         let mut allowed_yanks = FxHashMap::<PackageName, FxHashSet<Version>>::default();
         for requirement in manifest
             .requirements
@@ -20,12 +20,11 @@ impl AllowedYanks {
             .chain(manifest.constraints.iter())
             .chain(manifest.overrides.iter())
             .chain(manifest.preferences.iter())
-            .filter(|requirement| requirement.evaluate_markers(markers, &[]))
+            .filter(|requirement| requirement.evaluate_optional_environment(markers, &[]))
             .chain(manifest.editables.iter().flat_map(|(editable, metadata)| {
-                metadata
-                    .requires_dist
-                    .iter()
-                    .filter(|requirement| requirement.evaluate_markers(markers, &editable.extras))
+                metadata.requires_dist.iter().filter(|requirement| {
+                    requirement.evaluate_optional_environment(markers, &editable.extras)
+                })
             }))
         {
             let Some(pep508_rs::VersionOrUrl::VersionSpecifier(specifiers)) =
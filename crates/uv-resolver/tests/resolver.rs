@@ -5,10 +5,12 @@
 
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
+use tokio::sync::Semaphore;
 
 use distribution_types::{IndexLocations, Resolution, SourceDist};
 use pep508_rs::{MarkerEnvironment, Requirement, StringVersion};
@@ -19,9 +21,12 @@ use uv_client::{FlatIndex, RegistryClientBuilder};
 use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_resolver::{
     DisplayResolutionGraph, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
-    ResolutionGraph, ResolutionMode, Resolver,
+    PythonRequirement, ResolutionGraph, ResolutionMode, Resolver,
+};
+use uv_traits::{
+    BuildContext, BuildIsolation, BuildKind, Concurrency, ConfigSettings, NoBinary, NoBuild,
+    PackageBuildSettings, SetupPyStrategy, SourceBuildTrait,
 };
-use uv_traits::{BuildContext, BuildKind, NoBinary, NoBuild, SetupPyStrategy, SourceBuildTrait};
 
 // Exclude any packages uploaded after this date.
 static EXCLUDE_NEWER: Lazy<DateTime<Utc>> = Lazy::new(|| {
@@ -34,6 +39,10 @@ struct DummyContext {
     cache: Cache,
     interpreter: Interpreter,
     index_locations: IndexLocations,
+    config_settings: ConfigSettings,
+    package_build_settings: PackageBuildSettings,
+    concurrency: Concurrency,
+    build_semaphore: Arc<Semaphore>,
 }
 
 impl DummyContext {
@@ -42,6 +51,10 @@ impl DummyContext {
             cache,
             interpreter,
             index_locations: IndexLocations::default(),
+            config_settings: ConfigSettings::default(),
+            package_build_settings: PackageBuildSettings::default(),
+            concurrency: Concurrency::default(),
+            build_semaphore: Arc::new(Semaphore::new(1)),
         }
     }
 }
@@ -69,10 +82,32 @@ impl BuildContext for DummyContext {
         SetupPyStrategy::default()
     }
 
+    fn build_isolation(&self) -> BuildIsolation<'_> {
+        BuildIsolation::Isolated
+    }
+
+    fn config_settings(&self) -> &ConfigSettings {
+        &self.config_settings
+    }
+
+    fn package_build_settings(&self) -> &PackageBuildSettings {
+        &self.package_build_settings
+    }
+
     fn index_locations(&self) -> &IndexLocations {
         &self.index_locations
     }
 
+    fn concurrency(&self) -> &Concurrency {
+        &self.concurrency
+    }
+
+    fn build_semaphore(&self) -> &Arc<Semaphore> {
+        &self.build_semaphore
+    }
+
+    fn record_build_requirements(&self, _: &[Requirement]) {}
+
     async fn resolve<'a>(&'a self, _: &'a [Requirement]) -> Result<Resolution> {
         panic!("The test should not need to build source distributions")
     }
@@ -103,6 +138,10 @@ impl SourceBuildTrait for DummyBuilder {
     async fn wheel<'a>(&'a self, _: &'a Path) -> Result<String> {
         panic!("The test should not need to build source distributions")
     }
+
+    fn build_requirements(&self) -> &[Requirement] {
+        &[]
+    }
 }
 
 async fn resolve(
@@ -119,8 +158,8 @@ async fn resolve(
     let resolver = Resolver::new(
         manifest,
         options,
-        markers,
-        &interpreter,
+        Some(markers),
+        PythonRequirement::new(&interpreter, markers),
         tags,
         &client,
         &flat_index,
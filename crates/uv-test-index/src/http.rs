@@ -0,0 +1,264 @@
+//! A minimal HTTP/1.1 implementation, sufficient for serving a simple index to `uv`.
+//!
+//! Every response closes the connection, so there's no need to support keep-alive or chunked
+//! transfer encoding.
+
+use std::io::{self, BufRead, Write};
+
+/// An incoming HTTP request.
+#[derive(Debug)]
+pub(crate) struct Request {
+    pub(crate) method: String,
+    /// The percent-decoded path, without the query string.
+    pub(crate) path: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Read a request from the given stream, or `None` if the connection closed before a request
+    /// was received.
+    pub(crate) fn read(reader: &mut impl BufRead) -> io::Result<Option<Self>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid request line: {line:?}"),
+            ));
+        };
+        let method = method.to_string();
+        let path = percent_decode(target.split('?').next().unwrap_or(target));
+
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+
+        Ok(Some(Self {
+            method,
+            path,
+            headers,
+        }))
+    }
+
+    /// Return the value of the given header, if present.
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// An outgoing HTTP response.
+#[derive(Debug)]
+pub(crate) struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    /// The number of bytes of the body to send before closing the connection, if any.
+    truncate: Option<usize>,
+}
+
+impl Response {
+    /// Create an empty response with the given status code.
+    pub(crate) fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+            truncate: None,
+        }
+    }
+
+    /// Create a plain-text response with the given status code, e.g., for errors.
+    pub(crate) fn text(status: u16, body: impl Into<String>) -> Self {
+        Self::new(status).with_body("text/plain; charset=utf-8", body.into().into_bytes())
+    }
+
+    /// Add a header to the response.
+    #[must_use]
+    pub(crate) fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Set the body of the response, along with its content type.
+    #[must_use]
+    pub(crate) fn with_body(self, content_type: &str, body: Vec<u8>) -> Self {
+        Self { body, ..self }.with_header("Content-Type", content_type)
+    }
+
+    /// Send only the first `bytes` bytes of the body, while still reporting the full length, as
+    /// if the connection were reset mid-response.
+    #[must_use]
+    pub(crate) fn truncate(self, bytes: usize) -> Self {
+        Self {
+            truncate: Some(bytes),
+            ..self
+        }
+    }
+
+    /// Write the response to the given stream. If `head` is set, the body is omitted (but its
+    /// length is still reported).
+    pub(crate) fn write(&self, writer: &mut impl Write, head: bool) -> io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\n",
+            self.status,
+            reason(self.status)
+        )?;
+        for (name, value) in &self.headers {
+            write!(writer, "{name}: {value}\r\n")?;
+        }
+        write!(writer, "Content-Length: {}\r\n", self.body.len())?;
+        write!(writer, "Connection: close\r\n\r\n")?;
+        if !head {
+            let len = self.truncate.unwrap_or(usize::MAX).min(self.body.len());
+            writer.write_all(&self.body[..len])?;
+        }
+        writer.flush()
+    }
+}
+
+/// A byte range requested via a `Range` header.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct ByteRange {
+    /// The first byte in the range.
+    pub(crate) start: usize,
+    /// The last byte in the range (inclusive).
+    pub(crate) end: usize,
+}
+
+impl ByteRange {
+    /// Parse a `Range` header for a resource of the given length.
+    ///
+    /// Returns `None` if the range is malformed or unsatisfiable. Only a single range is
+    /// supported.
+    pub(crate) fn parse(header: &str, len: usize) -> Option<Self> {
+        if len == 0 {
+            return None;
+        }
+        let spec = header.trim().strip_prefix("bytes=")?;
+        let (start, end) = spec.split_once('-')?;
+        let (start, end) = match (start.trim(), end.trim()) {
+            // A suffix range, e.g., `bytes=-500` for the last 500 bytes.
+            ("", suffix) => {
+                let suffix = suffix.parse::<usize>().ok()?.min(len);
+                (len - suffix, len - 1)
+            }
+            // An open-ended range, e.g., `bytes=500-`.
+            (start, "") => (start.parse().ok()?, len - 1),
+            (start, end) => (start.parse().ok()?, end.parse::<usize>().ok()?.min(len - 1)),
+        };
+        if start > end || start >= len {
+            return None;
+        }
+        Some(Self { start, end })
+    }
+}
+
+/// Return the reason phrase for a status code.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        206 => "Partial Content",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        416 => "Range Not Satisfiable",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
+
+/// Decode percent-encoded bytes in a URL path.
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(byte) = path
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Escape a string for use in HTML text or a quoted attribute.
+pub(crate) fn html_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_range() {
+        assert_eq!(
+            ByteRange::parse("bytes=0-9", 100),
+            Some(ByteRange { start: 0, end: 9 })
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=90-", 100),
+            Some(ByteRange { start: 90, end: 99 })
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=-10", 100),
+            Some(ByteRange { start: 90, end: 99 })
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=50-500", 100),
+            Some(ByteRange { start: 50, end: 99 })
+        );
+        assert_eq!(ByteRange::parse("bytes=100-", 100), None);
+        assert_eq!(ByteRange::parse("bytes=9-0", 100), None);
+        assert_eq!(ByteRange::parse("items=0-9", 100), None);
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(percent_decode("/files/a%2Bb.whl"), "/files/a+b.whl");
+        assert_eq!(percent_decode("/files/100%"), "/files/100%");
+    }
+}
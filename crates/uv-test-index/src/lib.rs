@@ -0,0 +1,712 @@
+//! A local stand-in for a Python package index, for use in tests.
+//!
+//! [`TestIndex`] serves a set of wheels and source distributions over HTTP as a simple index,
+//! in both the [PEP 503](https://peps.python.org/pep-0503/) HTML and
+//! [PEP 691](https://peps.python.org/pep-0691/) JSON formats (selected via the `Accept` header),
+//! with [PEP 658](https://peps.python.org/pep-0658/) metadata files and support for HTTP range
+//! requests. Failures (e.g., `404` or `500` responses, slow responses, or connections that are
+//! reset mid-response) can be injected for any path, and the index can require HTTP basic
+//! authentication.
+//!
+//! The index is served from a background thread, and is shut down when the [`TestIndex`] is
+//! dropped.
+//!
+//! ```no_run
+//! use uv_test_index::TestIndex;
+//!
+//! let index = TestIndex::builder()
+//!     .dir("scripts/wheels")
+//!     .start()
+//!     .unwrap();
+//! println!("Serving a simple index at: {}", index.url());
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::{BufReader, Cursor, Read};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+
+use distribution_filename::DistFilename;
+use pypi_types::Metadata21;
+use uv_normalize::PackageName;
+
+use crate::http::{html_escape, ByteRange, Request, Response};
+
+mod http;
+
+/// The media type for PEP 691 JSON responses.
+const JSON_MEDIA_TYPE: &str = "application/vnd.pypi.simple.v1+json";
+
+/// A failure to inject into the responses for a path.
+#[derive(Debug, Clone)]
+pub struct Failure {
+    /// The path prefix to which the failure applies, e.g., `/simple/tqdm/` or `/files/`.
+    prefix: String,
+    /// The kind of failure.
+    kind: FailureKind,
+    /// The number of requests to which the failure applies, or `None` to apply to every request.
+    times: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub enum FailureKind {
+    /// Respond with the given status code (e.g., `404` or `500`).
+    Status(u16),
+    /// Wait for the given duration before responding as usual.
+    Delay(Duration),
+    /// Close the connection after sending the given number of bytes of the body, as if the
+    /// connection were reset mid-download. Only applies to `GET` requests.
+    Truncate(usize),
+}
+
+impl Failure {
+    /// Respond to requests for paths starting with `prefix` with the given status code.
+    pub fn status(prefix: impl Into<String>, status: u16) -> Self {
+        Self {
+            prefix: prefix.into(),
+            kind: FailureKind::Status(status),
+            times: None,
+        }
+    }
+
+    /// Delay responses to requests for paths starting with `prefix` by the given duration.
+    pub fn delay(prefix: impl Into<String>, delay: Duration) -> Self {
+        Self {
+            prefix: prefix.into(),
+            kind: FailureKind::Delay(delay),
+            times: None,
+        }
+    }
+
+    /// Close the connection after sending `bytes` bytes of the body of responses to `GET`
+    /// requests for paths starting with `prefix`.
+    pub fn truncate(prefix: impl Into<String>, bytes: usize) -> Self {
+        Self {
+            prefix: prefix.into(),
+            kind: FailureKind::Truncate(bytes),
+            times: None,
+        }
+    }
+
+    /// Only apply the failure to the first `times` matching requests, after which requests are
+    /// served as usual.
+    #[must_use]
+    pub fn times(self, times: usize) -> Self {
+        Self {
+            times: Some(times),
+            ..self
+        }
+    }
+}
+
+/// A builder for a [`TestIndex`].
+#[derive(Debug, Default)]
+pub struct TestIndexBuilder {
+    paths: Vec<PathBuf>,
+    credentials: Option<(String, String)>,
+    failures: Vec<Failure>,
+    yanked: BTreeMap<String, Option<String>>,
+    upload_times: BTreeMap<String, DateTime<Utc>>,
+    corrupt_metadata: BTreeSet<String>,
+    html_only: bool,
+    no_metadata: bool,
+    no_ranges: bool,
+}
+
+impl TestIndexBuilder {
+    /// Serve every wheel and source distribution in the given directory.
+    #[must_use]
+    pub fn dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Serve the given wheel or source distribution.
+    #[must_use]
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Require HTTP basic authentication with the given credentials for every request.
+    #[must_use]
+    pub fn auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Inject a failure into the responses for a path.
+    ///
+    /// When multiple failures apply to a request, the first one added takes precedence.
+    #[must_use]
+    pub fn failure(mut self, failure: Failure) -> Self {
+        self.failures.push(failure);
+        self
+    }
+
+    /// Mark the given file as yanked, with an optional reason.
+    #[must_use]
+    pub fn yank(mut self, filename: impl Into<String>, reason: Option<&str>) -> Self {
+        self.yanked
+            .insert(filename.into(), reason.map(ToString::to_string));
+        self
+    }
+
+    /// Set the upload time of the given file.
+    ///
+    /// By default, every file is reported as uploaded at `2023-01-01T00:00:00Z`, such that
+    /// `--exclude-newer` cutoffs after that date include every file.
+    #[must_use]
+    pub fn upload_time(mut self, filename: impl Into<String>, upload_time: DateTime<Utc>) -> Self {
+        self.upload_times.insert(filename.into(), upload_time);
+        self
+    }
+
+    /// Serve a PEP 658 metadata file for the given wheel that doesn't match the hash advertised
+    /// on the project page.
+    #[must_use]
+    pub fn corrupt_metadata(mut self, filename: impl Into<String>) -> Self {
+        self.corrupt_metadata.insert(filename.into());
+        self
+    }
+
+    /// Only serve HTML pages, regardless of the `Accept` header.
+    #[must_use]
+    pub fn html_only(mut self) -> Self {
+        self.html_only = true;
+        self
+    }
+
+    /// Don't serve PEP 658 metadata files, such that clients must read metadata from the wheels
+    /// themselves.
+    #[must_use]
+    pub fn no_metadata(mut self) -> Self {
+        self.no_metadata = true;
+        self
+    }
+
+    /// Don't support HTTP range requests, such that clients must download entire files.
+    #[must_use]
+    pub fn no_ranges(mut self) -> Self {
+        self.no_ranges = true;
+        self
+    }
+
+    /// Read the distributions and start serving the index on a random local port.
+    pub fn start(self) -> Result<TestIndex> {
+        let default_upload_time = DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z")
+            .expect("valid timestamp")
+            .with_timezone(&Utc);
+
+        let mut files = BTreeMap::new();
+        for path in &self.paths {
+            if path.is_dir() {
+                for entry in fs_err::read_dir(path)? {
+                    let path = entry?.path();
+                    if path.is_file() {
+                        if let Some(file) = IndexFile::read(&path)? {
+                            files.insert(file.filename.clone(), file);
+                        }
+                    }
+                }
+            } else {
+                let file = IndexFile::read(path)?.with_context(|| {
+                    format!("Not a wheel or source distribution: {}", path.display())
+                })?;
+                files.insert(file.filename.clone(), file);
+            }
+        }
+
+        for file in files.values_mut() {
+            file.yanked = self.yanked.get(&file.filename).cloned();
+            file.upload_time = self
+                .upload_times
+                .get(&file.filename)
+                .copied()
+                .unwrap_or(default_upload_time);
+            if self.corrupt_metadata.contains(&file.filename) {
+                if let Some(metadata) = file.metadata.as_mut() {
+                    metadata.extend_from_slice(b"\nCorrupted: true\n");
+                }
+            }
+        }
+
+        let state = Arc::new(State {
+            files,
+            credentials: self.credentials,
+            failures: Mutex::new(self.failures),
+            requests: Mutex::new(Vec::new()),
+            html_only: self.html_only,
+            metadata: !self.no_metadata,
+            ranges: !self.no_ranges,
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let state = state.clone();
+            let shutdown = shutdown.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    let state = state.clone();
+                    std::thread::spawn(move || {
+                        // Errors are typically due to the client closing the connection early,
+                        // which is expected (e.g., when a request times out).
+                        let _ = state.handle(stream);
+                    });
+                }
+            })
+        };
+
+        Ok(TestIndex {
+            addr,
+            state,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+}
+
+/// A running local package index.
+#[derive(Debug)]
+pub struct TestIndex {
+    addr: SocketAddr,
+    state: Arc<State>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl TestIndex {
+    /// Create a [`TestIndexBuilder`].
+    pub fn builder() -> TestIndexBuilder {
+        TestIndexBuilder::default()
+    }
+
+    /// Return the base URL of the server, e.g., `http://127.0.0.1:8080`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Return the URL of the simple index, e.g., `http://127.0.0.1:8080/simple`, for use with
+    /// `--index-url`.
+    pub fn url(&self) -> String {
+        format!("{}/simple", self.base_url())
+    }
+
+    /// Return the URL of the simple index with the configured credentials embedded, if any.
+    pub fn authenticated_url(&self) -> String {
+        match &self.state.credentials {
+            Some((username, password)) => {
+                format!("http://{username}:{password}@{}/simple", self.addr)
+            }
+            None => self.url(),
+        }
+    }
+
+    /// Return every request received by the server so far, as `<method> <path>` (e.g.,
+    /// `GET /simple/tqdm/`), in the order in which they were received.
+    pub fn requests(&self) -> Vec<String> {
+        self.state
+            .requests
+            .lock()
+            .expect("request log is not poisoned")
+            .clone()
+    }
+
+    /// Inject a failure into the responses for a path while the server is running.
+    pub fn inject(&self, failure: Failure) {
+        self.state
+            .failures
+            .lock()
+            .expect("failures are not poisoned")
+            .push(failure);
+    }
+}
+
+impl Drop for TestIndex {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the listener, so that it observes the shutdown.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// A wheel or source distribution served by the index.
+#[derive(Debug)]
+struct IndexFile {
+    filename: String,
+    name: PackageName,
+    version: String,
+    contents: Vec<u8>,
+    sha256: String,
+    /// The contents of the `METADATA` file, for wheels.
+    metadata: Option<Vec<u8>>,
+    metadata_sha256: Option<String>,
+    requires_python: Option<String>,
+    yanked: Option<Option<String>>,
+    upload_time: DateTime<Utc>,
+}
+
+impl IndexFile {
+    /// Read a distribution from disk, or `None` if the file isn't a wheel or source distribution.
+    fn read(path: &Path) -> Result<Option<Self>> {
+        let Some(filename) = path.file_name().and_then(|filename| filename.to_str()) else {
+            return Ok(None);
+        };
+        let Some(dist_filename) = DistFilename::try_from_normalized_filename(filename) else {
+            return Ok(None);
+        };
+
+        let contents = fs_err::read(path)?;
+        let sha256 = format!("{:x}", Sha256::digest(&contents));
+
+        let (metadata, requires_python) = match dist_filename {
+            DistFilename::WheelFilename(_) => {
+                let metadata = read_wheel_metadata(&contents)
+                    .with_context(|| format!("Failed to read metadata from: {filename}"))?;
+                let requires_python = Metadata21::parse(&metadata)
+                    .with_context(|| format!("Failed to parse metadata from: {filename}"))?
+                    .requires_python
+                    .map(|specifiers| specifiers.to_string());
+                (Some(metadata), requires_python)
+            }
+            DistFilename::SourceDistFilename(_) => (None, None),
+        };
+        let metadata_sha256 = metadata
+            .as_ref()
+            .map(|metadata| format!("{:x}", Sha256::digest(metadata)));
+
+        Ok(Some(Self {
+            filename: filename.to_string(),
+            name: dist_filename.name().clone(),
+            version: dist_filename.version().to_string(),
+            contents,
+            sha256,
+            metadata,
+            metadata_sha256,
+            requires_python,
+            yanked: None,
+            upload_time: DateTime::<Utc>::MIN_UTC,
+        }))
+    }
+}
+
+/// Read the `METADATA` file from the `.dist-info` directory of a wheel.
+fn read_wheel_metadata(contents: &[u8]) -> Result<Vec<u8>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(contents))?;
+    let name = archive
+        .file_names()
+        .find(|name| {
+            name.split_once('/')
+                .is_some_and(|(dir, file)| dir.ends_with(".dist-info") && file == "METADATA")
+        })
+        .map(ToString::to_string)
+        .context("No `.dist-info/METADATA` file found")?;
+    let mut metadata = Vec::new();
+    archive.by_name(&name)?.read_to_end(&mut metadata)?;
+    Ok(metadata)
+}
+
+#[derive(Debug)]
+struct State {
+    files: BTreeMap<String, IndexFile>,
+    credentials: Option<(String, String)>,
+    failures: Mutex<Vec<Failure>>,
+    requests: Mutex<Vec<String>>,
+    html_only: bool,
+    metadata: bool,
+    ranges: bool,
+}
+
+impl State {
+    /// Handle a single connection.
+    fn handle(&self, stream: TcpStream) -> std::io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let Some(request) = Request::read(&mut reader)? else {
+            return Ok(());
+        };
+        self.requests
+            .lock()
+            .expect("request log is not poisoned")
+            .push(format!("{} {}", request.method, request.path));
+
+        let response = self.respond(&request);
+        let mut stream = stream;
+        response.write(&mut stream, request.method == "HEAD")
+    }
+
+    /// Determine the response to a request.
+    fn respond(&self, request: &Request) -> Response {
+        // Apply any injected failures.
+        let failure = {
+            let mut failures = self.failures.lock().expect("failures are not poisoned");
+            failures
+                .iter_mut()
+                .find(|failure| {
+                    request.path.starts_with(&failure.prefix)
+                        && failure.times != Some(0)
+                        && (request.method == "GET"
+                            || !matches!(failure.kind, FailureKind::Truncate(_)))
+                })
+                .map(|failure| {
+                    if let Some(times) = failure.times.as_mut() {
+                        *times -= 1;
+                    }
+                    failure.kind.clone()
+                })
+        };
+        match failure {
+            Some(FailureKind::Status(status)) => {
+                return Response::text(status, format!("Injected failure for: {}", request.path));
+            }
+            Some(FailureKind::Delay(delay)) => std::thread::sleep(delay),
+            Some(FailureKind::Truncate(bytes)) => return self.route(request).truncate(bytes),
+            None => {}
+        }
+
+        self.route(request)
+    }
+
+    /// Determine the response to a request, absent any injected failures.
+    fn route(&self, request: &Request) -> Response {
+        // Require authentication, if configured.
+        if let Some((username, password)) = &self.credentials {
+            let expected = format!(
+                "Basic {}",
+                BASE64_STANDARD.encode(format!("{username}:{password}"))
+            );
+            if request.header("authorization") != Some(expected.as_str()) {
+                return Response::text(401, "Unauthorized")
+                    .with_header("WWW-Authenticate", "Basic realm=\"uv-test-index\"");
+            }
+        }
+
+        if request.method != "GET" && request.method != "HEAD" {
+            return Response::text(405, "Method Not Allowed").with_header("Allow", "GET, HEAD");
+        }
+
+        let json = !self.html_only
+            && request
+                .header("accept")
+                .is_some_and(|accept| accept.contains(JSON_MEDIA_TYPE));
+
+        let path = request.path.as_str();
+        if path == "/simple" || path == "/simple/" {
+            return if json {
+                self.root_json()
+            } else {
+                self.root_html()
+            };
+        }
+        if let Some(name) = path.strip_prefix("/simple/") {
+            let Ok(name) = PackageName::from_str(name.trim_end_matches('/')) else {
+                return Response::text(404, "Not Found");
+            };
+            let files = self
+                .files
+                .values()
+                .filter(|file| file.name == name)
+                .collect::<Vec<_>>();
+            if files.is_empty() {
+                return Response::text(404, "Not Found");
+            }
+            return if json {
+                Self::project_json(&name, &files, self.metadata)
+            } else {
+                Self::project_html(&name, &files, self.metadata)
+            };
+        }
+        if let Some(filename) = path.strip_prefix("/files/") {
+            if let Some(file) = self.files.get(filename) {
+                return self.file(request, file);
+            }
+            if let Some(metadata) = filename
+                .strip_suffix(".metadata")
+                .and_then(|filename| self.files.get(filename))
+                .and_then(|file| file.metadata.as_ref())
+                .filter(|_| self.metadata)
+            {
+                return Response::new(200).with_body("application/octet-stream", metadata.clone());
+            }
+        }
+
+        Response::text(404, "Not Found")
+    }
+
+    /// Serve the contents of a file, honoring any `Range` header.
+    ///
+    /// Files are tagged with their SHA-256 digest as an `ETag`. A `Range` request with an
+    /// `If-Range` header that doesn't match the `ETag` receives the entire file.
+    fn file(&self, request: &Request, file: &IndexFile) -> Response {
+        let contents = &file.contents;
+        let etag = format!("\"{}\"", file.sha256);
+        if !self.ranges {
+            return Response::new(200)
+                .with_header("ETag", etag)
+                .with_body("application/octet-stream", contents.clone());
+        }
+        let Some(range) = request
+            .header("range")
+            .filter(|_| request.header("if-range").map_or(true, |tag| tag == etag))
+        else {
+            return Response::new(200)
+                .with_header("Accept-Ranges", "bytes")
+                .with_header("ETag", etag)
+                .with_body("application/octet-stream", contents.clone());
+        };
+        match ByteRange::parse(range, contents.len()) {
+            Some(ByteRange { start, end }) => Response::new(206)
+                .with_header("Accept-Ranges", "bytes")
+                .with_header("ETag", etag)
+                .with_header(
+                    "Content-Range",
+                    format!("bytes {start}-{end}/{}", contents.len()),
+                )
+                .with_body("application/octet-stream", contents[start..=end].to_vec()),
+            None => Response::text(416, "Range Not Satisfiable")
+                .with_header("Content-Range", format!("bytes */{}", contents.len())),
+        }
+    }
+
+    /// Render the root page, listing every project, as HTML.
+    fn root_html(&self) -> Response {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n  <body>\n");
+        for name in self.projects() {
+            writeln!(html, "    <a href=\"/simple/{name}/\">{name}</a>")
+                .expect("writing to a string is infallible");
+        }
+        html.push_str("  </body>\n</html>\n");
+        Response::new(200).with_body("text/html", html.into_bytes())
+    }
+
+    /// Render the root page, listing every project, as JSON.
+    fn root_json(&self) -> Response {
+        let projects = self
+            .projects()
+            .into_iter()
+            .map(|name| serde_json::json!({ "name": name.to_string() }))
+            .collect::<Vec<_>>();
+        let body = serde_json::json!({
+            "meta": { "api-version": "1.1" },
+            "projects": projects,
+        });
+        Response::new(200).with_body(JSON_MEDIA_TYPE, body.to_string().into_bytes())
+    }
+
+    /// Render the page for a single project as HTML.
+    fn project_html(name: &PackageName, files: &[&IndexFile], metadata: bool) -> Response {
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n  <head>\n    <meta name=\"pypi:repository-version\" content=\"1.1\">\n    <title>Links for {name}</title>\n  </head>\n  <body>\n    <h1>Links for {name}</h1>\n"
+        );
+        for file in files {
+            let mut attributes = String::new();
+            if let Some(requires_python) = &file.requires_python {
+                write!(
+                    attributes,
+                    " data-requires-python=\"{}\"",
+                    html_escape(requires_python)
+                )
+                .expect("writing to a string is infallible");
+            }
+            if let Some(metadata_sha256) = file.metadata_sha256.as_ref().filter(|_| metadata) {
+                write!(
+                    attributes,
+                    " data-core-metadata=\"sha256={metadata_sha256}\" data-dist-info-metadata=\"sha256={metadata_sha256}\""
+                )
+                .expect("writing to a string is infallible");
+            }
+            match &file.yanked {
+                Some(Some(reason)) => {
+                    write!(attributes, " data-yanked=\"{}\"", html_escape(reason))
+                        .expect("writing to a string is infallible");
+                }
+                Some(None) => attributes.push_str(" data-yanked"),
+                None => {}
+            }
+            writeln!(
+                html,
+                "    <a href=\"../../files/{filename}#sha256={sha256}\"{attributes}>{filename}</a><br/>",
+                filename = html_escape(&file.filename),
+                sha256 = file.sha256,
+            )
+            .expect("writing to a string is infallible");
+        }
+        html.push_str("  </body>\n</html>\n");
+        Response::new(200).with_body("text/html", html.into_bytes())
+    }
+
+    /// Render the page for a single project as JSON, including the PEP 700 fields.
+    fn project_json(name: &PackageName, files: &[&IndexFile], metadata: bool) -> Response {
+        let versions = files
+            .iter()
+            .map(|file| file.version.as_str())
+            .collect::<BTreeSet<_>>();
+        let files = files
+            .iter()
+            .map(|file| {
+                let core_metadata = match file.metadata_sha256.as_ref().filter(|_| metadata) {
+                    Some(sha256) => serde_json::json!({ "sha256": sha256 }),
+                    None => serde_json::json!(false),
+                };
+                let yanked = match &file.yanked {
+                    Some(Some(reason)) => serde_json::json!(reason),
+                    Some(None) => serde_json::json!(true),
+                    None => serde_json::json!(false),
+                };
+                serde_json::json!({
+                    "filename": file.filename,
+                    "url": format!("../../files/{}", file.filename),
+                    "hashes": { "sha256": file.sha256 },
+                    "requires-python": file.requires_python,
+                    "core-metadata": core_metadata,
+                    "data-dist-info-metadata": core_metadata,
+                    "size": file.contents.len(),
+                    "upload-time": file.upload_time.to_rfc3339_opts(SecondsFormat::Micros, true),
+                    "yanked": yanked,
+                })
+            })
+            .collect::<Vec<_>>();
+        let body = serde_json::json!({
+            "meta": { "api-version": "1.1" },
+            "name": name.to_string(),
+            "files": files,
+            "versions": versions,
+        });
+        Response::new(200).with_body(JSON_MEDIA_TYPE, body.to_string().into_bytes())
+    }
+
+    /// Return the name of every project in the index.
+    fn projects(&self) -> Vec<&PackageName> {
+        let mut projects = self
+            .files
+            .values()
+            .map(|file| &file.name)
+            .collect::<Vec<_>>();
+        projects.sort();
+        projects.dedup();
+        projects
+    }
+}
@@ -4,14 +4,21 @@ use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::future::Future;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::Result;
+use rustc_hash::FxHashMap;
+use tokio::sync::Semaphore;
 
+use cache_key::{CacheKey, CacheKeyHasher};
 use distribution_types::{CachedDist, DistributionId, IndexLocations, Resolution, SourceDist};
 use once_map::OnceMap;
-use pep508_rs::Requirement;
+use pep440_rs::Operator;
+use pep508_rs::{MarkerEnvironment, Requirement, VersionOrUrl};
+use pypi_types::HashDigest;
 use uv_cache::Cache;
 use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_normalize::PackageName;
@@ -78,6 +85,30 @@ pub trait BuildContext: Sync {
     /// The strategy to use when building source distributions that lack a `pyproject.toml`.
     fn setup_py_strategy(&self) -> SetupPyStrategy;
 
+    /// The environment in which to run build backends, if build isolation is disabled.
+    fn build_isolation(&self) -> BuildIsolation<'_>;
+
+    /// The PEP 517 config settings to pass to every build backend.
+    fn config_settings(&self) -> &ConfigSettings;
+
+    /// The config settings and environment variables that only apply when building specific
+    /// packages.
+    fn package_build_settings(&self) -> &PackageBuildSettings;
+
+    /// The limits on the number of concurrent downloads, builds and installs.
+    fn concurrency(&self) -> &Concurrency;
+
+    /// The semaphore bounding the number of build backends that run at the same time, shared
+    /// across all (potentially nested) source distribution builds.
+    ///
+    /// A permit is only held while a build backend hook runs, and never while resolving or
+    /// installing the build requirements, such that nested builds can't deadlock.
+    fn build_semaphore(&self) -> &Arc<Semaphore>;
+
+    /// Record the resolved requirements of a build environment, whether it was set up for a build
+    /// or restored alongside a cached build.
+    fn record_build_requirements(&self, requirements: &[Requirement]);
+
     /// Resolve the given requirements into a ready-to-install set of package versions.
     fn resolve<'a>(
         &'a self,
@@ -95,7 +126,9 @@ pub trait BuildContext: Sync {
     /// Setup a source distribution build by installing the required dependencies. A wrapper for
     /// `uv_build::SourceBuild::setup`.
     ///
-    /// For PEP 517 builds, this calls `get_requires_for_build_wheel`.
+    /// For PEP 517 builds, this calls `get_requires_for_build_wheel`. If isolation is disabled for
+    /// the build (see [`BuildContext::build_isolation`]), the build runs in the shared environment
+    /// instead, and no requirements are installed.
     ///
     /// `package_id` is for error reporting only.
     /// `dist` is for safety checks and may be null for editable builds.
@@ -129,6 +162,11 @@ pub trait SourceBuildTrait {
     /// Returns the filename of the built wheel inside the given `wheel_dir`.
     fn wheel<'a>(&'a self, wheel_dir: &'a Path)
         -> impl Future<Output = Result<String>> + Send + 'a;
+
+    /// The resolved requirements installed into the build environment.
+    ///
+    /// Empty if the build runs without isolation, in a shared environment.
+    fn build_requirements(&self) -> &[Requirement];
 }
 
 #[derive(Default)]
@@ -137,6 +175,37 @@ pub struct InFlight {
     pub downloads: OnceMap<DistributionId, Result<CachedDist, String>>,
 }
 
+/// Limits on the number of concurrent operations.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Concurrency {
+    /// The maximum number of concurrent downloads, including metadata fetches.
+    pub downloads: usize,
+    /// The maximum number of source distribution builds that run at the same time.
+    pub builds: usize,
+    /// The maximum number of threads used to install wheels.
+    pub installs: usize,
+}
+
+impl Concurrency {
+    /// The default number of concurrent downloads.
+    pub const DEFAULT_DOWNLOADS: usize = 50;
+
+    /// The default number of concurrent builds and installs, i.e., the number of available cores.
+    pub fn threads() -> usize {
+        std::thread::available_parallelism().map_or(1, NonZeroUsize::get)
+    }
+}
+
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self {
+            downloads: Self::DEFAULT_DOWNLOADS,
+            builds: Self::threads(),
+            installs: Self::threads(),
+        }
+    }
+}
+
 /// The strategy to use when building source distributions that lack a `pyproject.toml`.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum SetupPyStrategy {
@@ -154,6 +223,18 @@ pub enum BuildKind {
     Wheel,
     /// A PEP 660 editable installation wheel build
     Editable,
+    /// A PEP 517 source distribution build
+    Sdist,
+}
+
+impl BuildKind {
+    /// A human-readable description of the build output, for error messages.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Wheel | Self::Editable => "wheel",
+            Self::Sdist => "source distribution",
+        }
+    }
 }
 
 impl Display for BuildKind {
@@ -161,6 +242,7 @@ impl Display for BuildKind {
         match self {
             Self::Wheel => f.write_str("wheel"),
             Self::Editable => f.write_str("editable"),
+            Self::Sdist => f.write_str("sdist"),
         }
     }
 }
@@ -287,6 +369,168 @@ impl NoBuild {
     }
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum NoBuildIsolation {
+    /// Build all source distributions in isolated environments.
+    #[default]
+    None,
+
+    /// Build all source distributions in the target environment, without isolation.
+    All,
+
+    /// Build the given packages' source distributions in the target environment, without
+    /// isolation.
+    Packages(Vec<PackageName>),
+}
+
+impl NoBuildIsolation {
+    /// Determine the build isolation strategy to use.
+    pub fn from_args(
+        no_build_isolation: bool,
+        no_build_isolation_package: Vec<PackageName>,
+    ) -> Self {
+        if no_build_isolation {
+            Self::All
+        } else if no_build_isolation_package.is_empty() {
+            Self::None
+        } else {
+            Self::Packages(no_build_isolation_package)
+        }
+    }
+
+    /// Return the [`BuildIsolation`] to use when building against the given environment.
+    ///
+    /// Without isolation, build backends run in the given environment (e.g., the target virtual
+    /// environment, or the interpreter's own environment when there is none), which is assumed to
+    /// contain the build requirements already.
+    pub fn build_isolation<'a>(&'a self, venv: &'a PythonEnvironment) -> BuildIsolation<'a> {
+        match self {
+            Self::None => BuildIsolation::Isolated,
+            Self::All => BuildIsolation::Shared(venv),
+            Self::Packages(packages) => BuildIsolation::SharedPackage(venv, packages),
+        }
+    }
+}
+
+/// The environment in which to run the build backend for a source distribution.
+#[derive(Debug, Default, Copy, Clone)]
+pub enum BuildIsolation<'a> {
+    /// Create an isolated environment for each build, with the `build-system.requires` installed.
+    #[default]
+    Isolated,
+
+    /// Build in the given environment, assuming the build requirements are already installed.
+    Shared(&'a PythonEnvironment),
+
+    /// Build the given packages in the given environment, and all other packages in isolation.
+    SharedPackage(&'a PythonEnvironment, &'a [PackageName]),
+}
+
+impl<'a> BuildIsolation<'a> {
+    /// Return the shared environment in which to build the given package, or `None` if the
+    /// package should be built in isolation.
+    ///
+    /// Builds for which the package name is unknown (e.g., editables) are only shared if isolation
+    /// is disabled for all packages.
+    pub fn shared_environment(
+        &self,
+        package: Option<&PackageName>,
+    ) -> Option<&'a PythonEnvironment> {
+        match *self {
+            Self::Isolated => None,
+            Self::Shared(venv) => Some(venv),
+            Self::SharedPackage(venv, packages) => package
+                .is_some_and(|package| packages.contains(package))
+                .then_some(venv),
+        }
+    }
+}
+
+/// The hashes that distributions are required to match when installed, keyed by package name.
+#[derive(Debug, Default, Clone)]
+pub struct RequiredHashes {
+    hashes: FxHashMap<PackageName, Vec<HashDigest>>,
+    /// Whether every distribution must have a hash (i.e., `--require-hashes` mode).
+    strict: bool,
+}
+
+impl RequiredHashes {
+    /// Generate the [`RequiredHashes`] from a set of requirements and their `--hash` values.
+    ///
+    /// If `require_hashes` is set, every requirement that applies to the current environment
+    /// must be pinned to an exact version (or URL) and include at least one hash.
+    pub fn from_requirements<'a>(
+        requirements: impl Iterator<Item = &'a Requirement>,
+        hashes: FxHashMap<PackageName, Vec<HashDigest>>,
+        markers: &MarkerEnvironment,
+        require_hashes: bool,
+    ) -> Result<Self, RequiredHashesError> {
+        if require_hashes {
+            for requirement in requirements {
+                // Skip any requirements that don't apply to the current environment.
+                if !requirement.evaluate_markers(markers, &[]) {
+                    continue;
+                }
+
+                // Every requirement must be pinned, e.g., `flask==3.0.0`.
+                let pinned = match requirement.version_or_url.as_ref() {
+                    Some(VersionOrUrl::Url(_)) => true,
+                    Some(VersionOrUrl::VersionSpecifier(specifiers)) => match &**specifiers {
+                        [specifier] => {
+                            matches!(specifier.operator(), Operator::Equal | Operator::ExactEqual)
+                        }
+                        _ => false,
+                    },
+                    None => false,
+                };
+                if !pinned {
+                    return Err(RequiredHashesError::UnpinnedRequirement(
+                        requirement.to_string(),
+                    ));
+                }
+
+                // Every requirement must include at least one hash.
+                if hashes.get(&requirement.name).map_or(true, Vec::is_empty) {
+                    return Err(RequiredHashesError::MissingHashes(requirement.to_string()));
+                }
+            }
+        }
+
+        Ok(Self {
+            hashes,
+            strict: require_hashes,
+        })
+    }
+
+    /// Return the hashes that the given package is required to match, if any.
+    pub fn get(&self, name: &PackageName) -> &[HashDigest] {
+        self.hashes.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns `true` if any hashes are required for the given package.
+    pub fn contains(&self, name: &PackageName) -> bool {
+        self.hashes.contains_key(name)
+    }
+
+    /// Returns `true` if no hashes are required for any package.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Returns `true` if every distribution must match a hash, as in `--require-hashes` mode.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RequiredHashesError {
+    #[error("In `--require-hashes` mode, all requirements must have their versions pinned with `==`, but found: {0}")]
+    UnpinnedRequirement(String),
+    #[error("In `--require-hashes` mode, all requirements must have a hash, but none were provided for: {0}")]
+    MissingHashes(String),
+}
+
 #[derive(Debug, Clone)]
 pub struct ConfigSettingEntry {
     /// The key of the setting. For example, given `key=value`, this would be `key`.
@@ -295,6 +539,16 @@ pub struct ConfigSettingEntry {
     value: String,
 }
 
+impl ConfigSettingEntry {
+    /// Create a new [`ConfigSettingEntry`] from a key and value.
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
 impl FromStr for ConfigSettingEntry {
     type Err = anyhow::Error;
 
@@ -311,6 +565,77 @@ impl FromStr for ConfigSettingEntry {
     }
 }
 
+/// A PEP 517 config setting for a specific package, parsed from a `PACKAGE:KEY=VALUE` string.
+#[derive(Debug, Clone)]
+pub struct PackageConfigSettingEntry {
+    /// The package to which the setting applies.
+    package: PackageName,
+    /// The setting itself.
+    setting: ConfigSettingEntry,
+}
+
+impl PackageConfigSettingEntry {
+    /// Create a new [`PackageConfigSettingEntry`] from a package name and a setting.
+    pub fn new(package: PackageName, setting: ConfigSettingEntry) -> Self {
+        Self { package, setting }
+    }
+}
+
+impl FromStr for PackageConfigSettingEntry {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((package, setting)) = s.split_once(':') else {
+            return Err(anyhow::anyhow!(
+                "Invalid config setting: {s} (expected `PACKAGE:KEY=VALUE`)"
+            ));
+        };
+        Ok(Self {
+            package: PackageName::from_str(package.trim())?,
+            setting: ConfigSettingEntry::from_str(setting)?,
+        })
+    }
+}
+
+/// An environment variable to set when building a specific package, parsed from a
+/// `PACKAGE:NAME=VALUE` string.
+#[derive(Debug, Clone)]
+pub struct PackageEnvEntry {
+    /// The package to which the environment variable applies.
+    package: PackageName,
+    /// The name of the environment variable.
+    name: String,
+    /// The value of the environment variable.
+    value: String,
+}
+
+impl PackageEnvEntry {
+    /// Create a new [`PackageEnvEntry`] from a package name, variable name, and value.
+    pub fn new(package: PackageName, name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            package,
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl FromStr for PackageEnvEntry {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || anyhow::anyhow!("Invalid environment variable: {s} (expected `PACKAGE:NAME=VALUE`)");
+        let (package, variable) = s.split_once(':').ok_or_else(invalid)?;
+        let (name, value) = variable.split_once('=').ok_or_else(invalid)?;
+        Ok(Self {
+            package: PackageName::from_str(package.trim())?,
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum ConfigSettingValue {
     /// The value consists of a single string.
@@ -349,6 +674,116 @@ impl FromIterator<ConfigSettingEntry> for ConfigSettings {
     }
 }
 
+impl ConfigSettings {
+    /// Returns `true` if there are no settings.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Merge the given settings into these settings. For any key present in both, the value in
+    /// `other` replaces the value in `self`.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut config = self.0.clone();
+        config.extend(
+            other
+                .0
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        Self(config)
+    }
+}
+
+impl CacheKey for ConfigSettings {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        self.0.len().cache_key(state);
+        for (key, value) in &self.0 {
+            key.cache_key(state);
+            match value {
+                ConfigSettingValue::String(value) => {
+                    0u8.cache_key(state);
+                    value.cache_key(state);
+                }
+                ConfigSettingValue::List(values) => {
+                    1u8.cache_key(state);
+                    values.cache_key(state);
+                }
+            }
+        }
+    }
+}
+
+/// The PEP 517 config settings and build environment variables that apply to specific packages
+/// only, in addition to those that apply to all builds.
+#[derive(Debug, Default, Clone)]
+pub struct PackageBuildSettings {
+    config_settings: BTreeMap<PackageName, ConfigSettings>,
+    environment_variables: BTreeMap<PackageName, BTreeMap<String, String>>,
+}
+
+impl PackageBuildSettings {
+    /// Collect the package-specific settings from the given entries.
+    pub fn from_entries(
+        config_settings: Vec<PackageConfigSettingEntry>,
+        environment_variables: Vec<PackageEnvEntry>,
+    ) -> Self {
+        let mut grouped: BTreeMap<PackageName, Vec<ConfigSettingEntry>> = BTreeMap::new();
+        for entry in config_settings {
+            grouped
+                .entry(entry.package)
+                .or_default()
+                .push(entry.setting);
+        }
+        let config_settings = grouped
+            .into_iter()
+            .map(|(package, entries)| (package, entries.into_iter().collect()))
+            .collect();
+
+        let mut grouped: BTreeMap<PackageName, BTreeMap<String, String>> = BTreeMap::new();
+        for entry in environment_variables {
+            grouped
+                .entry(entry.package)
+                .or_default()
+                .insert(entry.name, entry.value);
+        }
+
+        Self {
+            config_settings,
+            environment_variables: grouped,
+        }
+    }
+
+    /// Returns `true` if there are no package-specific settings.
+    pub fn is_empty(&self) -> bool {
+        self.config_settings.is_empty() && self.environment_variables.is_empty()
+    }
+
+    /// Return the config settings with which to build the given package: the settings for all
+    /// builds, overridden by any settings specific to the package.
+    ///
+    /// Builds for which the package name is unknown (e.g., editables) only receive the settings
+    /// for all builds.
+    pub fn config_settings(
+        &self,
+        package: Option<&PackageName>,
+        config_settings: &ConfigSettings,
+    ) -> ConfigSettings {
+        match package.and_then(|package| self.config_settings.get(package)) {
+            Some(package_config_settings) => config_settings.merge(package_config_settings),
+            None => config_settings.clone(),
+        }
+    }
+
+    /// Return the environment variables to set when building the given package, if any.
+    pub fn environment_variables(
+        &self,
+        package: Option<&PackageName>,
+    ) -> Option<&BTreeMap<String, String>> {
+        package.and_then(|package| self.environment_variables.get(package))
+    }
+}
+
 #[cfg(feature = "serde")]
 impl ConfigSettings {
     /// Convert the settings to a string that can be passed directly to a PEP 517 build backend.
@@ -477,6 +912,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn package_build_settings() -> Result<(), Error> {
+        let settings = PackageBuildSettings::from_entries(
+            vec![
+                PackageConfigSettingEntry::from_str("scipy:setup-args=-Dblas=openblas")?,
+                PackageConfigSettingEntry::from_str("SciPy:key=value")?,
+            ],
+            vec![PackageEnvEntry::from_str("flash-attn:MAX_JOBS=4")?],
+        );
+        let defaults = vec![
+            ConfigSettingEntry::new("key", "default"),
+            ConfigSettingEntry::new("other", "default"),
+        ]
+        .into_iter()
+        .collect::<ConfigSettings>();
+
+        // Package-specific settings take precedence over the settings for all builds.
+        let scipy = PackageName::from_str("scipy")?;
+        let config_settings = settings.config_settings(Some(&scipy), &defaults);
+        assert_eq!(
+            config_settings.0.get("setup-args"),
+            Some(&ConfigSettingValue::String("-Dblas=openblas".to_string()))
+        );
+        assert_eq!(
+            config_settings.0.get("key"),
+            Some(&ConfigSettingValue::String("value".to_string()))
+        );
+        assert_eq!(
+            config_settings.0.get("other"),
+            Some(&ConfigSettingValue::String("default".to_string()))
+        );
+        assert!(settings.environment_variables(Some(&scipy)).is_none());
+
+        // Other packages, and builds without a package name, receive the defaults.
+        let flash_attn = PackageName::from_str("flash-attn")?;
+        let config_settings = settings.config_settings(Some(&flash_attn), &defaults);
+        assert_eq!(config_settings.0, defaults.0);
+        let config_settings = settings.config_settings(None, &defaults);
+        assert_eq!(config_settings.0, defaults.0);
+        assert_eq!(
+            settings
+                .environment_variables(Some(&flash_attn))
+                .and_then(|vars| vars.get("MAX_JOBS"))
+                .map(String::as_str),
+            Some("4")
+        );
+        assert!(settings.environment_variables(None).is_none());
+
+        // Entries without a package are rejected.
+        assert!(PackageConfigSettingEntry::from_str("key=value").is_err());
+        assert!(PackageEnvEntry::from_str("MAX_JOBS=4").is_err());
+
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn escape_for_python() {
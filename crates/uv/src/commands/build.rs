@@ -0,0 +1,233 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use platform_host::Platform;
+use uv_auth::KeyringProvider;
+use uv_build::BuildLogOptions;
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, ProxyConfig, RegistryClientBuilder, TlsConfig,
+    TrustedHost,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Simplified;
+use uv_installer::NoBinary;
+use uv_interpreter::{find_default_python, find_requested_python, Interpreter, PythonEnvironment};
+use uv_resolver::{InMemoryIndex, OptionsBuilder};
+use uv_traits::{
+    BuildContext, BuildKind, Concurrency, ConfigSettings, InFlight, NoBuild, NoBuildIsolation,
+    SetupPyStrategy,
+};
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{read_build_constraints, RequirementsSource};
+
+/// The distributions to build from a source tree.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum BuildTarget {
+    /// Build a source distribution, then build a wheel from that source distribution.
+    SdistThenWheel,
+    /// Build a source distribution only.
+    Sdist,
+    /// Build a wheel directly from the source tree.
+    Wheel,
+    /// Build a source distribution and a wheel, both directly from the source tree.
+    SdistAndWheel,
+}
+
+impl BuildTarget {
+    /// Determine the build target from the `--sdist` and `--wheel` flags, matching the semantics
+    /// of `python -m build`.
+    pub(crate) fn from_args(sdist: bool, wheel: bool) -> Self {
+        match (sdist, wheel) {
+            (false, false) => Self::SdistThenWheel,
+            (true, false) => Self::Sdist,
+            (false, true) => Self::Wheel,
+            (true, true) => Self::SdistAndWheel,
+        }
+    }
+}
+
+/// Build source distributions and wheels from a source tree.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn build(
+    src: &Path,
+    out_dir: Option<&Path>,
+    target: BuildTarget,
+    python_request: Option<&str>,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    config_settings: &ConfigSettings,
+    no_build_isolation: &NoBuildIsolation,
+    build_constraints: &[RequirementsSource],
+    connectivity: Connectivity,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    retries: u32,
+    concurrency: Concurrency,
+    build_log_options: &BuildLogOptions,
+    exclude_newer: Option<DateTime<Utc>>,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    if !src.is_dir() {
+        return Err(anyhow!(
+            "Source tree not found at: {}",
+            src.simplified_display()
+        ));
+    }
+
+    // By default, write the distributions to `dist/` within the source tree.
+    let out_dir = out_dir.map_or_else(|| src.join("dist"), Path::to_path_buf);
+    fs_err::create_dir_all(&out_dir)?;
+
+    // Locate the Python interpreter, preferring the active virtual environment.
+    let platform = Platform::current()?;
+    let interpreter = find_interpreter(python_request, platform, cache)?;
+    debug!(
+        "Using Python {} interpreter at {} for builds",
+        interpreter.python_version(),
+        interpreter.sys_executable().simplified_display()
+    );
+
+    // Initialize the registry client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .connectivity(connectivity)
+        .tls(tls)
+        .proxy(proxy)
+        .retries(retries)
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_hosts)
+        .build();
+
+    let build_constraints = read_build_constraints(build_constraints, &client).await?;
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let tags = interpreter.tags()?;
+        let client = FlatIndexClient::new(&client, cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, Some(tags))
+    };
+
+    // Create a shared in-memory index.
+    let index = InMemoryIndex::default();
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    let build_environment = PythonEnvironment::from_interpreter(
+        interpreter.clone(),
+        interpreter.prefix().to_path_buf(),
+    );
+
+    // Prep the build context. Like `python -m build`, always perform a PEP 517 build; the build
+    // environments are resolved (and cached) as for any other source distribution build.
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        cache,
+        &interpreter,
+        &index_locations,
+        &flat_index,
+        &index,
+        &in_flight,
+        SetupPyStrategy::Pep517,
+        config_settings,
+        &NoBuild::None,
+        &NoBinary::None,
+    )
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+    .with_concurrency(concurrency)
+    .with_build_log_options(build_log_options.clone())
+    .with_build_isolation(no_build_isolation.build_isolation(&build_environment))
+    .with_build_constraints(&build_constraints);
+
+    // Build the source distribution first (if requested), such that the wheel can be built from
+    // it rather than from the source tree.
+    let build_kinds: &[BuildKind] = match target {
+        BuildTarget::Sdist => &[BuildKind::Sdist],
+        BuildTarget::Wheel => &[BuildKind::Wheel],
+        BuildTarget::SdistThenWheel | BuildTarget::SdistAndWheel => {
+            &[BuildKind::Sdist, BuildKind::Wheel]
+        }
+    };
+    let package_id = src.simplified_display().to_string();
+    let mut built: Vec<String> = Vec::with_capacity(build_kinds.len());
+    for &build_kind in build_kinds {
+        let source = match (target, build_kind, built.first()) {
+            (BuildTarget::SdistThenWheel, BuildKind::Wheel, Some(sdist)) => out_dir.join(sdist),
+            _ => src.to_path_buf(),
+        };
+        built.push(build_dist(&build_dispatch, &source, &out_dir, &package_id, build_kind).await?);
+    }
+
+    let s = if built.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Built {} {}",
+            format!("{} distribution{s}", built.len()).bold(),
+            format!("in {}", elapsed(start.elapsed())).dimmed()
+        )
+        .dimmed()
+    )?;
+    for filename in built {
+        writeln!(printer.stderr(), " {} {}", "+".green(), filename.bold())?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Build a single distribution of the given kind from a source tree or source distribution,
+/// returning the filename of the built distribution within `out_dir`.
+async fn build_dist(
+    build_dispatch: &BuildDispatch<'_>,
+    source: &Path,
+    out_dir: &Path,
+    package_id: &str,
+    build_kind: BuildKind,
+) -> Result<String> {
+    debug!(
+        "Building {} from: {}",
+        build_kind.description(),
+        source.simplified_display()
+    );
+    let builder = build_dispatch
+        .setup_build(source, None, package_id, None, build_kind)
+        .await?;
+    let filename = builder
+        .build(out_dir)
+        .await
+        .with_context(|| format!("Failed to build {}", build_kind.description()))?;
+    Ok(filename)
+}
+
+/// Find the interpreter to build with: the requested Python, if any, then the active virtual
+/// environment, then the default Python.
+fn find_interpreter(
+    python_request: Option<&str>,
+    platform: Platform,
+    cache: &Cache,
+) -> Result<Interpreter> {
+    if let Some(python_request) = python_request {
+        return find_requested_python(python_request, &platform, cache)?
+            .ok_or_else(|| anyhow!("No Python interpreter found for: {python_request}"));
+    }
+    match PythonEnvironment::from_virtualenv(platform.clone(), cache) {
+        Ok(venv) => Ok(venv.interpreter().clone()),
+        Err(uv_interpreter::Error::VenvNotFound) => Ok(find_default_python(&platform, cache)?),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt::Write;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use platform_host::Platform;
+use uv_auth::KeyringProvider;
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, ProxyConfig, RegistryClientBuilder, TlsConfig,
+    TrustedHost,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Simplified;
+use uv_installer::NoBinary;
+use uv_interpreter::{Interpreter, PythonVersion, TargetTriple};
+use uv_resolver::{InMemoryIndex, OptionsBuilder, Prefetcher, PythonRequirement};
+use uv_traits::{Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+
+use crate::commands::{elapsed, resolution_environment, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+
+/// Populate the cache with the index metadata required to resolve a set of requirements offline.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn cache_prefetch(
+    requirements: &[RequirementsSource],
+    constraints: &[RequirementsSource],
+    overrides: &[RequirementsSource],
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    retries: u32,
+    concurrency: Concurrency,
+    no_binary: &NoBinary,
+    python_version: Option<PythonVersion>,
+    python_platform: Option<TargetTriple>,
+    exclude_newer: Option<DateTime<Utc>>,
+    cache: Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Initialize the registry client.
+    let client_builder = RegistryClientBuilder::new(cache.clone())
+        .connectivity(Connectivity::Online)
+        .tls(tls)
+        .proxy(proxy)
+        .retries(retries)
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_hosts.clone());
+    let client = client_builder.clone().build();
+
+    // Read all requirements from the provided sources.
+    let RequirementsSpecification {
+        project: _,
+        requirements,
+        constraints,
+        overrides,
+        editables,
+        index_url,
+        extra_index_urls,
+        no_index,
+        find_links,
+        trusted_hosts: source_trusted_hosts,
+        extras: _,
+        hashes: _,
+    } = RequirementsSpecification::from_sources(
+        requirements,
+        constraints,
+        overrides,
+        &ExtrasSpecification::None,
+        &client,
+    )
+    .await?;
+
+    if !editables.is_empty() {
+        return Err(anyhow!(
+            "Editable requirements are not supported by `uv cache prefetch`"
+        ));
+    }
+
+    // Find an interpreter to determine the target environment.
+    let platform = Platform::current()?;
+    let interpreter = Interpreter::find_best(python_version.as_ref(), &platform, &cache)?;
+    debug!(
+        "Using Python {} interpreter at {}",
+        interpreter.python_version(),
+        interpreter.sys_executable().simplified_display().cyan()
+    );
+
+    // Determine the tags and markers to use for resolution.
+    let (tags, markers) = resolution_environment(python_version, python_platform, &interpreter)?;
+
+    // Incorporate any index locations from the provided sources.
+    let index_locations =
+        index_locations.combine(index_url, extra_index_urls, find_links, no_index);
+
+    // Rebuild the client, to take into account any index URLs and trusted hosts added by the
+    // sources (e.g., `--index-url` or `--trusted-host` in a `requirements.txt` file).
+    let client = client_builder
+        .index_urls(index_locations.index_urls())
+        .trusted_hosts(
+            trusted_hosts
+                .into_iter()
+                .chain(source_trusted_hosts)
+                .collect(),
+        )
+        .build();
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, &cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, Some(&tags))
+    };
+
+    // Create a shared in-memory index.
+    let index = InMemoryIndex::default();
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    // Source distributions are reported as uncached rather than built, so the build settings are
+    // never consulted.
+    let config_settings = ConfigSettings::default();
+
+    // Prep the build context.
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        &cache,
+        &interpreter,
+        &index_locations,
+        &flat_index,
+        &index,
+        &in_flight,
+        SetupPyStrategy::default(),
+        &config_settings,
+        &NoBuild::All,
+        no_binary,
+    )
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+    .with_concurrency(concurrency);
+
+    // Walk the candidate versions of every reachable package.
+    let python_requirement = PythonRequirement::new(&interpreter, &markers);
+    let report = Prefetcher::new(
+        &markers,
+        python_requirement,
+        &tags,
+        &client,
+        &flat_index,
+        exclude_newer,
+        &build_dispatch,
+    )
+    .prefetch(requirements, constraints, overrides)
+    .await?;
+
+    let s = if report.packages == 1 { "" } else { "s" };
+    let w = if report.metadata == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Prefetched {} and {} in {}",
+            format!("{} package{}", report.packages, s).bold(),
+            format!("{} wheel{}", report.metadata, w).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    if report.uncached.is_empty() {
+        return Ok(ExitStatus::Success);
+    }
+
+    // Report anything that a subsequent offline resolution would be unable to read from the cache.
+    writeln!(
+        printer.stderr(),
+        "{}",
+        "The following could not be prefetched, and will require network access to resolve:"
+            .yellow()
+            .bold()
+    )?;
+    for uncached in report
+        .uncached
+        .iter()
+        .sorted_unstable_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)))
+    {
+        match &uncached.version {
+            Some(version) => writeln!(
+                printer.stderr(),
+                " {} {}{}: {}",
+                "-".yellow(),
+                uncached.name.bold(),
+                format!("=={version}").bold(),
+                uncached.reason
+            )?,
+            None => writeln!(
+                printer.stderr(),
+                " {} {}: {}",
+                "-".yellow(),
+                uncached.name.bold(),
+                uncached.reason
+            )?,
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::Write;
 use std::process::ExitCode;
 use std::time::Duration;
@@ -5,33 +6,48 @@ use std::time::Duration;
 use anyhow::Context;
 use owo_colors::OwoColorize;
 
+pub(crate) use build::{build, BuildTarget};
 pub(crate) use cache_clean::cache_clean;
 pub(crate) use cache_dir::cache_dir;
+pub(crate) use cache_prefetch::cache_prefetch;
 use distribution_types::InstalledMetadata;
-pub(crate) use pip_compile::{extra_name_with_clap_error, pip_compile, Upgrade};
+use pep508_rs::MarkerEnvironment;
+pub(crate) use pip_check::pip_check;
+pub(crate) use pip_compile::{extra_name_with_clap_error, pip_compile, OutputFormat, Upgrade};
+pub(crate) use pip_download::pip_download;
 pub(crate) use pip_freeze::pip_freeze;
 pub(crate) use pip_install::pip_install;
 pub(crate) use pip_list::pip_list;
 pub(crate) use pip_show::pip_show;
 pub(crate) use pip_sync::pip_sync;
+pub(crate) use pip_tree::pip_tree;
 pub(crate) use pip_uninstall::pip_uninstall;
+use platform_tags::Tags;
+pub(crate) use reporters::BuildOutputReporter;
 use uv_cache::Cache;
 use uv_fs::Simplified;
 use uv_installer::compile_tree;
-use uv_interpreter::PythonEnvironment;
+use uv_interpreter::{Interpreter, PythonEnvironment, PythonVersion, TargetTriple};
+use uv_traits::NoBuild;
+use uv_warnings::warn_user;
 pub(crate) use venv::venv;
 pub(crate) use version::version;
 
 use crate::printer::Printer;
 
+mod build;
 mod cache_clean;
 mod cache_dir;
+mod cache_prefetch;
+mod pip_check;
 mod pip_compile;
+mod pip_download;
 mod pip_freeze;
 mod pip_install;
 mod pip_list;
 mod pip_show;
 mod pip_sync;
+mod pip_tree;
 mod pip_uninstall;
 mod reporters;
 mod venv;
@@ -62,6 +78,68 @@ impl From<ExitStatus> for ExitCode {
     }
 }
 
+/// Determine the tags and markers to use for resolution, applying any requested Python version
+/// or platform to those of the interpreter.
+pub(super) fn resolution_environment(
+    python_version: Option<PythonVersion>,
+    python_platform: Option<TargetTriple>,
+    interpreter: &Interpreter,
+) -> anyhow::Result<(Cow<'_, Tags>, Cow<'_, MarkerEnvironment>)> {
+    let tags = if python_version.is_some() || python_platform.is_some() {
+        let platform = python_platform.map(TargetTriple::platform);
+        Cow::Owned(Tags::from_env(
+            platform.as_ref().unwrap_or(interpreter.platform()),
+            python_version.as_ref().map_or_else(
+                || interpreter.python_tuple(),
+                |python_version| (python_version.major(), python_version.minor()),
+            ),
+            interpreter.implementation_name(),
+            interpreter.implementation_tuple(),
+        )?)
+    } else {
+        Cow::Borrowed(interpreter.tags()?)
+    };
+    let markers = match (python_platform, python_version) {
+        (Some(python_platform), Some(python_version)) => {
+            Cow::Owned(python_version.markers(&python_platform.markers(interpreter.markers())))
+        }
+        (Some(python_platform), None) => Cow::Owned(python_platform.markers(interpreter.markers())),
+        (None, Some(python_version)) => Cow::Owned(python_version.markers(interpreter.markers())),
+        (None, None) => Cow::Borrowed(interpreter.markers()),
+    };
+    Ok((tags, markers))
+}
+
+/// Warn if the interpreter used to build source distributions doesn't match the requested Python
+/// version.
+pub(super) fn warn_python_version_mismatch(
+    python_version: Option<&PythonVersion>,
+    interpreter: &Interpreter,
+    no_build: &NoBuild,
+) {
+    let Some(python_version) = python_version else {
+        return;
+    };
+
+    // If the requested version does not match the version we're using warn the user
+    // _unless_ they have not specified a patch version and that is the only difference
+    // _or_ if builds are disabled
+    let matches_without_patch = {
+        python_version.major() == interpreter.python_major()
+            && python_version.minor() == interpreter.python_minor()
+    };
+    if no_build.is_none()
+        && python_version.version() != interpreter.python_version()
+        && (python_version.patch().is_some() || !matches_without_patch)
+    {
+        warn_user!(
+            "The requested Python version {} is not available; {} will be used to build dependencies instead.",
+            python_version.version(),
+            interpreter.python_version(),
+        );
+    }
+}
+
 /// Format a duration as a human-readable string, Cargo-style.
 pub(super) fn elapsed(duration: Duration) -> String {
     let secs = duration.as_secs();
@@ -0,0 +1,92 @@
+use std::fmt::Write;
+use std::time::Instant;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+
+/// Check that the installed packages in the current environment are consistent.
+pub(crate) fn pip_check(
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let start = Instant::now();
+
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, &platform, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(&platform, cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(platform.clone(), cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(&platform, cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().simplified_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+
+    // Validate the environment, sorting the results for stable output.
+    let mut messages = site_packages
+        .check()?
+        .iter()
+        .map(uv_installer::Diagnostic::message)
+        .collect::<Vec<_>>();
+    messages.sort_unstable();
+
+    let packages = site_packages.iter().count();
+    let s = if packages == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Checked {} in {}",
+            format!("{packages} package{s}").bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    if messages.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "{}",
+            "All installed packages are compatible".green()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let s = if messages.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!("Found {} issue{s}", messages.len()).bold()
+    )?;
+    for message in messages {
+        writeln!(printer.stdout(), "{message}")?;
+    }
+
+    Ok(ExitStatus::Failure)
+}
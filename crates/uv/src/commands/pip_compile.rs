@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::env;
 use std::fmt::Write;
 use std::io::stdout;
@@ -14,30 +14,38 @@ use owo_colors::OwoColorize;
 use rustc_hash::FxHashSet;
 use tempfile::tempdir_in;
 use tracing::debug;
+use url::Url;
 
-use distribution_types::{IndexLocations, LocalEditable, Verbatim};
+use distribution_types::{IndexLocations, LocalEditable};
 use platform_host::Platform;
-use platform_tags::Tags;
 use requirements_txt::EditableRequirement;
+use uv_auth::KeyringProvider;
+use uv_build::BuildLogOptions;
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, ProxyConfig, RegistryClientBuilder, TlsConfig,
+    TrustedHost,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::{Downloader, NoBinary};
-use uv_interpreter::{Interpreter, PythonVersion};
+use uv_interpreter::{Interpreter, PythonEnvironment, PythonVersion, TargetTriple};
 use uv_normalize::{ExtraName, PackageName};
 use uv_resolver::{
-    AnnotationStyle, DependencyMode, DisplayResolutionGraph, InMemoryIndex, Manifest,
+    AnnotationStyle, DependencyMode, DisplayResolutionGraph, InMemoryIndex, Lock, Manifest,
     OptionsBuilder, PreReleaseMode, PythonRequirement, ResolutionMode, Resolver,
 };
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
-use uv_warnings::warn_user;
+use uv_traits::{
+    Concurrency, ConfigSettings, InFlight, NoBuild, NoBuildIsolation, PackageBuildSettings,
+    SetupPyStrategy,
+};
 
 use crate::commands::reporters::{DownloadReporter, ResolverReporter};
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::{elapsed, resolution_environment, warn_python_version_mismatch, ExitStatus};
 use crate::printer::Printer;
 use crate::requirements::{
-    read_lockfile, ExtrasSpecification, RequirementsSource, RequirementsSpecification,
+    is_lockfile, read_build_constraints, read_lockfile, ExtrasSpecification, RequirementsSource,
+    RequirementsSpecification,
 };
 
 /// Resolve a set of requirements into a set of pinned versions.
@@ -48,6 +56,7 @@ pub(crate) async fn pip_compile(
     overrides: &[RequirementsSource],
     extras: ExtrasSpecification<'_>,
     output_file: Option<&Path>,
+    output_format: OutputFormat,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
@@ -58,12 +67,26 @@ pub(crate) async fn pip_compile(
     include_header: bool,
     include_index_url: bool,
     include_find_links: bool,
+    include_trusted_host: bool,
+    include_build_requirements: bool,
     index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
     setup_py: SetupPyStrategy,
     config_settings: ConfigSettings,
+    package_build_settings: PackageBuildSettings,
+    no_build_isolation: NoBuildIsolation,
+    build_constraints: &[RequirementsSource],
     connectivity: Connectivity,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    retries: u32,
+    concurrency: Concurrency,
+    build_log_options: &BuildLogOptions,
     no_build: &NoBuild,
     python_version: Option<PythonVersion>,
+    python_platform: Option<TargetTriple>,
+    universal: bool,
     exclude_newer: Option<DateTime<Utc>>,
     annotation_style: AnnotationStyle,
     quiet: bool,
@@ -83,10 +106,33 @@ pub(crate) async fn pip_compile(
         ));
     }
 
+    // The lockfile format records hashes, sources, and build requirements for every package, so
+    // the `requirements.txt`-specific output options can't be honored.
+    if output_format.is_lock() {
+        let unsupported = [
+            ("--generate-hashes", generate_hashes),
+            ("--no-emit-package", !no_emit_packages.is_empty()),
+            ("--emit-index-url", include_index_url),
+            ("--emit-find-links", include_find_links),
+            ("--emit-trusted-host", include_trusted_host),
+            ("--emit-build-requirements", include_build_requirements),
+        ];
+        if let Some((flag, _)) = unsupported.iter().find(|(_, enabled)| *enabled) {
+            return Err(anyhow!(
+                "`{flag}` is not supported when writing a lockfile (`--output-format lock`)"
+            ));
+        }
+    }
+
     // Initialize the registry client.
-    let client = RegistryClientBuilder::new(cache.clone())
+    let client_builder = RegistryClientBuilder::new(cache.clone())
         .connectivity(connectivity)
-        .build();
+        .tls(tls)
+        .proxy(proxy)
+        .retries(retries)
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_hosts.clone());
+    let client = client_builder.clone().build();
 
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
@@ -99,7 +145,9 @@ pub(crate) async fn pip_compile(
         extra_index_urls,
         no_index,
         find_links,
+        trusted_hosts: source_trusted_hosts,
         extras: used_extras,
+        hashes: _hashes,
     } = RequirementsSpecification::from_sources(
         requirements,
         constraints,
@@ -127,7 +175,7 @@ pub(crate) async fn pip_compile(
     }
 
     // Read the lockfile, if present.
-    let preferences = read_lockfile(output_file, upgrade).await?;
+    let preferences = read_lockfile(output_file, output_format, upgrade).await?;
 
     // Find an interpreter to use for building distributions
     let platform = Platform::current()?;
@@ -137,72 +185,59 @@ pub(crate) async fn pip_compile(
         interpreter.python_version(),
         interpreter.sys_executable().simplified_display().cyan()
     );
-    if let Some(python_version) = python_version.as_ref() {
-        // If the requested version does not match the version we're using warn the user
-        // _unless_ they have not specified a patch version and that is the only difference
-        // _or_ if builds are disabled
-        let matches_without_patch = {
-            python_version.major() == interpreter.python_major()
-                && python_version.minor() == interpreter.python_minor()
-        };
-        if no_build.is_none()
-            && python_version.version() != interpreter.python_version()
-            && (python_version.patch().is_some() || !matches_without_patch)
-        {
-            warn_user!(
-                "The requested Python version {} is not available; {} will be used to build dependencies instead.",
-                python_version.version(),
-                interpreter.python_version(),
-            );
-        }
-    }
+    warn_python_version_mismatch(python_version.as_ref(), &interpreter, no_build);
 
     // Create a shared in-memory index.
     let source_index = InMemoryIndex::default();
 
-    // If we're resolving against a different Python version, use a separate index. Source
-    // distributions will be built against the installed version, and so the index may contain
-    // different package priorities than in the top-level resolution.
-    let top_level_index = if python_version.is_some() {
+    // If we're resolving against a different Python version or platform, use a separate index.
+    // Source distributions will be built against the installed version, and so the index may
+    // contain different package priorities than in the top-level resolution.
+    let top_level_index = if python_version.is_some() || python_platform.is_some() {
         InMemoryIndexRef::Owned(InMemoryIndex::default())
     } else {
         InMemoryIndexRef::Borrowed(&source_index)
     };
 
     // Determine the tags, markers, and interpreter to use for resolution.
-    let tags = if let Some(python_version) = python_version.as_ref() {
-        Cow::Owned(Tags::from_env(
-            interpreter.platform(),
-            (python_version.major(), python_version.minor()),
-            interpreter.implementation_name(),
-            interpreter.implementation_tuple(),
-        )?)
-    } else {
-        Cow::Borrowed(interpreter.tags()?)
-    };
-    let markers = python_version.map_or_else(
-        || Cow::Borrowed(interpreter.markers()),
-        |python_version| Cow::Owned(python_version.markers(interpreter.markers())),
-    );
+    let (tags, markers) = resolution_environment(python_version, python_platform, &interpreter)?;
 
     // Incorporate any index locations from the provided sources.
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
-    // Update the index URLs on the client, to take into account any index URLs added by the
-    // sources (e.g., `--index-url` in a `requirements.txt` file).
-    let client = client.with_index_url(index_locations.index_urls());
+    // Incorporate any trusted hosts from the provided sources.
+    let trusted_hosts = trusted_hosts
+        .into_iter()
+        .chain(source_trusted_hosts)
+        .unique()
+        .collect::<Vec<_>>();
+
+    // Rebuild the client, to take into account any index URLs and trusted hosts added by the
+    // sources (e.g., `--index-url` or `--trusted-host` in a `requirements.txt` file).
+    let client = client_builder
+        .index_urls(index_locations.index_urls())
+        .trusted_hosts(trusted_hosts.clone())
+        .build();
+
+    let build_constraints = read_build_constraints(build_constraints, &client).await?;
 
-    // Resolve the flat indexes from `--find-links`.
+    // Resolve the flat indexes from `--find-links`. In universal mode, wheels for any platform
+    // are candidates, as with the registry.
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, &tags)
+        FlatIndex::from_entries(entries, (!universal).then_some(&*tags))
     };
 
     // Track in-flight downloads, builds, etc., across resolutions.
     let in_flight = InFlight::default();
 
+    let build_environment = PythonEnvironment::from_interpreter(
+        interpreter.clone(),
+        interpreter.prefix().to_path_buf(),
+    );
+
     let build_dispatch = BuildDispatch::new(
         &client,
         &cache,
@@ -216,7 +251,12 @@ pub(crate) async fn pip_compile(
         no_build,
         &NoBinary::None,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+    .with_concurrency(concurrency)
+    .with_build_log_options(build_log_options.clone())
+    .with_build_isolation(no_build_isolation.build_isolation(&build_environment))
+    .with_build_constraints(&build_constraints)
+    .with_package_build_settings(package_build_settings);
 
     // Build the editables and add their requirements
     let editable_metadata = if editables.is_empty() {
@@ -291,12 +331,21 @@ pub(crate) async fn pip_compile(
         .exclude_newer(exclude_newer)
         .build();
 
+    // If requested, resolve for all environments, rather than the current environment.
+    let python_requirement = PythonRequirement::new(&interpreter, &markers);
+    let markers = if universal {
+        debug!("Performing a universal resolution");
+        None
+    } else {
+        Some(&*markers)
+    };
+
     // Resolve the dependencies.
     let resolver = Resolver::new(
         manifest,
         options,
-        &markers,
-        &interpreter,
+        markers,
+        python_requirement,
         &tags,
         &client,
         &flat_index,
@@ -354,17 +403,24 @@ pub(crate) async fn pip_compile(
         )?;
     }
 
+    // If requested, write the resolution in the lockfile format instead.
+    if output_format.is_lock() {
+        let lock = Lock::from_resolution_graph(&resolution)?;
+        write!(writer, "{}", lock.to_toml()?)?;
+        return Ok(ExitStatus::Success);
+    }
+
     // Write the index locations to the output channel.
     let mut wrote_index = false;
 
     // If necessary, include the `--index-url` and `--extra-index-url` locations.
     if include_index_url {
         if let Some(index) = index_locations.index() {
-            writeln!(writer, "--index-url {}", index.verbatim())?;
+            writeln!(writer, "--index-url {}", index.redacted())?;
             wrote_index = true;
         }
         for extra_index in index_locations.extra_index() {
-            writeln!(writer, "--extra-index-url {}", extra_index.verbatim())?;
+            writeln!(writer, "--extra-index-url {}", extra_index.redacted())?;
             wrote_index = true;
         }
     }
@@ -372,7 +428,15 @@ pub(crate) async fn pip_compile(
     // If necessary, include the `--find-links` locations.
     if include_find_links {
         for flat_index in index_locations.flat_index() {
-            writeln!(writer, "--find-links {flat_index}")?;
+            writeln!(writer, "--find-links {}", flat_index.redacted())?;
+            wrote_index = true;
+        }
+    }
+
+    // If necessary, include the `--trusted-host` entries.
+    if include_trusted_host {
+        for trusted_host in &trusted_hosts {
+            writeln!(writer, "--trusted-host {trusted_host}")?;
             wrote_index = true;
         }
     }
@@ -391,6 +455,7 @@ pub(crate) async fn pip_compile(
             generate_hashes,
             include_annotations,
             annotation_style,
+            index_locations.package_indexes(),
         )
     )?;
 
@@ -411,6 +476,27 @@ pub(crate) async fn pip_compile(
         }
     }
 
+    // If requested, include the pinned build dependencies of any source distributions that were
+    // built during the resolution, or whose builds were read from the cache.
+    if include_build_requirements {
+        let build_requirements = build_dispatch
+            .build_requirements()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<BTreeSet<_>>();
+        if !build_requirements.is_empty() {
+            writeln!(writer)?;
+            writeln!(
+                writer,
+                "{}",
+                "# The following packages were used to build source distributions:".green()
+            )?;
+            for requirement in build_requirements {
+                writeln!(writer, "# {requirement}")?;
+            }
+        }
+    }
+
     Ok(ExitStatus::Success)
 }
 
@@ -475,14 +561,33 @@ fn cmd(include_index_url: bool, include_find_links: bool) -> String {
                 return Some(None);
             }
 
-            // Return the argument.
-            Some(Some(arg))
+            // Return the argument, stripping any credentials from index URLs.
+            Some(Some(redact_credentials(arg)))
         })
         .flatten()
         .join(" ");
     format!("uv {args}")
 }
 
+/// Strip any credentials from a URL embedded in an argument, e.g., `URL`, `--flag=URL`, or
+/// `PACKAGE=URL` (as in `--package-index anyio=URL` or `--package-index=anyio=URL`).
+fn redact_credentials(arg: String) -> String {
+    let Some(separator) = arg.find("://") else {
+        return arg;
+    };
+    // The URL starts after the last `=` that precedes its scheme, if any.
+    let start = arg[..separator].rfind('=').map_or(0, |index| index + 1);
+    let Ok(mut url) = Url::parse(&arg[start..]) else {
+        return arg;
+    };
+    if url.username().is_empty() && url.password().is_none() {
+        return arg;
+    }
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    format!("{}{url}", &arg[..start])
+}
+
 /// A multi-casting writer that writes to both the standard output and an output file, if present.
 #[allow(clippy::disallowed_types)]
 struct OutputWriter {
@@ -523,6 +628,36 @@ impl OutputWriter {
     }
 }
 
+/// The format in which to write the resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// Write the resolution as a pinned `requirements.txt` file.
+    RequirementsTxt,
+
+    /// Write the resolution in the `uv.lock` format, which records the source, artifacts, and
+    /// dependencies of every package.
+    Lock,
+}
+
+impl OutputFormat {
+    /// Determine the output format from the command-line arguments, defaulting to the lockfile
+    /// format if the output file is named `uv.lock`.
+    pub(crate) fn from_args(output_format: Option<Self>, output_file: Option<&Path>) -> Self {
+        output_format.unwrap_or_else(|| {
+            if output_file.is_some_and(is_lockfile) {
+                Self::Lock
+            } else {
+                Self::RequirementsTxt
+            }
+        })
+    }
+
+    /// Returns `true` if the resolution should be written in the lockfile format.
+    pub(crate) fn is_lock(self) -> bool {
+        matches!(self, Self::Lock)
+    }
+}
+
 /// Whether to allow package upgrades.
 #[derive(Debug)]
 pub(crate) enum Upgrade {
@@ -569,7 +704,7 @@ pub(crate) fn extra_name_with_clap_error(arg: &str) -> Result<ExtraName> {
 }
 
 /// An owned or unowned [`InMemoryIndex`].
-enum InMemoryIndexRef<'a> {
+pub(crate) enum InMemoryIndexRef<'a> {
     Owned(InMemoryIndex),
     Borrowed(&'a InMemoryIndex),
 }
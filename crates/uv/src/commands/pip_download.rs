@@ -0,0 +1,313 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anstream::eprint;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::{DistributionMetadata, IndexLocations, Name};
+use platform_host::Platform;
+use uv_auth::KeyringProvider;
+use uv_build::BuildLogOptions;
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, ProxyConfig, RegistryClientBuilder, TlsConfig,
+    TrustedHost,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Simplified;
+use uv_installer::{Downloader, NoBinary};
+use uv_interpreter::{Interpreter, PythonVersion, TargetTriple};
+use uv_resolver::{
+    DependencyMode, InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode, PythonRequirement,
+    ResolutionMode, Resolver,
+};
+use uv_traits::{
+    Concurrency, ConfigSettings, InFlight, NoBuild, PackageBuildSettings, RequiredHashes,
+    SetupPyStrategy,
+};
+use uv_warnings::warn_user;
+
+use crate::commands::pip_compile::InMemoryIndexRef;
+use crate::commands::reporters::ResolverReporter;
+use crate::commands::{elapsed, resolution_environment, warn_python_version_mismatch, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+
+/// The name of the manifest file written alongside the downloaded distributions.
+const MANIFEST: &str = "manifest.txt";
+
+/// Resolve a set of requirements, and save the resolved distributions to a directory.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub(crate) async fn pip_download(
+    requirements: &[RequirementsSource],
+    constraints: &[RequirementsSource],
+    overrides: &[RequirementsSource],
+    dest: &Path,
+    build: bool,
+    resolution_mode: ResolutionMode,
+    prerelease_mode: PreReleaseMode,
+    dependency_mode: DependencyMode,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    setup_py: SetupPyStrategy,
+    config_settings: &ConfigSettings,
+    package_build_settings: &PackageBuildSettings,
+    connectivity: Connectivity,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    retries: u32,
+    concurrency: Concurrency,
+    build_log_options: &BuildLogOptions,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    python_version: Option<PythonVersion>,
+    python_platform: Option<TargetTriple>,
+    exclude_newer: Option<DateTime<Utc>>,
+    cache: Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Initialize the registry client.
+    let client_builder = RegistryClientBuilder::new(cache.clone())
+        .connectivity(connectivity)
+        .tls(tls)
+        .proxy(proxy)
+        .retries(retries)
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_hosts.clone());
+    let client = client_builder.clone().build();
+
+    // Read all requirements from the provided sources.
+    let RequirementsSpecification {
+        project,
+        requirements,
+        constraints,
+        overrides,
+        editables,
+        index_url,
+        extra_index_urls,
+        no_index,
+        find_links,
+        trusted_hosts: source_trusted_hosts,
+        extras: _,
+        hashes,
+    } = RequirementsSpecification::from_sources(
+        requirements,
+        constraints,
+        overrides,
+        &ExtrasSpecification::None,
+        &client,
+    )
+    .await?;
+
+    if !editables.is_empty() {
+        return Err(anyhow!(
+            "Editable requirements are not supported by `uv pip download`"
+        ));
+    }
+
+    // Find an interpreter to use for building distributions.
+    let platform = Platform::current()?;
+    let interpreter = Interpreter::find_best(python_version.as_ref(), &platform, &cache)?;
+    debug!(
+        "Using Python {} interpreter at {} for builds",
+        interpreter.python_version(),
+        interpreter.sys_executable().simplified_display().cyan()
+    );
+    warn_python_version_mismatch(python_version.as_ref(), &interpreter, no_build);
+
+    // Wheels are always built for the build interpreter, rather than the requested target.
+    if build && python_platform.is_some() {
+        warn_user!(
+            "Source distributions will be built for the current platform, rather than the requested `--python-platform`."
+        );
+    }
+
+    // Create a shared in-memory index.
+    let source_index = InMemoryIndex::default();
+
+    // If we're resolving against a different Python version or platform, use a separate index.
+    // Source distributions will be built against the installed version, and so the index may
+    // contain different package priorities than in the top-level resolution.
+    let top_level_index = if python_version.is_some() || python_platform.is_some() {
+        InMemoryIndexRef::Owned(InMemoryIndex::default())
+    } else {
+        InMemoryIndexRef::Borrowed(&source_index)
+    };
+
+    // Determine the tags, markers, and interpreter to use for resolution.
+    let (tags, markers) = resolution_environment(python_version, python_platform, &interpreter)?;
+
+    // Collect the hashes that each requirement must match.
+    let hashes = RequiredHashes::from_requirements(requirements.iter(), hashes, &markers, false)?;
+
+    // Incorporate any index locations from the provided sources.
+    let index_locations =
+        index_locations.combine(index_url, extra_index_urls, find_links, no_index);
+
+    // Rebuild the client, to take into account any index URLs and trusted hosts added by the
+    // sources (e.g., `--index-url` or `--trusted-host` in a `requirements.txt` file).
+    let client = client_builder
+        .index_urls(index_locations.index_urls())
+        .trusted_hosts(
+            trusted_hosts
+                .into_iter()
+                .chain(source_trusted_hosts)
+                .collect(),
+        )
+        .build();
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, &cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, Some(&tags))
+    };
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        &cache,
+        &interpreter,
+        &index_locations,
+        &flat_index,
+        &source_index,
+        &in_flight,
+        setup_py,
+        config_settings,
+        no_build,
+        no_binary,
+    )
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+    .with_concurrency(concurrency)
+    .with_build_log_options(build_log_options.clone())
+    .with_package_build_settings(package_build_settings.clone());
+
+    // Create a manifest of the requirements.
+    let manifest = Manifest::new(
+        requirements,
+        constraints,
+        overrides,
+        vec![],
+        project,
+        vec![],
+    );
+
+    let options = OptionsBuilder::new()
+        .resolution_mode(resolution_mode)
+        .prerelease_mode(prerelease_mode)
+        .dependency_mode(dependency_mode)
+        .exclude_newer(exclude_newer)
+        .build();
+
+    // Resolve the dependencies.
+    let python_requirement = PythonRequirement::new(&interpreter, &markers);
+    let resolver = Resolver::new(
+        manifest,
+        options,
+        Some(&*markers),
+        python_requirement,
+        &tags,
+        &client,
+        &flat_index,
+        &top_level_index,
+        &build_dispatch,
+    )?
+    .with_hashes(&hashes)
+    .with_reporter(ResolverReporter::from(printer));
+
+    let resolution = match resolver.resolve().await {
+        Err(uv_resolver::ResolveError::NoSolution(err)) => {
+            let report = miette::Report::msg(format!("{err}"))
+                .context("No solution found when resolving dependencies:");
+            eprint!("{report:?}");
+            return Ok(ExitStatus::Failure);
+        }
+        result => result,
+    }?;
+
+    let s = if resolution.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Resolved {} in {}",
+            format!("{} package{}", resolution.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    // Save the resolved distributions to the destination directory.
+    let start = std::time::Instant::now();
+
+    fs_err::create_dir_all(dest)?;
+
+    let distributions = resolution.petgraph().node_weights().cloned().collect();
+    let downloader = Downloader::new(&cache, &tags, &client, &build_dispatch).with_hashes(&hashes);
+    let archives = downloader
+        .download_archives(distributions, dest, build)
+        .await
+        .context("Failed to download distributions")?;
+
+    // Write a manifest that pins each distribution to the hash of its saved archive, such that
+    // the directory can be installed from with `--require-hashes`.
+    let mut contents = String::from(
+        "# This file was autogenerated by uv to record the distributions in this directory.\n",
+    );
+    for archive in archives
+        .iter()
+        .sorted_unstable_by(|a, b| a.remote().name().cmp(b.remote().name()))
+    {
+        let dist = archive.remote();
+        match resolution.version(&dist.package_id()) {
+            Some(version) => write!(contents, "{}=={version}", dist.name())?,
+            None => write!(contents, "{}", dist.name())?,
+        }
+        for hash in archive.hashes() {
+            write!(contents, " \\\n    --hash={hash}")?;
+        }
+        writeln!(contents)?;
+    }
+    fs_err::write(dest.join(MANIFEST), contents)?;
+
+    let s = if archives.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Downloaded {} to {} in {}",
+            format!("{} package{}", archives.len(), s).bold(),
+            dest.simplified_display(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    for archive in archives
+        .iter()
+        .sorted_unstable_by(|a, b| a.path().cmp(b.path()))
+    {
+        writeln!(
+            printer.stderr(),
+            " {} {}",
+            "+".green(),
+            archive
+                .path()
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .bold()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -19,8 +19,13 @@ use platform_host::Platform;
 use platform_tags::Tags;
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
+use uv_auth::KeyringProvider;
+use uv_build::BuildLogOptions;
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, ProxyConfig, RegistryClient, RegistryClientBuilder,
+    TlsConfig, TrustedHost,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::{
@@ -30,14 +35,19 @@ use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_normalize::PackageName;
 use uv_resolver::{
     DependencyMode, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
-    ResolutionGraph, ResolutionMode, Resolver,
+    PythonRequirement, ResolutionGraph, ResolutionMode, Resolver,
+};
+use uv_traits::{
+    BuildContext, Concurrency, ConfigSettings, InFlight, NoBuild, NoBuildIsolation,
+    PackageBuildSettings, RequiredHashes, SetupPyStrategy,
 };
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 
 use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
 use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
 use crate::printer::Printer;
-use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+use crate::requirements::{
+    read_build_constraints, ExtrasSpecification, RequirementsSource, RequirementsSpecification,
+};
 
 use super::Upgrade;
 
@@ -53,15 +63,26 @@ pub(crate) async fn pip_install(
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
     reinstall: &Reinstall,
     link_mode: LinkMode,
     compile: bool,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    retries: u32,
+    concurrency: Concurrency,
+    build_log_options: &BuildLogOptions,
     config_settings: &ConfigSettings,
+    package_build_settings: &PackageBuildSettings,
+    no_build_isolation: &NoBuildIsolation,
+    build_constraints: &[RequirementsSource],
     no_build: &NoBuild,
     no_binary: &NoBinary,
     strict: bool,
+    require_hashes: bool,
     exclude_newer: Option<DateTime<Utc>>,
     python: Option<String>,
     system: bool,
@@ -71,9 +92,14 @@ pub(crate) async fn pip_install(
     let start = std::time::Instant::now();
 
     // Initialize the registry client.
-    let client = RegistryClientBuilder::new(cache.clone())
+    let client_builder = RegistryClientBuilder::new(cache.clone())
         .connectivity(connectivity)
-        .build();
+        .tls(tls)
+        .proxy(proxy)
+        .retries(retries)
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_hosts.clone());
+    let client = client_builder.clone().build();
 
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
@@ -86,7 +112,9 @@ pub(crate) async fn pip_install(
         extra_index_urls,
         no_index,
         find_links,
+        trusted_hosts: source_trusted_hosts,
         extras: used_extras,
+        hashes,
     } = specification(requirements, constraints, overrides, extras, &client).await?;
 
     // Check that all provided extras are used
@@ -139,6 +167,19 @@ pub(crate) async fn pip_install(
 
     let _lock = venv.lock()?;
 
+    // Collect the hashes that each requirement must match.
+    if require_hashes && !editables.is_empty() {
+        return Err(anyhow!(
+            "In `--require-hashes` mode, editable requirements are not supported"
+        ));
+    }
+    let hashes = RequiredHashes::from_requirements(
+        requirements.iter(),
+        hashes,
+        venv.interpreter().markers(),
+        require_hashes,
+    )?;
+
     // Determine the set of installed packages.
     let site_packages =
         SitePackages::from_executable(&venv).context("Failed to list installed packages")?;
@@ -174,15 +215,25 @@ pub(crate) async fn pip_install(
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
-    // Update the index URLs on the client, to take into account any index URLs added by the
-    // sources (e.g., `--index-url` in a `requirements.txt` file).
-    let client = client.with_index_url(index_locations.index_urls());
+    // Rebuild the client, to take into account any index URLs and trusted hosts added by the
+    // sources (e.g., `--index-url` or `--trusted-host` in a `requirements.txt` file).
+    let client = client_builder
+        .index_urls(index_locations.index_urls())
+        .trusted_hosts(
+            trusted_hosts
+                .into_iter()
+                .chain(source_trusted_hosts)
+                .collect(),
+        )
+        .build();
+
+    let build_constraints = read_build_constraints(build_constraints, &client).await?;
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, tags)
+        FlatIndex::from_entries(entries, Some(tags))
     };
 
     // Create a shared in-memory index.
@@ -204,7 +255,12 @@ pub(crate) async fn pip_install(
         no_build,
         no_binary,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+    .with_concurrency(concurrency)
+    .with_build_log_options(build_log_options.clone())
+    .with_build_isolation(no_build_isolation.build_isolation(&venv))
+    .with_build_constraints(&build_constraints)
+    .with_package_build_settings(package_build_settings.clone());
 
     // Build all editable distributions. The editables are shared between resolution and
     // installation, and should live for the duration of the command. If an editable is already
@@ -251,6 +307,7 @@ pub(crate) async fn pip_install(
         &flat_index,
         &index,
         &resolve_dispatch,
+        &hashes,
         options,
         printer,
     )
@@ -266,6 +323,19 @@ pub(crate) async fn pip_install(
         Err(err) => return Err(err.into()),
     };
 
+    // In `--require-hashes` mode, every resolved distribution (including transitive dependencies)
+    // must have been provided with a hash.
+    if require_hashes {
+        if let Some(dist) = resolution
+            .distributions()
+            .find(|dist| !hashes.contains(dist.name()))
+        {
+            return Err(anyhow!(
+                "In `--require-hashes` mode, all requirements must have a hash, but none were provided for: {dist}"
+            ));
+        }
+    }
+
     // Re-initialize the in-flight map.
     let in_flight = InFlight::default();
 
@@ -288,6 +358,12 @@ pub(crate) async fn pip_install(
             no_binary,
         )
         .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_concurrency(concurrency)
+        .with_build_semaphore(resolve_dispatch.build_semaphore().clone())
+        .with_build_log_options(build_log_options.clone())
+        .with_build_isolation(no_build_isolation.build_isolation(&venv))
+        .with_build_constraints(&build_constraints)
+        .with_package_build_settings(package_build_settings.clone())
     };
 
     // Sync the environment.
@@ -297,6 +373,7 @@ pub(crate) async fn pip_install(
         site_packages,
         reinstall,
         no_binary,
+        &hashes,
         link_mode,
         compile,
         &index_locations,
@@ -450,6 +527,7 @@ async fn resolve(
     flat_index: &FlatIndex,
     index: &InMemoryIndex,
     build_dispatch: &BuildDispatch<'_>,
+    hashes: &RequiredHashes,
     options: Options,
     printer: Printer,
 ) -> Result<ResolutionGraph, Error> {
@@ -501,14 +579,15 @@ async fn resolve(
     let resolver = Resolver::new(
         manifest,
         options,
-        markers,
-        interpreter,
+        Some(markers),
+        PythonRequirement::new(interpreter, markers),
         tags,
         client,
         flat_index,
         index,
         build_dispatch,
     )?
+    .with_hashes(hashes)
     .with_reporter(ResolverReporter::from(printer));
     let resolution = resolver.resolve().await?;
 
@@ -535,6 +614,7 @@ async fn install(
     site_packages: SitePackages<'_>,
     reinstall: &Reinstall,
     no_binary: &NoBinary,
+    hashes: &RequiredHashes,
     link_mode: LinkMode,
     compile: bool,
     index_urls: &IndexLocations,
@@ -565,6 +645,7 @@ async fn install(
         extraneous: _,
     } = Planner::with_requirements(&requirements)
         .with_editable_requirements(&editables)
+        .with_hashes(hashes)
         .build(
             site_packages,
             reinstall,
@@ -611,6 +692,7 @@ async fn install(
         let start = std::time::Instant::now();
 
         let downloader = Downloader::new(cache, tags, client, build_dispatch)
+            .with_hashes(hashes)
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
         let wheels = downloader
@@ -1,7 +1,7 @@
 use std::fmt::Write;
 
 use anyhow::{anyhow, Context, Result};
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use owo_colors::OwoColorize;
 use tracing::debug;
 
@@ -11,8 +11,13 @@ use platform_host::Platform;
 use platform_tags::Tags;
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
+use uv_auth::KeyringProvider;
+use uv_build::BuildLogOptions;
 use uv_cache::{ArchiveTarget, ArchiveTimestamp, Cache};
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, ProxyConfig, RegistryClient, RegistryClientBuilder,
+    TlsConfig, TrustedHost,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::{
@@ -20,12 +25,17 @@ use uv_installer::{
 };
 use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_resolver::InMemoryIndex;
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{
+    Concurrency, ConfigSettings, InFlight, NoBuild, NoBuildIsolation, PackageBuildSettings,
+    RequiredHashes, SetupPyStrategy,
+};
 
 use crate::commands::reporters::{DownloadReporter, FinderReporter, InstallReporter};
 use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
 use crate::printer::Printer;
-use crate::requirements::{RequirementsSource, RequirementsSpecification};
+use crate::requirements::{
+    read_build_constraints, read_lock, RequirementsSource, RequirementsSpecification,
+};
 
 /// Install a set of locked requirements into the current Python environment.
 #[allow(clippy::too_many_arguments)]
@@ -35,12 +45,23 @@ pub(crate) async fn pip_sync(
     link_mode: LinkMode,
     compile: bool,
     index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    retries: u32,
+    concurrency: Concurrency,
+    build_log_options: &BuildLogOptions,
     config_settings: &ConfigSettings,
+    package_build_settings: &PackageBuildSettings,
+    no_build_isolation: &NoBuildIsolation,
+    build_constraints: &[RequirementsSource],
     no_build: &NoBuild,
     no_binary: &NoBinary,
     strict: bool,
+    require_hashes: bool,
     python: Option<String>,
     system: bool,
     cache: Cache,
@@ -49,9 +70,14 @@ pub(crate) async fn pip_sync(
     let start = std::time::Instant::now();
 
     // Initialize the registry client.
-    let client = RegistryClientBuilder::new(cache.clone())
+    let client_builder = RegistryClientBuilder::new(cache.clone())
         .connectivity(connectivity)
-        .build();
+        .tls(tls)
+        .proxy(proxy)
+        .retries(retries)
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_hosts.clone());
+    let client = client_builder.clone().build();
 
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
@@ -64,7 +90,9 @@ pub(crate) async fn pip_sync(
         extra_index_urls,
         no_index,
         find_links,
+        trusted_hosts: source_trusted_hosts,
         extras: _extras,
+        hashes,
     } = RequirementsSpecification::from_simple_sources(sources, &client).await?;
 
     let num_requirements = requirements.len() + editables.len();
@@ -73,6 +101,16 @@ pub(crate) async fn pip_sync(
         return Ok(ExitStatus::Success);
     }
 
+    // Read the lockfile, if provided, to install the locked distributions without querying the
+    // index.
+    let lock = match sources.iter().find_map(|source| match source {
+        RequirementsSource::Lockfile(path) => Some(path),
+        _ => None,
+    }) {
+        Some(path) => Some(read_lock(path).await?),
+        None => None,
+    };
+
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
     let venv = if let Some(python) = python.as_ref() {
@@ -106,22 +144,52 @@ pub(crate) async fn pip_sync(
 
     let _lock = venv.lock()?;
 
+    // Collect the hashes that each requirement must match.
+    if require_hashes && !editables.is_empty() {
+        return Err(anyhow!(
+            "In `--require-hashes` mode, editable requirements are not supported"
+        ));
+    }
+    let hashes = RequiredHashes::from_requirements(
+        requirements.iter(),
+        hashes,
+        venv.interpreter().markers(),
+        require_hashes,
+    )?;
+
     // Determine the current environment markers.
     let tags = venv.interpreter().tags()?;
 
+    // Select the locked distributions that apply to the current environment.
+    let locked = lock
+        .as_ref()
+        .map(|lock| lock.to_resolution(venv.interpreter().markers(), tags))
+        .transpose()?
+        .unwrap_or_default();
+
     // Incorporate any index locations from the provided sources.
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
-    // Update the index URLs on the client, to take into account any index URLs added by the
-    // sources (e.g., `--index-url` in a `requirements.txt` file).
-    let client = client.with_index_url(index_locations.index_urls());
+    // Rebuild the client, to take into account any index URLs and trusted hosts added by the
+    // sources (e.g., `--index-url` or `--trusted-host` in a `requirements.txt` file).
+    let client = client_builder
+        .index_urls(index_locations.index_urls())
+        .trusted_hosts(
+            trusted_hosts
+                .into_iter()
+                .chain(source_trusted_hosts)
+                .collect(),
+        )
+        .build();
+
+    let build_constraints = read_build_constraints(build_constraints, &client).await?;
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, tags)
+        FlatIndex::from_entries(entries, Some(tags))
     };
 
     // Create a shared in-memory index.
@@ -143,7 +211,12 @@ pub(crate) async fn pip_sync(
         config_settings,
         no_build,
         no_binary,
-    );
+    )
+    .with_concurrency(concurrency)
+    .with_build_log_options(build_log_options.clone())
+    .with_build_isolation(no_build_isolation.build_isolation(&venv))
+    .with_build_constraints(&build_constraints)
+    .with_package_build_settings(package_build_settings.clone());
 
     // Determine the set of installed packages.
     let site_packages =
@@ -172,6 +245,7 @@ pub(crate) async fn pip_sync(
         extraneous,
     } = Planner::with_requirements(&requirements)
         .with_editable_requirements(&resolved_editables.editables)
+        .with_hashes(&hashes)
         .build(
             site_packages,
             reinstall,
@@ -200,14 +274,24 @@ pub(crate) async fn pip_sync(
         return Ok(ExitStatus::Success);
     }
 
-    // Resolve any registry-based requirements.
+    // Use the locked distribution for any requirement that's present in the lockfile.
+    let (locked_dists, remote): (Vec<_>, Vec<_>) =
+        remote
+            .into_iter()
+            .partition_map(|requirement| match locked.get(&requirement.name) {
+                Some(dist) => Either::Left(dist.clone()),
+                None => Either::Right(requirement),
+            });
+
+    // Resolve any remaining registry-based requirements.
     let remote = if remote.is_empty() {
-        Vec::new()
+        locked_dists
     } else {
         let start = std::time::Instant::now();
 
         let wheel_finder =
             uv_resolver::DistFinder::new(tags, &client, venv.interpreter(), &flat_index, no_binary)
+                .with_concurrency(concurrency.downloads)
                 .with_reporter(FinderReporter::from(printer).with_length(remote.len() as u64));
         let resolution = wheel_finder.resolve(&remote).await?;
 
@@ -223,7 +307,10 @@ pub(crate) async fn pip_sync(
             .dimmed()
         )?;
 
-        resolution.into_distributions().collect::<Vec<_>>()
+        locked_dists
+            .into_iter()
+            .chain(resolution.into_distributions())
+            .collect::<Vec<_>>()
     };
 
     // Download, build, and unzip any missing distributions.
@@ -233,6 +320,7 @@ pub(crate) async fn pip_sync(
         let start = std::time::Instant::now();
 
         let downloader = Downloader::new(&cache, tags, &client, &build_dispatch)
+            .with_hashes(&hashes)
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
         let wheels = downloader
@@ -0,0 +1,465 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::debug;
+
+use distribution_types::{InstalledDist, Name};
+use pep508_rs::{MarkerEnvironment, Requirement};
+use platform_host::Platform;
+use pypi_types::Metadata21;
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::{Diagnostic, SitePackages};
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::{ExtraName, PackageName};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Display the installed packages in the current environment as a dependency tree.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pip_tree(
+    mut packages: Vec<PackageName>,
+    invert: bool,
+    depth: Option<usize>,
+    strict: bool,
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, &platform, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(&platform, cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(platform.clone(), cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(&platform, cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().simplified_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+
+    // Sort and deduplicate the packages, which are keyed by name.
+    packages.sort_unstable();
+    packages.dedup();
+
+    // Warn about any requested packages that aren't installed.
+    for package in &packages {
+        if site_packages.get_packages(package).is_empty() {
+            writeln!(
+                printer.stderr(),
+                "{}{} Package(s) not found for: {}",
+                "warning".yellow().bold(),
+                ":".bold(),
+                package.as_ref().bold()
+            )?;
+        }
+    }
+
+    let tree = DependencyTree::new(&site_packages, venv.interpreter().markers());
+
+    // Like `pip show`, if none of the requested packages were found, return a failure.
+    let roots = if packages.is_empty() {
+        tree.roots(invert)
+    } else {
+        packages
+            .iter()
+            .filter(|package| tree.nodes.contains_key(*package))
+            .collect()
+    };
+    if roots.is_empty() && !packages.is_empty() {
+        return Ok(ExitStatus::Failure);
+    }
+
+    let mut lines = Vec::new();
+    let mut visited = FxHashSet::default();
+    for root in roots {
+        let mut path = Vec::new();
+        if invert {
+            tree.render_inverted(
+                root,
+                None,
+                "",
+                "",
+                depth,
+                &mut path,
+                &mut visited,
+                &mut lines,
+            );
+        } else {
+            tree.render(
+                root,
+                &[],
+                None,
+                "",
+                "",
+                depth,
+                &mut path,
+                &mut visited,
+                &mut lines,
+            );
+        }
+    }
+    for line in lines {
+        writeln!(printer.stdout(), "{line}")?;
+    }
+
+    // Validate that the environment is consistent.
+    if strict {
+        for diagnostic in site_packages.diagnostics()? {
+            writeln!(
+                printer.stderr(),
+                "{}{} {}",
+                "warning".yellow().bold(),
+                ":".bold(),
+                diagnostic.message().bold()
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// An installed distribution, along with its parsed metadata.
+struct Node<'a> {
+    dist: &'a InstalledDist,
+    metadata: Metadata21,
+}
+
+/// The dependency graph of the installed packages in an environment.
+struct DependencyTree<'a> {
+    site_packages: &'a SitePackages<'a>,
+    markers: &'a MarkerEnvironment,
+    /// The installed distributions, keyed by name. Distributions without readable metadata, and
+    /// all but the first of any duplicate distributions, are omitted.
+    nodes: FxHashMap<PackageName, Node<'a>>,
+    /// The extras of each package that are requested by some other installed package.
+    extras: FxHashMap<PackageName, Vec<ExtraName>>,
+    /// For each package, the installed packages that depend on it, along with the requirement
+    /// through which they do so.
+    dependents: FxHashMap<PackageName, Vec<(PackageName, Requirement)>>,
+}
+
+impl<'a> DependencyTree<'a> {
+    fn new(site_packages: &'a SitePackages<'a>, markers: &'a MarkerEnvironment) -> Self {
+        let mut nodes = FxHashMap::default();
+        for dist in site_packages.iter() {
+            if nodes.contains_key(dist.name()) {
+                continue;
+            }
+            let Ok(metadata) = dist.metadata() else {
+                continue;
+            };
+            nodes.insert(dist.name().clone(), Node { dist, metadata });
+        }
+
+        // Determine the extras that are enabled for each package, iterating to a fixed point,
+        // since enabling an extra can enable further requirements (and thus further extras).
+        let mut extras: FxHashMap<PackageName, Vec<ExtraName>> = FxHashMap::default();
+        loop {
+            let mut changed = false;
+            for (name, node) in &nodes {
+                let enabled = extras.get(name).cloned().unwrap_or_default();
+                for requirement in &node.metadata.requires_dist {
+                    if !requirement.evaluate_markers(markers, &enabled) {
+                        continue;
+                    }
+                    for extra in &requirement.extras {
+                        let entry = extras.entry(requirement.name.clone()).or_default();
+                        if !entry.contains(extra) {
+                            entry.push(extra.clone());
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        for enabled in extras.values_mut() {
+            enabled.sort_unstable();
+        }
+
+        // Index the reverse edges.
+        let mut dependents: FxHashMap<PackageName, Vec<(PackageName, Requirement)>> =
+            FxHashMap::default();
+        for (name, node) in &nodes {
+            let enabled = extras.get(name).map(Vec::as_slice).unwrap_or_default();
+            for requirement in &node.metadata.requires_dist {
+                if requirement.evaluate_markers(markers, enabled) {
+                    dependents
+                        .entry(requirement.name.clone())
+                        .or_default()
+                        .push((name.clone(), requirement.clone()));
+                }
+            }
+        }
+        for edges in dependents.values_mut() {
+            edges.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        }
+
+        Self {
+            site_packages,
+            markers,
+            nodes,
+            extras,
+            dependents,
+        }
+    }
+
+    /// Returns the packages from which to start rendering: in the forward direction, those that
+    /// aren't required by any other installed package; in the inverted direction, those that
+    /// don't require any other installed package.
+    ///
+    /// Packages that are only reachable through a cycle are included as well, such that every
+    /// installed package appears in the output.
+    fn roots(&self, invert: bool) -> Vec<&PackageName> {
+        let names = self.nodes.keys().sorted_unstable().collect::<Vec<_>>();
+
+        let mut roots = names
+            .iter()
+            .copied()
+            .filter(|name| {
+                if invert {
+                    self.dependencies(name, self.extras(name))
+                        .iter()
+                        .all(|requirement| !self.nodes.contains_key(&requirement.name))
+                } else {
+                    !self.dependents.contains_key(*name)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Add any packages that aren't reachable from the roots.
+        let mut reachable = FxHashSet::default();
+        let mut stack = roots.clone();
+        for name in names {
+            while let Some(name) = stack.pop() {
+                if reachable.insert(name) {
+                    stack.extend(self.successors(name, invert));
+                }
+            }
+            if !reachable.contains(name) {
+                roots.push(name);
+                stack.push(name);
+            }
+        }
+
+        roots
+    }
+
+    /// Returns the installed packages that follow the given package in the rendered tree: its
+    /// dependencies, or, if inverted, its dependents.
+    fn successors(&self, name: &PackageName, invert: bool) -> Vec<&PackageName> {
+        if invert {
+            self.dependents
+                .get(name)
+                .into_iter()
+                .flatten()
+                .map(|(dependent, _)| dependent)
+                .collect()
+        } else {
+            self.dependencies(name, self.extras(name))
+                .into_iter()
+                .filter_map(|requirement| self.nodes.get_key_value(&requirement.name))
+                .map(|(name, _)| name)
+                .collect()
+        }
+    }
+
+    /// Returns the extras of the given package that are requested by other installed packages.
+    fn extras(&self, name: &PackageName) -> &[ExtraName] {
+        self.extras.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the requirements of the given package that apply to the current environment, with
+    /// the given extras enabled.
+    fn dependencies(&self, name: &PackageName, extras: &[ExtraName]) -> Vec<&Requirement> {
+        self.nodes
+            .get(name)
+            .into_iter()
+            .flat_map(|node| &node.metadata.requires_dist)
+            .filter(|requirement| requirement.evaluate_markers(self.markers, extras))
+            .sorted_unstable_by(|a, b| a.name.cmp(&b.name))
+            .collect()
+    }
+
+    /// Render the given package and (recursively) its dependencies.
+    ///
+    /// The `edge` is the requirement through which the package was reached, along with the
+    /// package that declared it, and is used to flag missing or unsatisfied dependencies.
+    #[allow(clippy::too_many_arguments)]
+    fn render<'b>(
+        &'b self,
+        name: &'b PackageName,
+        extras: &[ExtraName],
+        edge: Option<(&PackageName, &Requirement)>,
+        prefix: &str,
+        connector: &str,
+        depth: Option<usize>,
+        path: &mut Vec<&'b PackageName>,
+        visited: &mut FxHashSet<(&'b PackageName, Vec<ExtraName>)>,
+        lines: &mut Vec<String>,
+    ) {
+        let mut line = format!("{prefix}{connector}{}", self.label(name, extras));
+        if let Some((dependent, requirement)) = edge {
+            if let Some(diagnostic) = self
+                .site_packages
+                .diagnose_requirement(dependent, requirement)
+            {
+                write!(line, " {}", flag(&diagnostic)).unwrap();
+            }
+        }
+
+        let dependencies = self.dependencies(name, extras);
+        if dependencies.is_empty() || depth.is_some_and(|depth| path.len() >= depth) {
+            lines.push(line);
+            return;
+        }
+
+        // Avoid cycles, and avoid repeating a subtree that was already rendered.
+        if path.contains(&name) || !visited.insert((name, extras.to_vec())) {
+            write!(line, " {}", "(*)".dimmed()).unwrap();
+            lines.push(line);
+            return;
+        }
+        lines.push(line);
+
+        let prefix = format!("{prefix}{}", indent(connector));
+        path.push(name);
+        for (index, requirement) in dependencies.iter().copied().enumerate() {
+            self.render(
+                &requirement.name,
+                &requirement.extras,
+                Some((name, requirement)),
+                &prefix,
+                connector_for(index, dependencies.len()),
+                depth,
+                path,
+                visited,
+                lines,
+            );
+        }
+        path.pop();
+    }
+
+    /// Render the given package and (recursively) the packages that depend on it.
+    ///
+    /// The `edge` is the requirement through which the package depends on its parent in the
+    /// tree, and is used to flag unsatisfied dependencies.
+    #[allow(clippy::too_many_arguments)]
+    fn render_inverted<'b>(
+        &'b self,
+        name: &'b PackageName,
+        edge: Option<&Requirement>,
+        prefix: &str,
+        connector: &str,
+        depth: Option<usize>,
+        path: &mut Vec<&'b PackageName>,
+        visited: &mut FxHashSet<(&'b PackageName, Vec<ExtraName>)>,
+        lines: &mut Vec<String>,
+    ) {
+        let mut line = format!("{prefix}{connector}{}", self.label(name, &[]));
+        if let Some(requirement) = edge {
+            write!(line, " {}", format!("[requires: {requirement}]").dimmed()).unwrap();
+            if let Some(diagnostic) = self.site_packages.diagnose_requirement(name, requirement) {
+                write!(line, " {}", flag(&diagnostic)).unwrap();
+            }
+        }
+
+        let dependents = self
+            .dependents
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if dependents.is_empty() || depth.is_some_and(|depth| path.len() >= depth) {
+            lines.push(line);
+            return;
+        }
+
+        // Avoid cycles, and avoid repeating a subtree that was already rendered.
+        if path.contains(&name) || !visited.insert((name, Vec::new())) {
+            write!(line, " {}", "(*)".dimmed()).unwrap();
+            lines.push(line);
+            return;
+        }
+        lines.push(line);
+
+        let prefix = format!("{prefix}{}", indent(connector));
+        path.push(name);
+        for (index, (dependent, requirement)) in dependents.iter().enumerate() {
+            self.render_inverted(
+                dependent,
+                Some(requirement),
+                &prefix,
+                connector_for(index, dependents.len()),
+                depth,
+                path,
+                visited,
+                lines,
+            );
+        }
+        path.pop();
+    }
+
+    /// Format a package as `name[extras] vX.Y.Z`, omitting the version if it isn't installed.
+    fn label(&self, name: &PackageName, extras: &[ExtraName]) -> String {
+        let mut label = name.to_string();
+        if !extras.is_empty() {
+            write!(label, "[{}]", extras.iter().join(",")).unwrap();
+        }
+        if let Some(node) = self.nodes.get(name) {
+            write!(label, " v{}", node.dist.version()).unwrap();
+        }
+        label
+    }
+}
+
+/// Format an inline flag for a missing or conflicting requirement.
+fn flag(diagnostic: &Diagnostic) -> String {
+    match diagnostic {
+        Diagnostic::MissingDependency { requirement, .. } => {
+            format!("[missing: {requirement}]").red().to_string()
+        }
+        Diagnostic::IncompatibleDependency { requirement, .. } => {
+            format!("[conflict: {requirement}]").yellow().to_string()
+        }
+        _ => diagnostic.message(),
+    }
+}
+
+/// Returns the connector for the child at the given index.
+fn connector_for(index: usize, len: usize) -> &'static str {
+    if index + 1 == len {
+        "└── "
+    } else {
+        "├── "
+    }
+}
+
+/// Returns the indentation for the children of a node rendered with the given connector.
+fn indent(connector: &str) -> &'static str {
+    match connector {
+        "" => "",
+        "└── " => "    ",
+        _ => "│   ",
+    }
+}
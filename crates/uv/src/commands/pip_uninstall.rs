@@ -7,7 +7,7 @@ use tracing::debug;
 use distribution_types::{InstalledMetadata, Name};
 use platform_host::Platform;
 use uv_cache::Cache;
-use uv_client::{Connectivity, RegistryClientBuilder};
+use uv_client::{Connectivity, ProxyConfig, RegistryClientBuilder, TlsConfig};
 use uv_fs::Simplified;
 use uv_interpreter::PythonEnvironment;
 
@@ -22,6 +22,9 @@ pub(crate) async fn pip_uninstall(
     system: bool,
     cache: Cache,
     connectivity: Connectivity,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    retries: u32,
     printer: Printer,
 ) -> Result<ExitStatus> {
     let start = std::time::Instant::now();
@@ -29,6 +32,9 @@ pub(crate) async fn pip_uninstall(
     // Initialize the registry client.
     let client: uv_client::RegistryClient = RegistryClientBuilder::new(cache.clone())
         .connectivity(connectivity)
+        .tls(tls)
+        .proxy(proxy)
+        .retries(retries)
         .build();
 
     // Read all requirements from the provided sources.
@@ -42,7 +48,9 @@ pub(crate) async fn pip_uninstall(
         extra_index_urls: _extra_index_urls,
         no_index: _no_index,
         find_links: _find_links,
+        trusted_hosts: _trusted_hosts,
         extras: _extras,
+        hashes: _hashes,
     } = RequirementsSpecification::from_simple_sources(sources, &client).await?;
 
     // Detect the current Python interpreter.
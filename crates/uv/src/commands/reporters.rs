@@ -1,3 +1,4 @@
+use std::fmt::Write;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -303,6 +304,25 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
     }
 }
 
+/// Streams the output of build backends, prefixed with the package being built.
+#[derive(Debug)]
+pub(crate) struct BuildOutputReporter {
+    printer: Printer,
+}
+
+impl From<Printer> for BuildOutputReporter {
+    fn from(printer: Printer) -> Self {
+        Self { printer }
+    }
+}
+
+impl uv_build::BuildOutputReporter for BuildOutputReporter {
+    fn on_build_output(&self, package_id: &str, line: &str) {
+        // Streaming is best-effort; a closed stderr shouldn't fail the build.
+        let _ = writeln!(self.printer.stderr(), "{}: {line}", package_id.dimmed());
+    }
+}
+
 /// Like [`std::fmt::Display`], but with colors.
 trait ColorDisplay {
     fn to_color_string(&self) -> String;
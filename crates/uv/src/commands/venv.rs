@@ -15,13 +15,15 @@ use distribution_types::{DistributionMetadata, IndexLocations, Name};
 use pep508_rs::Requirement;
 use platform_host::Platform;
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, ProxyConfig, RegistryClientBuilder, TlsConfig,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::NoBinary;
 use uv_interpreter::{find_default_python, find_requested_python, Error};
 use uv_resolver::{InMemoryIndex, OptionsBuilder};
-use uv_traits::{BuildContext, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{BuildContext, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 
 use crate::commands::ExitStatus;
 use crate::printer::Printer;
@@ -36,6 +38,10 @@ pub(crate) async fn venv(
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
     connectivity: Connectivity,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    retries: u32,
+    concurrency: Concurrency,
     seed: bool,
     exclude_newer: Option<DateTime<Utc>>,
     cache: &Cache,
@@ -48,6 +54,10 @@ pub(crate) async fn venv(
         prompt,
         system_site_packages,
         connectivity,
+        tls,
+        proxy,
+        retries,
+        concurrency,
         seed,
         exclude_newer,
         cache,
@@ -91,6 +101,10 @@ async fn venv_impl(
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
     connectivity: Connectivity,
+    tls: TlsConfig,
+    proxy: ProxyConfig,
+    retries: u32,
+    concurrency: Concurrency,
     seed: bool,
     exclude_newer: Option<DateTime<Utc>>,
     cache: &Cache,
@@ -139,6 +153,9 @@ async fn venv_impl(
         let client = RegistryClientBuilder::new(cache.clone())
             .index_urls(index_locations.index_urls())
             .connectivity(connectivity)
+            .tls(tls)
+            .proxy(proxy)
+            .retries(retries)
             .build();
 
         // Resolve the flat indexes from `--find-links`.
@@ -149,7 +166,7 @@ async fn venv_impl(
                 .fetch(index_locations.flat_index())
                 .await
                 .map_err(VenvError::FlatIndex)?;
-            FlatIndex::from_entries(entries, tags)
+            FlatIndex::from_entries(entries, Some(tags))
         };
 
         // Create a shared in-memory index.
@@ -175,7 +192,8 @@ async fn venv_impl(
             &NoBuild::All,
             &NoBinary::None,
         )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_concurrency(concurrency);
 
         // Resolve the seed packages.
         let mut requirements = vec![Requirement::from_str("pip").unwrap()];
@@ -12,7 +12,7 @@ pub(crate) trait CompatArgs {
 /// These represent a subset of the `pip-compile` interface that uv supports by default.
 /// For example, users often pass `--allow-unsafe`, which is unnecessary with uv. But it's a
 /// nice user experience to warn, rather than fail, when users pass `--allow-unsafe`.
-#[derive(Args)]
+#[derive(Args, Debug)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct PipCompileCompatArgs {
     #[clap(long, hide = true)]
@@ -27,30 +27,12 @@ pub(crate) struct PipCompileCompatArgs {
     #[clap(long, hide = true)]
     no_reuse_hashes: bool,
 
-    #[clap(long, hide = true)]
-    build_isolation: bool,
-
-    #[clap(long, hide = true)]
-    no_build_isolation: bool,
-
     #[clap(long, hide = true)]
     resolver: Option<Resolver>,
 
     #[clap(long, hide = true)]
     max_rounds: Option<usize>,
 
-    #[clap(long, hide = true)]
-    cert: Option<String>,
-
-    #[clap(long, hide = true)]
-    client_cert: Option<String>,
-
-    #[clap(long, hide = true)]
-    trusted_host: Option<String>,
-
-    #[clap(long, hide = true)]
-    emit_trusted_host: bool,
-
     #[clap(long, hide = true)]
     no_emit_trusted_host: bool,
 
@@ -87,7 +69,7 @@ impl CompatArgs for PipCompileCompatArgs {
     ///
     /// This method will warn when an argument is passed that has no effect but matches uv's
     /// behavior. If an argument is passed that does _not_ match uv's behavior (e.g.,
-    /// `--no-build-isolation`), this method will return an error.
+    /// `--reuse-hashes`), this method will return an error.
     fn validate(&self) -> Result<()> {
         if self.allow_unsafe {
             warn_user!(
@@ -111,18 +93,6 @@ impl CompatArgs for PipCompileCompatArgs {
             );
         }
 
-        if self.build_isolation {
-            warn_user!(
-                "pip-compile's `--build-isolation` has no effect (uv always uses build isolation)."
-            );
-        }
-
-        if self.no_build_isolation {
-            return Err(anyhow!(
-                "pip-compile's `--no-build-isolation` is unsupported (uv always uses build isolation)."
-            ));
-        }
-
         if let Some(resolver) = self.resolver {
             match resolver {
                 Resolver::Backtracking => {
@@ -144,39 +114,21 @@ impl CompatArgs for PipCompileCompatArgs {
             ));
         }
 
-        if self.client_cert.is_some() {
-            return Err(anyhow!(
-                "pip-compile's `--client-cert` is unsupported (uv doesn't support dedicated client certificates)."
-            ));
-        }
-
-        if self.trusted_host.is_some() {
-            return Err(anyhow!(
-                "pip-compile's `--trusted-host` is unsupported (uv always requires HTTPS)."
-            ));
-        }
-
-        if self.emit_trusted_host {
-            return Err(anyhow!(
-                "pip-compile's `--emit-trusted-host` is unsupported (uv always requires HTTPS)."
-            ));
-        }
-
         if self.no_emit_trusted_host {
             warn_user!(
-                "pip-compile's `--no-emit-trusted-host` has no effect (uv never emits trusted hosts)."
+                "pip-compile's `--no-emit-trusted-host` has no effect (uv excludes trusted hosts by default)."
             );
         }
 
         if self.config.is_some() {
             return Err(anyhow!(
-                "pip-compile's `--config` is unsupported (uv does not use a configuration file)."
+                "pip-compile's `--config` is unsupported (uv reads settings from `[tool.uv]` in `pyproject.toml`, or from `uv.toml`; try `--config-file`)."
             ));
         }
 
         if self.no_config {
             warn_user!(
-                "pip-compile's `--no-config` has no effect (uv does not use a configuration file)."
+                "pip-compile's `--no-config` has no effect (try `--isolated` to ignore `uv.toml` and `pyproject.toml` settings)."
             );
         }
 
@@ -225,27 +177,18 @@ impl CompatArgs for PipCompileCompatArgs {
 /// Arguments for `pip-sync` compatibility.
 ///
 /// These represent a subset of the `pip-sync` interface that uv supports by default.
-#[derive(Args)]
+#[derive(Args, Debug)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct PipSyncCompatArgs {
     #[clap(short, long, hide = true)]
     ask: bool,
 
-    #[clap(long, hide = true)]
-    trusted_host: Option<String>,
-
     #[clap(long, hide = true)]
     python_executable: Option<String>,
 
     #[clap(long, hide = true)]
     user: bool,
 
-    #[clap(long, hide = true)]
-    cert: Option<String>,
-
-    #[clap(long, hide = true)]
-    client_cert: Option<String>,
-
     #[clap(long, hide = true)]
     config: Option<String>,
 
@@ -279,27 +222,15 @@ impl CompatArgs for PipSyncCompatArgs {
             return Err(anyhow!("pip-sync's `--user` is unsupported."));
         }
 
-        if self.client_cert.is_some() {
-            return Err(anyhow!(
-                "pip-sync's `--client-cert` is unsupported (uv doesn't support dedicated client certificates)."
-            ));
-        }
-
-        if self.trusted_host.is_some() {
-            return Err(anyhow!(
-                "pip-sync's `--trusted-host` is unsupported (uv always requires HTTPS)."
-            ));
-        }
-
         if self.config.is_some() {
             return Err(anyhow!(
-                "pip-sync's `--config` is unsupported (uv does not use a configuration file)."
+                "pip-sync's `--config` is unsupported (uv reads settings from `[tool.uv]` in `pyproject.toml`, or from `uv.toml`; try `--config-file`)."
             ));
         }
 
         if self.no_config {
             warn_user!(
-                "pip-sync's `--no-config` has no effect (uv does not use a configuration file)."
+                "pip-sync's `--no-config` has no effect (try `--isolated` to ignore `uv.toml` and `pyproject.toml` settings)."
             );
         }
 
@@ -322,7 +253,7 @@ enum Resolver {
 /// Arguments for `venv` compatibility.
 ///
 /// These represent a subset of the `virtualenv` interface that uv supports by default.
-#[derive(Args)]
+#[derive(Args, Debug)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct VenvCompatArgs {
     #[clap(long, hide = true)]
@@ -1,32 +1,46 @@
 use std::env;
+use std::fmt::Write;
 use std::io::stdout;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::process::ExitCode;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use anstream::eprintln;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
 use clap::error::{ContextKind, ContextValue};
 use clap::{Args, CommandFactory, Parser, Subcommand};
 use owo_colors::OwoColorize;
 use tracing::instrument;
 
-use distribution_types::{FlatIndexLocation, IndexLocations, IndexUrl};
+use distribution_types::{
+    FlatIndexLocation, IndexLocations, IndexStrategy, IndexUrl, PackageIndex,
+};
 use requirements::ExtrasSpecification;
+use uv_auth::KeyringProvider;
+use uv_build::BuildLogOptions;
 use uv_cache::{Cache, CacheArgs, Refresh};
-use uv_client::Connectivity;
+use uv_client::{
+    Connectivity, IndexProxy, ProxyConfig, ProxyUrl, RegistryClientBuilder, TlsConfig, TrustedHost,
+};
 use uv_installer::{NoBinary, Reinstall};
-use uv_interpreter::PythonVersion;
+use uv_interpreter::{PythonVersion, TargetTriple};
 use uv_normalize::{ExtraName, PackageName};
 use uv_resolver::{AnnotationStyle, DependencyMode, PreReleaseMode, ResolutionMode};
 use uv_traits::{
-    ConfigSettingEntry, ConfigSettings, NoBuild, PackageNameSpecifier, SetupPyStrategy,
+    Concurrency, ConfigSettingEntry, ConfigSettings, NoBuild, NoBuildIsolation,
+    PackageBuildSettings, PackageConfigSettingEntry, PackageEnvEntry, PackageNameSpecifier,
+    SetupPyStrategy,
 };
 
-use crate::commands::{extra_name_with_clap_error, ExitStatus, ListFormat, Upgrade, VersionFormat};
+use crate::commands::{
+    extra_name_with_clap_error, ExitStatus, ListFormat, OutputFormat, Upgrade, VersionFormat,
+};
 use crate::compat::CompatArgs;
 use crate::requirements::RequirementsSource;
+use crate::settings::Options;
 
 #[cfg(target_os = "windows")]
 #[global_allocator]
@@ -50,6 +64,7 @@ mod confirm;
 mod logging;
 mod printer;
 mod requirements;
+mod settings;
 mod shell;
 mod version;
 
@@ -87,6 +102,114 @@ struct Cli {
 
     #[command(flatten)]
     cache_args: CacheArgs,
+
+    /// Trust the bundled Mozilla root certificates, rather than the platform's native certificate
+    /// store.
+    ///
+    /// By default, `uv` trusts the certificates in the platform's native store, which includes
+    /// any corporate root certificates installed on the system (e.g., for a mandatory proxy). Use
+    /// the bundled roots instead for behavior that's consistent across platforms.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_WEBPKI_ROOTS",
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    webpki_roots: bool,
+
+    /// The path to a PEM bundle of CA certificates to trust.
+    ///
+    /// When provided, only the certificates in the bundle are trusted, in lieu of the bundled or
+    /// native root certificates. Applies to index requests and Git fetches alike.
+    #[arg(global = true, long, env = "SSL_CERT_FILE", value_name = "PATH")]
+    cert: Option<PathBuf>,
+
+    /// The path to a PEM file containing a client certificate and its private key.
+    ///
+    /// The certificate is presented to any server that requests one, for mutual TLS.
+    #[arg(global = true, long, env = "SSL_CLIENT_CERT", value_name = "PATH")]
+    client_cert: Option<PathBuf>,
+
+    /// The proxy to use for all HTTP and HTTPS requests, in the form
+    /// `[scheme://][user:password@]host:port`.
+    ///
+    /// By default, the proxy is read from the `HTTPS_PROXY`, `HTTP_PROXY`, and `ALL_PROXY`
+    /// environment variables.
+    #[arg(global = true, long, env = "UV_PROXY", value_name = "URL")]
+    proxy: Option<ProxyUrl>,
+
+    /// A proxy to use for the requests to a given index, in the form `<URL>=<PROXY>`, or
+    /// `<URL>=direct` to connect to the index directly.
+    ///
+    /// Applies to any request whose URL starts with the given index URL, and takes precedence over
+    /// `--proxy` and `--no-proxy`.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_INDEX_PROXY",
+        value_delimiter = ' ',
+        value_name = "URL=PROXY"
+    )]
+    index_proxy: Vec<IndexProxy>,
+
+    /// Hosts to connect to directly, bypassing the proxy, in addition to those in `NO_PROXY`.
+    ///
+    /// Each entry matches the host itself and any of its subdomains; `*` matches every host.
+    #[arg(global = true, long, value_delimiter = ',', value_name = "HOST")]
+    no_proxy: Vec<String>,
+
+    /// The number of times to retry a request that fails with a transient error, such as a dropped
+    /// connection, a timeout, or a server error.
+    ///
+    /// Applies to index requests and downloads alike; interrupted downloads are resumed where
+    /// possible. Retries are made with an exponential backoff. Defaults to 3.
+    #[arg(global = true, long, env = "UV_HTTP_RETRIES", value_name = "N")]
+    http_retries: Option<u32>,
+
+    /// The maximum number of concurrent downloads, including metadata fetches.
+    ///
+    /// Defaults to 50.
+    #[arg(global = true, long, env = "UV_CONCURRENT_DOWNLOADS", value_name = "N")]
+    concurrent_downloads: Option<NonZeroUsize>,
+
+    /// The maximum number of source distributions to build at the same time.
+    ///
+    /// Defaults to the number of available CPU cores.
+    #[arg(global = true, long, env = "UV_CONCURRENT_BUILDS", value_name = "N")]
+    concurrent_builds: Option<NonZeroUsize>,
+
+    /// The maximum number of threads to use when installing wheels.
+    ///
+    /// Defaults to the number of available CPU cores.
+    #[arg(global = true, long, env = "UV_CONCURRENT_INSTALLS", value_name = "N")]
+    concurrent_installs: Option<NonZeroUsize>,
+
+    /// The directory in which to write the logs of source distribution builds.
+    ///
+    /// A log is written for every build, whether it succeeds or not, and includes the resolved
+    /// build requirements, the commands invoked and their output. Defaults to the cache.
+    #[arg(global = true, long, env = "UV_BUILD_LOG_DIR", value_name = "DIR")]
+    build_log_dir: Option<PathBuf>,
+
+    /// Stream the output of build backends while source distributions are being built.
+    #[arg(global = true, long)]
+    verbose_build: bool,
+
+    /// The path to a `uv.toml` file to use for configuration.
+    ///
+    /// By default, settings are read from the nearest `uv.toml` file, or `pyproject.toml` file
+    /// with a `[tool.uv]` table, in the current directory or any parent directory, along with the
+    /// user-level `uv.toml` file. When a configuration file is provided, discovery is disabled.
+    #[arg(global = true, long, env = "UV_CONFIG_FILE")]
+    config_file: Option<PathBuf>,
+
+    /// Avoid discovering a `pyproject.toml` or `uv.toml` file for configuration.
+    #[arg(global = true, long, conflicts_with = "config_file")]
+    isolated: bool,
+
+    /// Display the resolved settings for the command, then exit.
+    #[arg(global = true, long, hide = true)]
+    show_settings: bool,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -119,6 +242,8 @@ enum Commands {
     /// Create a virtual environment.
     #[clap(alias = "virtualenv", alias = "v")]
     Venv(VenvArgs),
+    /// Build source distributions and wheels from a Python project.
+    Build(BuildArgs),
     /// Manage the cache.
     Cache(CacheNamespace),
     /// Remove all items from the cache.
@@ -146,6 +271,8 @@ enum CacheCommand {
     Clean(CleanArgs),
     /// Show the cache directory.
     Dir,
+    /// Populate the cache with the index metadata needed to resolve a set of requirements offline.
+    Prefetch(PrefetchArgs),
 }
 
 #[derive(Args)]
@@ -162,6 +289,264 @@ struct DirArgs {
     package: Vec<PackageName>,
 }
 
+#[derive(Args, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+#[command(group = clap::ArgGroup::new("sources").required(true).multiple(true))]
+struct PrefetchArgs {
+    /// Prefetch the metadata for all listed packages.
+    #[clap(group = "sources")]
+    package: Vec<String>,
+
+    /// Prefetch the metadata for all packages listed in the given requirements files.
+    #[clap(long, short, group = "sources")]
+    requirement: Vec<PathBuf>,
+
+    /// Constrain versions using the given requirements files.
+    ///
+    /// Only versions that satisfy the constraints are prefetched. The constraints should match
+    /// those passed to any subsequent `--offline` resolution.
+    #[clap(long, short)]
+    constraint: Vec<PathBuf>,
+
+    /// Override versions using the given requirements files.
+    ///
+    /// The overrides should match those passed to any subsequent `--offline` resolution.
+    #[clap(long)]
+    r#override: Vec<PathBuf>,
+
+    /// Refresh all cached data.
+    #[clap(long)]
+    refresh: bool,
+
+    /// Refresh cached data for a specific package.
+    #[clap(long)]
+    refresh_package: Vec<PackageName>,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// The index given by this flag is given lower priority than all other
+    /// indexes specified via the `--extra-index-url` flag.
+    #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    ///
+    /// All indexes given via this flag take priority over the index
+    /// in `--index-url` (which defaults to PyPI). And when multiple
+    /// `--extra-index-url` flags are given, earlier values take priority.
+    #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    extra_index_url: Vec<Maybe<IndexUrl>>,
+
+    /// Locations to search for candidate distributions, beyond those found in the indexes.
+    ///
+    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
+    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    ///
+    /// If a URL, the page must contain a flat list of links to package files.
+    #[clap(long, short)]
+    find_links: Vec<FlatIndexLocation>,
+
+    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
+    /// discovered via `--find-links`.
+    #[clap(
+        long,
+        conflicts_with = "index_url",
+        conflicts_with = "extra_index_url",
+        overrides_with = "index"
+    )]
+    no_index: bool,
+
+    #[clap(long, overrides_with = "no_index", hide = true)]
+    index: bool,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available, and
+    /// limit resolutions to those present on that first index (`first-match`).
+    #[clap(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    index_strategy: Option<IndexStrategy>,
+
+    /// Pin a package to an index, as `<package>=<url>`; may be provided more than once.
+    #[clap(long)]
+    package_index: Vec<PackageIndex>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    #[clap(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    keyring_provider: Option<KeyringProvider>,
+
+    /// Allow insecure connections to a host, as `<host>` or `<host>:<port>`; may be provided more
+    /// than once.
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
+    /// Don't use pre-built wheels.
+    ///
+    /// The metadata for the given packages will be read from source distributions, which can't be
+    /// prefetched without building them.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long)]
+    no_binary: Vec<PackageNameSpecifier>,
+
+    /// The minimum Python version that should be supported by the resolution (e.g., `3.7` or
+    /// `3.7.9`).
+    ///
+    /// If a patch version is omitted, the most recent known patch version for that minor version
+    /// is assumed. For example, `3.7` is mapped to `3.7.17`.
+    #[arg(long)]
+    python_version: Option<PythonVersion>,
+
+    /// The platform for which the resolution should be prefetched, rather than the current
+    /// platform (e.g., `x86_64-manylinux_2_28` or `aarch64-apple-darwin`).
+    #[arg(long)]
+    python_platform: Option<TargetTriple>,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long, value_parser = date_or_datetime)]
+    exclude_newer: Option<DateTime<Utc>>,
+}
+
+#[derive(Args, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+struct BuildArgs {
+    /// The directory from which distributions should be built.
+    ///
+    /// Defaults to the current working directory.
+    src: Option<PathBuf>,
+
+    /// Build a source distribution (`.tar.gz`).
+    ///
+    /// By default, a source distribution is built, followed by a wheel built from that source
+    /// distribution. If `--sdist` and `--wheel` are both provided, both are built directly from the
+    /// source tree.
+    #[clap(long)]
+    sdist: bool,
+
+    /// Build a wheel (`.whl`).
+    ///
+    /// By default, a source distribution is built, followed by a wheel built from that source
+    /// distribution. If only `--wheel` is provided, the wheel is built directly from the source
+    /// tree.
+    #[clap(long)]
+    wheel: bool,
+
+    /// The output directory to which distributions should be written.
+    ///
+    /// Defaults to the `dist` subdirectory within the source directory.
+    #[clap(long, short)]
+    out_dir: Option<PathBuf>,
+
+    /// The Python interpreter to use for the build environments.
+    ///
+    /// By default, `uv` uses the Python interpreter of the virtual environment in the current
+    /// working directory (or any parent directory), falling back to the default Python
+    /// installation.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment)]
+    python: Option<String>,
+
+    /// Settings to pass to the PEP 517 build backend, specified as `KEY=VALUE` pairs.
+    #[clap(long, short = 'C', alias = "config-settings")]
+    config_setting: Vec<ConfigSettingEntry>,
+
+    /// Disable isolation when building, like `python -m build --no-isolation`.
+    ///
+    /// Assumes that the packages listed in `build-system.requires` are already installed in the
+    /// Python environment.
+    #[clap(long, overrides_with = "build_isolation")]
+    no_build_isolation: bool,
+
+    #[clap(long, overrides_with = "no_build_isolation", hide = true)]
+    build_isolation: bool,
+
+    /// Constrain the build dependencies using the given requirements files.
+    ///
+    /// Build constraints files are `requirements.txt`-like files that only control the _version_
+    /// of a requirement that's installed into a build environment (i.e., the packages listed in
+    /// `build-system.requires`). They have no effect on the packages that are installed or
+    /// resolved outside of build environments.
+    #[clap(long)]
+    build_constraint: Vec<PathBuf>,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// The index given by this flag is given lower priority than all other
+    /// indexes specified via the `--extra-index-url` flag.
+    #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    ///
+    /// All indexes given via this flag take priority over the index
+    /// in `--index-url` (which defaults to PyPI). And when multiple
+    /// `--extra-index-url` flags are given, earlier values take priority.
+    #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    extra_index_url: Vec<Maybe<IndexUrl>>,
+
+    /// Locations to search for candidate distributions, beyond those found in the indexes.
+    ///
+    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
+    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    ///
+    /// If a URL, the page must contain a flat list of links to package files.
+    #[clap(long, short)]
+    find_links: Vec<FlatIndexLocation>,
+
+    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
+    /// discovered via `--find-links`.
+    #[clap(
+        long,
+        conflicts_with = "index_url",
+        conflicts_with = "extra_index_url",
+        overrides_with = "index"
+    )]
+    no_index: bool,
+
+    #[clap(long, overrides_with = "no_index", hide = true)]
+    index: bool,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available, and
+    /// limit resolutions to those present on that first index (`first-match`).
+    #[clap(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    index_strategy: Option<IndexStrategy>,
+
+    /// Pin a package to an index, as `<package>=<url>`; may be provided more than once.
+    #[clap(long)]
+    package_index: Vec<PackageIndex>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    #[clap(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    keyring_provider: Option<KeyringProvider>,
+
+    /// Allow insecure connections to a host, as `<host>` or `<host>:<port>`; may be provided more
+    /// than once.
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
+    /// Run offline, i.e., without accessing the network.
+    #[clap(long)]
+    offline: bool,
+
+    /// Limit candidate packages for the build requirements to those that were uploaded prior to
+    /// the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long, value_parser = date_or_datetime)]
+    exclude_newer: Option<DateTime<Utc>>,
+}
+
 #[derive(Args)]
 struct PipNamespace {
     #[clap(subcommand)]
@@ -176,6 +561,8 @@ enum PipCommand {
     Sync(PipSyncArgs),
     /// Install packages into the current environment.
     Install(PipInstallArgs),
+    /// Download packages into a directory, for later installation with `--find-links`.
+    Download(PipDownloadArgs),
     /// Uninstall packages from the current environment.
     Uninstall(PipUninstallArgs),
     /// Enumerate the installed packages in the current environment.
@@ -184,6 +571,10 @@ enum PipCommand {
     List(PipListArgs),
     /// Show information about one or more installed packages.
     Show(PipShowArgs),
+    /// Display the installed packages in the current environment as a dependency tree.
+    Tree(PipTreeArgs),
+    /// Verify that the installed packages in the current environment are consistent.
+    Check(PipCheckArgs),
 }
 
 /// Clap parser for the union of date and datetime
@@ -233,7 +624,7 @@ fn parse_index_url(input: &str) -> Result<Maybe<IndexUrl>, String> {
     }
 }
 
-#[derive(Args)]
+#[derive(Args, Debug)]
 #[allow(clippy::struct_excessive_bools)]
 struct PipCompileArgs {
     /// Include all packages listed in the given `requirements.in` files.
@@ -277,11 +668,11 @@ struct PipCompileArgs {
     #[clap(long)]
     no_deps: bool,
 
-    #[clap(long, value_enum, default_value_t = ResolutionMode::default())]
-    resolution: ResolutionMode,
+    #[clap(long, value_enum)]
+    resolution: Option<ResolutionMode>,
 
-    #[clap(long, value_enum, default_value_t = PreReleaseMode::default(), conflicts_with = "pre")]
-    prerelease: PreReleaseMode,
+    #[clap(long, value_enum, conflicts_with = "pre")]
+    prerelease: Option<PreReleaseMode>,
 
     #[clap(long, hide = true, conflicts_with = "prerelease")]
     pre: bool,
@@ -290,6 +681,12 @@ struct PipCompileArgs {
     #[clap(long, short)]
     output_file: Option<PathBuf>,
 
+    /// The format in which to write the compiled requirements.
+    ///
+    /// Defaults to `lock` if the output file is named `uv.lock`, and `requirements-txt` otherwise.
+    #[clap(long, value_enum)]
+    output_format: Option<OutputFormat>,
+
     /// Exclude comment annotations indicating the source of each package.
     #[clap(long)]
     no_annotate: bool,
@@ -317,12 +714,15 @@ struct PipCompileArgs {
 
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
+    /// A `file://` URL may point to a local directory laid out as a simple index, with an
+    /// `index.html` (or PEP 691 `index.json`) file in each package's directory.
+    ///
     /// The index given by this flag is given lower priority than all other
     /// indexes specified via the `--extra-index-url` flag.
     ///
     /// Unlike `pip`, `uv` will stop looking for versions of a package as soon
-    /// as it finds it in an index. That is, it isn't possible for `uv` to
-    /// consider versions of the same package across multiple indexes.
+    /// as it finds it in an index, unless a different `--index-strategy` is
+    /// selected.
     #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
     index_url: Option<Maybe<IndexUrl>>,
 
@@ -333,16 +733,61 @@ struct PipCompileArgs {
     /// `--extra-index-url` flags are given, earlier values take priority.
     ///
     /// Unlike `pip`, `uv` will stop looking for versions of a package as soon
-    /// as it finds it in an index. That is, it isn't possible for `uv` to
-    /// consider versions of the same package across multiple indexes.
+    /// as it finds it in an index, unless a different `--index-strategy` is
+    /// selected.
     #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
     extra_index_url: Vec<Maybe<IndexUrl>>,
 
     /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
     /// discovered via `--find-links`.
-    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
+    #[clap(
+        long,
+        conflicts_with = "index_url",
+        conflicts_with = "extra_index_url",
+        overrides_with = "index"
+    )]
     no_index: bool,
 
+    #[clap(long, overrides_with = "no_index", hide = true)]
+    index: bool,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available, and
+    /// limit resolutions to those present on that first index (`first-match`). This prevents
+    /// "dependency confusion" attacks, whereby an attacker can upload a malicious package under the
+    /// same name to a secondary index.
+    #[clap(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    index_strategy: Option<IndexStrategy>,
+
+    /// Pin a package to an index, as `<package>=<url>`; may be provided more than once.
+    ///
+    /// A pinned package is only ever fetched from the given index, regardless of any other
+    /// configured indexes or `--find-links` locations, and the index is never consulted for any
+    /// other package. This can be used to prevent "dependency confusion" attacks on internal
+    /// package names.
+    #[clap(long)]
+    package_index: Vec<PackageIndex>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    ///
+    /// Credentials are otherwise read from the index URL itself, from the `.netrc` file (or the
+    /// file at `NETRC`), or from the `UV_HTTP_BASIC_<HOST>_USERNAME` and
+    /// `UV_HTTP_BASIC_<HOST>_PASSWORD` environment variables. With `subprocess`, `uv` will fall
+    /// back to `keyring get <url> <username>` if the index responds with `401 Unauthorized`, which
+    /// requires a username to be provided through one of the above.
+    #[clap(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    keyring_provider: Option<KeyringProvider>,
+
+    /// Allow insecure connections to a host, as `<host>` or `<host>:<port>`; may be provided more
+    /// than once.
+    ///
+    /// Requests to a trusted host may use plain HTTP, and skip TLS certificate verification. All
+    /// other indexes must be served over HTTPS with a valid certificate, unless they're on the
+    /// local machine.
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
     /// Locations to search for candidate distributions, beyond those found in the indexes.
     ///
     /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
@@ -362,14 +807,47 @@ struct PipCompileArgs {
     upgrade_package: Vec<PackageName>,
 
     /// Include distribution hashes in the output file.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_generate_hashes")]
     generate_hashes: bool,
 
+    #[clap(long, overrides_with = "generate_hashes", hide = true)]
+    no_generate_hashes: bool,
+
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_legacy_setup_py")]
     legacy_setup_py: bool,
 
+    #[clap(long, overrides_with = "legacy_setup_py", hide = true)]
+    no_legacy_setup_py: bool,
+
+    /// Disable isolation when building source distributions.
+    ///
+    /// Assumes that the packages listed in `build-system.requires` are already installed in the
+    /// Python environment.
+    #[clap(long, overrides_with = "build_isolation")]
+    no_build_isolation: bool,
+
+    #[clap(long, overrides_with = "no_build_isolation", hide = true)]
+    build_isolation: bool,
+
+    /// Disable isolation when building source distributions for a specific package.
+    ///
+    /// Assumes that the packages listed in `build-system.requires` are already installed in the
+    /// Python environment.
+    #[clap(long, conflicts_with = "no_build_isolation")]
+    no_build_isolation_package: Vec<PackageName>,
+
+    /// Constrain build dependencies using the given requirements files when building source
+    /// distributions.
+    ///
+    /// Build constraints files are `requirements.txt`-like files that only control the _version_
+    /// of a requirement that's installed into a build environment (i.e., the packages listed in
+    /// `build-system.requires`). They have no effect on the packages that are installed or
+    /// resolved outside of build environments.
+    #[clap(long)]
+    build_constraint: Vec<PathBuf>,
+
     /// Don't build source distributions.
     ///
     /// When enabled, resolving will not run arbitrary code. The cached wheels of already-built
@@ -377,9 +855,12 @@ struct PipCompileArgs {
     /// exit with an error.
     ///
     /// Alias for `--only-binary :all:`.
-    #[clap(long, conflicts_with = "only_binary")]
+    #[clap(long, conflicts_with = "only_binary", overrides_with = "build")]
     no_build: bool,
 
+    #[clap(long, overrides_with = "no_build", hide = true)]
+    build: bool,
+
     /// Only use pre-built wheels; don't build source distributions.
     ///
     /// When enabled, resolving will not run code from the given packages. The cached wheels of already-built
@@ -395,6 +876,18 @@ struct PipCompileArgs {
     #[clap(long, short = 'C', alias = "config-settings")]
     config_setting: Vec<ConfigSettingEntry>,
 
+    /// Settings to pass to the PEP 517 build backend of a specific package, specified as
+    /// `PACKAGE:KEY=VALUE` pairs (e.g., `scipy:setup-args=-Dblas=openblas`).
+    ///
+    /// Package-specific settings take precedence over those provided via `--config-setting`.
+    #[clap(long, alias = "config-settings-package")]
+    config_setting_package: Vec<PackageConfigSettingEntry>,
+
+    /// Environment variables to set when building a specific package, specified as
+    /// `PACKAGE:NAME=VALUE` pairs (e.g., `flash-attn:MAX_JOBS=4`).
+    #[clap(long)]
+    build_env_package: Vec<PackageEnvEntry>,
+
     /// The minimum Python version that should be supported by the compiled requirements (e.g.,
     /// `3.7` or `3.7.9`).
     ///
@@ -403,6 +896,26 @@ struct PipCompileArgs {
     #[arg(long, short)]
     python_version: Option<PythonVersion>,
 
+    /// The platform for which requirements should be resolved, rather than the current platform
+    /// (e.g., `x86_64-manylinux_2_28` or `aarch64-apple-darwin`).
+    ///
+    /// Represented as a "target triple", a string that describes the target platform in terms of
+    /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
+    /// `aarch64-apple-darwin`. The aliases `windows`, `linux`, and `macos` select the default
+    /// target for each operating system.
+    #[arg(long, conflicts_with = "universal")]
+    python_platform: Option<TargetTriple>,
+
+    /// Perform a universal resolution, attempting to generate a single `requirements.txt` output
+    /// file that is compatible with all operating systems, architectures, and Python
+    /// implementations.
+    ///
+    /// Requirements are annotated with the environment markers under which they apply. Python
+    /// version constraints are still enforced against the target Python version (see
+    /// `--python-version`).
+    #[arg(long)]
+    universal: bool,
+
     /// Limit candidate packages to those that were uploaded prior to the given date.
     ///
     /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
@@ -423,6 +936,17 @@ struct PipCompileArgs {
     #[clap(long, hide = true)]
     emit_find_links: bool,
 
+    /// Include `--trusted-host` entries in the generated output file.
+    #[clap(long)]
+    emit_trusted_host: bool,
+
+    /// Include the pinned build dependencies of any source distributions built during resolution
+    /// in the generated output file, as comments.
+    ///
+    /// The emitted pins can be copied into a build constraints file (see `--build-constraint`).
+    #[clap(long)]
+    emit_build_requirements: bool,
+
     /// Choose the style of the annotation comments, which indicate the source of each package.
     #[clap(long, default_value_t=AnnotationStyle::Split, value_enum)]
     annotation_style: AnnotationStyle,
@@ -431,10 +955,13 @@ struct PipCompileArgs {
     compat_args: compat::PipCompileCompatArgs,
 }
 
-#[derive(Args)]
+#[derive(Args, Debug)]
 #[allow(clippy::struct_excessive_bools)]
 struct PipSyncArgs {
     /// Include all packages listed in the given `requirements.txt` files.
+    ///
+    /// If a file is named `uv.lock`, the locked distributions are installed without querying the
+    /// package index.
     #[clap(required(true))]
     src_file: Vec<PathBuf>,
 
@@ -464,17 +991,20 @@ struct PipSyncArgs {
     refresh_package: Vec<PackageName>,
 
     /// The method to use when installing packages from the global cache.
-    #[clap(long, value_enum, default_value_t = install_wheel_rs::linker::LinkMode::default())]
-    link_mode: install_wheel_rs::linker::LinkMode,
+    #[clap(long, value_enum)]
+    link_mode: Option<install_wheel_rs::linker::LinkMode>,
 
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
+    /// A `file://` URL may point to a local directory laid out as a simple index, with an
+    /// `index.html` (or PEP 691 `index.json`) file in each package's directory.
+    ///
     /// The index given by this flag is given lower priority than all other
     /// indexes specified via the `--extra-index-url` flag.
     ///
     /// Unlike `pip`, `uv` will stop looking for versions of a package as soon
-    /// as it finds it in an index. That is, it isn't possible for `uv` to
-    /// consider versions of the same package across multiple indexes.
+    /// as it finds it in an index, unless a different `--index-strategy` is
+    /// selected.
     #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
     index_url: Option<Maybe<IndexUrl>>,
 
@@ -485,8 +1015,8 @@ struct PipSyncArgs {
     /// `--extra-index-url` flags are given, earlier values take priority.
     ///
     /// Unlike `pip`, `uv` will stop looking for versions of a package as soon
-    /// as it finds it in an index. That is, it isn't possible for `uv` to
-    /// consider versions of the same package across multiple indexes.
+    /// as it finds it in an index, unless a different `--index-strategy` is
+    /// selected.
     #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
     extra_index_url: Vec<Maybe<IndexUrl>>,
 
@@ -501,9 +1031,54 @@ struct PipSyncArgs {
 
     /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
     /// discovered via `--find-links`.
-    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
+    #[clap(
+        long,
+        conflicts_with = "index_url",
+        conflicts_with = "extra_index_url",
+        overrides_with = "index"
+    )]
     no_index: bool,
 
+    #[clap(long, overrides_with = "no_index", hide = true)]
+    index: bool,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available, and
+    /// limit resolutions to those present on that first index (`first-match`). This prevents
+    /// "dependency confusion" attacks, whereby an attacker can upload a malicious package under the
+    /// same name to a secondary index.
+    #[clap(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    index_strategy: Option<IndexStrategy>,
+
+    /// Pin a package to an index, as `<package>=<url>`; may be provided more than once.
+    ///
+    /// A pinned package is only ever fetched from the given index, regardless of any other
+    /// configured indexes or `--find-links` locations, and the index is never consulted for any
+    /// other package. This can be used to prevent "dependency confusion" attacks on internal
+    /// package names.
+    #[clap(long)]
+    package_index: Vec<PackageIndex>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    ///
+    /// Credentials are otherwise read from the index URL itself, from the `.netrc` file (or the
+    /// file at `NETRC`), or from the `UV_HTTP_BASIC_<HOST>_USERNAME` and
+    /// `UV_HTTP_BASIC_<HOST>_PASSWORD` environment variables. With `subprocess`, `uv` will fall
+    /// back to `keyring get <url> <username>` if the index responds with `401 Unauthorized`, which
+    /// requires a username to be provided through one of the above.
+    #[clap(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    keyring_provider: Option<KeyringProvider>,
+
+    /// Allow insecure connections to a host, as `<host>` or `<host>:<port>`; may be provided more
+    /// than once.
+    ///
+    /// Requests to a trusted host may use plain HTTP, and skip TLS certificate verification. All
+    /// other indexes must be served over HTTPS with a valid certificate, unless they're on the
+    /// local machine.
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
     /// The Python interpreter into which packages should be installed.
     ///
     /// By default, `uv` installs into the virtual environment in the current working directory or
@@ -532,9 +1107,39 @@ struct PipSyncArgs {
 
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_legacy_setup_py")]
     legacy_setup_py: bool,
 
+    #[clap(long, overrides_with = "legacy_setup_py", hide = true)]
+    no_legacy_setup_py: bool,
+
+    /// Disable isolation when building source distributions.
+    ///
+    /// Assumes that the packages listed in `build-system.requires` are already installed in the
+    /// Python environment.
+    #[clap(long, overrides_with = "build_isolation")]
+    no_build_isolation: bool,
+
+    #[clap(long, overrides_with = "no_build_isolation", hide = true)]
+    build_isolation: bool,
+
+    /// Disable isolation when building source distributions for a specific package.
+    ///
+    /// Assumes that the packages listed in `build-system.requires` are already installed in the
+    /// Python environment.
+    #[clap(long, conflicts_with = "no_build_isolation")]
+    no_build_isolation_package: Vec<PackageName>,
+
+    /// Constrain build dependencies using the given requirements files when building source
+    /// distributions.
+    ///
+    /// Build constraints files are `requirements.txt`-like files that only control the _version_
+    /// of a requirement that's installed into a build environment (i.e., the packages listed in
+    /// `build-system.requires`). They have no effect on the packages that are installed or
+    /// resolved outside of build environments.
+    #[clap(long)]
+    build_constraint: Vec<PathBuf>,
+
     /// Don't build source distributions.
     ///
     /// When enabled, resolving will not run arbitrary code. The cached wheels of already-built
@@ -542,9 +1147,17 @@ struct PipSyncArgs {
     /// exit with an error.
     ///
     /// Alias for `--only-binary :all:`.
-    #[clap(long, conflicts_with = "no_binary", conflicts_with = "only_binary")]
+    #[clap(
+        long,
+        conflicts_with = "no_binary",
+        conflicts_with = "only_binary",
+        overrides_with = "build"
+    )]
     no_build: bool,
 
+    #[clap(long, overrides_with = "no_build", hide = true)]
+    build: bool,
+
     /// Don't install pre-built wheels.
     ///
     /// The given packages will be installed from a source distribution. The resolver
@@ -575,23 +1188,52 @@ struct PipSyncArgs {
     ///
     /// The compile option will process the entire site-packages directory for consistency and
     /// (like pip) ignore all errors.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_compile")]
     compile: bool,
 
+    #[clap(long, overrides_with = "compile", hide = true)]
+    no_compile: bool,
+
     /// Settings to pass to the PEP 517 build backend, specified as `KEY=VALUE` pairs.
     #[clap(long, short = 'C', alias = "config-settings")]
     config_setting: Vec<ConfigSettingEntry>,
 
+    /// Settings to pass to the PEP 517 build backend of a specific package, specified as
+    /// `PACKAGE:KEY=VALUE` pairs (e.g., `scipy:setup-args=-Dblas=openblas`).
+    ///
+    /// Package-specific settings take precedence over those provided via `--config-setting`.
+    #[clap(long, alias = "config-settings-package")]
+    config_setting_package: Vec<PackageConfigSettingEntry>,
+
+    /// Environment variables to set when building a specific package, specified as
+    /// `PACKAGE:NAME=VALUE` pairs (e.g., `flash-attn:MAX_JOBS=4`).
+    #[clap(long)]
+    build_env_package: Vec<PackageEnvEntry>,
+
     /// Validate the virtual environment after completing the installation, to detect packages with
     /// missing dependencies or other issues.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_strict")]
     strict: bool,
 
+    #[clap(long, overrides_with = "strict", hide = true)]
+    no_strict: bool,
+
+    /// Require a matching hash for each requirement.
+    ///
+    /// Hash-checking mode is all or nothing. If enabled, _all_ requirements must be provided
+    /// with a corresponding hash or set of hashes, and must be pinned to an exact version (or
+    /// URL). Any hashes provided via `--hash` are verified regardless of this flag.
+    #[clap(long, overrides_with = "no_require_hashes")]
+    require_hashes: bool,
+
+    #[clap(long, overrides_with = "require_hashes", hide = true)]
+    no_require_hashes: bool,
+
     #[command(flatten)]
     compat_args: compat::PipSyncCompatArgs,
 }
 
-#[derive(Args)]
+#[derive(Args, Debug)]
 #[allow(clippy::struct_excessive_bools)]
 #[command(group = clap::ArgGroup::new("sources").required(true).multiple(true))]
 struct PipInstallArgs {
@@ -676,14 +1318,14 @@ struct PipInstallArgs {
     no_deps: bool,
 
     /// The method to use when installing packages from the global cache.
-    #[clap(long, value_enum, default_value_t = install_wheel_rs::linker::LinkMode::default())]
-    link_mode: install_wheel_rs::linker::LinkMode,
+    #[clap(long, value_enum)]
+    link_mode: Option<install_wheel_rs::linker::LinkMode>,
 
-    #[clap(long, value_enum, default_value_t = ResolutionMode::default())]
-    resolution: ResolutionMode,
+    #[clap(long, value_enum)]
+    resolution: Option<ResolutionMode>,
 
-    #[clap(long, value_enum, default_value_t = PreReleaseMode::default(), conflicts_with = "pre")]
-    prerelease: PreReleaseMode,
+    #[clap(long, value_enum, conflicts_with = "pre")]
+    prerelease: Option<PreReleaseMode>,
 
     #[clap(long, hide = true, conflicts_with = "prerelease")]
     pre: bool,
@@ -694,12 +1336,15 @@ struct PipInstallArgs {
 
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
+    /// A `file://` URL may point to a local directory laid out as a simple index, with an
+    /// `index.html` (or PEP 691 `index.json`) file in each package's directory.
+    ///
     /// The index given by this flag is given lower priority than all other
     /// indexes specified via the `--extra-index-url` flag.
     ///
     /// Unlike `pip`, `uv` will stop looking for versions of a package as soon
-    /// as it finds it in an index. That is, it isn't possible for `uv` to
-    /// consider versions of the same package across multiple indexes.
+    /// as it finds it in an index, unless a different `--index-strategy` is
+    /// selected.
     #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
     index_url: Option<Maybe<IndexUrl>>,
 
@@ -710,8 +1355,8 @@ struct PipInstallArgs {
     /// `--extra-index-url` flags are given, earlier values take priority.
     ///
     /// Unlike `pip`, `uv` will stop looking for versions of a package as soon
-    /// as it finds it in an index. That is, it isn't possible for `uv` to
-    /// consider versions of the same package across multiple indexes.
+    /// as it finds it in an index, unless a different `--index-strategy` is
+    /// selected.
     #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
     extra_index_url: Vec<Maybe<IndexUrl>>,
 
@@ -726,9 +1371,54 @@ struct PipInstallArgs {
 
     /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
     /// discovered via `--find-links`.
-    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
+    #[clap(
+        long,
+        conflicts_with = "index_url",
+        conflicts_with = "extra_index_url",
+        overrides_with = "index"
+    )]
     no_index: bool,
 
+    #[clap(long, overrides_with = "no_index", hide = true)]
+    index: bool,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available, and
+    /// limit resolutions to those present on that first index (`first-match`). This prevents
+    /// "dependency confusion" attacks, whereby an attacker can upload a malicious package under the
+    /// same name to a secondary index.
+    #[clap(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    index_strategy: Option<IndexStrategy>,
+
+    /// Pin a package to an index, as `<package>=<url>`; may be provided more than once.
+    ///
+    /// A pinned package is only ever fetched from the given index, regardless of any other
+    /// configured indexes or `--find-links` locations, and the index is never consulted for any
+    /// other package. This can be used to prevent "dependency confusion" attacks on internal
+    /// package names.
+    #[clap(long)]
+    package_index: Vec<PackageIndex>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    ///
+    /// Credentials are otherwise read from the index URL itself, from the `.netrc` file (or the
+    /// file at `NETRC`), or from the `UV_HTTP_BASIC_<HOST>_USERNAME` and
+    /// `UV_HTTP_BASIC_<HOST>_PASSWORD` environment variables. With `subprocess`, `uv` will fall
+    /// back to `keyring get <url> <username>` if the index responds with `401 Unauthorized`, which
+    /// requires a username to be provided through one of the above.
+    #[clap(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    keyring_provider: Option<KeyringProvider>,
+
+    /// Allow insecure connections to a host, as `<host>` or `<host>:<port>`; may be provided more
+    /// than once.
+    ///
+    /// Requests to a trusted host may use plain HTTP, and skip TLS certificate verification. All
+    /// other indexes must be served over HTTPS with a valid certificate, unless they're on the
+    /// local machine.
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
     /// The Python interpreter into which packages should be installed.
     ///
     /// By default, `uv` installs into the virtual environment in the current working directory or
@@ -757,9 +1447,39 @@ struct PipInstallArgs {
 
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_legacy_setup_py")]
     legacy_setup_py: bool,
 
+    #[clap(long, overrides_with = "legacy_setup_py", hide = true)]
+    no_legacy_setup_py: bool,
+
+    /// Disable isolation when building source distributions.
+    ///
+    /// Assumes that the packages listed in `build-system.requires` are already installed in the
+    /// Python environment.
+    #[clap(long, overrides_with = "build_isolation")]
+    no_build_isolation: bool,
+
+    #[clap(long, overrides_with = "no_build_isolation", hide = true)]
+    build_isolation: bool,
+
+    /// Disable isolation when building source distributions for a specific package.
+    ///
+    /// Assumes that the packages listed in `build-system.requires` are already installed in the
+    /// Python environment.
+    #[clap(long, conflicts_with = "no_build_isolation")]
+    no_build_isolation_package: Vec<PackageName>,
+
+    /// Constrain build dependencies using the given requirements files when building source
+    /// distributions.
+    ///
+    /// Build constraints files are `requirements.txt`-like files that only control the _version_
+    /// of a requirement that's installed into a build environment (i.e., the packages listed in
+    /// `build-system.requires`). They have no effect on the packages that are installed or
+    /// resolved outside of build environments.
+    #[clap(long)]
+    build_constraint: Vec<PathBuf>,
+
     /// Don't build source distributions.
     ///
     /// When enabled, resolving will not run arbitrary code. The cached wheels of already-built
@@ -767,9 +1487,17 @@ struct PipInstallArgs {
     /// exit with an error.
     ///
     /// Alias for `--only-binary :all:`.
-    #[clap(long, conflicts_with = "no_binary", conflicts_with = "only_binary")]
+    #[clap(
+        long,
+        conflicts_with = "no_binary",
+        conflicts_with = "only_binary",
+        overrides_with = "build"
+    )]
     no_build: bool,
 
+    #[clap(long, overrides_with = "no_build", hide = true)]
+    build: bool,
+
     /// Don't install pre-built wheels.
     ///
     /// The given packages will be installed from a source distribution. The resolver
@@ -800,18 +1528,300 @@ struct PipInstallArgs {
     ///
     /// The compile option will process the entire site-packages directory for consistency and
     /// (like pip) ignore all errors.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_compile")]
     compile: bool,
 
+    #[clap(long, overrides_with = "compile", hide = true)]
+    no_compile: bool,
+
     /// Settings to pass to the PEP 517 build backend, specified as `KEY=VALUE` pairs.
     #[clap(long, short = 'C', alias = "config-settings")]
     config_setting: Vec<ConfigSettingEntry>,
 
+    /// Settings to pass to the PEP 517 build backend of a specific package, specified as
+    /// `PACKAGE:KEY=VALUE` pairs (e.g., `scipy:setup-args=-Dblas=openblas`).
+    ///
+    /// Package-specific settings take precedence over those provided via `--config-setting`.
+    #[clap(long, alias = "config-settings-package")]
+    config_setting_package: Vec<PackageConfigSettingEntry>,
+
+    /// Environment variables to set when building a specific package, specified as
+    /// `PACKAGE:NAME=VALUE` pairs (e.g., `flash-attn:MAX_JOBS=4`).
+    #[clap(long)]
+    build_env_package: Vec<PackageEnvEntry>,
+
     /// Validate the virtual environment after completing the installation, to detect packages with
     /// missing dependencies or other issues.
-    #[clap(long)]
+    #[clap(long, overrides_with = "no_strict")]
     strict: bool,
 
+    #[clap(long, overrides_with = "strict", hide = true)]
+    no_strict: bool,
+
+    /// Require a matching hash for each requirement.
+    ///
+    /// Hash-checking mode is all or nothing. If enabled, _all_ requirements must be provided
+    /// with a corresponding hash or set of hashes, and must be pinned to an exact version (or
+    /// URL). Any hashes provided via `--hash` are verified regardless of this flag.
+    #[clap(long, overrides_with = "no_require_hashes")]
+    require_hashes: bool,
+
+    #[clap(long, overrides_with = "require_hashes", hide = true)]
+    no_require_hashes: bool,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long, value_parser = date_or_datetime)]
+    exclude_newer: Option<DateTime<Utc>>,
+}
+
+#[derive(Args, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+#[command(group = clap::ArgGroup::new("sources").required(true).multiple(true))]
+struct PipDownloadArgs {
+    /// Download all listed packages.
+    #[clap(group = "sources")]
+    package: Vec<String>,
+
+    /// Download all packages listed in the given requirements files.
+    #[clap(long, short, group = "sources")]
+    requirement: Vec<PathBuf>,
+
+    /// Constrain versions using the given requirements files.
+    ///
+    /// Constraints files are `requirements.txt`-like files that only control the _version_ of a
+    /// requirement that's installed. However, including a package in a constraints file will _not_
+    /// trigger the installation of that package.
+    ///
+    /// This is equivalent to pip's `--constraint` option.
+    #[clap(long, short)]
+    constraint: Vec<PathBuf>,
+
+    /// Override versions using the given requirements files.
+    ///
+    /// Overrides files are `requirements.txt`-like files that force a specific version of a
+    /// requirement to be installed, regardless of the requirements declared by any constituent
+    /// package, and regardless of whether this would be considered an invalid resolution.
+    ///
+    /// While constraints are _additive_, in that they're combined with the requirements of the
+    /// constituent packages, overrides are _absolute_, in that they completely replace the
+    /// requirements of the constituent packages.
+    #[clap(long)]
+    r#override: Vec<PathBuf>,
+
+    /// The directory to which the resolved distributions should be saved.
+    ///
+    /// Alongside the distributions, a `manifest.txt` file pins each package to the hash of its
+    /// saved archive, such that the directory can be installed from with
+    /// `--no-index --find-links <dest> --require-hashes -r <dest>/manifest.txt`.
+    #[clap(long, short)]
+    dest: PathBuf,
+
+    /// Build any source distributions, and save the resulting wheels in their place.
+    ///
+    /// By default, the original source distributions are saved as-is. Git and local directory
+    /// dependencies can only be saved as built wheels.
+    #[clap(long)]
+    build: bool,
+
+    /// Ignore package dependencies, instead only downloading those packages explicitly listed
+    /// on the command line or in the requirements files.
+    #[clap(long)]
+    no_deps: bool,
+
+    #[clap(long, value_enum)]
+    resolution: Option<ResolutionMode>,
+
+    #[clap(long, value_enum, conflicts_with = "pre")]
+    prerelease: Option<PreReleaseMode>,
+
+    #[clap(long, hide = true, conflicts_with = "prerelease")]
+    pre: bool,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(
+        global = true,
+        long,
+        conflicts_with = "refresh",
+        conflicts_with = "refresh_package"
+    )]
+    offline: bool,
+
+    /// Refresh all cached data.
+    #[clap(long)]
+    refresh: bool,
+
+    /// Refresh cached data for a specific package.
+    #[clap(long)]
+    refresh_package: Vec<PackageName>,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// A `file://` URL may point to a local directory laid out as a simple index, with an
+    /// `index.html` (or PEP 691 `index.json`) file in each package's directory.
+    ///
+    /// The index given by this flag is given lower priority than all other
+    /// indexes specified via the `--extra-index-url` flag.
+    ///
+    /// Unlike `pip`, `uv` will stop looking for versions of a package as soon
+    /// as it finds it in an index, unless a different `--index-strategy` is
+    /// selected.
+    #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    ///
+    /// All indexes given via this flag take priority over the index
+    /// in `--index-url` (which defaults to PyPI). And when multiple
+    /// `--extra-index-url` flags are given, earlier values take priority.
+    ///
+    /// Unlike `pip`, `uv` will stop looking for versions of a package as soon
+    /// as it finds it in an index, unless a different `--index-strategy` is
+    /// selected.
+    #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    extra_index_url: Vec<Maybe<IndexUrl>>,
+
+    /// Locations to search for candidate distributions, beyond those found in the indexes.
+    ///
+    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
+    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    ///
+    /// If a URL, the page must contain a flat list of links to package files.
+    #[clap(long, short)]
+    find_links: Vec<FlatIndexLocation>,
+
+    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
+    /// discovered via `--find-links`.
+    #[clap(
+        long,
+        conflicts_with = "index_url",
+        conflicts_with = "extra_index_url",
+        overrides_with = "index"
+    )]
+    no_index: bool,
+
+    #[clap(long, overrides_with = "no_index", hide = true)]
+    index: bool,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available, and
+    /// limit resolutions to those present on that first index (`first-match`). This prevents
+    /// "dependency confusion" attacks, whereby an attacker can upload a malicious package under the
+    /// same name to a secondary index.
+    #[clap(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    index_strategy: Option<IndexStrategy>,
+
+    /// Pin a package to an index, as `<package>=<url>`; may be provided more than once.
+    ///
+    /// A pinned package is only ever fetched from the given index, regardless of any other
+    /// configured indexes or `--find-links` locations, and the index is never consulted for any
+    /// other package. This can be used to prevent "dependency confusion" attacks on internal
+    /// package names.
+    #[clap(long)]
+    package_index: Vec<PackageIndex>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    ///
+    /// Credentials are otherwise read from the index URL itself, from the `.netrc` file (or the
+    /// file at `NETRC`), or from the `UV_HTTP_BASIC_<HOST>_USERNAME` and
+    /// `UV_HTTP_BASIC_<HOST>_PASSWORD` environment variables. With `subprocess`, `uv` will fall
+    /// back to `keyring get <url> <username>` if the index responds with `401 Unauthorized`, which
+    /// requires a username to be provided through one of the above.
+    #[clap(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    keyring_provider: Option<KeyringProvider>,
+
+    /// Allow insecure connections to a host, as `<host>` or `<host>:<port>`; may be provided more
+    /// than once.
+    ///
+    /// Requests to a trusted host may use plain HTTP, and skip TLS certificate verification. All
+    /// other indexes must be served over HTTPS with a valid certificate, unless they're on the
+    /// local machine.
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
+    /// Use legacy `setuptools` behavior when building source distributions without a
+    /// `pyproject.toml`.
+    #[clap(long, overrides_with = "no_legacy_setup_py")]
+    legacy_setup_py: bool,
+
+    #[clap(long, overrides_with = "legacy_setup_py", hide = true)]
+    no_legacy_setup_py: bool,
+
+    /// Don't build source distributions.
+    ///
+    /// When enabled, resolving will not run arbitrary code. The cached wheels of already-built
+    /// source distributions will be reused, but operations that require building distributions will
+    /// exit with an error.
+    ///
+    /// Alias for `--only-binary :all:`.
+    #[clap(
+        long,
+        conflicts_with = "no_binary",
+        conflicts_with = "only_binary",
+        overrides_with = "allow_build"
+    )]
+    no_build: bool,
+
+    #[clap(long, overrides_with = "no_build", hide = true)]
+    allow_build: bool,
+
+    /// Don't download pre-built wheels.
+    ///
+    /// The given packages will be downloaded as source distributions. The resolver will still use
+    /// pre-built wheels for metadata.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    no_binary: Vec<PackageNameSpecifier>,
+
+    /// Only use pre-built wheels; don't build source distributions.
+    ///
+    /// When enabled, resolving will not run code from the given packages. The cached wheels of already-built
+    /// source distributions will be reused, but operations that require building distributions will
+    /// exit with an error.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    only_binary: Vec<PackageNameSpecifier>,
+
+    /// Settings to pass to the PEP 517 build backend, specified as `KEY=VALUE` pairs.
+    #[clap(long, short = 'C', alias = "config-settings")]
+    config_setting: Vec<ConfigSettingEntry>,
+
+    /// Settings to pass to the PEP 517 build backend of a specific package, specified as
+    /// `PACKAGE:KEY=VALUE` pairs (e.g., `scipy:setup-args=-Dblas=openblas`).
+    ///
+    /// Package-specific settings take precedence over those provided via `--config-setting`.
+    #[clap(long, alias = "config-settings-package")]
+    config_setting_package: Vec<PackageConfigSettingEntry>,
+
+    /// Environment variables to set when building a specific package, specified as
+    /// `PACKAGE:NAME=VALUE` pairs (e.g., `flash-attn:MAX_JOBS=4`).
+    #[clap(long)]
+    build_env_package: Vec<PackageEnvEntry>,
+
+    /// The Python version for which distributions should be downloaded (e.g., `3.7` or `3.7.9`).
+    ///
+    /// If a patch version is omitted, the most recent known patch version for that minor version
+    /// is assumed. For example, `3.7` is mapped to `3.7.17`.
+    #[arg(long)]
+    python_version: Option<PythonVersion>,
+
+    /// The platform for which distributions should be downloaded, rather than the current platform
+    /// (e.g., `x86_64-manylinux_2_28` or `aarch64-apple-darwin`).
+    ///
+    /// Represented as a "target triple", a string that describes the target platform in terms of
+    /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
+    /// `aarch64-apple-darwin`. The aliases `windows`, `linux`, and `macos` select the default
+    /// target for each operating system.
+    #[arg(long)]
+    python_platform: Option<TargetTriple>,
+
     /// Limit candidate packages to those that were uploaded prior to the given date.
     ///
     /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
@@ -873,7 +1883,58 @@ struct PipFreezeArgs {
     /// Validate the virtual environment, to detect packages with missing dependencies or other
     /// issues.
     #[clap(long)]
-    strict: bool,
+    strict: bool,
+
+    /// The Python interpreter for which packages should be listed.
+    ///
+    /// By default, `uv` lists packages in the currently activated virtual environment, or a virtual
+    /// environment (`.venv`) located in the current working directory or any parent directory,
+    /// falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// List packages for the system Python.
+    ///
+    /// By default, `uv` lists packages in the currently activated virtual environment, or a virtual
+    /// environment (`.venv`) located in the current working directory or any parent directory,
+    /// falling back to the system Python if no virtual environment is found. The `--system` option
+    /// instructs `uv` to use the first Python found in the system `PATH`.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct PipListArgs {
+    /// Validate the virtual environment, to detect packages with missing dependencies or other
+    /// issues.
+    #[clap(long)]
+    strict: bool,
+
+    /// Only include editable projects.
+    #[clap(short, long)]
+    editable: bool,
+
+    /// Exclude any editable packages from output.
+    #[clap(long)]
+    exclude_editable: bool,
+
+    /// Exclude the specified package(s) from the output.
+    #[clap(long)]
+    r#exclude: Vec<PackageName>,
+
+    /// Select the output format between: `columns` (default), `freeze`, or `json`.
+    #[clap(long, value_enum, default_value_t = ListFormat::default())]
+    format: ListFormat,
 
     /// The Python interpreter for which packages should be listed.
     ///
@@ -904,28 +1965,15 @@ struct PipFreezeArgs {
 
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
-struct PipListArgs {
+struct PipShowArgs {
+    /// The package(s) to display.
+    package: Vec<PackageName>,
+
     /// Validate the virtual environment, to detect packages with missing dependencies or other
     /// issues.
     #[clap(long)]
     strict: bool,
 
-    /// Only include editable projects.
-    #[clap(short, long)]
-    editable: bool,
-
-    /// Exclude any editable packages from output.
-    #[clap(long)]
-    exclude_editable: bool,
-
-    /// Exclude the specified package(s) from the output.
-    #[clap(long)]
-    r#exclude: Vec<PackageName>,
-
-    /// Select the output format between: `columns` (default), `freeze`, or `json`.
-    #[clap(long, value_enum, default_value_t = ListFormat::default())]
-    format: ListFormat,
-
     /// The Python interpreter for which packages should be listed.
     ///
     /// By default, `uv` lists packages in the currently activated virtual environment, or a virtual
@@ -955,10 +2003,22 @@ struct PipListArgs {
 
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
-struct PipShowArgs {
-    /// The package(s) to display.
+struct PipTreeArgs {
+    /// The package(s) from which to start the tree.
+    ///
+    /// By default, the tree starts from all packages that aren't required by any other installed
+    /// package (or, with `--invert`, from all packages that don't require any other installed
+    /// package).
     package: Vec<PackageName>,
 
+    /// Show the packages that depend on each package, rather than its dependencies.
+    #[clap(long)]
+    invert: bool,
+
+    /// The maximum depth of the tree to display, where `0` displays only the root packages.
+    #[clap(long, short)]
+    depth: Option<usize>,
+
     /// Validate the virtual environment, to detect packages with missing dependencies or other
     /// issues.
     #[clap(long)]
@@ -991,6 +2051,36 @@ struct PipShowArgs {
     system: bool,
 }
 
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct PipCheckArgs {
+    /// The Python interpreter for which packages should be checked.
+    ///
+    /// By default, `uv` checks packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, conflicts_with = "system")]
+    python: Option<String>,
+
+    /// Check packages for the system Python.
+    ///
+    /// By default, `uv` checks packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found. The
+    /// `--system` option instructs `uv` to use the first Python found in the system `PATH`.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, conflicts_with = "python")]
+    system: bool,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 struct VenvArgs {
@@ -1050,6 +2140,9 @@ struct VenvArgs {
 
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
+    /// A `file://` URL may point to a local directory laid out as a simple index, with an
+    /// `index.html` (or PEP 691 `index.json`) file in each package's directory.
+    ///
     /// The index given by this flag is given lower priority than all other
     /// indexes specified via the `--extra-index-url` flag.
     ///
@@ -1105,6 +2198,16 @@ struct RemoveArgs {
     name: PackageName,
 }
 
+/// Size the global thread pool used to install wheels.
+///
+/// Only commands that install packages need the thread pool, so it's initialized on demand.
+fn init_install_thread_pool(threads: usize) -> Result<()> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .context("Failed to initialize the install thread pool")
+}
+
 #[instrument] // Anchor span to check for overhead
 async fn run() -> Result<ExitStatus> {
     let cli = match Cli::try_parse() {
@@ -1208,13 +2311,46 @@ async fn run() -> Result<ExitStatus> {
     }))?;
 
     let cache = Cache::try_from(cli.cache_args)?;
+    // Only networked commands need the TLS configuration, so avoid reading any certificates until
+    // it's requested.
+    let tls = || TlsConfig::new(cli.webpki_roots, cli.cert.clone(), cli.client_cert.clone());
+    let proxy = ProxyConfig::new(cli.proxy, cli.index_proxy, cli.no_proxy);
+    let retries = cli
+        .http_retries
+        .unwrap_or(RegistryClientBuilder::DEFAULT_RETRIES);
+
+    // Determine the concurrency limits.
+    let concurrency = Concurrency {
+        downloads: cli
+            .concurrent_downloads
+            .map_or(Concurrency::DEFAULT_DOWNLOADS, NonZeroUsize::get),
+        builds: cli
+            .concurrent_builds
+            .map_or_else(Concurrency::threads, NonZeroUsize::get),
+        installs: cli
+            .concurrent_installs
+            .map_or_else(Concurrency::threads, NonZeroUsize::get),
+    };
+    let build_log_options = BuildLogOptions {
+        directory: cli.build_log_dir,
+        reporter: cli.verbose_build.then(|| {
+            Arc::new(commands::BuildOutputReporter::from(printer))
+                as Arc<dyn uv_build::BuildOutputReporter>
+        }),
+    };
 
     match cli.command {
         Commands::Pip(PipNamespace {
-            command: PipCommand::Compile(args),
+            command: PipCommand::Compile(mut args),
         }) => {
             args.compat_args.validate()?;
 
+            args.apply(Options::load(cli.config_file.as_deref(), cli.isolated)?);
+            if cli.show_settings {
+                writeln!(printer.stdout(), "{args:#?}")?;
+                return Ok(ExitStatus::Success);
+            }
+
             let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
             let requirements = args
                 .src_file
@@ -1239,7 +2375,9 @@ async fn run() -> Result<ExitStatus> {
                     .collect(),
                 args.find_links,
                 args.no_index,
-            );
+            )
+            .with_index_strategy(args.index_strategy.unwrap_or_default())
+            .with_package_indexes(args.package_index);
             let extras = if args.all_extras {
                 ExtrasSpecification::All
             } else if args.extra.is_empty() {
@@ -1248,6 +2386,8 @@ async fn run() -> Result<ExitStatus> {
                 ExtrasSpecification::Some(&args.extra)
             };
             let upgrade = Upgrade::from_args(args.upgrade, args.upgrade_package);
+            let output_format =
+                OutputFormat::from_args(args.output_format, args.output_file.as_deref());
             let no_build = NoBuild::from_args(args.only_binary, args.no_build);
             let dependency_mode = if args.no_deps {
                 DependencyMode::Direct
@@ -1257,7 +2397,7 @@ async fn run() -> Result<ExitStatus> {
             let prerelease = if args.pre {
                 PreReleaseMode::Allow
             } else {
-                args.prerelease
+                args.prerelease.unwrap_or_default()
             };
             let setup_py = if args.legacy_setup_py {
                 SetupPyStrategy::Setuptools
@@ -1265,13 +2405,27 @@ async fn run() -> Result<ExitStatus> {
                 SetupPyStrategy::Pep517
             };
             let config_settings = args.config_setting.into_iter().collect::<ConfigSettings>();
+            let package_build_settings = PackageBuildSettings::from_entries(
+                args.config_setting_package,
+                args.build_env_package,
+            );
+            let no_build_isolation = NoBuildIsolation::from_args(
+                args.no_build_isolation,
+                args.no_build_isolation_package,
+            );
+            let build_constraints = args
+                .build_constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
             commands::pip_compile(
                 &requirements,
                 &constraints,
                 &overrides,
                 extras,
                 args.output_file.as_deref(),
-                args.resolution,
+                output_format,
+                args.resolution.unwrap_or_default(),
                 prerelease,
                 dependency_mode,
                 upgrade,
@@ -1281,16 +2435,30 @@ async fn run() -> Result<ExitStatus> {
                 !args.no_header,
                 args.emit_index_url,
                 args.emit_find_links,
+                args.emit_trusted_host,
+                args.emit_build_requirements,
                 index_urls,
+                args.keyring_provider.unwrap_or_default(),
+                args.trusted_host,
                 setup_py,
                 config_settings,
+                package_build_settings,
+                no_build_isolation,
+                &build_constraints,
                 if args.offline {
                     Connectivity::Offline
                 } else {
                     Connectivity::Online
                 },
+                tls()?,
+                proxy,
+                retries,
+                concurrency,
+                &build_log_options,
                 &no_build,
                 args.python_version,
+                args.python_platform,
+                args.universal,
                 args.exclude_newer,
                 args.annotation_style,
                 cli.quiet,
@@ -1300,10 +2468,18 @@ async fn run() -> Result<ExitStatus> {
             .await
         }
         Commands::Pip(PipNamespace {
-            command: PipCommand::Sync(args),
+            command: PipCommand::Sync(mut args),
         }) => {
             args.compat_args.validate()?;
 
+            args.apply(Options::load(cli.config_file.as_deref(), cli.isolated)?);
+            if cli.show_settings {
+                writeln!(printer.stdout(), "{args:#?}")?;
+                return Ok(ExitStatus::Success);
+            }
+
+            init_install_thread_pool(concurrency.installs)?;
+
             let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
             let index_urls = IndexLocations::new(
                 args.index_url.and_then(Maybe::into_option),
@@ -1313,7 +2489,9 @@ async fn run() -> Result<ExitStatus> {
                     .collect(),
                 args.find_links,
                 args.no_index,
-            );
+            )
+            .with_index_strategy(args.index_strategy.unwrap_or_default())
+            .with_package_indexes(args.package_index);
             let sources = args
                 .src_file
                 .into_iter()
@@ -1328,23 +2506,47 @@ async fn run() -> Result<ExitStatus> {
                 SetupPyStrategy::Pep517
             };
             let config_settings = args.config_setting.into_iter().collect::<ConfigSettings>();
+            let package_build_settings = PackageBuildSettings::from_entries(
+                args.config_setting_package,
+                args.build_env_package,
+            );
+            let no_build_isolation = NoBuildIsolation::from_args(
+                args.no_build_isolation,
+                args.no_build_isolation_package,
+            );
+            let build_constraints = args
+                .build_constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
 
             commands::pip_sync(
                 &sources,
                 &reinstall,
-                args.link_mode,
+                args.link_mode.unwrap_or_default(),
                 args.compile,
                 index_urls,
+                args.keyring_provider.unwrap_or_default(),
+                args.trusted_host,
                 setup_py,
                 if args.offline {
                     Connectivity::Offline
                 } else {
                     Connectivity::Online
                 },
+                tls()?,
+                proxy,
+                retries,
+                concurrency,
+                &build_log_options,
                 &config_settings,
+                &package_build_settings,
+                &no_build_isolation,
+                &build_constraints,
                 &no_build,
                 &no_binary,
                 args.strict,
+                args.require_hashes,
                 args.python,
                 args.system,
                 cache,
@@ -1353,8 +2555,16 @@ async fn run() -> Result<ExitStatus> {
             .await
         }
         Commands::Pip(PipNamespace {
-            command: PipCommand::Install(args),
+            command: PipCommand::Install(mut args),
         }) => {
+            args.apply(Options::load(cli.config_file.as_deref(), cli.isolated)?);
+            if cli.show_settings {
+                writeln!(printer.stdout(), "{args:#?}")?;
+                return Ok(ExitStatus::Success);
+            }
+
+            init_install_thread_pool(concurrency.installs)?;
+
             let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
             let requirements = args
                 .package
@@ -1385,7 +2595,9 @@ async fn run() -> Result<ExitStatus> {
                     .collect(),
                 args.find_links,
                 args.no_index,
-            );
+            )
+            .with_index_strategy(args.index_strategy.unwrap_or_default())
+            .with_package_indexes(args.package_index);
             let extras = if args.all_extras {
                 ExtrasSpecification::All
             } else if args.extra.is_empty() {
@@ -1405,7 +2617,7 @@ async fn run() -> Result<ExitStatus> {
             let prerelease = if args.pre {
                 PreReleaseMode::Allow
             } else {
-                args.prerelease
+                args.prerelease.unwrap_or_default()
             };
             let setup_py = if args.legacy_setup_py {
                 SetupPyStrategy::Setuptools
@@ -1413,19 +2625,34 @@ async fn run() -> Result<ExitStatus> {
                 SetupPyStrategy::Pep517
             };
             let config_settings = args.config_setting.into_iter().collect::<ConfigSettings>();
+            let package_build_settings = PackageBuildSettings::from_entries(
+                args.config_setting_package,
+                args.build_env_package,
+            );
+            let no_build_isolation = NoBuildIsolation::from_args(
+                args.no_build_isolation,
+                args.no_build_isolation_package,
+            );
+            let build_constraints = args
+                .build_constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
 
             commands::pip_install(
                 &requirements,
                 &constraints,
                 &overrides,
                 &extras,
-                args.resolution,
+                args.resolution.unwrap_or_default(),
                 prerelease,
                 dependency_mode,
                 upgrade,
                 index_urls,
+                args.keyring_provider.unwrap_or_default(),
+                args.trusted_host,
                 &reinstall,
-                args.link_mode,
+                args.link_mode.unwrap_or_default(),
                 args.compile,
                 setup_py,
                 if args.offline {
@@ -1433,10 +2660,19 @@ async fn run() -> Result<ExitStatus> {
                 } else {
                     Connectivity::Online
                 },
+                tls()?,
+                proxy,
+                retries,
+                concurrency,
+                &build_log_options,
                 &config_settings,
+                &package_build_settings,
+                &no_build_isolation,
+                &build_constraints,
                 &no_build,
                 &no_binary,
                 args.strict,
+                args.require_hashes,
                 args.exclude_newer,
                 args.python,
                 args.system,
@@ -1445,6 +2681,105 @@ async fn run() -> Result<ExitStatus> {
             )
             .await
         }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Download(mut args),
+        }) => {
+            args.apply(Options::load(cli.config_file.as_deref(), cli.isolated)?);
+            if cli.show_settings {
+                writeln!(printer.stdout(), "{args:#?}")?;
+                return Ok(ExitStatus::Success);
+            }
+
+            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+            let requirements = args
+                .package
+                .into_iter()
+                .map(RequirementsSource::from_package)
+                .chain(
+                    args.requirement
+                        .into_iter()
+                        .map(RequirementsSource::from_path),
+                )
+                .collect::<Vec<_>>();
+            let constraints = args
+                .constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let overrides = args
+                .r#override
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let index_urls = IndexLocations::new(
+                args.index_url.and_then(Maybe::into_option),
+                args.extra_index_url
+                    .into_iter()
+                    .filter_map(Maybe::into_option)
+                    .collect(),
+                args.find_links,
+                args.no_index,
+            )
+            .with_index_strategy(args.index_strategy.unwrap_or_default())
+            .with_package_indexes(args.package_index);
+            let no_binary = NoBinary::from_args(args.no_binary);
+            let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+            let dependency_mode = if args.no_deps {
+                DependencyMode::Direct
+            } else {
+                DependencyMode::Transitive
+            };
+            let prerelease = if args.pre {
+                PreReleaseMode::Allow
+            } else {
+                args.prerelease.unwrap_or_default()
+            };
+            let setup_py = if args.legacy_setup_py {
+                SetupPyStrategy::Setuptools
+            } else {
+                SetupPyStrategy::Pep517
+            };
+            let config_settings = args.config_setting.into_iter().collect::<ConfigSettings>();
+            let package_build_settings = PackageBuildSettings::from_entries(
+                args.config_setting_package,
+                args.build_env_package,
+            );
+
+            commands::pip_download(
+                &requirements,
+                &constraints,
+                &overrides,
+                &args.dest,
+                args.build,
+                args.resolution.unwrap_or_default(),
+                prerelease,
+                dependency_mode,
+                index_urls,
+                args.keyring_provider.unwrap_or_default(),
+                args.trusted_host,
+                setup_py,
+                &config_settings,
+                &package_build_settings,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                tls()?,
+                proxy,
+                retries,
+                concurrency,
+                &build_log_options,
+                &no_build,
+                &no_binary,
+                args.python_version,
+                args.python_platform,
+                args.exclude_newer,
+                cache,
+                printer,
+            )
+            .await
+        }
         Commands::Pip(PipNamespace {
             command: PipCommand::Uninstall(args),
         }) => {
@@ -1469,6 +2804,9 @@ async fn run() -> Result<ExitStatus> {
                 } else {
                     Connectivity::Online
                 },
+                tls()?,
+                proxy,
+                retries,
                 printer,
             )
             .await
@@ -1505,6 +2843,21 @@ async fn run() -> Result<ExitStatus> {
             &cache,
             printer,
         ),
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Tree(args),
+        }) => commands::pip_tree(
+            args.package,
+            args.invert,
+            args.depth,
+            args.strict,
+            args.python.as_deref(),
+            args.system,
+            &cache,
+            printer,
+        ),
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Check(args),
+        }) => commands::pip_check(args.python.as_deref(), args.system, &cache, printer),
         Commands::Cache(CacheNamespace {
             command: CacheCommand::Clean(args),
         })
@@ -1515,9 +2868,76 @@ async fn run() -> Result<ExitStatus> {
             commands::cache_dir(&cache);
             Ok(ExitStatus::Success)
         }
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Prefetch(mut args),
+        }) => {
+            args.apply(Options::load(cli.config_file.as_deref(), cli.isolated)?);
+            if cli.show_settings {
+                writeln!(printer.stdout(), "{args:#?}")?;
+                return Ok(ExitStatus::Success);
+            }
+
+            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+            let requirements = args
+                .package
+                .into_iter()
+                .map(RequirementsSource::from_package)
+                .chain(
+                    args.requirement
+                        .into_iter()
+                        .map(RequirementsSource::from_path),
+                )
+                .collect::<Vec<_>>();
+            let constraints = args
+                .constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let overrides = args
+                .r#override
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let index_urls = IndexLocations::new(
+                args.index_url.and_then(Maybe::into_option),
+                args.extra_index_url
+                    .into_iter()
+                    .filter_map(Maybe::into_option)
+                    .collect(),
+                args.find_links,
+                args.no_index,
+            )
+            .with_index_strategy(args.index_strategy.unwrap_or_default())
+            .with_package_indexes(args.package_index);
+            let no_binary = NoBinary::from_args(args.no_binary);
+
+            commands::cache_prefetch(
+                &requirements,
+                &constraints,
+                &overrides,
+                index_urls,
+                args.keyring_provider.unwrap_or_default(),
+                args.trusted_host,
+                tls()?,
+                proxy,
+                retries,
+                concurrency,
+                &no_binary,
+                args.python_version,
+                args.python_platform,
+                args.exclude_newer,
+                cache,
+                printer,
+            )
+            .await
+        }
         Commands::Venv(args) => {
             args.compat_args.validate()?;
 
+            if args.seed {
+                init_install_thread_pool(concurrency.installs)?;
+            }
+
             let index_locations = IndexLocations::new(
                 args.index_url.and_then(Maybe::into_option),
                 args.extra_index_url
@@ -1549,6 +2969,10 @@ async fn run() -> Result<ExitStatus> {
                 } else {
                     Connectivity::Online
                 },
+                tls()?,
+                proxy,
+                retries,
+                concurrency,
                 args.seed,
                 args.exclude_newer,
                 &cache,
@@ -1556,6 +2980,61 @@ async fn run() -> Result<ExitStatus> {
             )
             .await
         }
+        Commands::Build(mut args) => {
+            args.apply(Options::load(cli.config_file.as_deref(), cli.isolated)?);
+            if cli.show_settings {
+                writeln!(printer.stdout(), "{args:#?}")?;
+                return Ok(ExitStatus::Success);
+            }
+
+            let src = args.src.unwrap_or_else(|| PathBuf::from("."));
+            let index_urls = IndexLocations::new(
+                args.index_url.and_then(Maybe::into_option),
+                args.extra_index_url
+                    .into_iter()
+                    .filter_map(Maybe::into_option)
+                    .collect(),
+                args.find_links,
+                args.no_index,
+            )
+            .with_index_strategy(args.index_strategy.unwrap_or_default())
+            .with_package_indexes(args.package_index);
+            let config_settings = args.config_setting.into_iter().collect::<ConfigSettings>();
+            let no_build_isolation =
+                NoBuildIsolation::from_args(args.no_build_isolation, Vec::new());
+            let build_constraints = args
+                .build_constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+
+            commands::build(
+                &src,
+                args.out_dir.as_deref(),
+                commands::BuildTarget::from_args(args.sdist, args.wheel),
+                args.python.as_deref(),
+                index_urls,
+                args.keyring_provider.unwrap_or_default(),
+                args.trusted_host,
+                &config_settings,
+                &no_build_isolation,
+                &build_constraints,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                tls()?,
+                proxy,
+                retries,
+                concurrency,
+                &build_log_options,
+                args.exclude_newer,
+                &cache,
+                printer,
+            )
+            .await
+        }
         Commands::Version { output_format } => {
             commands::version(output_format, &mut stdout())?;
             Ok(ExitStatus::Success)
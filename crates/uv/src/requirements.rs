@@ -6,18 +6,20 @@ use std::str::FromStr;
 use anyhow::{Context, Result};
 use console::Term;
 use indexmap::IndexMap;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tracing::{instrument, Level};
 
 use distribution_types::{FlatIndexLocation, IndexUrl};
-use pep508_rs::Requirement;
+use pep508_rs::{Requirement, VerbatimUrl};
+use pypi_types::HashDigest;
 use requirements_txt::{EditableRequirement, FindLink, RequirementsTxt};
-use uv_client::RegistryClient;
+use uv_client::{RegistryClient, TrustedHost};
 use uv_fs::Simplified;
 use uv_normalize::{ExtraName, PackageName};
+use uv_resolver::{Distribution, Lock, Source};
 use uv_warnings::warn_user;
 
-use crate::commands::Upgrade;
+use crate::commands::{OutputFormat, Upgrade};
 use crate::confirm;
 
 #[derive(Debug)]
@@ -30,6 +32,8 @@ pub(crate) enum RequirementsSource {
     RequirementsTxt(PathBuf),
     /// Dependencies were provided via a `pyproject.toml` file (e.g., `pip-compile pyproject.toml`).
     PyprojectToml(PathBuf),
+    /// Dependencies were provided via a `uv.lock` file (e.g., `pip sync uv.lock`).
+    Lockfile(PathBuf),
 }
 
 impl RequirementsSource {
@@ -37,6 +41,8 @@ impl RequirementsSource {
     pub(crate) fn from_path(path: PathBuf) -> Self {
         if path.ends_with("pyproject.toml") {
             Self::PyprojectToml(path)
+        } else if is_lockfile(&path) {
+            Self::Lockfile(path)
         } else {
             Self::RequirementsTxt(path)
         }
@@ -85,7 +91,7 @@ impl std::fmt::Display for RequirementsSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Editable(path) => write!(f, "-e {path}"),
-            Self::RequirementsTxt(path) | Self::PyprojectToml(path) => {
+            Self::RequirementsTxt(path) | Self::PyprojectToml(path) | Self::Lockfile(path) => {
                 write!(f, "{}", path.display())
             }
             Self::Package(package) => write!(f, "{package}"),
@@ -134,6 +140,10 @@ pub(crate) struct RequirementsSpecification {
     pub(crate) no_index: bool,
     /// The `--find-links` locations to use for fetching packages.
     pub(crate) find_links: Vec<FlatIndexLocation>,
+    /// The `--trusted-host` entries to allow insecure connections to.
+    pub(crate) trusted_hosts: Vec<TrustedHost>,
+    /// The `--hash` values provided for each requirement, keyed by package name.
+    pub(crate) hashes: FxHashMap<PackageName, Vec<HashDigest>>,
 }
 
 impl RequirementsSpecification {
@@ -159,6 +169,8 @@ impl RequirementsSpecification {
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    trusted_hosts: vec![],
+                    hashes: FxHashMap::default(),
                 }
             }
             RequirementsSource::Editable(name) => {
@@ -175,11 +187,28 @@ impl RequirementsSpecification {
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    trusted_hosts: vec![],
+                    hashes: FxHashMap::default(),
                 }
             }
             RequirementsSource::RequirementsTxt(path) => {
                 let requirements_txt =
                     RequirementsTxt::parse(path, std::env::current_dir()?, Some(client)).await?;
+
+                // Parse the `--hash` values for each requirement.
+                let mut hashes = FxHashMap::<PackageName, Vec<HashDigest>>::default();
+                for entry in &requirements_txt.requirements {
+                    for hash in &entry.hashes {
+                        let digest = HashDigest::from_str(hash).with_context(|| {
+                            format!("Invalid hash for `{}`", entry.requirement.name)
+                        })?;
+                        hashes
+                            .entry(entry.requirement.name.clone())
+                            .or_default()
+                            .push(digest);
+                    }
+                }
+
                 Self {
                     project: None,
                     requirements: requirements_txt
@@ -206,6 +235,8 @@ impl RequirementsSpecification {
                             FindLink::Path(path) => FlatIndexLocation::Path(path),
                         })
                         .collect(),
+                    trusted_hosts: requirements_txt.trusted_hosts,
+                    hashes,
                 }
             }
             RequirementsSource::PyprojectToml(path) => {
@@ -270,6 +301,48 @@ impl RequirementsSpecification {
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    trusted_hosts: vec![],
+                    hashes: FxHashMap::default(),
+                }
+            }
+            RequirementsSource::Lockfile(path) => {
+                let lock = read_lock(path).await?;
+
+                // Pin every distribution to its locked version or URL, and require that it
+                // matches one of its locked hashes.
+                let mut requirements = Vec::new();
+                let mut editables = Vec::new();
+                let mut hashes = FxHashMap::<PackageName, Vec<HashDigest>>::default();
+                for distribution in lock.distributions() {
+                    if let Source::Editable(path) = &distribution.source {
+                        editables.push(EditableRequirement {
+                            url: VerbatimUrl::from_path(path),
+                            extras: vec![],
+                            path: path.clone(),
+                        });
+                        continue;
+                    }
+                    requirements.push(distribution.to_requirement());
+                    hashes
+                        .entry(distribution.name.clone())
+                        .or_default()
+                        .extend(distribution.hashes().cloned());
+                }
+                hashes.retain(|_, hashes| !hashes.is_empty());
+
+                Self {
+                    project: None,
+                    requirements,
+                    constraints: vec![],
+                    overrides: vec![],
+                    editables,
+                    extras: FxHashSet::default(),
+                    index_url: None,
+                    extra_index_urls: vec![],
+                    no_index: false,
+                    find_links: vec![],
+                    trusted_hosts: vec![],
+                    hashes,
                 }
             }
         })
@@ -295,6 +368,9 @@ impl RequirementsSpecification {
             spec.overrides.extend(source.overrides);
             spec.extras.extend(source.extras);
             spec.editables.extend(source.editables);
+            for (name, hashes) in source.hashes {
+                spec.hashes.entry(name).or_default().extend(hashes);
+            }
 
             // Use the first project name discovered.
             if spec.project.is_none() {
@@ -312,6 +388,7 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            spec.trusted_hosts.extend(source.trusted_hosts);
         }
 
         // Read all constraints, treating _everything_ as a constraint.
@@ -332,6 +409,7 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            spec.trusted_hosts.extend(source.trusted_hosts);
         }
 
         // Read all overrides, treating both requirements _and_ constraints as overrides.
@@ -352,6 +430,7 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            spec.trusted_hosts.extend(source.trusted_hosts);
         }
 
         Ok(spec)
@@ -434,9 +513,44 @@ fn flatten_extra(
     )
 }
 
+/// Returns `true` if the path refers to a `uv.lock` file, rather than a `requirements.txt` file.
+pub(crate) fn is_lockfile(path: &Path) -> bool {
+    path.ends_with("uv.lock")
+}
+
+/// Read a [`Lock`] from a `uv.lock` file.
+pub(crate) async fn read_lock(path: &Path) -> Result<Lock> {
+    let contents = uv_fs::read_to_string(path).await?;
+    Lock::from_toml(&contents)
+        .with_context(|| format!("Failed to parse `{}`", path.simplified_display()))
+}
+
+/// Read the build constraints from the given `requirements.txt`-like files.
+///
+/// As with `--constraint`, every entry is treated as a constraint. Build constraints only apply to
+/// the resolution of build environments, and never to the top-level resolution.
+pub(crate) async fn read_build_constraints(
+    sources: &[RequirementsSource],
+    client: &RegistryClient,
+) -> Result<Vec<Requirement>> {
+    if sources.is_empty() {
+        return Ok(Vec::new());
+    }
+    let spec = RequirementsSpecification::from_sources(
+        &[],
+        sources,
+        &[],
+        &ExtrasSpecification::None,
+        client,
+    )
+    .await?;
+    Ok(spec.constraints)
+}
+
 /// Load the preferred requirements from an existing lockfile, applying the upgrade strategy.
 pub(crate) async fn read_lockfile(
     output_file: Option<&Path>,
+    output_format: OutputFormat,
     upgrade: Upgrade,
 ) -> Result<Vec<Requirement>> {
     // As an optimization, skip reading the lockfile is we're upgrading all packages anyway.
@@ -448,19 +562,29 @@ pub(crate) async fn read_lockfile(
     };
 
     // Parse the requirements from the lockfile.
-    let requirements_txt =
-        RequirementsTxt::parse(output_file, std::env::current_dir()?, None).await?;
-    let requirements = requirements_txt
-        .requirements
-        .into_iter()
-        .filter_map(|entry| {
-            if entry.editable {
-                None
-            } else {
-                Some(entry.requirement)
-            }
-        })
-        .collect::<Vec<_>>();
+    let requirements = if output_format.is_lock() {
+        read_lock(output_file)
+            .await?
+            .distributions()
+            .iter()
+            .filter(|distribution| !distribution.is_editable())
+            .map(Distribution::to_requirement)
+            .collect::<Vec<_>>()
+    } else {
+        let requirements_txt =
+            RequirementsTxt::parse(output_file, std::env::current_dir()?, None).await?;
+        requirements_txt
+            .requirements
+            .into_iter()
+            .filter_map(|entry| {
+                if entry.editable {
+                    None
+                } else {
+                    Some(entry.requirement)
+                }
+            })
+            .collect::<Vec<_>>()
+    };
 
     // Apply the upgrade strategy to the requirements.
     Ok(match upgrade {
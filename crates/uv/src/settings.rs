@@ -0,0 +1,892 @@
+//! Persistent settings, read from `[tool.uv]` in a `pyproject.toml` file, or from a `uv.toml` file.
+//!
+//! Settings are resolved in order of precedence: command-line arguments, then environment
+//! variables, then the project configuration (the nearest `uv.toml` or `pyproject.toml` with a
+//! `[tool.uv]` table, searching upwards from the current directory), then the user configuration
+//! (`uv.toml` in `$XDG_CONFIG_HOME/uv`, or `%APPDATA%\uv` on Windows).
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use fs_err as fs;
+use serde::{de, Deserialize, Deserializer};
+use tracing::debug;
+
+use distribution_types::{FlatIndexLocation, IndexStrategy, IndexUrl, PackageIndex};
+use install_wheel_rs::linker::LinkMode;
+use uv_auth::KeyringProvider;
+use uv_client::TrustedHost;
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+use uv_resolver::{PreReleaseMode, ResolutionMode};
+use uv_traits::{
+    ConfigSettingEntry, PackageConfigSettingEntry, PackageEnvEntry, PackageNameSpecifier,
+};
+
+use crate::{
+    BuildArgs, Maybe, PipCompileArgs, PipDownloadArgs, PipInstallArgs, PipSyncArgs, PrefetchArgs,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum SettingsError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse: `{}`", _0.simplified_display())]
+    Toml(PathBuf, #[source] toml::de::Error),
+}
+
+/// The options that can be set in `[tool.uv]` or in a `uv.toml` file.
+///
+/// Each option mirrors the command-line argument of the same name, and is ignored by commands that
+/// don't accept that argument.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct Options {
+    #[serde(default, deserialize_with = "deserialize_from_str")]
+    pub(crate) index_url: Option<IndexUrl>,
+    #[serde(default, deserialize_with = "deserialize_vec_from_str")]
+    pub(crate) extra_index_url: Option<Vec<IndexUrl>>,
+    #[serde(default, deserialize_with = "deserialize_vec_from_str")]
+    pub(crate) find_links: Option<Vec<FlatIndexLocation>>,
+    pub(crate) no_index: Option<bool>,
+    pub(crate) index_strategy: Option<IndexStrategy>,
+    #[serde(default, deserialize_with = "deserialize_package_index")]
+    pub(crate) package_index: Option<Vec<PackageIndex>>,
+    pub(crate) keyring_provider: Option<KeyringProvider>,
+    #[serde(default, deserialize_with = "deserialize_vec_from_str")]
+    pub(crate) trusted_host: Option<Vec<TrustedHost>>,
+    pub(crate) resolution: Option<ResolutionMode>,
+    pub(crate) prerelease: Option<PreReleaseMode>,
+    pub(crate) link_mode: Option<LinkMode>,
+    pub(crate) no_build: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_vec_from_str")]
+    pub(crate) no_binary: Option<Vec<PackageNameSpecifier>>,
+    #[serde(default, deserialize_with = "deserialize_vec_from_str")]
+    pub(crate) only_binary: Option<Vec<PackageNameSpecifier>>,
+    pub(crate) legacy_setup_py: Option<bool>,
+    pub(crate) no_build_isolation: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_vec_from_str")]
+    pub(crate) no_build_isolation_package: Option<Vec<PackageName>>,
+    pub(crate) build_constraint: Option<Vec<PathBuf>>,
+    #[serde(default, deserialize_with = "deserialize_config_settings")]
+    pub(crate) config_settings: Option<Vec<ConfigSettingEntry>>,
+    #[serde(default, deserialize_with = "deserialize_package_config_settings")]
+    pub(crate) config_settings_package: Option<Vec<PackageConfigSettingEntry>>,
+    #[serde(default, deserialize_with = "deserialize_package_env")]
+    pub(crate) build_env_package: Option<Vec<PackageEnvEntry>>,
+    pub(crate) compile_bytecode: Option<bool>,
+    pub(crate) generate_hashes: Option<bool>,
+    pub(crate) require_hashes: Option<bool>,
+    pub(crate) strict: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_exclude_newer")]
+    pub(crate) exclude_newer: Option<DateTime<Utc>>,
+}
+
+impl Options {
+    /// Load the options for the current invocation.
+    ///
+    /// If a configuration file is provided explicitly, it's used in lieu of discovery. Otherwise,
+    /// unless `isolated` is set, the project and user configuration are discovered and merged,
+    /// with the project configuration taking precedence.
+    pub(crate) fn load(config_file: Option<&Path>, isolated: bool) -> Result<Self, SettingsError> {
+        if let Some(config_file) = config_file {
+            debug!(
+                "Reading settings from: {}",
+                config_file.simplified_display()
+            );
+            return read_uv_toml(config_file);
+        }
+
+        if isolated {
+            return Ok(Self::default());
+        }
+
+        let project = Self::find_project(&std::env::current_dir()?)?;
+        let user = Self::find_user()?;
+        Ok(match (project, user) {
+            (Some(project), Some(user)) => project.combine(user),
+            (Some(options), None) | (None, Some(options)) => options,
+            (None, None) => Self::default(),
+        })
+    }
+
+    /// Find the project configuration: the nearest `uv.toml`, or `pyproject.toml` with a
+    /// `[tool.uv]` table, in the given directory or any of its ancestors.
+    fn find_project(dir: &Path) -> Result<Option<Self>, SettingsError> {
+        for ancestor in dir.ancestors() {
+            let uv_toml = ancestor.join("uv.toml");
+            if uv_toml.is_file() {
+                debug!("Reading settings from: {}", uv_toml.simplified_display());
+                return read_uv_toml(&uv_toml).map(Some);
+            }
+
+            let pyproject_toml = ancestor.join("pyproject.toml");
+            if pyproject_toml.is_file() {
+                if let Some(options) = read_pyproject_toml(&pyproject_toml)? {
+                    debug!(
+                        "Reading settings from: {}",
+                        pyproject_toml.simplified_display()
+                    );
+                    return Ok(Some(options));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find the user configuration, in `uv.toml` within the user's configuration directory.
+    fn find_user() -> Result<Option<Self>, SettingsError> {
+        let Some(config_dir) = user_config_dir() else {
+            return Ok(None);
+        };
+        let uv_toml = config_dir.join("uv").join("uv.toml");
+        if !uv_toml.is_file() {
+            return Ok(None);
+        }
+        debug!("Reading settings from: {}", uv_toml.simplified_display());
+        read_uv_toml(&uv_toml).map(Some)
+    }
+
+    /// Combine two sets of options, preferring the values in `self`.
+    #[must_use]
+    fn combine(self, other: Self) -> Self {
+        Self {
+            index_url: self.index_url.or(other.index_url),
+            extra_index_url: self.extra_index_url.or(other.extra_index_url),
+            find_links: self.find_links.or(other.find_links),
+            no_index: self.no_index.or(other.no_index),
+            index_strategy: self.index_strategy.or(other.index_strategy),
+            package_index: self.package_index.or(other.package_index),
+            keyring_provider: self.keyring_provider.or(other.keyring_provider),
+            trusted_host: self.trusted_host.or(other.trusted_host),
+            resolution: self.resolution.or(other.resolution),
+            prerelease: self.prerelease.or(other.prerelease),
+            link_mode: self.link_mode.or(other.link_mode),
+            no_build: self.no_build.or(other.no_build),
+            no_binary: self.no_binary.or(other.no_binary),
+            only_binary: self.only_binary.or(other.only_binary),
+            legacy_setup_py: self.legacy_setup_py.or(other.legacy_setup_py),
+            no_build_isolation: self.no_build_isolation.or(other.no_build_isolation),
+            no_build_isolation_package: self
+                .no_build_isolation_package
+                .or(other.no_build_isolation_package),
+            build_constraint: self.build_constraint.or(other.build_constraint),
+            config_settings: self.config_settings.or(other.config_settings),
+            config_settings_package: self
+                .config_settings_package
+                .or(other.config_settings_package),
+            build_env_package: self.build_env_package.or(other.build_env_package),
+            compile_bytecode: self.compile_bytecode.or(other.compile_bytecode),
+            generate_hashes: self.generate_hashes.or(other.generate_hashes),
+            require_hashes: self.require_hashes.or(other.require_hashes),
+            strict: self.strict.or(other.strict),
+            exclude_newer: self.exclude_newer.or(other.exclude_newer),
+        }
+    }
+}
+
+/// Returns the user's configuration directory: `$XDG_CONFIG_HOME` (or `~/.config`) on Unix, and
+/// `%APPDATA%` on Windows.
+fn user_config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        directories::BaseDirs::new().map(|dirs| dirs.config_dir().to_path_buf())
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".config")))
+    }
+}
+
+/// Read the options from a `uv.toml` file.
+fn read_uv_toml(path: &Path) -> Result<Options, SettingsError> {
+    let contents = fs::read_to_string(path)?;
+    toml::from_str(&contents).map_err(|err| SettingsError::Toml(path.to_path_buf(), err))
+}
+
+/// Read the options from the `[tool.uv]` table of a `pyproject.toml` file, if present.
+fn read_pyproject_toml(path: &Path) -> Result<Option<Options>, SettingsError> {
+    #[derive(Deserialize)]
+    struct PyProjectToml {
+        tool: Option<Tool>,
+    }
+
+    #[derive(Deserialize)]
+    struct Tool {
+        uv: Option<Options>,
+    }
+
+    let contents = fs::read_to_string(path)?;
+    let pyproject_toml: PyProjectToml =
+        toml::from_str(&contents).map_err(|err| SettingsError::Toml(path.to_path_buf(), err))?;
+    Ok(pyproject_toml.tool.and_then(|tool| tool.uv))
+}
+
+/// Resolve a pair of boolean flags (e.g., `--no-build` and `--build`), returning `None` if neither
+/// was provided, such that the value can fall back to the configuration.
+fn flag(yes: bool, no: bool) -> Option<bool> {
+    match (yes, no) {
+        (true, false) => Some(true),
+        (false, true) => Some(false),
+        (false, false) => None,
+        (true, true) => unreachable!("Clap should make this impossible"),
+    }
+}
+
+/// Deserialize a value via its [`FromStr`] implementation.
+fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = String::deserialize(deserializer)?;
+    T::from_str(&value).map(Some).map_err(de::Error::custom)
+}
+
+/// Deserialize a list of values via their [`FromStr`] implementation.
+fn deserialize_vec_from_str<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|value| T::from_str(value).map_err(de::Error::custom))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// Deserialize a table of packages pinned to an index, mapping each package name to an index URL.
+fn deserialize_package_index<'de, D>(deserializer: D) -> Result<Option<Vec<PackageIndex>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    BTreeMap::<String, String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(package, index)| {
+            Ok(PackageIndex {
+                package: PackageName::from_str(&package)
+                    .map_err(<D::Error as de::Error>::custom)?,
+                index: IndexUrl::from_str(&index).map_err(<D::Error as de::Error>::custom)?,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// A PEP 517 build backend setting value, either a string or a list of strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ConfigSettingValue {
+    String(String),
+    List(Vec<String>),
+}
+
+/// Flatten a table of PEP 517 build backend settings into a list of entries.
+fn config_setting_entries(table: BTreeMap<String, ConfigSettingValue>) -> Vec<ConfigSettingEntry> {
+    let mut entries = Vec::new();
+    for (key, value) in table {
+        match value {
+            ConfigSettingValue::String(value) => entries.push(ConfigSettingEntry::new(key, value)),
+            ConfigSettingValue::List(values) => entries.extend(
+                values
+                    .into_iter()
+                    .map(|value| ConfigSettingEntry::new(key.clone(), value)),
+            ),
+        }
+    }
+    entries
+}
+
+/// Deserialize a table of PEP 517 build backend settings, in which each value is either a string
+/// or a list of strings.
+fn deserialize_config_settings<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<ConfigSettingEntry>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let table = BTreeMap::<String, ConfigSettingValue>::deserialize(deserializer)?;
+    Ok(Some(config_setting_entries(table)))
+}
+
+/// Deserialize a table of packages to PEP 517 build backend settings, mapping each package name to
+/// a table of settings (as in `config-settings`).
+fn deserialize_package_config_settings<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<PackageConfigSettingEntry>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut entries = Vec::new();
+    for (package, table) in
+        BTreeMap::<String, BTreeMap<String, ConfigSettingValue>>::deserialize(deserializer)?
+    {
+        let package = PackageName::from_str(&package).map_err(de::Error::custom)?;
+        entries.extend(
+            config_setting_entries(table)
+                .into_iter()
+                .map(|setting| PackageConfigSettingEntry::new(package.clone(), setting)),
+        );
+    }
+    Ok(Some(entries))
+}
+
+/// Deserialize a table of packages to build environment variables, mapping each package name to a
+/// table of variable names and values.
+fn deserialize_package_env<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<PackageEnvEntry>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut entries = Vec::new();
+    for (package, variables) in
+        BTreeMap::<String, BTreeMap<String, String>>::deserialize(deserializer)?
+    {
+        let package = PackageName::from_str(&package).map_err(de::Error::custom)?;
+        entries.extend(
+            variables
+                .into_iter()
+                .map(|(name, value)| PackageEnvEntry::new(package.clone(), name, value)),
+        );
+    }
+    Ok(Some(entries))
+}
+
+/// Deserialize an `exclude-newer` value, accepting either an RFC 3339 timestamp or a date.
+fn deserialize_exclude_newer<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    crate::date_or_datetime(&value)
+        .map(Some)
+        .map_err(de::Error::custom)
+}
+
+impl PipCompileArgs {
+    /// Fill in any settings that weren't provided on the command line (or via environment
+    /// variables) from the given [`Options`].
+    pub(crate) fn apply(&mut self, options: Options) {
+        let Options {
+            index_url,
+            extra_index_url,
+            find_links,
+            no_index,
+            index_strategy,
+            package_index,
+            keyring_provider,
+            trusted_host,
+            resolution,
+            prerelease,
+            link_mode: _,
+            no_build,
+            no_binary: _,
+            only_binary,
+            legacy_setup_py,
+            no_build_isolation,
+            no_build_isolation_package,
+            build_constraint,
+            config_settings,
+            config_settings_package,
+            build_env_package,
+            compile_bytecode: _,
+            generate_hashes,
+            require_hashes: _,
+            strict: _,
+            exclude_newer,
+        } = options;
+
+        if self.index_url.is_none() {
+            self.index_url = index_url.map(Maybe::Some);
+        }
+        if self.extra_index_url.is_empty() {
+            self.extra_index_url = extra_index_url
+                .into_iter()
+                .flatten()
+                .map(Maybe::Some)
+                .collect();
+        }
+        if self.find_links.is_empty() {
+            self.find_links = find_links.unwrap_or_default();
+        }
+        self.no_index = flag(self.no_index, self.index)
+            .or(no_index)
+            .unwrap_or_default();
+        self.index_strategy = self.index_strategy.or(index_strategy);
+        if self.package_index.is_empty() {
+            self.package_index = package_index.unwrap_or_default();
+        }
+        self.keyring_provider = self.keyring_provider.or(keyring_provider);
+        if self.trusted_host.is_empty() {
+            self.trusted_host = trusted_host.unwrap_or_default();
+        }
+        if !self.pre {
+            self.prerelease = self.prerelease.or(prerelease);
+        }
+        self.resolution = self.resolution.or(resolution);
+        self.no_build = flag(self.no_build, self.build)
+            .or(no_build)
+            .unwrap_or_default();
+        if self.only_binary.is_empty() {
+            self.only_binary = only_binary.unwrap_or_default();
+        }
+        self.legacy_setup_py = flag(self.legacy_setup_py, self.no_legacy_setup_py)
+            .or(legacy_setup_py)
+            .unwrap_or_default();
+        self.no_build_isolation = flag(self.no_build_isolation, self.build_isolation)
+            .or(no_build_isolation)
+            .unwrap_or_default();
+        if self.no_build_isolation_package.is_empty() {
+            self.no_build_isolation_package = no_build_isolation_package.unwrap_or_default();
+        }
+        if self.build_constraint.is_empty() {
+            self.build_constraint = build_constraint.unwrap_or_default();
+        }
+        if self.config_setting.is_empty() {
+            self.config_setting = config_settings.unwrap_or_default();
+        }
+        if self.config_setting_package.is_empty() {
+            self.config_setting_package = config_settings_package.unwrap_or_default();
+        }
+        if self.build_env_package.is_empty() {
+            self.build_env_package = build_env_package.unwrap_or_default();
+        }
+        self.generate_hashes = flag(self.generate_hashes, self.no_generate_hashes)
+            .or(generate_hashes)
+            .unwrap_or_default();
+        self.exclude_newer = self.exclude_newer.or(exclude_newer);
+    }
+}
+
+impl PipSyncArgs {
+    /// Fill in any settings that weren't provided on the command line (or via environment
+    /// variables) from the given [`Options`].
+    pub(crate) fn apply(&mut self, options: Options) {
+        let Options {
+            index_url,
+            extra_index_url,
+            find_links,
+            no_index,
+            index_strategy,
+            package_index,
+            keyring_provider,
+            trusted_host,
+            resolution: _,
+            prerelease: _,
+            link_mode,
+            no_build,
+            no_binary,
+            only_binary,
+            legacy_setup_py,
+            no_build_isolation,
+            no_build_isolation_package,
+            build_constraint,
+            config_settings,
+            config_settings_package,
+            build_env_package,
+            compile_bytecode,
+            generate_hashes: _,
+            require_hashes,
+            strict,
+            exclude_newer: _,
+        } = options;
+
+        if self.index_url.is_none() {
+            self.index_url = index_url.map(Maybe::Some);
+        }
+        if self.extra_index_url.is_empty() {
+            self.extra_index_url = extra_index_url
+                .into_iter()
+                .flatten()
+                .map(Maybe::Some)
+                .collect();
+        }
+        if self.find_links.is_empty() {
+            self.find_links = find_links.unwrap_or_default();
+        }
+        self.no_index = flag(self.no_index, self.index)
+            .or(no_index)
+            .unwrap_or_default();
+        self.index_strategy = self.index_strategy.or(index_strategy);
+        if self.package_index.is_empty() {
+            self.package_index = package_index.unwrap_or_default();
+        }
+        self.keyring_provider = self.keyring_provider.or(keyring_provider);
+        if self.trusted_host.is_empty() {
+            self.trusted_host = trusted_host.unwrap_or_default();
+        }
+        self.link_mode = self.link_mode.or(link_mode);
+        self.no_build = flag(self.no_build, self.build)
+            .or(no_build)
+            .unwrap_or_default();
+        if self.no_binary.is_empty() {
+            self.no_binary = no_binary.unwrap_or_default();
+        }
+        if self.only_binary.is_empty() {
+            self.only_binary = only_binary.unwrap_or_default();
+        }
+        self.legacy_setup_py = flag(self.legacy_setup_py, self.no_legacy_setup_py)
+            .or(legacy_setup_py)
+            .unwrap_or_default();
+        self.no_build_isolation = flag(self.no_build_isolation, self.build_isolation)
+            .or(no_build_isolation)
+            .unwrap_or_default();
+        if self.no_build_isolation_package.is_empty() {
+            self.no_build_isolation_package = no_build_isolation_package.unwrap_or_default();
+        }
+        if self.build_constraint.is_empty() {
+            self.build_constraint = build_constraint.unwrap_or_default();
+        }
+        if self.config_setting.is_empty() {
+            self.config_setting = config_settings.unwrap_or_default();
+        }
+        if self.config_setting_package.is_empty() {
+            self.config_setting_package = config_settings_package.unwrap_or_default();
+        }
+        if self.build_env_package.is_empty() {
+            self.build_env_package = build_env_package.unwrap_or_default();
+        }
+        self.compile = flag(self.compile, self.no_compile)
+            .or(compile_bytecode)
+            .unwrap_or_default();
+        self.require_hashes = flag(self.require_hashes, self.no_require_hashes)
+            .or(require_hashes)
+            .unwrap_or_default();
+        self.strict = flag(self.strict, self.no_strict)
+            .or(strict)
+            .unwrap_or_default();
+    }
+}
+
+impl PipInstallArgs {
+    /// Fill in any settings that weren't provided on the command line (or via environment
+    /// variables) from the given [`Options`].
+    pub(crate) fn apply(&mut self, options: Options) {
+        let Options {
+            index_url,
+            extra_index_url,
+            find_links,
+            no_index,
+            index_strategy,
+            package_index,
+            keyring_provider,
+            trusted_host,
+            resolution,
+            prerelease,
+            link_mode,
+            no_build,
+            no_binary,
+            only_binary,
+            legacy_setup_py,
+            no_build_isolation,
+            no_build_isolation_package,
+            build_constraint,
+            config_settings,
+            config_settings_package,
+            build_env_package,
+            compile_bytecode,
+            generate_hashes: _,
+            require_hashes,
+            strict,
+            exclude_newer,
+        } = options;
+
+        if self.index_url.is_none() {
+            self.index_url = index_url.map(Maybe::Some);
+        }
+        if self.extra_index_url.is_empty() {
+            self.extra_index_url = extra_index_url
+                .into_iter()
+                .flatten()
+                .map(Maybe::Some)
+                .collect();
+        }
+        if self.find_links.is_empty() {
+            self.find_links = find_links.unwrap_or_default();
+        }
+        self.no_index = flag(self.no_index, self.index)
+            .or(no_index)
+            .unwrap_or_default();
+        self.index_strategy = self.index_strategy.or(index_strategy);
+        if self.package_index.is_empty() {
+            self.package_index = package_index.unwrap_or_default();
+        }
+        self.keyring_provider = self.keyring_provider.or(keyring_provider);
+        if self.trusted_host.is_empty() {
+            self.trusted_host = trusted_host.unwrap_or_default();
+        }
+        if !self.pre {
+            self.prerelease = self.prerelease.or(prerelease);
+        }
+        self.resolution = self.resolution.or(resolution);
+        self.link_mode = self.link_mode.or(link_mode);
+        self.no_build = flag(self.no_build, self.build)
+            .or(no_build)
+            .unwrap_or_default();
+        if self.no_binary.is_empty() {
+            self.no_binary = no_binary.unwrap_or_default();
+        }
+        if self.only_binary.is_empty() {
+            self.only_binary = only_binary.unwrap_or_default();
+        }
+        self.legacy_setup_py = flag(self.legacy_setup_py, self.no_legacy_setup_py)
+            .or(legacy_setup_py)
+            .unwrap_or_default();
+        self.no_build_isolation = flag(self.no_build_isolation, self.build_isolation)
+            .or(no_build_isolation)
+            .unwrap_or_default();
+        if self.no_build_isolation_package.is_empty() {
+            self.no_build_isolation_package = no_build_isolation_package.unwrap_or_default();
+        }
+        if self.build_constraint.is_empty() {
+            self.build_constraint = build_constraint.unwrap_or_default();
+        }
+        if self.config_setting.is_empty() {
+            self.config_setting = config_settings.unwrap_or_default();
+        }
+        if self.config_setting_package.is_empty() {
+            self.config_setting_package = config_settings_package.unwrap_or_default();
+        }
+        if self.build_env_package.is_empty() {
+            self.build_env_package = build_env_package.unwrap_or_default();
+        }
+        self.compile = flag(self.compile, self.no_compile)
+            .or(compile_bytecode)
+            .unwrap_or_default();
+        self.require_hashes = flag(self.require_hashes, self.no_require_hashes)
+            .or(require_hashes)
+            .unwrap_or_default();
+        self.strict = flag(self.strict, self.no_strict)
+            .or(strict)
+            .unwrap_or_default();
+        self.exclude_newer = self.exclude_newer.or(exclude_newer);
+    }
+}
+
+impl PipDownloadArgs {
+    /// Fill in any settings that weren't provided on the command line (or via environment
+    /// variables) from the given [`Options`].
+    pub(crate) fn apply(&mut self, options: Options) {
+        let Options {
+            index_url,
+            extra_index_url,
+            find_links,
+            no_index,
+            index_strategy,
+            package_index,
+            keyring_provider,
+            trusted_host,
+            resolution,
+            prerelease,
+            link_mode: _,
+            no_build,
+            no_binary,
+            only_binary,
+            legacy_setup_py,
+            no_build_isolation: _,
+            no_build_isolation_package: _,
+            build_constraint: _,
+            config_settings,
+            config_settings_package,
+            build_env_package,
+            compile_bytecode: _,
+            generate_hashes: _,
+            require_hashes: _,
+            strict: _,
+            exclude_newer,
+        } = options;
+
+        if self.index_url.is_none() {
+            self.index_url = index_url.map(Maybe::Some);
+        }
+        if self.extra_index_url.is_empty() {
+            self.extra_index_url = extra_index_url
+                .into_iter()
+                .flatten()
+                .map(Maybe::Some)
+                .collect();
+        }
+        if self.find_links.is_empty() {
+            self.find_links = find_links.unwrap_or_default();
+        }
+        self.no_index = flag(self.no_index, self.index)
+            .or(no_index)
+            .unwrap_or_default();
+        self.index_strategy = self.index_strategy.or(index_strategy);
+        if self.package_index.is_empty() {
+            self.package_index = package_index.unwrap_or_default();
+        }
+        self.keyring_provider = self.keyring_provider.or(keyring_provider);
+        if self.trusted_host.is_empty() {
+            self.trusted_host = trusted_host.unwrap_or_default();
+        }
+        if !self.pre {
+            self.prerelease = self.prerelease.or(prerelease);
+        }
+        self.resolution = self.resolution.or(resolution);
+        self.no_build = flag(self.no_build, self.allow_build)
+            .or(no_build)
+            .unwrap_or_default();
+        if self.no_binary.is_empty() {
+            self.no_binary = no_binary.unwrap_or_default();
+        }
+        if self.only_binary.is_empty() {
+            self.only_binary = only_binary.unwrap_or_default();
+        }
+        self.legacy_setup_py = flag(self.legacy_setup_py, self.no_legacy_setup_py)
+            .or(legacy_setup_py)
+            .unwrap_or_default();
+        if self.config_setting.is_empty() {
+            self.config_setting = config_settings.unwrap_or_default();
+        }
+        if self.config_setting_package.is_empty() {
+            self.config_setting_package = config_settings_package.unwrap_or_default();
+        }
+        if self.build_env_package.is_empty() {
+            self.build_env_package = build_env_package.unwrap_or_default();
+        }
+        self.exclude_newer = self.exclude_newer.or(exclude_newer);
+    }
+}
+
+impl PrefetchArgs {
+    /// Fill in any settings that weren't provided on the command line (or via environment
+    /// variables) from the given [`Options`].
+    pub(crate) fn apply(&mut self, options: Options) {
+        let Options {
+            index_url,
+            extra_index_url,
+            find_links,
+            no_index,
+            index_strategy,
+            package_index,
+            keyring_provider,
+            trusted_host,
+            resolution: _,
+            prerelease: _,
+            link_mode: _,
+            no_build: _,
+            no_binary,
+            only_binary: _,
+            legacy_setup_py: _,
+            no_build_isolation: _,
+            no_build_isolation_package: _,
+            build_constraint: _,
+            config_settings: _,
+            config_settings_package: _,
+            build_env_package: _,
+            compile_bytecode: _,
+            generate_hashes: _,
+            require_hashes: _,
+            strict: _,
+            exclude_newer,
+        } = options;
+
+        if self.index_url.is_none() {
+            self.index_url = index_url.map(Maybe::Some);
+        }
+        if self.extra_index_url.is_empty() {
+            self.extra_index_url = extra_index_url
+                .into_iter()
+                .flatten()
+                .map(Maybe::Some)
+                .collect();
+        }
+        if self.find_links.is_empty() {
+            self.find_links = find_links.unwrap_or_default();
+        }
+        self.no_index = flag(self.no_index, self.index)
+            .or(no_index)
+            .unwrap_or_default();
+        self.index_strategy = self.index_strategy.or(index_strategy);
+        if self.package_index.is_empty() {
+            self.package_index = package_index.unwrap_or_default();
+        }
+        self.keyring_provider = self.keyring_provider.or(keyring_provider);
+        if self.trusted_host.is_empty() {
+            self.trusted_host = trusted_host.unwrap_or_default();
+        }
+        if self.no_binary.is_empty() {
+            self.no_binary = no_binary.unwrap_or_default();
+        }
+        self.exclude_newer = self.exclude_newer.or(exclude_newer);
+    }
+}
+
+impl BuildArgs {
+    /// Fill in any settings that weren't provided on the command line (or via environment
+    /// variables) from the given [`Options`].
+    pub(crate) fn apply(&mut self, options: Options) {
+        let Options {
+            index_url,
+            extra_index_url,
+            find_links,
+            no_index,
+            index_strategy,
+            package_index,
+            keyring_provider,
+            trusted_host,
+            resolution: _,
+            prerelease: _,
+            link_mode: _,
+            no_build: _,
+            no_binary: _,
+            only_binary: _,
+            legacy_setup_py: _,
+            no_build_isolation,
+            no_build_isolation_package: _,
+            build_constraint,
+            config_settings,
+            config_settings_package: _,
+            build_env_package: _,
+            compile_bytecode: _,
+            generate_hashes: _,
+            require_hashes: _,
+            strict: _,
+            exclude_newer,
+        } = options;
+
+        if self.index_url.is_none() {
+            self.index_url = index_url.map(Maybe::Some);
+        }
+        if self.extra_index_url.is_empty() {
+            self.extra_index_url = extra_index_url
+                .into_iter()
+                .flatten()
+                .map(Maybe::Some)
+                .collect();
+        }
+        if self.find_links.is_empty() {
+            self.find_links = find_links.unwrap_or_default();
+        }
+        self.no_index = flag(self.no_index, self.index)
+            .or(no_index)
+            .unwrap_or_default();
+        self.index_strategy = self.index_strategy.or(index_strategy);
+        if self.package_index.is_empty() {
+            self.package_index = package_index.unwrap_or_default();
+        }
+        self.keyring_provider = self.keyring_provider.or(keyring_provider);
+        if self.trusted_host.is_empty() {
+            self.trusted_host = trusted_host.unwrap_or_default();
+        }
+        if self.config_setting.is_empty() {
+            self.config_setting = config_settings.unwrap_or_default();
+        }
+        self.no_build_isolation = flag(self.no_build_isolation, self.build_isolation)
+            .or(no_build_isolation)
+            .unwrap_or_default();
+        if self.build_constraint.is_empty() {
+            self.build_constraint = build_constraint.unwrap_or_default();
+        }
+        self.exclude_newer = self.exclude_newer.or(exclude_newer);
+    }
+}
@@ -0,0 +1,165 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::predicate;
+
+use common::{uv_snapshot, venv_to_interpreter, TestContext, EXCLUDE_NEWER};
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a `build` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("build")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("--python")
+        .arg(venv_to_interpreter(&context.venv))
+        .current_dir(&context.temp_dir);
+    context.add_index_args(&mut command);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Write a minimal `hatchling` project to the `project` directory.
+fn write_project(context: &TestContext) -> Result<()> {
+    let project = context.temp_dir.child("project");
+    project.child("pyproject.toml").write_str(
+        r#"[project]
+name = "example"
+version = "0.1.0"
+requires-python = ">=3.8"
+
+[build-system]
+requires = ["hatchling"]
+build-backend = "hatchling.build"
+"#,
+    )?;
+    project
+        .child("example")
+        .child("__init__.py")
+        .write_str("")?;
+    Ok(())
+}
+
+/// Build a source distribution, then a wheel from that source distribution.
+#[test]
+fn build_sdist_then_wheel() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_project(&context)?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("project"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 2 distributions in [TIME]
+     + example-0.1.0.tar.gz
+     + example-0.1.0-py3-none-any.whl
+    "###
+    );
+
+    let dist = context.temp_dir.child("project").child("dist");
+    dist.child("example-0.1.0.tar.gz")
+        .assert(predicate::path::is_file());
+    dist.child("example-0.1.0-py3-none-any.whl")
+        .assert(predicate::path::is_file());
+
+    Ok(())
+}
+
+/// Build only a source distribution, into a custom output directory.
+#[test]
+fn build_sdist_only() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_project(&context)?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("project")
+        .arg("--sdist")
+        .arg("--out-dir")
+        .arg("out"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 distribution in [TIME]
+     + example-0.1.0.tar.gz
+    "###
+    );
+
+    context
+        .temp_dir
+        .child("out")
+        .child("example-0.1.0.tar.gz")
+        .assert(predicate::path::is_file());
+    context
+        .temp_dir
+        .child("project")
+        .child("dist")
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// Build only a wheel, directly from the source tree.
+#[test]
+fn build_wheel_only() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_project(&context)?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("project")
+        .arg("--wheel"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 distribution in [TIME]
+     + example-0.1.0-py3-none-any.whl
+    "###
+    );
+
+    let dist = context.temp_dir.child("project").child("dist");
+    dist.child("example-0.1.0-py3-none-any.whl")
+        .assert(predicate::path::is_file());
+    dist.child("example-0.1.0.tar.gz")
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// Report a missing source tree.
+#[test]
+fn build_missing_source_tree() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("missing"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Source tree not found at: missing
+    "###
+    );
+}
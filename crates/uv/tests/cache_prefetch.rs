@@ -0,0 +1,109 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use common::{uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a `cache prefetch` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("cache")
+        .arg("prefetch")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    context.add_index_args(&mut command);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Prefetch the metadata for a set of requirements, then resolve them with `--offline`.
+#[test]
+fn prefetch_offline() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.0.0")?;
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("idna==3.4\nsniffio==1.3.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.in")
+        .arg("--constraint")
+        .arg("constraints.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Prefetched 3 packages and 3 wheels in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--constraint")
+        .arg("constraints.txt")
+        .arg("--offline"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt --offline
+    anyio==4.0.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Report versions whose metadata can only be read by building a source distribution.
+#[test]
+fn prefetch_source_distribution() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("idna==3.4\nsniffio==1.3.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--constraint")
+        .arg("constraints.txt")
+        .arg("--no-binary")
+        .arg("anyio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Prefetched 1 package and 0 wheels in [TIME]
+    The following could not be prefetched, and will require network access to resolve:
+     - anyio==4.0.0: only available as a source distribution
+    "###
+    );
+
+    Ok(())
+}
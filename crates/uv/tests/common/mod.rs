@@ -4,22 +4,25 @@
 use assert_cmd::assert::{Assert, OutputAssertExt};
 use assert_cmd::Command;
 use assert_fs::assert::PathAssert;
-use assert_fs::fixture::PathChild;
+use assert_fs::fixture::{FileWriteStr, PathChild};
 #[cfg(unix)]
 use fs_err::os::unix::fs::symlink as symlink_file;
 #[cfg(windows)]
 use fs_err::os::windows::fs::symlink_file;
+use indoc::indoc;
 use regex::Regex;
 use std::borrow::BorrowMut;
 use std::env;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::process::Output;
+use url::Url;
 use uv_fs::Simplified;
 
 use platform_host::Platform;
 use uv_cache::Cache;
 use uv_interpreter::find_requested_python;
+use uv_test_index::TestIndex;
 
 // Exclude any packages uploaded after this date.
 pub static EXCLUDE_NEWER: &str = "2023-11-18T12:00:00Z";
@@ -47,6 +50,9 @@ pub struct TestContext {
     pub venv: PathBuf,
     pub python_version: String,
 
+    /// The URL of a local index to resolve against, in lieu of PyPI.
+    index_url: Option<String>,
+
     // Standard filters for this test context
     filters: Vec<(String, String)>,
 }
@@ -79,10 +85,20 @@ impl TestContext {
             cache_dir,
             venv,
             python_version: python_version.to_string(),
+            index_url: None,
             filters,
         }
     }
 
+    /// Resolve against the given local index, rather than PyPI.
+    #[must_use]
+    pub fn with_index(mut self, index: &TestIndex) -> Self {
+        self.filters
+            .push((regex::escape(&index.base_url()), "[INDEX]".to_string()));
+        self.index_url = Some(index.url());
+        self
+    }
+
     /// Set shared defaults between tests:
     /// * Set the current directory to a temporary directory (`temp_dir`).
     /// * Set the cache dir to a different temporary directory (`cache_dir`).
@@ -99,6 +115,8 @@ impl TestContext {
             .env("VIRTUAL_ENV", self.venv.as_os_str())
             .current_dir(self.temp_dir.path());
 
+        self.add_index_args(&mut cmd);
+
         if cfg!(all(windows, debug_assertions)) {
             // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
             // default windows stack of 1MB
@@ -108,6 +126,17 @@ impl TestContext {
         cmd
     }
 
+    /// Add the arguments that point a command at the index configured for this test context, if
+    /// any.
+    ///
+    /// Every command builder that resolves or installs packages should call this, such that tests
+    /// using [`TestContext::with_index`] never fall back to PyPI.
+    pub fn add_index_args(&self, command: &mut std::process::Command) {
+        if let Some(index_url) = &self.index_url {
+            command.arg("--index-url").arg(index_url);
+        }
+    }
+
     /// Run the given python code and check whether it succeeds.
     pub fn assert_command(&self, command: &str) -> Assert {
         std::process::Command::new(venv_to_interpreter(&self.venv))
@@ -129,6 +158,79 @@ impl TestContext {
         .stdout(version);
     }
 
+    /// Create a `setuptools` project named `example` in the `project` directory, returning a
+    /// direct URL requirement on it.
+    ///
+    /// Building the project requires `setuptools` and `wheel`. Tests that need a different build
+    /// can overwrite its `setup.py`.
+    pub fn setuptools_project(&self) -> String {
+        let project = self.temp_dir.child("project");
+        project
+            .child("pyproject.toml")
+            .write_str(indoc! {r#"
+                [build-system]
+                requires = ["setuptools>=40.8.0", "wheel"]
+                build-backend = "setuptools.build_meta"
+            "#})
+            .expect("Failed to write `pyproject.toml`");
+        project
+            .child("setup.py")
+            .write_str(indoc! {r#"
+                from setuptools import setup
+
+                setup(name="example", version="0.1.0", packages=["example"])
+            "#})
+            .expect("Failed to write `setup.py`");
+        project
+            .child("example")
+            .child("__init__.py")
+            .write_str("")
+            .expect("Failed to write `__init__.py`");
+        format!(
+            "example @ {}",
+            Url::from_directory_path(project.path()).unwrap()
+        )
+    }
+
+    /// Create a `setuptools` project like [`TestContext::setuptools_project`], whose build fails
+    /// unless `EXAMPLE_BUILD_FLAG` is set to `1` in the build environment.
+    ///
+    /// The build prints `Building example` before checking the variable.
+    pub fn flag_gated_setuptools_project(&self) -> String {
+        let requirement = self.setuptools_project();
+        self.temp_dir
+            .child("project")
+            .child("setup.py")
+            .write_str(indoc! {r#"
+                import os
+
+                from setuptools import setup
+
+                print("Building example")
+                if os.environ.get("EXAMPLE_BUILD_FLAG") != "1":
+                    raise RuntimeError("`EXAMPLE_BUILD_FLAG` is not set")
+
+                setup(name="example", version="0.1.0", packages=["example"])
+            "#})
+            .expect("Failed to write `setup.py`");
+        requirement
+    }
+
+    /// Standard snapshot filters _plus_ those for the error of a failed source distribution build.
+    pub fn build_failure_filters(&self) -> Vec<(&str, &str)> {
+        let mut filters = self.filters();
+        // The traceback depends on the version of `setuptools`.
+        filters.push((
+            r"(?s)Traceback \(most recent call last\):\n.*\n(RuntimeError: )",
+            "Traceback (most recent call last):\n[TRACEBACK]\n$1",
+        ));
+        // The log file names are randomized.
+        filters.push((r"(Full build log: \S+/)[^/\s]+\.log", "$1[LOG]"));
+        // The exit status is displayed differently on Windows.
+        filters.push(("exit status", "exit code"));
+        filters
+    }
+
     /// Generate an escaped regex pattern for the given path.
     fn path_patterns(path: impl AsRef<Path>) -> Vec<String> {
         vec![
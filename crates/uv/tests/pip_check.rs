@@ -0,0 +1,174 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::fixture::FileWriteStr;
+use assert_fs::fixture::PathChild;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `pip install` command with options shared across scenarios.
+fn install_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("install")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    context.add_index_args(&mut command);
+    command
+}
+
+/// Create a `pip check` command with options shared across scenarios.
+fn check_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("check")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+#[test]
+fn check_compatible_packages() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("flask==3.0.0")?;
+
+    uv_snapshot!(install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    Downloaded 7 packages in [TIME]
+    Installed 7 packages in [TIME]
+     + blinker==1.7.0
+     + click==8.1.7
+     + flask==3.0.0
+     + itsdangerous==2.1.2
+     + jinja2==3.1.2
+     + markupsafe==2.1.3
+     + werkzeug==3.0.1
+    "###
+    );
+
+    uv_snapshot!(check_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Checked 7 packages in [TIME]
+    All installed packages are compatible
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn check_incompatible_packages() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("flask==3.0.0\nwerkzeug==2.3.8")?;
+
+    uv_snapshot!(install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--no-deps"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + flask==3.0.0
+     + werkzeug==2.3.8
+    "###
+    );
+
+    uv_snapshot!(check_command(&context), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    The package `flask` requires `blinker >=1.6.2`, but it's not installed.
+    The package `flask` requires `click >=8.1.3`, but it's not installed.
+    The package `flask` requires `itsdangerous >=2.1.2`, but it's not installed.
+    The package `flask` requires `jinja2 >=3.1.2`, but it's not installed.
+    The package `flask` requires `werkzeug >=3.0.0`, but `2.3.8` is installed.
+    The package `werkzeug` requires `markupsafe >=2.1.1`, but it's not installed.
+
+    ----- stderr -----
+    Checked 2 packages in [TIME]
+    Found 6 issues
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn check_missing_files() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    // Remove a file that's listed in the `RECORD`.
+    fs_err::remove_file(
+        context
+            .venv
+            .join("lib/python3.12/site-packages/markupsafe/__init__.py"),
+    )?;
+
+    uv_snapshot!(context.filters(), check_command(&context), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    The package `markupsafe` is missing files listed in its `RECORD`:
+      - [VENV]/lib/python3.12/site-packages/markupsafe/__init__.py
+
+    ----- stderr -----
+    Checked 1 package in [TIME]
+    Found 1 issue
+    "###
+    );
+
+    Ok(())
+}
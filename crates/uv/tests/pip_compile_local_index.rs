@@ -0,0 +1,312 @@
+#![cfg(feature = "python")]
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::prelude::*;
+
+use common::{uv_snapshot, TestContext};
+use uv_test_index::{Failure, TestIndex};
+
+mod common;
+
+/// The directory of wheels served by the local index.
+fn wheels() -> Result<PathBuf> {
+    Ok(std::env::current_dir()?
+        .join("..")
+        .join("..")
+        .join("scripts")
+        .join("wheels"))
+}
+
+/// Resolve via the JSON API, reading the metadata from the PEP 658 metadata file.
+#[test]
+fn compile_json() -> Result<()> {
+    let index = TestIndex::builder().dir(wheels()?).start()?;
+    let context = TestContext::new("3.12").with_index(&index);
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    assert_eq!(
+        index.requests(),
+        vec![
+            "GET /simple/tqdm/",
+            "GET /files/tqdm-1000.0.0-py3-none-any.whl.metadata",
+        ]
+    );
+
+    Ok(())
+}
+
+/// Resolve via the HTML API, without PEP 658 metadata, such that the metadata is read from the
+/// wheel via range requests.
+#[test]
+fn compile_html_range_requests() -> Result<()> {
+    let index = TestIndex::builder()
+        .dir(wheels()?)
+        .html_only()
+        .no_metadata()
+        .start()?;
+    let context = TestContext::new("3.12").with_index(&index);
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    let requests = index.requests();
+    assert!(requests.contains(&"HEAD /files/tqdm-1000.0.0-py3-none-any.whl".to_string()));
+    assert!(!requests
+        .iter()
+        .any(|request| request.ends_with(".metadata")));
+
+    Ok(())
+}
+
+/// Retry a request that fails with a transient server error.
+#[test]
+fn compile_server_error_retry() -> Result<()> {
+    let index = TestIndex::builder()
+        .dir(wheels()?)
+        .failure(Failure::status("/simple/tqdm/", 500).times(1))
+        .start()?;
+    let context = TestContext::new("3.12").with_index(&index);
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    assert_eq!(
+        index
+            .requests()
+            .iter()
+            .filter(|request| *request == "GET /simple/tqdm/")
+            .count(),
+        2
+    );
+
+    Ok(())
+}
+
+/// Authenticate against an index with credentials from the environment.
+#[test]
+fn compile_authenticated() -> Result<()> {
+    let index = TestIndex::builder()
+        .dir(wheels()?)
+        .auth("public", "heron")
+        .start()?;
+    let context = TestContext::new("3.12").with_index(&index);
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    // Without credentials, the index rejects the request.
+    context.compile().arg("requirements.in").assert().failure();
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in")
+        .env("UV_HTTP_BASIC_127_0_0_1_USERNAME", "public")
+        .env("UV_HTTP_BASIC_127_0_0_1_PASSWORD", "heron"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Report the number of attempts when a request continues to fail after retrying.
+#[test]
+fn compile_server_error_retries_exhausted() -> Result<()> {
+    let index = TestIndex::builder()
+        .dir(wheels()?)
+        .failure(Failure::status("/simple/tqdm/", 500))
+        .start()?;
+    let context = TestContext::new("3.12").with_index(&index);
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in")
+        .arg("--http-retries")
+        .arg("1"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Request failed after 2 attempts
+      Caused by: HTTP status server error (500 Internal Server Error) for url ([INDEX]/simple/tqdm/)
+    "###
+    );
+
+    assert_eq!(
+        index
+            .requests()
+            .iter()
+            .filter(|request| *request == "GET /simple/tqdm/")
+            .count(),
+        2
+    );
+
+    Ok(())
+}
+
+/// Reject a PEP 658 metadata file that doesn't match the hash advertised by the index.
+#[test]
+fn compile_metadata_hash_mismatch() -> Result<()> {
+    let index = TestIndex::builder()
+        .dir(wheels()?)
+        .corrupt_metadata("tqdm-1000.0.0-py3-none-any.whl")
+        .start()?;
+    let context = TestContext::new("3.12").with_index(&index);
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    let filters = [(r"sha256:[0-9a-f]{64}", "sha256:[SHA256]")]
+        .into_iter()
+        .chain(context.filters())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, context.compile()
+        .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download: tqdm==1000.0.0
+      Caused by: Hash mismatch for metadata of tqdm-1000.0.0-py3-none-any.whl from [INDEX]/files/tqdm-1000.0.0-py3-none-any.whl.metadata
+
+    Expected:
+      sha256:[SHA256]
+
+    Computed:
+      sha256:[SHA256]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resume a download that's interrupted mid-response, from a server that doesn't support range
+/// requests (such that the already-received bytes are skipped).
+#[test]
+fn compile_interrupted_download() -> Result<()> {
+    let index = TestIndex::builder()
+        .dir(wheels()?)
+        .no_metadata()
+        .no_ranges()
+        .failure(Failure::truncate("/files/tqdm-1000.0.0-py3-none-any.whl", 64).times(1))
+        .start()?;
+    let context = TestContext::new("3.12").with_index(&index);
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    assert_eq!(
+        index
+            .requests()
+            .iter()
+            .filter(|request| *request == "GET /files/tqdm-1000.0.0-py3-none-any.whl")
+            .count(),
+        2
+    );
+
+    Ok(())
+}
+
+/// Retry range requests for a wheel's metadata that are interrupted mid-response.
+#[test]
+fn compile_interrupted_range_request() -> Result<()> {
+    let index = TestIndex::builder()
+        .dir(wheels()?)
+        .no_metadata()
+        .failure(Failure::truncate("/files/tqdm-1000.0.0-py3-none-any.whl", 16).times(1))
+        .start()?;
+    let context = TestContext::new("3.12").with_index(&index);
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    assert_eq!(
+        index
+            .requests()
+            .iter()
+            .filter(|request| *request == "HEAD /files/tqdm-1000.0.0-py3-none-any.whl")
+            .count(),
+        2
+    );
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use fs_err as fs;
+
+use common::{uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a `pip download` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("download")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    context.add_index_args(&mut command);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Download a set of registry distributions into a wheelhouse.
+#[test]
+fn download_registry() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--dest")
+        .arg("wheelhouse"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages to wheelhouse in [TIME]
+     + anyio-4.0.0-py3-none-any.whl
+     + idna-3.4-py3-none-any.whl
+     + sniffio-1.3.0-py3-none-any.whl
+    "###
+    );
+
+    // The manifest should pin each package to the hash of its saved archive.
+    let manifest = fs::read_to_string(context.temp_dir.child("wheelhouse").child("manifest.txt"))?;
+    insta::assert_snapshot!(manifest, @r###"
+    # This file was autogenerated by uv to record the distributions in this directory.
+    anyio==4.0.0 \
+        --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+    idna==3.4 \
+        --hash=sha256:90b77e79eaa3eba6de819a0c442c0b4ceefc341a7a2ab77d7562bf49f425c5c2
+    sniffio==1.3.0 \
+        --hash=sha256:eecefdce1e5bbfb7ad2eeaabf7c1eeb404d7757c379bd1f7e5cce9d8bf425384
+    "###);
+
+    Ok(())
+}
+
+/// Download a source distribution as-is, and then as a built wheel with `--build`.
+#[test]
+fn download_source_distribution() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--no-deps")
+        .arg("--no-binary")
+        .arg("anyio")
+        .arg("--dest")
+        .arg("sdists"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package to sdists in [TIME]
+     + anyio-4.0.0.tar.gz
+    "###
+    );
+
+    uv_snapshot!(command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--no-deps")
+        .arg("--no-binary")
+        .arg("anyio")
+        .arg("--build")
+        .arg("--dest")
+        .arg("wheels"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package to wheels in [TIME]
+     + anyio-4.0.0-py3-none-any.whl
+    "###
+    );
+
+    Ok(())
+}
+
+/// Download a wheel from a `--find-links` directory, then install it from the wheelhouse in
+/// hash-checking mode, using the generated manifest.
+#[test]
+fn download_find_links_install() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project_root = fs::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    context
+        .temp_dir
+        .child("links")
+        .child("tqdm-1000.0.0-py3-none-any.whl")
+        .write_binary(&fs::read(
+            project_root.join("scripts/wheels/tqdm-1000.0.0-py3-none-any.whl"),
+        )?)?;
+
+    uv_snapshot!(command(&context)
+        .arg("tqdm")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg("links")
+        .arg("--dest")
+        .arg("wheelhouse"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package to wheelhouse in [TIME]
+     + tqdm-1000.0.0-py3-none-any.whl
+    "###
+    );
+
+    let manifest = fs::read_to_string(context.temp_dir.child("wheelhouse").child("manifest.txt"))?;
+    insta::assert_snapshot!(manifest, @r###"
+    # This file was autogenerated by uv to record the distributions in this directory.
+    tqdm==1000.0.0 \
+        --hash=sha256:a34996d4bd5abb2336e14ff0a2d22b92cfd0f0ed344e6883041ce01953276a13
+    "###);
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg("wheelhouse")
+        .arg("--require-hashes")
+        .arg("-r")
+        .arg("wheelhouse/manifest.txt")
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0
+    "###
+    );
+
+    Ok(())
+}
@@ -175,3 +175,25 @@ fn freeze_url() -> Result<()> {
 
     Ok(())
 }
+
+/// Ignore the TLS configuration, which isn't needed to list the installed packages.
+#[test]
+fn freeze_invalid_client_cert() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let client_cert = context.temp_dir.child("client.pem");
+    client_cert.write_str("not a certificate")?;
+
+    uv_snapshot!(command(&context)
+        .arg("--client-cert")
+        .arg("client.pem"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
@@ -11,6 +11,7 @@ use itertools::Itertools;
 use url::Url;
 
 use common::{uv_snapshot, TestContext, EXCLUDE_NEWER, INSTA_FILTERS};
+use uv_test_index::TestIndex;
 
 use crate::common::get_bin;
 
@@ -56,6 +57,7 @@ fn command_without_exclude_newer(context: &TestContext) -> Command {
         .arg(context.cache_dir.path())
         .env("VIRTUAL_ENV", context.venv.as_os_str())
         .current_dir(&context.temp_dir);
+    context.add_index_args(&mut command);
 
     if cfg!(all(windows, debug_assertions)) {
         // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
@@ -183,6 +185,53 @@ fn install_package() {
     context.assert_command("import flask").success();
 }
 
+/// Install a package from source with every concurrency limit set to one, which requires that
+/// builds don't hold their permit while installing their own build requirements.
+#[test]
+fn install_concurrency_limits() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--no-binary")
+        .arg("anyio")
+        .arg("--strict")
+        .env("UV_CONCURRENT_DOWNLOADS", "1")
+        .env("UV_CONCURRENT_BUILDS", "1")
+        .env("UV_CONCURRENT_INSTALLS", "1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==4.0.0
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    context.assert_command("import anyio").success();
+
+    // A limit of zero is rejected.
+    uv_snapshot!(command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--concurrent-builds")
+        .arg("0"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value '0' for '--concurrent-builds <N>': number would be zero for non-zero type
+
+    For more information, try '--help'.
+    "###
+    );
+}
+
 /// Install a package from a `requirements.txt` into a virtual environment.
 #[test]
 fn install_requirements_txt() -> Result<()> {
@@ -876,6 +925,58 @@ fn install_extra_index_url_has_priority() {
     context.assert_command("import flask").failure();
 }
 
+/// Install a package that's only available in the requested version on a lower-priority index.
+///
+/// `black` is available on both test.pypi.org and pypi.org, but `black==24.2.0` is only available
+/// on pypi.org. Since test.pypi.org is given priority via `--extra-index-url`, the default
+/// `first-match` strategy fails to find it, while `unsafe-any-match` searches both indexes.
+#[test]
+fn install_index_strategy() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command_without_exclude_newer(&context)
+        .arg("--index-url")
+        .arg("https://pypi.org/simple")
+        .arg("--extra-index-url")
+        .arg("https://test.pypi.org/simple")
+        .arg("--no-deps")
+        .arg("black==24.2.0"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because there is no version of black==24.2.0 and you require
+          black==24.2.0, we can conclude that the requirements are
+          unsatisfiable.
+    "###
+    );
+
+    uv_snapshot!(command_without_exclude_newer(&context)
+        .arg("--index-url")
+        .arg("https://pypi.org/simple")
+        .arg("--extra-index-url")
+        .arg("https://test.pypi.org/simple")
+        .arg("--index-strategy")
+        .arg("unsafe-any-match")
+        .arg("--no-deps")
+        .arg("black==24.2.0"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + black==24.2.0
+    "###
+    );
+
+    context.assert_installed("black", "24.2.0");
+}
+
 /// Install a package from a public GitHub repository
 #[test]
 #[cfg(feature = "git")]
@@ -2348,3 +2449,289 @@ requires-python = "<=3.8"
 
     Ok(())
 }
+
+/// Build a source tree without isolation, using the build requirements that are installed in the
+/// target environment.
+#[test]
+fn install_no_build_isolation() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirement = context.setuptools_project();
+
+    // Without an index, the build requirements can't be installed into an isolated environment.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(&requirement)
+        .arg("--no-index"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: example @ file://[TEMP_DIR]/project/
+      Caused by: Failed to build: example @ file://[TEMP_DIR]/project/
+      Caused by: Failed to install requirements from build-system.requires (resolve)
+      Caused by: No solution found when resolving: setuptools>=40.8.0, wheel
+      Caused by: Because setuptools was not found in the provided package locations and you require setuptools>=40.8.0, we can conclude that the requirements are unsatisfiable.
+
+    hint: Packages were unavailable because index lookups were disabled and no additional package locations were provided (try: `--find-links <uri>`)
+    "###
+    );
+
+    // Install the build requirements into the target environment.
+    command(&context)
+        .arg("setuptools")
+        .arg("wheel")
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(&requirement)
+        .arg("--no-index")
+        .arg("--no-build-isolation"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.1.0 (from file://[TEMP_DIR]/project/)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Set an environment variable when building a specific package with `--build-env-package`.
+#[test]
+fn install_build_env_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // The build fails unless `EXAMPLE_BUILD_FLAG` is set.
+    let requirement = context.flag_gated_setuptools_project();
+    let filters = context.build_failure_filters();
+
+    // Variables for other packages aren't set.
+    uv_snapshot!(filters, command(&context)
+        .arg(&requirement)
+        .arg("--build-env-package")
+        .arg("other:EXAMPLE_BUILD_FLAG=1"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: example @ file://[TEMP_DIR]/project/
+      Caused by: Failed to build: example @ file://[TEMP_DIR]/project/
+      Caused by: Build backend failed to determine extra requires with `build_wheel()` with exit code: 1
+    --- stdout:
+    Building example
+    --- stderr:
+    Traceback (most recent call last):
+    [TRACEBACK]
+    RuntimeError: `EXAMPLE_BUILD_FLAG` is not set
+    ---
+    Full build log: [CACHE_DIR]/build-logs-v0/example/[LOG]
+    "###
+    );
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(&requirement)
+        .arg("--build-env-package")
+        .arg("example:EXAMPLE_BUILD_FLAG=1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.1.0 (from file://[TEMP_DIR]/project/)
+    "###
+    );
+
+    // The wheel built with the variable set isn't reused for builds without it.
+    uv_snapshot!(filters, command(&context)
+        .arg(&requirement)
+        .arg("--reinstall"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: example @ file://[TEMP_DIR]/project/
+      Caused by: Failed to build: example @ file://[TEMP_DIR]/project/
+      Caused by: Build backend failed to determine extra requires with `build_wheel()` with exit code: 1
+    --- stdout:
+    Building example
+    --- stderr:
+    Traceback (most recent call last):
+    [TRACEBACK]
+    RuntimeError: `EXAMPLE_BUILD_FLAG` is not set
+    ---
+    Full build log: [CACHE_DIR]/build-logs-v0/example/[LOG]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Persist the log of every source distribution build, and point to it when the build fails.
+#[test]
+fn install_build_log() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // The build fails unless `EXAMPLE_BUILD_FLAG` is set.
+    let requirement = context.flag_gated_setuptools_project();
+    let filters = context.build_failure_filters();
+
+    // The error points to the log, which records the build environment and the output.
+    let logs = context.temp_dir.child("logs");
+    uv_snapshot!(filters, command(&context)
+        .arg(&requirement)
+        .arg("--build-log-dir")
+        .arg(logs.path()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: example @ file://[TEMP_DIR]/project/
+      Caused by: Failed to build: example @ file://[TEMP_DIR]/project/
+      Caused by: Build backend failed to determine extra requires with `build_wheel()` with exit code: 1
+    --- stdout:
+    Building example
+    --- stderr:
+    Traceback (most recent call last):
+    [TRACEBACK]
+    RuntimeError: `EXAMPLE_BUILD_FLAG` is not set
+    ---
+    Full build log: [TEMP_DIR]/logs/[LOG]
+    "###
+    );
+
+    let log = fs_err::read_dir(logs.path())?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let [log] = log.as_slice() else {
+        panic!("Expected exactly one build log, found: {log:?}");
+    };
+    let log = fs_err::read_to_string(log)?;
+    assert!(log.contains("Build requirements:"), "{log}");
+    assert!(log.contains("    setuptools=="), "{log}");
+    assert!(log.contains("get_requires_for_build_wheel"), "{log}");
+    assert!(log.contains("`EXAMPLE_BUILD_FLAG` is not set"), "{log}");
+
+    // Successful builds are logged too, and their output can be streamed.
+    let logs = context.temp_dir.child("success-logs");
+    command(&context)
+        .arg(&requirement)
+        .arg("--build-env-package")
+        .arg("example:EXAMPLE_BUILD_FLAG=1")
+        .arg("--build-log-dir")
+        .arg(logs.path())
+        .arg("--verbose-build")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Building example"));
+    assert!(fs_err::read_dir(logs.path())?.next().is_some());
+
+    // The streamed output respects `--quiet`. Bypass the cache, to force a rebuild.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(&requirement)
+        .arg("--reinstall")
+        .arg("--no-cache")
+        .arg("--build-env-package")
+        .arg("example:EXAMPLE_BUILD_FLAG=1")
+        .arg("--verbose-build")
+        .arg("--quiet"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Verify a source distribution against its `--hash` before building it to determine its
+/// metadata.
+#[test]
+fn require_hashes_source_dist_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(
+        "anyio @ https://files.pythonhosted.org/packages/2d/b8/7333d87d5f03247215d86a86362fd3e324111788c6cdd8d2e6196a6ba833/anyio-4.2.0.tar.gz --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000",
+    )?;
+
+    let filters = [(r"(Computed:\n  sha256:)[0-9a-f]{64}", "$1[SHA256]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: anyio @ https://files.pythonhosted.org/packages/2d/b8/7333d87d5f03247215d86a86362fd3e324111788c6cdd8d2e6196a6ba833/anyio-4.2.0.tar.gz
+      Caused by: Hash mismatch for `anyio-4.2.0.tar.gz`
+
+    Expected:
+      sha256:0000000000000000000000000000000000000000000000000000000000000000
+
+    Computed:
+      sha256:[SHA256]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install from the local index configured for the test context, rather than PyPI.
+#[test]
+fn install_local_index() -> Result<()> {
+    let index = TestIndex::builder()
+        .dir(
+            std::env::current_dir()?
+                .join("..")
+                .join("..")
+                .join("scripts")
+                .join("wheels"),
+        )
+        .start()?;
+    let context = TestContext::new("3.12").with_index(&index);
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("tqdm"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0
+    "###
+    );
+
+    assert_eq!(
+        index.requests(),
+        vec![
+            "GET /simple/tqdm/",
+            "GET /files/tqdm-1000.0.0-py3-none-any.whl.metadata",
+            "GET /files/tqdm-1000.0.0-py3-none-any.whl",
+        ]
+    );
+
+    Ok(())
+}
@@ -43,6 +43,7 @@ fn command(context: &TestContext) -> Command {
         .arg(context.cache_dir.path())
         .env("VIRTUAL_ENV", context.venv.as_os_str())
         .current_dir(&context.temp_dir);
+    context.add_index_args(&mut command);
 
     if cfg!(all(windows, debug_assertions)) {
         // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
@@ -2652,6 +2653,47 @@ fn find_links() -> Result<()> {
     Ok(())
 }
 
+/// Reject an index served over plain HTTP, unless its host is trusted.
+#[test]
+fn insecure_index_url() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("black==23.10.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--index-url")
+        .arg("http://example.com/simple"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Refusing to connect to `http://example.com/simple` over plain HTTP; to allow it, mark the host as trusted with `--trusted-host example.com`
+    "###
+    );
+
+    // Trusting the host on a different port has no effect.
+    requirements_txt.write_str(indoc! {"
+        --index-url http://example.com/simple
+        --trusted-host example.com:8080
+        black==23.10.1
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Refusing to connect to `http://example.com/simple` over plain HTTP; to allow it, mark the host as trusted with `--trusted-host example.com`
+    "###
+    );
+
+    Ok(())
+}
+
 /// Install without network access via the `--offline` flag.
 #[test]
 fn offline() -> Result<()> {
@@ -2993,3 +3035,256 @@ requires-python = "<=3.5"
 
     Ok(())
 }
+
+/// Reject unpinned requirements in `--require-hashes` mode.
+#[test]
+fn require_hashes_unpinned() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(
+        "anyio --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f",
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: In `--require-hashes` mode, all requirements must have their versions pinned with `==`, but found: anyio
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject requirements without hashes in `--require-hashes` mode.
+#[test]
+fn require_hashes_missing_hash() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==4.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: In `--require-hashes` mode, all requirements must have a hash, but none were provided for: anyio==4.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install a requirement whose hash matches the downloaded wheel.
+#[test]
+fn require_hashes_match() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(
+        "anyio==4.0.0 --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f",
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + anyio==4.0.0
+    "###
+    );
+
+    // Create a new virtual environment.
+    let venv = create_venv(&context.temp_dir, &context.cache_dir, "3.12");
+
+    // Reinstall. The wheel should come from the cache, but is still verified against its stored
+    // digest, so it's reported as a "download".
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + anyio==4.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject a wheel whose hash doesn't match the provided `--hash`, even without
+/// `--require-hashes`.
+#[test]
+fn require_hashes_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(
+        "anyio==4.0.0 --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000",
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Failed to download distributions
+      Caused by: Failed to fetch wheel: anyio==4.0.0
+      Caused by: Hash mismatch for `anyio-4.0.0-py3-none-any.whl`
+
+    Expected:
+      sha256:0000000000000000000000000000000000000000000000000000000000000000
+
+    Computed:
+      sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install from a `uv.lock` file generated by `pip compile`, without querying the index.
+#[test]
+fn sync_lockfile() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.0.0")?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("uv.lock")
+        .arg("--quiet"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+
+    // The lockfile should record the source, artifacts, and dependencies of each package.
+    let lock = fs::read_to_string(context.temp_dir.child("uv.lock"))?;
+    let filters = [
+        (
+            r"https://files.pythonhosted.org/packages/[0-9a-f]{2}/[0-9a-f]{2}/[0-9a-f]+/",
+            "https://files.pythonhosted.org/packages/[PATH]/",
+        ),
+        (r"sha256:[0-9a-f]{64}", "sha256:[SHA256]"),
+        (r"size = \d+", "size = [SIZE]"),
+        (r#"upload-time = "[^"]+""#, r#"upload-time = "[DATE]""#),
+    ]
+    .into_iter()
+    .chain(context.filters())
+    .collect::<Vec<_>>();
+    insta::with_settings!({ filters => filters }, {
+        insta::assert_snapshot!(lock, @r###"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file uv.lock
+        version = 1
+
+        [[distribution]]
+        name = "anyio"
+        version = "4.0.0"
+        source = "registry+https://pypi.org/simple"
+
+        [distribution.sdist]
+        url = "https://files.pythonhosted.org/packages/[PATH]/anyio-4.0.0.tar.gz"
+        hash = "sha256:[SHA256]"
+        size = [SIZE]
+        upload-time = "[DATE]"
+
+        [[distribution.wheel]]
+        url = "https://files.pythonhosted.org/packages/[PATH]/anyio-4.0.0-py3-none-any.whl"
+        hash = "sha256:[SHA256]"
+        size = [SIZE]
+        upload-time = "[DATE]"
+
+        [[distribution.dependencies]]
+        name = "idna"
+        version = "3.4"
+
+        [[distribution.dependencies]]
+        name = "sniffio"
+        version = "1.3.0"
+
+        [[distribution]]
+        name = "idna"
+        version = "3.4"
+        source = "registry+https://pypi.org/simple"
+
+        [distribution.sdist]
+        url = "https://files.pythonhosted.org/packages/[PATH]/idna-3.4.tar.gz"
+        hash = "sha256:[SHA256]"
+        size = [SIZE]
+        upload-time = "[DATE]"
+
+        [[distribution.wheel]]
+        url = "https://files.pythonhosted.org/packages/[PATH]/idna-3.4-py3-none-any.whl"
+        hash = "sha256:[SHA256]"
+        size = [SIZE]
+        upload-time = "[DATE]"
+
+        [[distribution]]
+        name = "sniffio"
+        version = "1.3.0"
+        source = "registry+https://pypi.org/simple"
+
+        [distribution.sdist]
+        url = "https://files.pythonhosted.org/packages/[PATH]/sniffio-1.3.0.tar.gz"
+        hash = "sha256:[SHA256]"
+        size = [SIZE]
+        upload-time = "[DATE]"
+
+        [[distribution.wheel]]
+        url = "https://files.pythonhosted.org/packages/[PATH]/sniffio-1.3.0-py3-none-any.whl"
+        hash = "sha256:[SHA256]"
+        size = [SIZE]
+        upload-time = "[DATE]"
+        "###);
+    });
+
+    uv_snapshot!(command(&context)
+        .arg("uv.lock"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==4.0.0
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    context.assert_command("import anyio").success();
+
+    Ok(())
+}
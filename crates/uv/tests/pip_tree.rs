@@ -0,0 +1,214 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::fixture::FileWriteStr;
+use assert_fs::fixture::PathChild;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `pip install` command with options shared across scenarios.
+fn install_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("install")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    context.add_index_args(&mut command);
+    command
+}
+
+/// Create a `pip tree` command with options shared across scenarios.
+fn tree_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("tree")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+#[test]
+fn tree_empty() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(tree_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+}
+
+#[test]
+fn tree_flask() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("flask==3.0.0")?;
+
+    uv_snapshot!(install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    Downloaded 7 packages in [TIME]
+    Installed 7 packages in [TIME]
+     + blinker==1.7.0
+     + click==8.1.7
+     + flask==3.0.0
+     + itsdangerous==2.1.2
+     + jinja2==3.1.2
+     + markupsafe==2.1.3
+     + werkzeug==3.0.1
+    "###
+    );
+
+    uv_snapshot!(tree_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    flask v3.0.0
+    ├── blinker v1.7.0
+    ├── click v8.1.7
+    ├── itsdangerous v2.1.2
+    ├── jinja2 v3.1.2
+    │   └── markupsafe v2.1.3
+    └── werkzeug v3.0.1
+        └── markupsafe v2.1.3
+
+    ----- stderr -----
+    "###
+    );
+
+    // Limit the depth of the tree.
+    uv_snapshot!(tree_command(&context)
+        .arg("--depth")
+        .arg("1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    flask v3.0.0
+    ├── blinker v1.7.0
+    ├── click v8.1.7
+    ├── itsdangerous v2.1.2
+    ├── jinja2 v3.1.2
+    └── werkzeug v3.0.1
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn tree_invert() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("flask==3.0.0")?;
+
+    uv_snapshot!(install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    Downloaded 7 packages in [TIME]
+    Installed 7 packages in [TIME]
+     + blinker==1.7.0
+     + click==8.1.7
+     + flask==3.0.0
+     + itsdangerous==2.1.2
+     + jinja2==3.1.2
+     + markupsafe==2.1.3
+     + werkzeug==3.0.1
+    "###
+    );
+
+    uv_snapshot!(tree_command(&context)
+        .arg("markupsafe")
+        .arg("--invert"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    markupsafe v2.1.3
+    ├── jinja2 v3.1.2 [requires: markupsafe >=2.0]
+    │   └── flask v3.0.0 [requires: jinja2 >=3.1.2]
+    └── werkzeug v3.0.1 [requires: markupsafe >=2.1.1]
+        └── flask v3.0.0 [requires: werkzeug >=3.0.0]
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn tree_missing_dependencies() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("flask==3.0.0\nwerkzeug==2.3.8")?;
+
+    uv_snapshot!(install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--no-deps"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + flask==3.0.0
+     + werkzeug==2.3.8
+    "###
+    );
+
+    // Missing and conflicting requirements are flagged inline.
+    uv_snapshot!(tree_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    flask v3.0.0
+    ├── blinker [missing: blinker >=1.6.2]
+    ├── click [missing: click >=8.1.3]
+    ├── itsdangerous [missing: itsdangerous >=2.1.2]
+    ├── jinja2 [missing: jinja2 >=3.1.2]
+    └── werkzeug v2.3.8 [conflict: werkzeug >=3.0.0]
+        └── markupsafe [missing: markupsafe >=2.1.1]
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}